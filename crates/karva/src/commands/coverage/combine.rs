@@ -0,0 +1,39 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use camino::Utf8Path;
+use karva_cli::CombineArgs;
+use karva_coverage::PathRemap;
+use karva_project::path::absolute;
+
+use super::data_files;
+use crate::ExitStatus;
+
+pub fn combine(cwd: &Utf8Path, args: &CombineArgs, stdout: &mut impl Write) -> Result<ExitStatus> {
+    let files = data_files(cwd, &args.files)?;
+    if files.is_empty() {
+        writeln!(
+            stdout,
+            "No coverage data found. Run `karva test --cov` first."
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    let remaps: Vec<PathRemap> = args
+        .remaps
+        .iter()
+        .map(|remap| PathRemap::new(&remap.from, absolute(&remap.to, cwd).as_std_path()))
+        .collect();
+    let output = args
+        .output
+        .as_deref()
+        .unwrap_or(Utf8Path::new(karva_coverage::DATA_FILE));
+
+    let count = karva_coverage::combine_data_files(&files, &remaps, &absolute(output, cwd))?;
+    writeln!(
+        stdout,
+        "Combined {} data file(s) covering {count} source file(s) into {output}.",
+        files.len()
+    )?;
+    Ok(ExitStatus::Success)
+}
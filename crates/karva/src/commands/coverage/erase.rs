@@ -0,0 +1,25 @@
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+
+use crate::ExitStatus;
+
+pub fn erase(cwd: &Utf8Path, stdout: &mut impl Write) -> Result<ExitStatus> {
+    let cache_dir = cwd.join(karva_cache::CACHE_DIR);
+    let mut removed = karva_cache::erase_coverage(&cache_dir)?;
+
+    let data_file = cwd.join(karva_coverage::DATA_FILE);
+    if data_file.exists() {
+        std::fs::remove_file(&data_file)
+            .with_context(|| format!("Failed to remove `{data_file}`"))?;
+        removed += 1;
+    }
+
+    if removed == 0 {
+        writeln!(stdout, "No coverage data to erase.")?;
+    } else {
+        writeln!(stdout, "Removed {removed} coverage data file(s).")?;
+    }
+    Ok(ExitStatus::Success)
+}
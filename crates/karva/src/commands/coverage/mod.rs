@@ -0,0 +1,63 @@
+mod combine;
+mod erase;
+mod report;
+mod who_covers;
+
+use anyhow::Result;
+use camino::{Utf8Path, Utf8PathBuf};
+use karva_cache::RunCache;
+use karva_cli::{CoverageAction, CoverageCommand};
+use karva_logging::Printer;
+use karva_metadata::{CovReport, CoverageSettings};
+use karva_project::path::absolute;
+
+use crate::ExitStatus;
+use crate::utils::cwd;
+
+pub fn coverage(args: &CoverageCommand) -> Result<ExitStatus> {
+    let cwd = cwd()?;
+
+    let printer = Printer::default();
+    let mut stdout = printer.stream_for_message().lock();
+
+    match &args.action {
+        CoverageAction::Combine(combine_args) => combine::combine(&cwd, combine_args, &mut stdout),
+        CoverageAction::Report(report_args) => report::report(&cwd, report_args, &mut stdout),
+        CoverageAction::Erase => erase::erase(&cwd, &mut stdout),
+        CoverageAction::WhoCovers(who_covers_args) => {
+            who_covers::who_covers(&cwd, &who_covers_args.location, &mut stdout)
+        }
+    }
+}
+
+/// The data files to read: `files` resolved against `cwd`, or the
+/// per-worker files of the most recent run when none are given.
+fn data_files(cwd: &Utf8Path, files: &[Utf8PathBuf]) -> Result<Vec<Utf8PathBuf>> {
+    if !files.is_empty() {
+        return Ok(files.iter().map(|file| absolute(file, cwd)).collect());
+    }
+    let cache_dir = cwd.join(karva_cache::CACHE_DIR);
+    match RunCache::most_recent(&cache_dir)? {
+        Some(run) => run.coverage_files(),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// The report options of the `[coverage]` settings, with report paths
+/// resolved against `cwd`.
+pub(crate) fn report_options(
+    cwd: &Utf8Path,
+    settings: &CoverageSettings,
+    filter: karva_coverage::SourceFilter,
+    diff: Option<karva_coverage::DiffLines>,
+) -> karva_coverage::ReportOptions {
+    karva_coverage::ReportOptions {
+        show_missing: matches!(settings.report, CovReport::TermMissing),
+        xml_path: settings.xml_path.as_ref().map(|path| absolute(path, cwd)),
+        json_path: settings.json_path.as_ref().map(|path| absolute(path, cwd)),
+        lcov_path: settings.lcov_path.as_ref().map(|path| absolute(path, cwd)),
+        html_dir: settings.html_dir.as_ref().map(|dir| absolute(dir, cwd)),
+        filter,
+        diff,
+    }
+}
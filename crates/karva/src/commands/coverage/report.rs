@@ -0,0 +1,98 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use camino::Utf8Path;
+use karva_cli::CoverageReportArgs;
+use karva_metadata::{ProjectMetadata, ProjectOptionsOverrides};
+use karva_project::Project;
+use karva_project::path::absolute;
+use karva_python_semantic::current_python_version;
+
+use super::{data_files, report_options};
+use crate::ExitStatus;
+
+pub fn report(
+    cwd: &Utf8Path,
+    args: &CoverageReportArgs,
+    stdout: &mut impl Write,
+) -> Result<ExitStatus> {
+    let project = load_project(cwd, args)?;
+    let settings = project.settings().coverage();
+
+    // The file written by `karva coverage combine` holds the data of every
+    // run it was given, so it is preferred over the most recent run.
+    let combined = cwd.join(karva_coverage::DATA_FILE);
+    let files = if args.files.is_empty() && combined.is_file() {
+        vec![combined]
+    } else {
+        data_files(cwd, &args.files)?
+    };
+
+    let filter =
+        karva_coverage::SourceFilter::new(project.cwd(), &settings.include, &settings.omit)?;
+    let diff = karva_coverage::DiffLines::read(
+        project.cwd(),
+        settings.diff_file.as_deref(),
+        settings.diff_base.as_deref(),
+    )?;
+    let options = report_options(project.cwd(), settings, filter, diff);
+
+    let totals = if files.is_empty() {
+        None
+    } else {
+        karva_coverage::combine_and_report(
+            project.cwd(),
+            &files,
+            &options,
+            &mut std::io::stdout().lock(),
+        )?
+    };
+    let Some(totals) = totals else {
+        writeln!(
+            stdout,
+            "No coverage data found. Run `karva test --cov` first."
+        )?;
+        return Ok(ExitStatus::Failure);
+    };
+
+    let mut status = ExitStatus::Success;
+    if let Some(threshold) = settings.fail_under
+        && totals.total < threshold
+    {
+        writeln!(
+            stdout,
+            "\ncoverage failure: required total coverage of {threshold}% not reached, total coverage was {:.2}%",
+            totals.total,
+        )?;
+        status = ExitStatus::Failure;
+    }
+    if let Some(threshold) = settings.fail_under_diff
+        && let Some(diff) = totals.diff
+        && diff < threshold
+    {
+        writeln!(
+            stdout,
+            "\ncoverage failure: required diff coverage of {threshold}% not reached, diff coverage was {diff:.2}%",
+        )?;
+        status = ExitStatus::Failure;
+    }
+    Ok(status)
+}
+
+/// The project around `cwd`, with the command line options layered over
+/// its configuration like `karva test` does.
+fn load_project(cwd: &Utf8Path, args: &CoverageReportArgs) -> Result<Project> {
+    let python_version = current_python_version();
+    let config_file = args.config_file.as_ref().map(|path| absolute(path, cwd));
+    let mut metadata = if let Some(config_file) = &config_file {
+        ProjectMetadata::from_config_file(config_file, cwd, python_version)?
+    } else {
+        ProjectMetadata::discover(cwd, python_version)?
+    };
+    let overrides = ProjectOptionsOverrides::new(config_file, args.to_options())
+        .with_profile(args.profile.clone());
+    metadata
+        .apply_overrides(&overrides)
+        .map_err(|err| anyhow::anyhow!("{err}"))?;
+    Ok(Project::from_metadata(metadata))
+}
@@ -0,0 +1,42 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use camino::Utf8Path;
+use karva_cli::SourceLine;
+use karva_project::path::absolute;
+
+use super::data_files;
+use crate::ExitStatus;
+
+pub fn who_covers(
+    cwd: &Utf8Path,
+    location: &SourceLine,
+    stdout: &mut impl Write,
+) -> Result<ExitStatus> {
+    let files = data_files(cwd, &[])?;
+    if files.is_empty() {
+        writeln!(
+            stdout,
+            "No coverage data found. Run `karva test --cov --cov-context` first."
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    let path = absolute(&location.path, cwd);
+    let Some(tests) = karva_coverage::tests_covering(&files, path.as_std_path(), location.line)?
+    else {
+        writeln!(
+            stdout,
+            "The most recent coverage run did not record which tests ran each line. Run `karva test --cov --cov-context` first."
+        )?;
+        return Ok(ExitStatus::Failure);
+    };
+
+    if tests.is_empty() {
+        writeln!(stdout, "No tests ran {}:{}.", location.path, location.line)?;
+    }
+    for test in tests {
+        writeln!(stdout, "{test}")?;
+    }
+    Ok(ExitStatus::Success)
+}
@@ -1,4 +1,5 @@
 pub mod cache;
+pub mod coverage;
 pub mod snapshot;
 pub mod test;
 pub mod version;
@@ -1,6 +1,8 @@
 use std::fmt::Write;
 
 use anyhow::Result;
+use karva_snapshot::review::format_metadata;
+use karva_snapshot::storage::read_snapshot;
 
 use super::pending_setup;
 use crate::ExitStatus;
@@ -11,6 +13,9 @@ pub fn pending(filter_paths: &[String]) -> Result<ExitStatus> {
     };
     for info in &filtered {
         writeln!(stdout, "{}", info.pending_path)?;
+        if let Some(snapshot) = read_snapshot(&info.pending_path) {
+            write!(stdout, "{}", format_metadata(&snapshot.metadata, "  "))?;
+        }
     }
     writeln!(stdout, "\n{} pending snapshot(s).", filtered.len())?;
     Ok(ExitStatus::Success)
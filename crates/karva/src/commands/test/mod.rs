@@ -1,3 +1,4 @@
+mod unreferenced;
 mod watch;
 
 use std::collections::HashMap;
@@ -5,16 +6,19 @@ use std::fmt::Write;
 use std::time::{Duration, Instant};
 
 use anyhow::{Context as _, Result};
-use karva_cache::{AggregatedResults, DisplayFlakyTests};
+use karva_cache::{AggregatedResults, DisplayFlakyTests, JunitReport, RunEvent};
 use karva_cli::TestCommand;
-use karva_logging::{Printer, Stdout, set_colored_override, setup_tracing};
+use karva_logging::{
+    FinalStatusLevel, Printer, StatusLevel, Stdout, set_colored_override, setup_tracing,
+};
 use karva_metadata::filter::FiltersetSet;
-use karva_metadata::{CovReport, NoTestsMode, ProjectMetadata, ProjectOptionsOverrides};
+use karva_metadata::{NoTestsMode, ProjectMetadata, ProjectOptionsOverrides};
 use karva_project::Project;
 use karva_project::path::absolute;
 use karva_python_semantic::current_python_version;
 
 use crate::ExitStatus;
+use crate::commands::coverage::report_options;
 use crate::utils::cwd;
 
 pub fn test(args: TestCommand) -> Result<ExitStatus> {
@@ -47,6 +51,7 @@ pub fn test(args: TestCommand) -> Result<ExitStatus> {
     let durations = args.durations;
     let last_failed = args.last_failed;
     let partition = args.partition;
+    let unreferenced = args.unreferenced;
     let no_cache = args.no_cache.unwrap_or(false);
     let num_workers = if args.no_parallel.unwrap_or(false) || args.no_capture {
         1
@@ -64,13 +69,45 @@ pub fn test(args: TestCommand) -> Result<ExitStatus> {
 
     let project = Project::from_metadata(project_metadata);
 
-    let printer = Printer::new(
-        project.settings().terminal().status_level,
-        project.settings().terminal().final_status_level,
-    );
+    let message_format = sub_command.message_format.unwrap_or_default();
+    if message_format.is_json() && watch {
+        anyhow::bail!("`--message-format json` cannot be used with `--watch`");
+    }
+
+    // The JSON event stream owns stdout, so every human-readable stream is
+    // silenced.
+    let printer = if message_format.is_json() {
+        Printer::new(StatusLevel::None, FinalStatusLevel::None)
+    } else {
+        Printer::new(
+            project.settings().terminal().status_level,
+            project.settings().terminal().final_status_level,
+        )
+    };
 
     FiltersetSet::new(&sub_command.filter_expressions).context("invalid `--filter` expression")?;
 
+    let coverage_settings = project.settings().coverage();
+    let coverage_filter = karva_coverage::SourceFilter::new(
+        project.cwd(),
+        &coverage_settings.include,
+        &coverage_settings.omit,
+    )?;
+    karva_coverage::Exclusions::new(
+        &coverage_settings.exclude_lines,
+        &coverage_settings.partial_branches,
+    )?;
+    // Read the diff up front so a bad ref or path fails before the tests run.
+    let coverage_diff = if coverage_settings.sources.is_empty() {
+        None
+    } else {
+        karva_coverage::DiffLines::read(
+            project.cwd(),
+            coverage_settings.diff_file.as_deref(),
+            coverage_settings.diff_base.as_deref(),
+        )?
+    };
+
     let config = karva_runner::ParallelTestConfig {
         num_workers,
         no_cache,
@@ -92,14 +129,34 @@ pub fn test(args: TestCommand) -> Result<ExitStatus> {
         coverage_files,
     } = karva_runner::run_parallel_tests(&project, &config, &sub_command, printer)?;
 
-    print_test_output(printer, start_time, &result, durations)?;
+    if message_format.is_json() {
+        print_json_output(start_time, &result)?;
+    } else {
+        print_test_output(printer, start_time, &result, durations)?;
+    }
+    write_junit_report(&project, &result)?;
 
-    let coverage_total = if coverage_files.is_empty() {
+    let coverage_totals = if coverage_files.is_empty() {
         None
     } else {
-        let show_missing = matches!(project.settings().coverage().report, CovReport::TermMissing);
-        match karva_coverage::combine_and_report(project.cwd(), &coverage_files, show_missing) {
-            Ok(total) => total,
+        let report_options = report_options(
+            project.cwd(),
+            coverage_settings,
+            coverage_filter,
+            coverage_diff,
+        );
+        let mut out: Box<dyn std::io::Write> = if message_format.is_json() {
+            Box::new(std::io::stderr().lock())
+        } else {
+            Box::new(std::io::stdout().lock())
+        };
+        match karva_coverage::combine_and_report(
+            project.cwd(),
+            &coverage_files,
+            &report_options,
+            &mut out,
+        ) {
+            Ok(totals) => totals,
             Err(err) => {
                 tracing::error!("Coverage report failed: {err:#}");
                 None
@@ -107,18 +164,41 @@ pub fn test(args: TestCommand) -> Result<ExitStatus> {
         }
     };
 
-    let coverage_below_threshold = if let Some(total) = coverage_total
-        && let Some(threshold) = project.settings().coverage().fail_under
-        && total < threshold
-    {
+    let mut coverage_below_threshold = false;
+    if let Some(totals) = coverage_totals {
         let mut stdout = printer.stream_for_message().lock();
-        writeln!(
-            stdout,
-            "\ncoverage failure: required total coverage of {threshold}% not reached, total coverage was {total:.2}%",
-        )?;
-        true
-    } else {
-        false
+        if let Some(threshold) = coverage_settings.fail_under
+            && totals.total < threshold
+        {
+            writeln!(
+                stdout,
+                "\ncoverage failure: required total coverage of {threshold}% not reached, total coverage was {:.2}%",
+                totals.total,
+            )?;
+            coverage_below_threshold = true;
+        }
+        if let Some(threshold) = coverage_settings.fail_under_diff
+            && let Some(diff) = totals.diff
+            && diff < threshold
+        {
+            writeln!(
+                stdout,
+                "\ncoverage failure: required diff coverage of {threshold}% not reached, diff coverage was {diff:.2}%",
+            )?;
+            coverage_below_threshold = true;
+        }
+    }
+
+    let unreferenced_rejected = match unreferenced {
+        Some(action) => unreferenced::handle_unreferenced_snapshots(
+            printer,
+            project.cwd(),
+            &config,
+            &sub_command,
+            &result,
+            action,
+        )?,
+        None => false,
     };
 
     if no_tests_collected(&result) {
@@ -140,7 +220,11 @@ pub fn test(args: TestCommand) -> Result<ExitStatus> {
         }
     }
 
-    if result.stats.is_success() && result.diagnostics.is_empty() && !coverage_below_threshold {
+    if result.stats.is_success()
+        && result.diagnostics.is_empty()
+        && !coverage_below_threshold
+        && !unreferenced_rejected
+    {
         Ok(ExitStatus::Success)
     } else {
         Ok(ExitStatus::Failure)
@@ -151,6 +235,43 @@ fn no_tests_collected(result: &AggregatedResults) -> bool {
     result.stats.total() == 0 && result.diagnostics.is_empty()
 }
 
+/// Write the JUnit XML report, if one was requested.
+pub fn write_junit_report(project: &Project, result: &AggregatedResults) -> Result<()> {
+    let Some(junit_path) = &project.settings().terminal().junit_path else {
+        return Ok(());
+    };
+
+    let junit_path = absolute(junit_path, project.cwd());
+    if let Some(parent) = junit_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory `{parent}`"))?;
+    }
+
+    let report = JunitReport::new(&result.test_cases, project.cwd());
+    std::fs::write(&junit_path, report.to_string())
+        .with_context(|| format!("Failed to write JUnit report to `{junit_path}`"))?;
+
+    Ok(())
+}
+
+/// Finish the JSON event stream: diagnostics go to stderr, followed by the
+/// `run-finished` event on stdout.
+fn print_json_output(start_time: Instant, result: &AggregatedResults) -> Result<()> {
+    if !result.diagnostics.is_empty() {
+        let mut stderr = std::io::stderr().lock();
+        std::io::Write::write_all(&mut stderr, result.diagnostics.as_bytes())?;
+    }
+
+    RunEvent::RunFinished {
+        duration: start_time.elapsed().as_secs_f64(),
+        success: result.stats.is_success() && result.diagnostics.is_empty(),
+        stats: (&result.stats).into(),
+    }
+    .emit();
+
+    Ok(())
+}
+
 /// Print test output: diagnostics, durations, and result summary.
 pub fn print_test_output(
     printer: Printer,
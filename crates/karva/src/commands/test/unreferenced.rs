@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::Result;
+use camino::{Utf8Path, Utf8PathBuf};
+use colored::Colorize;
+use karva_cache::{AggregatedResults, TestCaseOutcome};
+use karva_cli::{RunIgnored, SubTestCommand, Unreferenced};
+use karva_logging::Printer;
+use karva_runner::ParallelTestConfig;
+use karva_snapshot::storage::{find_unused_snapshots, remove_snapshot};
+
+/// Report or delete the snapshot files that no assertion used during the run.
+///
+/// Returns whether the run should fail because of them.
+pub fn handle_unreferenced_snapshots(
+    printer: Printer,
+    cwd: &Utf8Path,
+    config: &ParallelTestConfig,
+    sub_command: &SubTestCommand,
+    result: &AggregatedResults,
+    action: Unreferenced,
+) -> Result<bool> {
+    let mut stdout = printer.stream_for_message().lock();
+
+    let partial_run = config.last_failed
+        || config.partition.is_some()
+        || !sub_command.filter_expressions.is_empty()
+        || sub_command.run_ignored == Some(RunIgnored::Only)
+        || sub_command.paths.iter().any(|path| path.contains("::"));
+    let skipped_because = if partial_run {
+        Some("only some tests ran")
+    } else if !result.stats.is_success() || !result.diagnostics.is_empty() {
+        Some("the run failed")
+    } else {
+        None
+    };
+    if let Some(reason) = skipped_because {
+        writeln!(
+            stdout,
+            "\n{} Skipped the unreferenced snapshot check because {reason}.",
+            "warning:".yellow().bold()
+        )?;
+        return Ok(false);
+    }
+
+    let unused = unused_snapshots(result);
+    if unused.is_empty() {
+        return Ok(false);
+    }
+
+    let count = unused.len();
+    let label = if count == 1 { "snapshot" } else { "snapshots" };
+    match action {
+        Unreferenced::Warn => writeln!(
+            stdout,
+            "\n{} {count} unreferenced {label}:",
+            "warning:".yellow().bold()
+        )?,
+        Unreferenced::Reject => writeln!(
+            stdout,
+            "\n{} {count} unreferenced {label}:",
+            "error:".red().bold()
+        )?,
+        Unreferenced::Delete => writeln!(stdout, "\nRemoved {count} unreferenced {label}:")?,
+    }
+    for path in &unused {
+        if action == Unreferenced::Delete {
+            remove_snapshot(path)?;
+        }
+        writeln!(stdout, "  {}", path.strip_prefix(cwd).unwrap_or(path))?;
+    }
+    if action == Unreferenced::Reject {
+        writeln!(stdout, "(hint: use `--unreferenced=delete` to remove them)")?;
+    }
+
+    Ok(action == Unreferenced::Reject)
+}
+
+/// The `.snap` files of the test modules that ran which no assertion used,
+/// leaving out those of skipped tests.
+fn unused_snapshots(result: &AggregatedResults) -> Vec<Utf8PathBuf> {
+    let mut skipped_by_file: BTreeMap<&Utf8Path, Vec<String>> = BTreeMap::new();
+    for record in &result.test_cases {
+        let skipped = skipped_by_file.entry(&record.file).or_default();
+        if record.outcome != TestCaseOutcome::Passed {
+            // Snapshot names write a test method as `Class.method`.
+            skipped.push(record.function_name.replace("::", "."));
+        }
+    }
+
+    skipped_by_file
+        .into_iter()
+        .flat_map(|(file, skipped)| find_unused_snapshots(file, &result.snapshots, &skipped))
+        .collect()
+}
@@ -13,7 +13,7 @@ use karva_logging::Printer;
 use karva_project::Project;
 use karva_runner::ParallelTestConfig;
 
-use super::print_test_output;
+use super::{print_test_output, write_junit_report};
 
 fn run_and_print(
     project: &Project,
@@ -28,6 +28,9 @@ fn run_and_print(
             if let Err(err) = print_test_output(printer, start_time, &output.results, durations) {
                 tracing::error!("Failed to print test output: {err}");
             }
+            if let Err(err) = write_junit_report(project, &output.results) {
+                tracing::error!("{err:#}");
+            }
         }
         Err(err) => {
             use std::io::Write as _;
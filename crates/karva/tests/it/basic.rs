@@ -871,7 +871,7 @@ def test_9():
     INFO Worker 0 spawned with 5 tests
     INFO Worker 1 spawned with 5 tests
     INFO Waiting for 2 workers to complete (Ctrl+C to cancel)
-    INFO Fail-fast signal received — stopping remaining workers
+    INFO Max-fail limit reached — stopping remaining workers
     ");
 }
 
@@ -1387,6 +1387,78 @@ def test_fourth_skipped():
     ");
 }
 
+/// Workers killed when the run reaches the max-fail limit still count the
+/// tests they finished. Here each worker fails its first test and hangs on
+/// the next, so both are under their own share of the limit and get killed.
+#[test]
+fn test_max_fail_keeps_results_of_stopped_workers() {
+    let context = TestContext::with_file(
+        "test_max_fail.py",
+        r"
+import time
+
+ran = []
+
+def check():
+    ran.append(None)
+    if len(ran) == 1:
+        assert False, 'first test of the worker'
+    time.sleep(30)
+
+def test_1():
+    check()
+
+def test_2():
+    check()
+
+def test_3():
+    check()
+
+def test_4():
+    check()
+
+def test_5():
+    check()
+
+def test_6():
+    check()
+
+def test_7():
+    check()
+
+def test_8():
+    check()
+
+def test_9():
+    check()
+
+def test_10():
+    check()
+        ",
+    );
+
+    let output = context
+        .command()
+        .args(["--max-fail=2", "--num-workers", "2"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success());
+    assert!(
+        stdout.contains("Starting 10 tests across 2 workers"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("2 tests run: 0 passed, 2 failed, 0 skipped"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("was stopped at the max-fail limit"),
+        "{stdout}"
+    );
+}
+
 /// `--no-fail-fast` disables the limit, so every test runs even when some fail.
 #[test]
 fn test_no_fail_fast_runs_every_test() {
@@ -2655,6 +2727,7 @@ fn test_no_subcommand_prints_help() {
     Commands:
       test      Run tests
       snapshot  Manage snapshots created by `karva.assert_snapshot()`
+      coverage  Combine, report and query saved coverage data
       cache     Manage the karva cache
       version   Display Karva's version
       help      Print this message or the help of the given subcommand(s)
@@ -142,6 +142,12 @@ impl TestContext {
     fn karva_command(&self) -> Command {
         let mut command = Command::new(self.venv_binary("karva"));
         command.env("VIRTUAL_ENV", self.venv_path.as_str());
+        // Snapshot tests expect the local `--snapshot-update=auto` behaviour,
+        // also when the suite itself runs on CI.
+        command
+            .env_remove("CI")
+            .env_remove("TF_BUILD")
+            .env_remove("KARVA_SNAPSHOT_UPDATE");
         command
     }
 
@@ -185,6 +191,15 @@ impl TestContext {
         command
     }
 
+    pub fn coverage(&self, subcommand: &str) -> Command {
+        let mut command = self.karva_command();
+        command
+            .arg("coverage")
+            .arg(subcommand)
+            .current_dir(self.root());
+        command
+    }
+
     pub fn version(&self) -> Command {
         let mut command = self.karva_command();
         command.arg("version").current_dir(self.root());
@@ -1,7 +1,17 @@
+use insta::assert_snapshot;
 use insta_cmd::assert_cmd_snapshot;
 
 use crate::common::TestContext;
 
+fn bind_cobertura_filter() -> insta::internals::SettingsBindDropGuard {
+    let mut settings = insta::Settings::clone_current();
+    settings.add_filter(
+        r#"version="[^"]+" timestamp="\d+""#,
+        r#"version="[VERSION]" timestamp="[TIMESTAMP]""#,
+    );
+    settings.bind_to_scope()
+}
+
 #[test]
 fn test_no_cov_no_coverage_table() {
     let context = TestContext::with_file(
@@ -921,18 +931,165 @@ def test_one():
     );
 }
 
-/// Regression test for <https://github.com/MatthewMckee4/karva/issues/760>.
-///
-/// On Python 3.12+ the `sys.monitoring` LINE callback (and on older versions
-/// the `sys.settrace` callback) can fire on threads other than the one that
-/// installed the tracer. The `PyO3` `#[pyclass]` must therefore be safe to
-/// access cross-thread; otherwise `unsendable` triggers a panic of the form
-/// `CoverageTracer is unsendable, but sent to another thread`.
+/// `--cov-diff-file` reports the changed executable lines and
+/// `--cov-fail-under-diff` fails the run when too few of them ran, even
+/// though the total passes `--cov-fail-under`.
 #[test]
-fn test_cov_traces_python_threads() {
-    let context = TestContext::with_file(
-        "test_threaded.py",
+fn test_cov_diff_file_fails_under_diff_threshold() {
+    let context = TestContext::with_files([
+        (
+            "test_diff.py",
+            r"
+def add(a, b):
+    return a + b
+
+def sub(a, b):
+    return a - b
+
+def test_add():
+    assert add(1, 2) == 3
+",
+        ),
+        (
+            "changes.diff",
+            "\
+--- a/test_diff.py
++++ b/test_diff.py
+@@ -3,0 +4,3 @@
++
++def sub(a, b):
++    return a - b
+",
+        ),
+    ]);
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--cov-diff-file=changes.diff")
+            .arg("--cov-fail-under=80")
+            .arg("--cov-fail-under-diff=100")
+            .arg("--status-level=none")
+            .arg("test_diff.py"),
+        @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name           Stmts   Miss   Cover
+    [LONG-LINE]
+    test_diff.py       6      1     83%
+    [LONG-LINE]
+    TOTAL              6      1     83%
+
+    Diff coverage
+    Name           Stmts   Miss   Cover   Missing
+    [LONG-LINE]
+    test_diff.py       2      1     50%   6
+    [LONG-LINE]
+    TOTAL              2      1     50%
+
+    coverage failure: required diff coverage of 100% not reached, diff coverage was 50.00%
+
+    ----- stderr -----
+    "
+    );
+}
+
+/// Run git in the project, failing the test if it fails.
+fn git(context: &TestContext, args: &[&str]) {
+    let output = std::process::Command::new("git")
+        .args([
+            "-c",
+            "user.name=karva",
+            "-c",
+            "user.email=karva@example.com",
+        ])
+        .args(["-c", "commit.gpgsign=false"])
+        .args(args)
+        .current_dir(context.root())
+        .output()
+        .expect("failed to run git");
+    assert!(
+        output.status.success(),
+        "`git {}` failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// `--cov-diff-base` measures the lines changed since the branch left the
+/// base, so a change that landed on the base afterwards isn't reported as
+/// part of the diff.
+#[test]
+fn test_cov_diff_base_ignores_changes_on_base_branch() {
+    let base = r"
+def add(a, b):
+    return a + b
+
+def test_add():
+    assert add(1, 2) == 3
+";
+    let context = TestContext::with_file("test_diff.py", base);
+    git(&context, &["init", "--quiet", "--initial-branch=main"]);
+    git(&context, &["add", "test_diff.py"]);
+    git(&context, &["commit", "--quiet", "-m", "base"]);
+
+    git(&context, &["checkout", "--quiet", "-b", "feature"]);
+    context.write_file(
+        "test_diff.py",
         r"
+def add(a, b):
+    return a + b
+
+def sub(a, b):
+    return a - b
+
+def test_add():
+    assert add(1, 2) == 3
+",
+    );
+    git(&context, &["commit", "--quiet", "-am", "add sub"]);
+
+    git(&context, &["checkout", "--quiet", "main"]);
+    context.write_file("test_diff.py", &base.replace("a + b", "b + a"));
+    git(&context, &["commit", "--quiet", "-am", "swap operands"]);
+    git(&context, &["checkout", "--quiet", "feature"]);
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--cov-diff-base=main")
+            .arg("--status-level=none")
+            .arg("test_diff.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name           Stmts   Miss   Cover
+    [LONG-LINE]
+    test_diff.py       6      1     83%
+    [LONG-LINE]
+    TOTAL              6      1     83%
+
+    Diff coverage
+    Name           Stmts   Miss   Cover   Missing
+    [LONG-LINE]
+    test_diff.py       2      1     50%   6
+    [LONG-LINE]
+    TOTAL              2      1     50%
+
+    ----- stderr -----
+    "
+    );
+}
+
+const THREADED_TEST: &str = r"
 import threading
 
 def in_thread():
@@ -951,11 +1108,121 @@ def test_thread_runs():
     for t in threads:
         t.join()
     assert results == [2, 2, 2, 2]
-",
+";
+
+const MULTIPROCESSING_TEST: &str = r#"
+import multiprocessing
+
+def square(x):
+    return x * x
+
+def work(queue):
+    queue.put(square(3))
+
+def test_spawned_process():
+    ctx = multiprocessing.get_context("spawn")
+    queue = ctx.Queue()
+    process = ctx.Process(target=work, args=(queue,))
+    process.start()
+    assert queue.get() == 9
+    process.join()
+"#;
+
+/// Regression test for <https://github.com/MatthewMckee4/karva/issues/760>.
+///
+/// On Python 3.12+ the `sys.monitoring` LINE callback (and on older versions
+/// the `sys.settrace` callback) can fire on threads other than the one that
+/// installed the tracer. The `PyO3` `#[pyclass]` must therefore be safe to
+/// access cross-thread; otherwise `unsendable` triggers a panic of the form
+/// `CoverageTracer is unsendable, but sent to another thread`.
+#[test]
+fn test_cov_traces_python_threads() {
+    let context = TestContext::with_file("test_threaded.py", THREADED_TEST);
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--status-level=none")
+            .arg("test_threaded.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name               Stmts   Miss   Cover
+    [LONG-LINE]
+    test_threaded.py      13      0    100%
+    [LONG-LINE]
+    TOTAL                 13      0    100%
+
+    ----- stderr -----
+    "
+    );
+}
+
+/// A Python subprocess started by a test measures its own coverage, which
+/// is merged into the report.
+#[test]
+fn test_cov_measures_python_subprocesses() {
+    let context = TestContext::with_files([
+        (
+            "cli.py",
+            r#"
+import sys
+
+def main():
+    print(int(sys.argv[1]) * 2)
+
+if __name__ == "__main__":
+    main()
+"#,
+        ),
+        (
+            "test_cli.py",
+            r#"
+import subprocess, sys
+
+def test_cli():
+    out = subprocess.run([sys.executable, "cli.py", "21"], capture_output=True, text=True)
+    assert out.stdout.strip() == "42", out.stderr
+"#,
+        ),
+    ]);
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--status-level=none")
+            .arg("test_cli.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name          Stmts   Miss   Cover
+    [LONG-LINE]
+    cli.py            5      0    100%
+    test_cli.py       4      0    100%
+    [LONG-LINE]
+    TOTAL             9      0    100%
+
+    ----- stderr -----
+    "
     );
+}
+
+/// The `sys.settrace` backend hands the tracer to threads started by tests.
+#[test]
+fn test_cov_traces_python_threads_with_settrace() {
+    let context = TestContext::with_file("test_threaded.py", THREADED_TEST);
 
     assert_cmd_snapshot!(
         context.command_no_parallel()
+            .env("KARVA_COVERAGE_TRACER", "settrace")
             .arg("--cov")
             .arg("--status-level=none")
             .arg("test_threaded.py"),
@@ -977,6 +1244,115 @@ def test_thread_runs():
     );
 }
 
+/// Code run by a `multiprocessing` child is merged into the report.
+#[test]
+fn test_cov_measures_multiprocessing_children() {
+    let context = TestContext::with_file("test_mp.py", MULTIPROCESSING_TEST);
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--status-level=none")
+            .arg("test_mp.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name         Stmts   Miss   Cover
+    [LONG-LINE]
+    test_mp.py      12      0    100%
+    [LONG-LINE]
+    TOTAL           12      0    100%
+
+    ----- stderr -----
+    "
+    );
+}
+
+#[test]
+fn test_cov_measures_multiprocessing_children_with_settrace() {
+    let context = TestContext::with_file("test_mp.py", MULTIPROCESSING_TEST);
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .env("KARVA_COVERAGE_TRACER", "settrace")
+            .arg("--cov")
+            .arg("--status-level=none")
+            .arg("test_mp.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name         Stmts   Miss   Cover
+    [LONG-LINE]
+    test_mp.py      12      0    100%
+    [LONG-LINE]
+    TOTAL           12      0    100%
+
+    ----- stderr -----
+    "
+    );
+}
+
+/// A forked child starts from a copy of the worker's tracer, and must
+/// still save what it ran itself.
+#[cfg(unix)]
+#[test]
+fn test_cov_measures_forked_multiprocessing_children() {
+    let context = TestContext::with_file(
+        "test_mp.py",
+        r#"
+import multiprocessing
+import warnings
+
+def square(x):
+    return x * x
+
+def work(queue):
+    queue.put(square(3))
+
+def test_forked_process():
+    ctx = multiprocessing.get_context("fork")
+    queue = ctx.Queue()
+    process = ctx.Process(target=work, args=(queue,))
+    with warnings.catch_warnings():
+        # Python 3.12+ warns about forking a multi-threaded process.
+        warnings.simplefilter("ignore", DeprecationWarning)
+        process.start()
+    assert queue.get() == 9
+    process.join()
+"#,
+    );
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--status-level=none")
+            .arg("test_mp.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name         Stmts   Miss   Cover
+    [LONG-LINE]
+    test_mp.py      15      0    100%
+    [LONG-LINE]
+    TOTAL           15      0    100%
+
+    ----- stderr -----
+    "
+    );
+}
+
 #[test]
 fn test_cov_report_term_missing_from_config() {
     let context = TestContext::with_files([
@@ -1024,3 +1400,1029 @@ def test_only_covered():
     "
     );
 }
+
+#[test]
+fn test_cov_branch_reports_partial_branches() {
+    let context = TestContext::with_file(
+        "test_branch.py",
+        r"
+def clamp(x):
+    if x > 10:
+        x = 10
+    return x
+
+def test_clamp():
+    assert clamp(20) == 10
+",
+    );
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--cov-branch")
+            .arg("--cov-report=term-missing")
+            .arg("--status-level=none")
+            .arg("test_branch.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name             Stmts   Miss   Branch   BrPart   Cover   Missing
+    [LONG-LINE]
+    test_branch.py       6      0        2        1     88%   3->5
+    [LONG-LINE]
+    TOTAL                6      0        2        1     88%
+
+    ----- stderr -----
+    "
+    );
+}
+
+/// With branch coverage on, `fail-under` checks the total that counts
+/// branches alongside statements.
+#[test]
+fn test_cov_branch_from_config_counts_towards_fail_under() {
+    let context = TestContext::with_files([
+        (
+            "karva.toml",
+            r#"
+[profile.default.coverage]
+sources = [""]
+branch = true
+fail-under = 90
+"#,
+        ),
+        (
+            "test_branch.py",
+            r"
+def clamp(x):
+    if x > 10:
+        x = 10
+    return x
+
+def test_clamp():
+    assert clamp(20) == 10
+",
+        ),
+    ]);
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--status-level=none")
+            .arg("test_branch.py"),
+        @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name             Stmts   Miss   Branch   BrPart   Cover
+    [LONG-LINE]
+    test_branch.py       6      0        2        1     88%
+    [LONG-LINE]
+    TOTAL                6      0        2        1     88%
+
+    coverage failure: required total coverage of 90% not reached, total coverage was 87.50%
+
+    ----- stderr -----
+    "
+    );
+}
+
+#[test]
+fn test_cov_report_xml_and_lcov() {
+    let context = TestContext::with_file(
+        "test_branch.py",
+        r"
+def clamp(x):
+    if x > 10:
+        x = 10
+    return x
+
+def test_clamp():
+    assert clamp(20) == 10
+",
+    );
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--cov-branch")
+            .arg("--cov-report=xml:reports/coverage.xml")
+            .arg("--cov-report=lcov")
+            .arg("--status-level=none")
+            .arg("test_branch.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name             Stmts   Miss   Branch   BrPart   Cover
+    [LONG-LINE]
+    test_branch.py       6      0        2        1     88%
+    [LONG-LINE]
+    TOTAL                6      0        2        1     88%
+
+    ----- stderr -----
+    "
+    );
+
+    assert_snapshot!(context.read_file("coverage.lcov"), @"
+    SF:test_branch.py
+    DA:2,1
+    DA:3,1
+    DA:4,1
+    DA:5,1
+    DA:7,1
+    DA:8,1
+    BRDA:3,0,0,1
+    BRDA:3,0,1,0
+    BRF:2
+    BRH:1
+    LF:6
+    LH:6
+    end_of_record
+    ");
+
+    let _guard = bind_cobertura_filter();
+    assert_snapshot!(context.read_file("reports/coverage.xml"), @r#"
+    <?xml version="1.0" ?>
+    <coverage version="[VERSION]" timestamp="[TIMESTAMP]" lines-valid="6" lines-covered="6" line-rate="1" branches-valid="2" branches-covered="1" branch-rate="0.5" complexity="0">
+      <sources>
+        <source><temp_dir>/</source>
+      </sources>
+      <packages>
+        <package name="." line-rate="1" branch-rate="0.5" complexity="0">
+          <classes>
+            <class name="test_branch.py" filename="test_branch.py" complexity="0" line-rate="1" branch-rate="0.5">
+              <methods/>
+              <lines>
+                <line number="2" hits="1"/>
+                <line number="3" hits="1" branch="true" condition-coverage="50% (1/2)" missing-branches="5"/>
+                <line number="4" hits="1"/>
+                <line number="5" hits="1"/>
+                <line number="7" hits="1"/>
+                <line number="8" hits="1"/>
+              </lines>
+            </class>
+          </classes>
+        </package>
+      </packages>
+    </coverage>
+    "#);
+}
+
+#[test]
+fn test_cov_lcov_path_from_config() {
+    let context = TestContext::with_files([
+        (
+            "karva.toml",
+            r#"
+[profile.default.coverage]
+sources = [""]
+lcov-path = "out/coverage.lcov"
+"#,
+        ),
+        (
+            "test_lines.py",
+            r"
+def covered():
+    return 1
+
+def uncovered():
+    return 2
+
+def test_only_covered():
+    assert covered() == 1
+",
+        ),
+    ]);
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--status-level=none")
+            .arg("test_lines.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name            Stmts   Miss   Cover
+    [LONG-LINE]
+    test_lines.py       6      1     83%
+    [LONG-LINE]
+    TOTAL               6      1     83%
+
+    ----- stderr -----
+    "
+    );
+
+    assert_snapshot!(context.read_file("out/coverage.lcov"), @"
+    SF:test_lines.py
+    DA:2,1
+    DA:3,1
+    DA:5,1
+    DA:6,0
+    DA:8,1
+    DA:9,1
+    LF:6
+    LH:5
+    end_of_record
+    ");
+}
+
+#[test]
+fn test_cov_report_html() {
+    let context = TestContext::with_file(
+        "test_branch.py",
+        r"
+def clamp(x):
+    if x > 10:
+        x = 10
+    return x
+
+def unused():  # pragma: no cover
+    return 0
+
+def test_clamp():
+    assert clamp(20) == 10
+",
+    );
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--cov-branch")
+            .arg("--cov-report=html:reports/html")
+            .arg("--status-level=none")
+            .arg("test_branch.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name             Stmts   Miss   Branch   BrPart   Cover
+    [LONG-LINE]
+    test_branch.py       6      0        2        1     88%
+    [LONG-LINE]
+    TOTAL                6      0        2        1     88%
+
+    ----- stderr -----
+    "
+    );
+
+    assert_snapshot!(context.read_file("reports/html/index.html"), @r#"
+    <!DOCTYPE html>
+    <html lang="en">
+    <head>
+    <meta charset="utf-8">
+    <title>Coverage report: 88%</title>
+    <link rel="stylesheet" href="style.css">
+    </head>
+    <body>
+    <h1>Coverage report: <span class="pc">88%</span></h1>
+    <table class="index">
+    <thead>
+    <tr><th class="name">File</th><th>Statements</th><th>Missing</th><th>Excluded</th><th>Branches</th><th>Partial</th><th>Coverage</th></tr>
+    </thead>
+    <tbody>
+    <tr><td class="name"><a href="test_branch_py.html">test_branch.py</a></td><td>6</td><td>0</td><td>2</td><td>2</td><td>1</td><td>88%</td></tr>
+    </tbody>
+    <tfoot>
+    <tr><td class="name">Total</td><td>6</td><td>0</td><td>2</td><td>2</td><td>1</td><td>88%</td></tr>
+    </tfoot>
+    </table>
+    </body>
+    </html>
+    "#);
+
+    let page = context.read_file("reports/html/test_branch_py.html");
+    assert!(page.contains(r#"<div class="line par" id="t3">"#));
+    assert!(page.contains(r#"<span class="annotate">3 &#x219B; 5</span>"#));
+    assert!(page.contains(r#"<div class="line exc" id="t7">"#));
+    assert!(page.contains(r#"<div class="line exc" id="t8">"#));
+    assert!(page.contains(r#"<div class="line run" id="t11">"#));
+    assert!(page.contains(r#"<span class="kw">def</span> clamp(x):"#));
+    assert!(context.read_file("reports/html/style.css").contains(".mis"));
+}
+
+#[test]
+fn test_cov_report_json_with_contexts() {
+    let context = TestContext::with_file(
+        "test_calc.py",
+        r"
+def add(a, b):
+    return a + b
+
+def unused():
+    return 0
+
+def test_add():
+    assert add(1, 2) == 3
+",
+    );
+
+    context
+        .command_no_parallel()
+        .arg("--cov")
+        .arg("--cov-context")
+        .arg("--cov-report=json:out/coverage.json")
+        .arg("test_calc.py")
+        .output()
+        .unwrap();
+
+    let mut settings = insta::Settings::clone_current();
+    settings.add_filter(r#""version": "[^"]+""#, r#""version": "[VERSION]""#);
+    let _guard = settings.bind_to_scope();
+
+    assert_snapshot!(context.read_file("out/coverage.json"), @r#"
+    {
+      "meta": {
+        "version": "[VERSION]",
+        "branch_coverage": false,
+        "show_contexts": true
+      },
+      "files": {
+        "test_calc.py": {
+          "executed_lines": [
+            2,
+            3,
+            5,
+            8,
+            9
+          ],
+          "summary": {
+            "covered_lines": 5,
+            "num_statements": 6,
+            "percent_covered": 83.33333333333334,
+            "missing_lines": 1,
+            "excluded_lines": 0
+          },
+          "missing_lines": [
+            6
+          ],
+          "excluded_lines": [],
+          "contexts": {
+            "3": [
+              "test_calc::test_add"
+            ],
+            "9": [
+              "test_calc::test_add"
+            ]
+          }
+        }
+      },
+      "totals": {
+        "covered_lines": 5,
+        "num_statements": 6,
+        "percent_covered": 83.33333333333334,
+        "missing_lines": 1,
+        "excluded_lines": 0
+      }
+    }
+    "#);
+}
+
+/// `who-covers` lists every test that ran a line. Lines run on import are
+/// not attributed to a test.
+#[test]
+fn test_cov_context_who_covers() {
+    let context = TestContext::with_files([
+        (
+            "calc.py",
+            r"
+def add(a, b):
+    return a + b
+
+def sub(a, b):
+    return a - b
+",
+        ),
+        (
+            "test_calc.py",
+            r"
+from calc import add, sub
+
+def test_add():
+    assert add(1, 2) == 3
+
+def test_add_and_sub():
+    assert sub(add(1, 2), 2) == 1
+",
+        ),
+    ]);
+
+    context
+        .command_no_parallel()
+        .arg("--cov")
+        .arg("--cov-context")
+        .output()
+        .unwrap();
+
+    assert_cmd_snapshot!(context.coverage("who-covers").arg("calc.py:3"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    test_calc::test_add
+    test_calc::test_add_and_sub
+
+    ----- stderr -----
+    ");
+
+    assert_cmd_snapshot!(context.coverage("who-covers").arg("calc.py:6"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    test_calc::test_add_and_sub
+
+    ----- stderr -----
+    ");
+
+    assert_cmd_snapshot!(context.coverage("who-covers").arg("calc.py:2"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    No tests ran calc.py:2.
+
+    ----- stderr -----
+    ");
+}
+
+/// Function fixtures are set up before the test's name is known, so neither
+/// their set-up nor their teardown is attributed to the test.
+#[test]
+fn test_cov_context_skips_fixture_setup_and_teardown() {
+    let context = TestContext::with_files([
+        (
+            "db.py",
+            r"
+def open_db():
+    return {}
+
+def close_db(db):
+    db.clear()
+
+def put(db, key):
+    db[key] = 1
+",
+        ),
+        (
+            "test_db.py",
+            r"
+import karva
+from db import close_db, open_db, put
+
+@karva.fixture
+def db():
+    db = open_db()
+    yield db
+    close_db(db)
+
+def test_put(db):
+    put(db, 'a')
+",
+        ),
+    ]);
+
+    context
+        .command_no_parallel()
+        .arg("--cov")
+        .arg("--cov-context")
+        .output()
+        .unwrap();
+
+    assert_cmd_snapshot!(context.coverage("who-covers").arg("db.py:9"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    test_db::test_put
+
+    ----- stderr -----
+    ");
+
+    assert_cmd_snapshot!(context.coverage("who-covers").arg("db.py:3"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    No tests ran db.py:3.
+
+    ----- stderr -----
+    ");
+
+    assert_cmd_snapshot!(context.coverage("who-covers").arg("db.py:6"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    No tests ran db.py:6.
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_cov_context_from_config_in_html_report() {
+    let context = TestContext::with_files([
+        (
+            "karva.toml",
+            r#"
+[profile.default.coverage]
+sources = [""]
+context = true
+html-dir = "htmlcov"
+"#,
+        ),
+        (
+            "test_calc.py",
+            r"
+def add(a, b):
+    return a + b
+
+def test_add():
+    assert add(1, 2) == 3
+
+def test_add_twice():
+    assert add(add(1, 1), 1) == 3
+",
+        ),
+    ]);
+
+    context.command_no_parallel().output().unwrap();
+
+    let page = context.read_file("htmlcov/test_calc_py.html");
+    assert!(page.contains(
+        r#"<details class="ctx"><summary>2 tests</summary><div>test_calc::test_add</div><div>test_calc::test_add_twice</div></details>"#
+    ));
+}
+
+#[test]
+fn test_who_covers_without_contexts() {
+    let context = TestContext::with_file(
+        "test_simple.py",
+        r"
+def test_one():
+    assert 1 + 1 == 2
+",
+    );
+
+    assert_cmd_snapshot!(context.coverage("who-covers").arg("test_simple.py:3"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    No coverage data found. Run `karva test --cov --cov-context` first.
+
+    ----- stderr -----
+    ");
+
+    context.command_no_parallel().arg("--cov").output().unwrap();
+
+    assert_cmd_snapshot!(context.coverage("who-covers").arg("test_simple.py:3"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    The most recent coverage run did not record which tests ran each line. Run `karva test --cov --cov-context` first.
+
+    ----- stderr -----
+    ");
+}
+
+/// `exclude-lines` patterns exclude matching lines and the blocks they
+/// start, and a pattern on a decorator excludes the decorated definition.
+#[test]
+fn test_cov_exclude_lines_from_config() {
+    let context = TestContext::with_files([
+        (
+            "karva.toml",
+            r#"
+[profile.default.coverage]
+sources = [""]
+exclude-lines = ["if TYPE_CHECKING:", "raise NotImplementedError", "@overload"]
+"#,
+        ),
+        (
+            "test_exclude.py",
+            r"
+from typing import TYPE_CHECKING, overload
+
+if TYPE_CHECKING:
+    from collections.abc import Sequence
+
+@overload
+def double(x: int) -> int: ...
+@overload
+def double(x: str) -> str: ...
+def double(x):
+    return x * 2
+
+class Base:
+    def run(self):
+        raise NotImplementedError
+
+def test_double():
+    assert double(2) == 4
+",
+        ),
+    ]);
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--status-level=none")
+            .arg("test_exclude.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name              Stmts   Miss   Cover
+    [LONG-LINE]
+    test_exclude.py       7      0    100%
+    [LONG-LINE]
+    TOTAL                 7      0    100%
+
+    ----- stderr -----
+    "
+    );
+}
+
+/// Only files matching `--cov-include` are measured, minus those matching
+/// `--cov-omit`.
+#[test]
+fn test_cov_include_and_omit() {
+    let context = TestContext::with_files([
+        ("src/__init__.py", ""),
+        (
+            "src/app.py",
+            r"
+def used():
+    return 1
+",
+        ),
+        ("src/migrations/__init__.py", ""),
+        ("src/migrations/m0001.py", "VALUE = 1\n"),
+        (
+            "test_app.py",
+            r"
+from src.app import used
+from src.migrations import m0001
+
+def test_used():
+    assert used() == m0001.VALUE
+",
+        ),
+    ]);
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--cov-include=src/*")
+            .arg("--cov-omit=*/migrations/*")
+            .arg("--status-level=none")
+            .arg("test_app.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name         Stmts   Miss   Cover
+    [LONG-LINE]
+    src/app.py       2      0    100%
+    [LONG-LINE]
+    TOTAL            2      0    100%
+
+    ----- stderr -----
+    "
+    );
+}
+
+/// A line matching `--cov-partial-branch` is no longer counted as a branch.
+#[test]
+fn test_cov_partial_branch_drops_branch() {
+    let context = TestContext::with_file(
+        "test_branch.py",
+        r"
+def clamp(x):
+    if x > 10:
+        x = 10
+    return x
+
+def test_clamp():
+    assert clamp(20) == 10
+",
+    );
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--cov-branch")
+            .arg("--cov-partial-branch=if x > 10:")
+            .arg("--status-level=none")
+            .arg("test_branch.py"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Name             Stmts   Miss   Branch   BrPart   Cover
+    [LONG-LINE]
+    test_branch.py       6      0        0        0    100%
+    [LONG-LINE]
+    TOTAL                6      0        0        0    100%
+
+    ----- stderr -----
+    "
+    );
+}
+
+#[test]
+fn test_cov_invalid_exclude_line_pattern() {
+    let context = TestContext::with_file("test.py", "def test_one(): pass\n");
+
+    assert_cmd_snapshot!(
+        context.command_no_parallel()
+            .arg("--cov")
+            .arg("--cov-exclude-line=(")
+            .arg("test.py"),
+        @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Karva failed
+      Cause: invalid `exclude-lines` pattern
+      Cause: regex parse error:
+        (
+        ^
+    error: unclosed group
+    "
+    );
+}
+
+/// Data saved from separate runs, like CI shards, is merged by `karva
+/// coverage combine` and reported on without rerunning the tests.
+#[test]
+fn test_coverage_combine_runs_and_report() {
+    let context = TestContext::with_files([
+        (
+            "calc.py",
+            r"
+def add(a, b):
+    return a + b
+
+def sub(a, b):
+    return a - b
+",
+        ),
+        (
+            "test_a.py",
+            r"
+from calc import add
+
+def test_add():
+    assert add(1, 2) == 3
+",
+        ),
+        (
+            "test_b.py",
+            r"
+from calc import sub
+
+def test_sub():
+    assert sub(3, 2) == 1
+",
+        ),
+    ]);
+
+    assert_cmd_snapshot!(context.coverage("combine"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    No coverage data found. Run `karva test --cov` first.
+
+    ----- stderr -----
+    ");
+
+    context
+        .command_no_parallel()
+        .arg("--cov")
+        .arg("test_a.py")
+        .output()
+        .unwrap();
+    assert_cmd_snapshot!(context.coverage("combine").arg("-o").arg("shard-1.json"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Combined 1 data file(s) covering 3 source file(s) into shard-1.json.
+
+    ----- stderr -----
+    ");
+
+    context
+        .command_no_parallel()
+        .arg("--cov")
+        .arg("test_b.py")
+        .output()
+        .unwrap();
+    context
+        .coverage("combine")
+        .arg("-o")
+        .arg("shard-2.json")
+        .output()
+        .unwrap();
+
+    assert_cmd_snapshot!(
+        context
+            .coverage("report")
+            .arg("shard-1.json")
+            .arg("shard-2.json")
+            .arg("--report=term-missing"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    Name        Stmts   Miss   Cover
+    [LONG-LINE]
+    calc.py         4      0    100%
+    test_a.py       3      0    100%
+    test_b.py       3      0    100%
+    [LONG-LINE]
+    TOTAL          10      0    100%
+
+    ----- stderr -----
+    "
+    );
+}
+
+/// `--remap` moves paths recorded on another machine onto the local
+/// checkout, and `--fail-under` fails the report.
+#[test]
+fn test_coverage_combine_remaps_paths() {
+    let context = TestContext::with_files([
+        (
+            "calc.py",
+            r"
+def add(a, b):
+    return a + b
+
+def sub(a, b):
+    return a - b
+",
+        ),
+        (
+            "shard.json",
+            r#"{"branch": false, "files": {"/ci/build/calc.py": {"executable": [2, 3, 5, 6], "executed": [2, 3, 5]}}}"#,
+        ),
+    ]);
+
+    assert_cmd_snapshot!(
+        context
+            .coverage("combine")
+            .arg("shard.json")
+            .arg("--remap=/ci/build=.")
+            .arg("--output=combined.json"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Combined 1 data file(s) covering 1 source file(s) into combined.json.
+
+    ----- stderr -----
+    "
+    );
+
+    assert_cmd_snapshot!(
+        context
+            .coverage("report")
+            .arg("combined.json")
+            .arg("--report=term-missing")
+            .arg("--fail-under=80"),
+        @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    Name      Stmts   Miss   Cover   Missing
+    [LONG-LINE]
+    calc.py       4      1     75%   6
+    [LONG-LINE]
+    TOTAL         4      1     75%
+
+    coverage failure: required total coverage of 80% not reached, total coverage was 75.00%
+
+    ----- stderr -----
+    "
+    );
+}
+
+/// Without files, `karva coverage report` reads the data written by
+/// `karva coverage combine` and applies the `[coverage]` settings.
+#[test]
+fn test_coverage_report_uses_combined_data_and_config() {
+    let context = TestContext::with_files([
+        (
+            "karva.toml",
+            r#"
+[profile.default.coverage]
+omit = ["helper.py"]
+report = "term-missing"
+fail-under = 80
+"#,
+        ),
+        (
+            "calc.py",
+            r"
+def add(a, b):
+    return a + b
+
+def sub(a, b):
+    return a - b
+",
+        ),
+        ("helper.py", "VALUE = 1\n"),
+        (
+            "shard.json",
+            r#"{"branch": false, "files": {"/ci/build/calc.py": {"executable": [2, 3, 5, 6], "executed": [2, 3, 5]}, "/ci/build/helper.py": {"executable": [1], "executed": [1]}}}"#,
+        ),
+    ]);
+
+    context
+        .coverage("combine")
+        .arg("shard.json")
+        .arg("--remap=/ci/build=.")
+        .output()
+        .unwrap();
+
+    assert_cmd_snapshot!(context.coverage("report"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    Name      Stmts   Miss   Cover   Missing
+    [LONG-LINE]
+    calc.py       4      1     75%   6
+    [LONG-LINE]
+    TOTAL         4      1     75%
+
+    coverage failure: required total coverage of 80% not reached, total coverage was 75.00%
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_coverage_erase() {
+    let context = TestContext::with_file(
+        "test_simple.py",
+        r"
+def test_one():
+    assert 1 + 1 == 2
+",
+    );
+
+    context.command_no_parallel().arg("--cov").output().unwrap();
+    context.coverage("combine").output().unwrap();
+    assert!(context.root().join(".karva_coverage.json").exists());
+
+    assert_cmd_snapshot!(context.coverage("erase"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Removed 2 coverage data file(s).
+
+    ----- stderr -----
+    ");
+
+    assert_cmd_snapshot!(context.coverage("report"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    No coverage data found. Run `karva test --cov` first.
+
+    ----- stderr -----
+    ");
+
+    assert_cmd_snapshot!(context.coverage("erase"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    No coverage data to erase.
+
+    ----- stderr -----
+    ");
+}
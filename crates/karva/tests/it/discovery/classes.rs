@@ -0,0 +1,340 @@
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+const MATH_TESTS: &str = r"
+def test_top_level():
+    assert True
+
+class TestMath:
+    def test_add(self):
+        assert 1 + 1 == 2
+
+    def test_subtract(self):
+        assert 2 - 1 == 1
+
+    def helper(self):
+        assert False
+";
+
+#[test]
+fn test_class_methods_are_collected() {
+    let context = TestContext::with_file("test.py", MATH_TESTS);
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 3 tests across 1 worker
+            PASS [TIME] test::test_top_level
+            PASS [TIME] test::TestMath::test_add
+            PASS [TIME] test::TestMath::test_subtract
+    ────────────
+         Summary [TIME] 3 tests run: 3 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+/// Classes without the prefix, and classes that define `__init__`, are not
+/// collected.
+#[test]
+fn test_classes_not_collected() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+class Helper:
+    def test_not_collected(self):
+        assert False
+
+class TestWithInit:
+    def __init__(self, value):
+        self.value = value
+
+    def test_not_collected(self):
+        assert False
+
+class TestCollected:
+    def test_collected(self):
+        assert True
+",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::TestCollected::test_collected
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_class_instance_per_test() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+class TestState:
+    def test_first(self):
+        self.items = [1]
+        assert self.items == [1]
+
+    def test_second(self):
+        assert not hasattr(self, "items")
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test::TestState::test_first
+            PASS [TIME] test::TestState::test_second
+    ────────────
+         Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_class_fixture_methods() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture
+def name():
+    return "module"
+
+@karva.fixture
+def greeting():
+    return "hello"
+
+class TestFixtures:
+    @karva.fixture(auto_use=True)
+    def setup(self):
+        self.ready = True
+
+    @karva.fixture
+    def name(self, greeting):
+        return f"{greeting} class"
+
+    def test_auto_use(self):
+        assert self.ready
+
+    def test_method_fixture(self, name):
+        assert name == "hello class"
+
+def test_module_fixture(name):
+    assert name == "module"
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 3 tests across 1 worker
+            PASS [TIME] test::test_module_fixture(name=module)
+            PASS [TIME] test::TestFixtures::test_auto_use
+            PASS [TIME] test::TestFixtures::test_method_fixture(name=hello class)
+    ────────────
+         Summary [TIME] 3 tests run: 3 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_class_static_method_fixture() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture
+def greeting():
+    return "hello"
+
+class TestFixtures:
+    @staticmethod
+    @karva.fixture
+    def name(greeting):
+        return f"{greeting} static"
+
+    def test_static_fixture(self, name):
+        assert name == "hello static"
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::TestFixtures::test_static_fixture(name=hello static)
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_same_named_fixtures_of_classes_are_cached_separately() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+class TestFirst:
+    @karva.fixture(scope="module")
+    def value(self):
+        return "first"
+
+    def test_value(self, value):
+        assert value == "first"
+
+class TestSecond:
+    @karva.fixture(scope="module")
+    def value(self):
+        return "second"
+
+    def test_value(self, value):
+        assert value == "second"
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test::TestFirst::test_value(value=first)
+            PASS [TIME] test::TestSecond::test_value(value=second)
+    ────────────
+         Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_class_tags_apply_to_methods() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+@karva.tags.parametrize('value', [1, 2])
+class TestValues:
+    def test_positive(self, value):
+        assert value > 0
+
+    @karva.tags.skip
+    def test_skipped(self, value):
+        assert False
+",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test::TestValues::test_positive(value=1)
+            PASS [TIME] test::TestValues::test_positive(value=2)
+    ────────────
+         Summary [TIME] 4 tests run: 2 passed, 2 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_select_class_method() {
+    let context = TestContext::with_file("test.py", MATH_TESTS);
+
+    assert_cmd_snapshot!(context.command().arg("test.py::TestMath::test_add"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::TestMath::test_add
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_select_class() {
+    let context = TestContext::with_file("test.py", MATH_TESTS);
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("test.py::TestMath"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test::TestMath::test_add
+            PASS [TIME] test::TestMath::test_subtract
+    ────────────
+         Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_filter_by_class_name() {
+    let context = TestContext::with_file("test.py", MATH_TESTS);
+
+    assert_cmd_snapshot!(context.command_no_parallel().args(["-E", "test(~TestMath::)"]), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 3 tests across 1 worker
+            PASS [TIME] test::TestMath::test_add
+            PASS [TIME] test::TestMath::test_subtract
+    ────────────
+         Summary [TIME] 3 tests run: 2 passed, 1 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_custom_class_prefix() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+class TestIgnored:
+    def test_ignored(self):
+        assert False
+
+class CheckMath:
+    def test_add(self):
+        assert 1 + 1 == 2
+",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().args(["--test-class-prefix", "Check"]), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::CheckMath::test_add
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
@@ -1,3 +1,4 @@
+mod classes;
 mod edge_cases;
 mod git_boundary;
 mod nested_layouts;
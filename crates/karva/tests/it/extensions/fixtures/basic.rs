@@ -405,6 +405,50 @@ def test_2(x_function):
     }
 }
 
+#[rstest]
+fn test_dynamic_fixture_scope_config(#[values("pytest", "karva")] framework: &str) {
+    let context = TestContext::with_file(
+        "test.py",
+        &format!(
+            r#"
+from {framework} import fixture
+
+def dynamic_scope(fixture_name, config):
+    if (config.rootpath / "test.py").exists() and config.getoption("--keep", "session") == "session":
+        return "session"
+    return "function"
+
+@fixture(scope=dynamic_scope)
+def x():
+    return []
+
+def test_1(x):
+    x.append(1)
+    assert x == [1]
+
+def test_2(x):
+    x.append(2)
+    assert x == [1, 2]
+    "#,
+        ),
+    );
+
+    allow_duplicates! {
+        assert_cmd_snapshot!(context.command_no_parallel(), @"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+            Starting 2 tests across 1 worker
+                PASS [TIME] test::test_1(x=[])
+                PASS [TIME] test::test_2(x=[1])
+        ────────────
+             Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+        ----- stderr -----
+        ")
+    };
+}
+
 #[test]
 fn test_fixture_override_in_test_modules() {
     let context = TestContext::with_files([
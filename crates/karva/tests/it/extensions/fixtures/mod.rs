@@ -5,6 +5,8 @@ pub mod decorators;
 pub mod generators;
 pub mod invalid;
 pub mod more_builtins;
+pub mod params;
 pub mod parametrized;
 pub mod pytest_monkeypatch;
 pub mod pytest_vendor_tests;
+pub mod request;
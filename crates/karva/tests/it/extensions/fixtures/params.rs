@@ -0,0 +1,370 @@
+use insta::allow_duplicates;
+use insta_cmd::assert_cmd_snapshot;
+use rstest::rstest;
+
+use crate::common::TestContext;
+
+#[rstest]
+fn test_fixture_params(#[values("pytest", "karva")] framework: &str) {
+    let context = TestContext::with_file(
+        "test.py",
+        &format!(
+            r"
+                import {framework}
+
+                @{framework}.fixture(params=[1, 2, 3])
+                def number(request):
+                    return request.param * 10
+
+                def test_number(number):
+                    assert number in (10, 20, 30)
+"
+        ),
+    );
+
+    allow_duplicates! {
+        assert_cmd_snapshot!(context.command_no_parallel(), @"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+            Starting 1 test across 1 worker
+                PASS [TIME] test::test_number(number=1)
+                PASS [TIME] test::test_number(number=2)
+                PASS [TIME] test::test_number(number=3)
+        ────────────
+             Summary [TIME] 3 tests run: 3 passed, 0 skipped
+
+        ----- stderr -----
+        ");
+    }
+}
+
+#[test]
+fn test_fixture_params_ids() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture(params=[0, 1, 100], ids=["zero", None, "many"])
+def count(request):
+    return request.param
+
+@karva.fixture(params=[(1, 2), (3, 4)], ids=lambda pair: f"{pair[0]}-{pair[1]}")
+def pair(request):
+    return request.param
+
+def test_count(count):
+    assert count >= 0
+
+def test_pair(pair):
+    assert len(pair) == 2
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test::test_count(count=zero)
+            PASS [TIME] test::test_count(count=1)
+            PASS [TIME] test::test_count(count=many)
+            PASS [TIME] test::test_pair(pair=1-2)
+            PASS [TIME] test::test_pair(pair=3-4)
+    ────────────
+         Summary [TIME] 5 tests run: 5 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_fixture_params_ids_length_mismatch() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture(params=[0, 1], ids=["zero"])
+def count(request):
+    return request.param
+
+def test_count(count):
+    assert count >= 0
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+    diagnostics:
+
+    error[failed-to-import-module]: Failed to import python module `test`: `ids` must have one id for each of the 2 params, got 1
+
+    ────────────
+         Summary [TIME] 0 tests run: 0 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+/// A test that depends on a parametrized fixture through another fixture
+/// runs once per param, and shows the param in its name.
+#[test]
+fn test_fixture_params_indirect() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture(params=["sqlite", "postgres"])
+def backend(request):
+    return request.param
+
+@karva.fixture
+def url(backend):
+    return f"{backend}://localhost"
+
+def test_url(url):
+    assert url.endswith("://localhost")
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_url(backend=sqlite, url=sqlite://localhost)
+            PASS [TIME] test::test_url(backend=postgres, url=postgres://localhost)
+    ────────────
+         Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_fixture_params_with_parametrize() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture(params=["a", "b"])
+def letter(request):
+    return request.param
+
+@karva.tags.parametrize("number", [1, 2])
+def test_combination(letter, number):
+    assert letter in ("a", "b")
+    assert number in (1, 2)
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_combination(letter=a, number=1)
+            PASS [TIME] test::test_combination(letter=a, number=2)
+            PASS [TIME] test::test_combination(letter=b, number=1)
+            PASS [TIME] test::test_combination(letter=b, number=2)
+    ────────────
+         Summary [TIME] 4 tests run: 4 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_multiple_parametrized_fixtures() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture(params=["x", "y"])
+def first(request):
+    return request.param
+
+@karva.fixture(params=[1, 2])
+def second(request):
+    return request.param
+
+def test_both(first, second):
+    pass
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_both(first=x, second=1)
+            PASS [TIME] test::test_both(first=x, second=2)
+            PASS [TIME] test::test_both(first=y, second=1)
+            PASS [TIME] test::test_both(first=y, second=2)
+    ────────────
+         Summary [TIME] 4 tests run: 4 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+/// Two fixtures depending on the same parametrized fixture always see the
+/// same param within a test.
+#[test]
+fn test_shared_parametrized_dependency() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture(params=[1, 2])
+def base(request):
+    return request.param
+
+@karva.fixture
+def double(base):
+    return base * 2
+
+@karva.fixture
+def triple(base):
+    return base * 3
+
+def test_shared(double, triple):
+    assert double * 3 == triple * 2
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_shared(base=1, double=2, triple=3)
+            PASS [TIME] test::test_shared(base=2, double=4, triple=6)
+    ────────────
+         Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_module_scoped_fixture_params() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+setups = []
+
+@karva.fixture(scope="module", params=["a", "b"])
+def resource(request):
+    setups.append(request.param)
+    return request.param
+
+def test_first(resource):
+    assert resource in ("a", "b")
+
+def test_second(resource):
+    assert resource in ("a", "b")
+
+def test_setups():
+    assert setups == ["a", "b"]
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 3 tests across 1 worker
+            PASS [TIME] test::test_first(resource=a)
+            PASS [TIME] test::test_first(resource=b)
+            PASS [TIME] test::test_second(resource=a)
+            PASS [TIME] test::test_second(resource=b)
+            PASS [TIME] test::test_setups
+    ────────────
+         Summary [TIME] 5 tests run: 5 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_fixture_param_tags() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+@karva.fixture(params=[1, karva.param(2, tags=[karva.tags.skip])])
+def number(request):
+    return request.param
+
+def test_number(number):
+    assert number == 1
+",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_number(number=1)
+    ────────────
+         Summary [TIME] 2 tests run: 1 passed, 1 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_generator_fixture_params_teardown() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+events = []
+
+@karva.fixture(params=["a", "b"])
+def resource(request):
+    events.append(f"setup {request.param}")
+    yield request.param
+    events.append(f"teardown {request.param}")
+
+def test_resource(resource):
+    events.append(f"test {resource}")
+
+def test_events():
+    assert events == [
+        "setup a", "test a", "teardown a",
+        "setup b", "test b", "teardown b",
+    ]
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test::test_resource(resource=a)
+            PASS [TIME] test::test_resource(resource=b)
+            PASS [TIME] test::test_events
+    ────────────
+         Summary [TIME] 3 tests run: 3 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
@@ -0,0 +1,335 @@
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+/// A fixture's `request` shows the test, module and package it is set up
+/// for, as far as the fixture's scope allows.
+#[test]
+fn test_request_context() {
+    let context = TestContext::with_files([
+        (
+            "conftest.py",
+            r#"
+import karva
+
+def describe(request):
+    return "|".join(
+        str(value)
+        for value in (
+            request.scope,
+            request.test_name,
+            request.module,
+            request.package,
+            request.tags,
+        )
+    )
+
+@karva.fixture
+def function_request(request):
+    return describe(request)
+
+@karva.fixture(scope="module")
+def module_request(request):
+    return describe(request)
+
+@karva.fixture(scope="session")
+def session_request(request):
+    return describe(request)
+"#,
+        ),
+        (
+            "tests/test_request.py",
+            r"
+import karva
+
+@karva.tags.slow
+def test_function(function_request):
+    pass
+
+def test_module(module_request):
+    pass
+
+def test_session(session_request):
+    pass
+",
+        ),
+    ]);
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 3 tests across 1 worker
+            PASS [TIME] tests.test_request::test_function(function_request=function|tests.test_request::test_function|tests.test_request|tests|['slow'])
+            PASS [TIME] tests.test_request::test_module(module_request=module|None|tests.test_request|tests|[])
+            PASS [TIME] tests.test_request::test_session(session_request=session|None|None|None|[])
+    ────────────
+         Summary [TIME] 3 tests run: 3 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_request_fixture_name() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture(name="renamed")
+def original(request):
+    return request.fixture_name
+
+def test_name(renamed):
+    assert renamed == "renamed"
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_name(renamed=renamed)
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+/// Finalizers run when the fixture's scope ends, after the fixture's own
+/// teardown and in the reverse order they were added.
+#[test]
+fn test_request_addfinalizer() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+events = []
+
+def write_events():
+    with open("events.txt", "w") as f:
+        f.write("\n".join(events))
+
+@karva.fixture(scope="module")
+def module_resource(request):
+    request.addfinalizer(write_events)
+    yield
+    events.append("module teardown")
+
+@karva.fixture
+def resource(request):
+    events.append("setup")
+    request.addfinalizer(lambda: events.append("first finalizer"))
+    request.addfinalizer(lambda: events.append("second finalizer"))
+    yield "value"
+    events.append("teardown")
+
+def test_one(module_resource, resource):
+    events.append("test_one")
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_one(module_resource=None, resource=value)
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    insta::assert_snapshot!(context.read_file("events.txt"), @"
+    setup
+    test_one
+    teardown
+    second finalizer
+    first finalizer
+    module teardown
+    ");
+}
+
+#[test]
+fn test_request_addfinalizer_failure() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def fail():
+    raise RuntimeError("cleanup failed")
+
+@karva.fixture
+def resource(request):
+    request.addfinalizer(fail)
+    return 1
+
+def test_resource(resource):
+    pass
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_resource(resource=1)
+
+    diagnostics:
+
+    error[invalid-fixture-finalizer]: Discovered an invalid fixture finalizer `resource`
+     --> test.py:8:5
+      |
+    8 | def resource(request):
+      |     ^^^^^^^^
+      |
+    info: Finalizer raised an exception: cleanup failed
+
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_request_getfixturevalue() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture
+def sqlite():
+    return "sqlite"
+
+@karva.fixture(scope="module")
+def postgres():
+    return "postgres"
+
+@karva.fixture(params=["sqlite", "postgres"])
+def backend(request):
+    if request.param == "sqlite":
+        return request.getfixturevalue("sqlite")
+    return request.getfixturevalue("postgres")
+
+def test_backend(backend):
+    assert backend in ("sqlite", "postgres")
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_backend(backend=sqlite)
+            PASS [TIME] test::test_backend(backend=postgres)
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+/// Only fixtures named with a string literal can be looked up.
+#[test]
+fn test_request_getfixturevalue_dynamic_name() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture
+def value():
+    return 1
+
+@karva.fixture
+def lookup(request):
+    name = "value"
+    return request.getfixturevalue(name)
+
+def test_lookup(lookup):
+    pass
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            FAIL [TIME] test::test_lookup
+
+    diagnostics:
+
+    error[dynamic-fixture-request]: Fixture `lookup` looks up a fixture by a dynamic name
+      --> test.py:11:36
+       |
+    11 |     return request.getfixturevalue(name)
+       |                                    ^^^^
+       |
+    info: Fixtures looked up with `request.getfixturevalue` must be named with a string literal
+
+    error[missing-fixtures]: Test `test_lookup` has missing fixtures
+      --> test.py:13:5
+       |
+    13 | def test_lookup(lookup):
+       |     ^^^^^^^^^^^
+       |
+    info: Missing fixtures: `lookup`
+    info: Fixture `lookup` failed here
+      --> test.py:11:5
+       |
+    11 |     return request.getfixturevalue(name)
+       |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+       |
+    info: Fixture `value` is not available to `lookup`. Fixtures looked up with `getfixturevalue` must exist and be named with a string literal
+
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+/// A user-defined fixture named `request` replaces the built-in one.
+#[test]
+fn test_request_fixture_override() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.fixture
+def request():
+    return "custom"
+
+@karva.fixture
+def uses_request(request):
+    return request
+
+def test_override(uses_request):
+    assert uses_request == "custom"
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_override(uses_request=custom)
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
@@ -48,7 +48,9 @@ def test_hello():
     let content = context.read_file("snapshots/test__test_hello.snap.new");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_hello
+    source: test.py::test_hello
+    assertion_line: 5
+    expression: 'hello world'
     ---
     hello world
     ");
@@ -81,7 +83,9 @@ def test_hello():
     let content = context.read_file("snapshots/test__test_hello.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_hello
+    source: test.py::test_hello
+    assertion_line: 5
+    expression: 'hello world'
     ---
     hello world
     ");
@@ -209,7 +213,9 @@ def test_multi():
     let content_1 = context.read_file("snapshots/test__test_multi-0.snap");
     insta::assert_snapshot!(content_1, @r"
     ---
-    source: test.py:6::test_multi
+    source: test.py::test_multi
+    assertion_line: 6
+    expression: 'first'
     ---
     first
     ");
@@ -217,7 +223,9 @@ def test_multi():
     let content_2 = context.read_file("snapshots/test__test_multi-1.snap");
     insta::assert_snapshot!(content_2, @r"
     ---
-    source: test.py:7::test_multi
+    source: test.py::test_multi
+    assertion_line: 7
+    expression: 'second'
     ---
     second
     ");
@@ -225,7 +233,9 @@ def test_multi():
     let content_3 = context.read_file("snapshots/test__test_multi-2.snap");
     insta::assert_snapshot!(content_3, @r"
     ---
-    source: test.py:8::test_multi
+    source: test.py::test_multi
+    assertion_line: 8
+    expression: 'third'
     ---
     third
     ");
@@ -260,7 +270,10 @@ def test_param(x):
     let content = context.read_file("snapshots/test__test_param(x=1).snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:6::test_param(x=1)
+    source: test.py::test_param(x=1)
+    assertion_line: 6
+    expression: str(x)
+    variant: x=1
     ---
     1
     ");
@@ -286,7 +299,9 @@ def test_overwrite():
     let content = context.read_file("snapshots/test__test_overwrite.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_overwrite
+    source: test.py::test_overwrite
+    assertion_line: 5
+    expression: 'original'
     ---
     original
     ");
@@ -316,7 +331,9 @@ def test_overwrite():
     let content = context.read_file("snapshots/test__test_overwrite.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_overwrite
+    source: test.py::test_overwrite
+    assertion_line: 5
+    expression: 'updated'
     ---
     updated
     ");
@@ -471,7 +488,9 @@ def test_from_two():
     let one = context.read_file("snapshots/test_one__test_from_one.snap");
     insta::assert_snapshot!(one, @r"
     ---
-    source: test_one.py:5::test_from_one
+    source: test_one.py::test_from_one
+    assertion_line: 5
+    expression: 'from file one'
     ---
     from file one
     ");
@@ -479,7 +498,9 @@ def test_from_two():
     let two = context.read_file("snapshots/test_two__test_from_two.snap");
     insta::assert_snapshot!(two, @r"
     ---
-    source: test_two.py:5::test_from_two
+    source: test_two.py::test_from_two
+    assertion_line: 5
+    expression: 'from file two'
     ---
     from file two
     ");
@@ -557,7 +578,9 @@ def test_multi():
     let content_1 = context.read_file("snapshots/test__test_multi-0.snap");
     insta::assert_snapshot!(content_1, @r"
     ---
-    source: test.py:6::test_multi
+    source: test.py::test_multi
+    assertion_line: 6
+    expression: 'first'
     ---
     first
     ");
@@ -565,7 +588,9 @@ def test_multi():
     let content_2 = context.read_file("snapshots/test__test_multi-1.snap");
     insta::assert_snapshot!(content_2, @r"
     ---
-    source: test.py:7::test_multi
+    source: test.py::test_multi
+    assertion_line: 7
+    expression: 'second'
     ---
     second
     ");
@@ -599,7 +624,9 @@ def test_in_sub():
     let content = context.read_file("sub/snapshots/test_nested__test_in_sub.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test_nested.py:5::test_in_sub
+    source: test_nested.py::test_in_sub
+    assertion_line: 5
+    expression: 'sub value'
     ---
     sub value
     ");
@@ -637,3 +664,37 @@ def test_single():
         "Should NOT have -0 suffix for single unnamed snapshot"
     );
 }
+
+#[test]
+fn test_snapshot_settings_info_recorded_in_metadata() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_info():
+    with karva.snapshot_settings(info={"locale": "en", "retries": [1, 2]}):
+        karva.assert_snapshot('hello world')
+        "#,
+    );
+
+    let _ = context
+        .command_no_parallel()
+        .arg("--snapshot-update")
+        .output();
+
+    let content = context.read_file("snapshots/test__test_info.snap");
+    insta::assert_snapshot!(content, @r"
+    ---
+    source: test.py::test_info
+    assertion_line: 6
+    expression: 'hello world'
+    info:
+      locale: en
+      retries:
+        - 1
+        - 2
+    ---
+    hello world
+    ");
+}
@@ -31,7 +31,10 @@ def test_echo():
     let content = context.read_file("snapshots/test__test_echo.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:7::test_echo
+    source: test.py::test_echo
+    assertion_line: 7
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -89,7 +92,10 @@ def test_echo():
     let content = context.read_file("snapshots/test__test_echo.snap.new");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:7::test_echo
+    source: test.py::test_echo
+    assertion_line: 7
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -128,7 +134,10 @@ def test_failing_cmd():
     let content = context.read_file("snapshots/test__test_failing_cmd.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:7::test_failing_cmd
+    source: test.py::test_failing_cmd
+    assertion_line: 7
+    expression: cmd
+    serializer: cmd
     ---
     success: false
     exit_code: 1
@@ -167,7 +176,10 @@ def test_stderr():
     let content = context.read_file("snapshots/test__test_stderr.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:7::test_stderr
+    source: test.py::test_stderr
+    assertion_line: 7
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -207,7 +219,10 @@ def test_named_cmd():
     let content = context.read_file("snapshots/test__test_named_cmd--my_cmd.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:7::test_named_cmd
+    source: test.py::test_named_cmd
+    assertion_line: 7
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -250,7 +265,10 @@ def test_stdin():
     let content = context.read_file("snapshots/test__test_stdin.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:11::test_stdin
+    source: test.py::test_stdin
+    assertion_line: 11
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -295,7 +313,10 @@ def test_cwd():
     let content = context.read_file("snapshots/test__test_cwd.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:13::test_cwd
+    source: test.py::test_cwd
+    assertion_line: 13
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -337,7 +358,10 @@ def test_filtered():
     let content = context.read_file("snapshots/test__test_filtered.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:10::test_filtered
+    source: test.py::test_filtered
+    assertion_line: 10
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -380,7 +404,10 @@ def test_env():
     let content = context.read_file("snapshots/test__test_env.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:11::test_env
+    source: test.py::test_env
+    assertion_line: 11
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -546,7 +573,10 @@ def test_change():
     let content = context.read_file("snapshots/test__test_change.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:7::test_change
+    source: test.py::test_change
+    assertion_line: 7
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -681,7 +711,10 @@ def test_multi():
     let content_0 = context.read_file("snapshots/test__test_multi-0.snap");
     insta::assert_snapshot!(content_0, @"
     ---
-    source: test.py:8::test_multi
+    source: test.py::test_multi
+    assertion_line: 8
+    expression: cmd1
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -693,7 +726,10 @@ def test_multi():
     let content_1 = context.read_file("snapshots/test__test_multi-1.snap");
     insta::assert_snapshot!(content_1, @r"
     ---
-    source: test.py:10::test_multi
+    source: test.py::test_multi
+    assertion_line: 10
+    expression: cmd2
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -736,7 +772,10 @@ def test_envs():
     let content = context.read_file("snapshots/test__test_envs.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:11::test_envs
+    source: test.py::test_envs
+    assertion_line: 11
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -776,7 +815,10 @@ def test_multiline():
     let content = context.read_file("snapshots/test__test_multiline.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:8::test_multiline
+    source: test.py::test_multiline
+    assertion_line: 8
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -817,7 +859,10 @@ def test_exit42():
     let content = context.read_file("snapshots/test__test_exit42.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:7::test_exit42
+    source: test.py::test_exit42
+    assertion_line: 7
+    expression: cmd
+    serializer: cmd
     ---
     success: false
     exit_code: 42
@@ -860,7 +905,10 @@ def test_chained():
     let content = context.read_file("snapshots/test__test_chained.snap");
     insta::assert_snapshot!(content, @"
     ---
-    source: test.py:12::test_chained
+    source: test.py::test_chained
+    assertion_line: 12
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -899,7 +947,10 @@ def test_both():
     let content = context.read_file("snapshots/test__test_both.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:7::test_both
+    source: test.py::test_both
+    assertion_line: 7
+    expression: cmd
+    serializer: cmd
     ---
     success: false
     exit_code: 2
@@ -939,7 +990,10 @@ def test_silent():
     let content = context.read_file("snapshots/test__test_silent.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:7::test_silent
+    source: test.py::test_silent
+    assertion_line: 7
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -1057,7 +1111,10 @@ def test_multi_filter():
     let content = context.read_file("snapshots/test__test_multi_filter.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:11::test_multi_filter
+    source: test.py::test_multi_filter
+    assertion_line: 11
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -1102,7 +1159,10 @@ def test_nested():
     let content = context.read_file("snapshots/test__test_nested.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:13::test_nested
+    source: test.py::test_nested
+    assertion_line: 13
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -1147,7 +1207,10 @@ def test_filter_stderr():
     let content = context.read_file("snapshots/test__test_filter_stderr.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:13::test_filter_stderr
+    source: test.py::test_filter_stderr
+    assertion_line: 13
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: 0
@@ -1190,7 +1253,10 @@ def test_filter_named():
     let content = context.read_file("snapshots/test__test_filter_named--counted.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:10::test_filter_named
+    source: test.py::test_filter_named
+    assertion_line: 10
+    expression: cmd
+    serializer: cmd
     ---
     success: true
     exit_code: [N]
@@ -1235,7 +1301,10 @@ def test_dup_filtered():
     let content_0 = context.read_file("snapshots/test__test_dup_filtered-0.snap");
     insta::assert_snapshot!(content_0, @r"
     ---
-    source: test.py:11::test_dup_filtered
+    source: test.py::test_dup_filtered
+    assertion_line: 11
+    expression: cmd1
+    serializer: cmd
     ---
     success: true
     exit_code: [N]
@@ -1247,7 +1316,10 @@ def test_dup_filtered():
     let content_1 = context.read_file("snapshots/test__test_dup_filtered-1.snap");
     insta::assert_snapshot!(content_1, @r"
     ---
-    source: test.py:13::test_dup_filtered
+    source: test.py::test_dup_filtered
+    assertion_line: 13
+    expression: cmd2
+    serializer: cmd
     ---
     success: true
     exit_code: [N]
@@ -30,7 +30,9 @@ def test_hello():
     let content = context.read_file("snapshots/test__test_hello.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_hello
+    source: test.py::test_hello
+    assertion_line: 5
+    expression: 'hello world'
     ---
     hello world
     ");
@@ -95,6 +97,8 @@ def test_hello():
     exit_code: 0
     ----- stdout -----
     <temp_dir>/snapshots/test__test_hello.snap.new
+      Source: test.py:5::test_hello
+      Expression: 'hello world'
 
     1 pending snapshot(s).
 
@@ -286,8 +290,14 @@ def test_ccc():
     exit_code: 0
     ----- stdout -----
     <temp_dir>/snapshots/test__test_aaa.snap.new
+      Source: test.py:5::test_aaa
+      Expression: 'aaa'
     <temp_dir>/snapshots/test__test_bbb.snap.new
+      Source: test.py:8::test_bbb
+      Expression: 'bbb'
     <temp_dir>/snapshots/test__test_ccc.snap.new
+      Source: test.py:11::test_ccc
+      Expression: 'ccc'
 
     3 pending snapshot(s).
 
@@ -22,7 +22,9 @@ def test_lines():
     let content = context.read_file("snapshots/test__test_lines.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_lines
+    source: test.py::test_lines
+    assertion_line: 5
+    expression: 'line one\nline two\nline three'
     ---
     line one
     line two
@@ -50,7 +52,9 @@ def test_spaces():
     let content = context.read_file("snapshots/test__test_spaces.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_spaces
+    source: test.py::test_spaces
+    assertion_line: 5
+    expression: '  hello  '
     ---
       hello
     ");
@@ -76,7 +80,9 @@ def test_tabs():
     let content = context.read_file("snapshots/test__test_tabs.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_tabs
+    source: test.py::test_tabs
+    assertion_line: 5
+    expression: 'col1\tcol2\tcol3\n  indented\n\ttab indented'
     ---
     col1	col2	col3
       indented
@@ -104,7 +110,9 @@ def test_empty():
     let content = context.read_file("snapshots/test__test_empty.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_empty
+    source: test.py::test_empty
+    assertion_line: 5
+    expression: ''
     ---
 
     ");
@@ -130,7 +138,9 @@ def test_unicode():
     let content = context.read_file("snapshots/test__test_unicode.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_unicode
+    source: test.py::test_unicode
+    assertion_line: 5
+    expression: 'Hello éèê ☃ ❤ üñîçödé'
     ---
     Hello éèê ☃ ❤ üñîçödé
     ");
@@ -156,7 +166,9 @@ def test_special():
     let content = context.read_file("snapshots/test__test_special.snap");
     insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:5::test_special
+    source: test.py::test_special
+    assertion_line: 5
+    expression: 'angle <brackets> & ampersand\n"double quotes"\n$dollar @at #hash'
     ---
     angle <brackets> & ampersand
     "double quotes"
@@ -184,7 +196,9 @@ def test_dashes():
     let content = context.read_file("snapshots/test__test_dashes.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_dashes
+    source: test.py::test_dashes
+    assertion_line: 5
+    expression: '---\nthis looks like yaml\n---'
     ---
     ---
     this looks like yaml
@@ -276,7 +290,9 @@ def test_blanks():
     let content = context.read_file("snapshots/test__test_blanks.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_blanks
+    source: test.py::test_blanks
+    assertion_line: 5
+    expression: 'line one\n\n\nline four'
     ---
     line one
 
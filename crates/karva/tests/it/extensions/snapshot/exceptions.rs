@@ -0,0 +1,269 @@
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+#[test]
+fn test_raises_snapshot_from_callable() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def load(config):
+    try:
+        return int(config["port"])
+    except KeyError as e:
+        error = ValueError("config is invalid")
+        error.add_note("check the `port` key")
+        raise error from e
+
+def test_missing_port():
+    karva.assert_raises_snapshot(lambda: load({}))
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_missing_port
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_missing_port.snap");
+    insta::assert_snapshot!(content, @r"
+    ---
+    source: test.py::test_missing_port
+    assertion_line: 13
+    expression: lambda: load({})
+    serializer: raises
+    ---
+    ValueError: config is invalid
+      note: check the `port` key
+    caused by: KeyError: 'port'
+    ");
+}
+
+#[test]
+fn test_raises_snapshot_from_raises_context() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+class ConfigError(Exception):
+    pass
+
+def test_context():
+    with karva.raises(ConfigError) as exc_info:
+        try:
+            1 / 0
+        except ZeroDivisionError:
+            raise ConfigError("bad ratio")
+    karva.assert_raises_snapshot(exc_info, name="full")
+    karva.assert_raises_snapshot(exc_info, name="outer", chain=False)
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_context
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_context--full.snap");
+    insta::assert_snapshot!(content, @r"
+    ---
+    source: test.py::test_context
+    assertion_line: 13
+    expression: exc_info
+    serializer: raises
+    ---
+    test.ConfigError: bad ratio
+    while handling: ZeroDivisionError: division by zero
+    ");
+
+    let content = context.read_file("snapshots/test__test_context--outer.snap");
+    insta::assert_snapshot!(content, @r"
+    ---
+    source: test.py::test_context
+    assertion_line: 14
+    expression: exc_info
+    serializer: raises
+    ---
+    test.ConfigError: bad ratio
+    ");
+}
+
+#[test]
+fn test_raises_snapshot_did_not_raise() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+def test_no_error():
+    karva.assert_raises_snapshot(lambda: 1)
+        ",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            FAIL [TIME] test::test_no_error
+
+    diagnostics:
+
+    error[test-failure]: Test `test_no_error` failed
+     --> test.py:4:5
+      |
+    4 | def test_no_error():
+      |     ^^^^^^^^^^^^^
+      |
+    info: Test failed here
+     --> test.py:5:5
+      |
+    5 |     karva.assert_raises_snapshot(lambda: 1)
+      |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+      |
+    info: DID NOT RAISE
+
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_warns_snapshot() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import warnings
+import karva
+
+def legacy():
+    warnings.warn("legacy() is deprecated", DeprecationWarning)
+    warnings.warn("legacy() is slow")
+    warnings.warn("legacy() is deprecated", DeprecationWarning)
+
+def test_legacy():
+    karva.assert_warns_snapshot(legacy)
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_legacy
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_legacy.snap");
+    insta::assert_snapshot!(content, @r"
+    ---
+    source: test.py::test_legacy
+    assertion_line: 11
+    expression: legacy
+    serializer: warns
+    ---
+    DeprecationWarning: legacy() is deprecated
+    UserWarning: legacy() is slow
+    DeprecationWarning: legacy() is deprecated
+    ");
+}
+
+#[test]
+fn test_warns_snapshot_inline() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import warnings
+import karva
+
+def test_legacy():
+    karva.assert_warns_snapshot(lambda: warnings.warn("old", FutureWarning), inline="")
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_legacy
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let source = context.read_file("test.py");
+    insta::assert_snapshot!(source, @r#"
+    import warnings
+    import karva
+
+    def test_legacy():
+        karva.assert_warns_snapshot(lambda: warnings.warn("old", FutureWarning), inline="FutureWarning: old")
+    "#);
+}
+
+#[test]
+fn test_warns_snapshot_did_not_warn() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+def test_quiet():
+    karva.assert_warns_snapshot(lambda: None)
+        ",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            FAIL [TIME] test::test_quiet
+
+    diagnostics:
+
+    error[test-failure]: Test `test_quiet` failed
+     --> test.py:4:5
+      |
+    4 | def test_quiet():
+      |     ^^^^^^^^^^
+      |
+    info: Test failed here
+     --> test.py:5:5
+      |
+    5 |     karva.assert_warns_snapshot(lambda: None)
+      |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+      |
+    info: DID NOT WARN
+
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
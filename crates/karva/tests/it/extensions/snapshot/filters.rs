@@ -30,12 +30,14 @@ def test_filtered():
     ");
 
     let content = context.read_file("snapshots/test__test_filtered.snap");
-    insta::assert_snapshot!(content, @r"
+    insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:8::test_filtered
+    source: test.py::test_filtered
+    assertion_line: 8
+    expression: "created on 2024-01-15"
     ---
     created on [date]
-    ");
+    "#);
 }
 
 #[test]
@@ -67,12 +69,14 @@ def test_multi_filter():
     ");
 
     let content = context.read_file("snapshots/test__test_multi_filter.snap");
-    insta::assert_snapshot!(content, @r"
+    insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:9::test_multi_filter
+    source: test.py::test_multi_filter
+    assertion_line: 9
+    expression: "id=550e8400-e29b-41d4-a716-446655440000 date=2024-01-15"
     ---
     id=[uuid] date=[date]
-    ");
+    "#);
 }
 
 #[test]
@@ -103,12 +107,14 @@ def test_no_match():
     ");
 
     let content = context.read_file("snapshots/test__test_no_match.snap");
-    insta::assert_snapshot!(content, @r"
+    insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:8::test_no_match
+    source: test.py::test_no_match
+    assertion_line: 8
+    expression: "hello world"
     ---
     hello world
-    ");
+    "#);
 }
 
 #[test]
@@ -183,12 +189,14 @@ def test_nested():
     ");
 
     let content = context.read_file("snapshots/test__test_nested.snap");
-    insta::assert_snapshot!(content, @r"
+    insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:7::test_nested
+    source: test.py::test_nested
+    assertion_line: 7
+    expression: "took 42ms at /tmp/foo"
     ---
     took [duration] at [path]
-    ");
+    "#);
 }
 
 #[test]
@@ -236,12 +244,14 @@ def test_update_filtered():
         .output();
 
     let content = context.read_file("snapshots/test__test_update_filtered.snap");
-    insta::assert_snapshot!(content, @r"
+    insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:6::test_update_filtered
+    source: test.py::test_update_filtered
+    assertion_line: 6
+    expression: "created 2024-06-15"
     ---
     created [date]
-    ");
+    "#);
 
     context.write_file(
         "test.py",
@@ -293,10 +303,12 @@ def test_empty_filters():
     ");
 
     let content = context.read_file("snapshots/test__test_empty_filters.snap");
-    insta::assert_snapshot!(content, @r"
+    insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:6::test_empty_filters
+    source: test.py::test_empty_filters
+    assertion_line: 6
+    expression: "unchanged"
     ---
     unchanged
-    ");
+    "#);
 }
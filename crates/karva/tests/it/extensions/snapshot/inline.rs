@@ -429,16 +429,18 @@ def test_hello():
 
     let _ = context.command_no_parallel().output();
 
-    assert_cmd_snapshot!(context.snapshot("pending"), @r"
+    assert_cmd_snapshot!(context.snapshot("pending"), @r#"
     success: true
     exit_code: 0
     ----- stdout -----
     <temp_dir>/snapshots/test__test_hello_inline_5.snap.new
+      Source: test.py:5::test_hello
+      Expression: "hello world"
 
     1 pending snapshot(s).
 
     ----- stderr -----
-    ");
+    "#);
 }
 
 #[test]
@@ -29,7 +29,10 @@ def test_data():
     let content = context.read_file("snapshots/test__test_data.snap");
     insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:5::test_data
+    source: test.py::test_data
+    assertion_line: 5
+    expression: {"b": 2, "a": 1}
+    serializer: json
     ---
     {
       "a": 1,
@@ -66,7 +69,10 @@ def test_nested():
     let content = context.read_file("snapshots/test__test_nested.snap");
     insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:6::test_nested
+    source: test.py::test_nested
+    assertion_line: 6
+    expression: data
+    serializer: json
     ---
     {
       "count": 2,
@@ -184,7 +190,10 @@ def test_json_filtered():
     let content = context.read_file("snapshots/test__test_json_filtered.snap");
     insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:8::test_json_filtered
+    source: test.py::test_json_filtered
+    assertion_line: 8
+    expression: {"event": "created", "date": "2024-01-15"}
+    serializer: json
     ---
     {
       "date": "[date]",
@@ -283,7 +292,10 @@ def test_fn():
     let content = context.read_file("snapshots/test__test_fn--config.snap");
     insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:5::test_fn
+    source: test.py::test_fn
+    assertion_line: 5
+    expression: {'b': 2, 'a': 1}
+    serializer: json
     ---
     {
       "a": 1,
@@ -425,7 +437,10 @@ def test_list():
     let content = context.read_file("snapshots/test__test_list.snap");
     insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:5::test_list
+    source: test.py::test_list
+    assertion_line: 5
+    expression: [1, "two", True, None]
+    serializer: json
     ---
     [
       1,
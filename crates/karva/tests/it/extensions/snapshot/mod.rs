@@ -3,10 +3,15 @@ mod cmd;
 mod commands;
 mod content;
 mod delete;
+mod exceptions;
 mod filters;
 mod inline;
 mod json;
+mod modes;
 mod named;
 mod prune;
+mod redactions;
 mod review;
+mod serializers;
+mod unreferenced;
 mod update;
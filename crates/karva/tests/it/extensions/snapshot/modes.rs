@@ -0,0 +1,274 @@
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+const HELLO_TEST: &str = r"
+import karva
+
+def test_hello():
+    karva.assert_snapshot('hello world')
+        ";
+
+#[test]
+fn test_snapshot_update_no_fails_without_writing() {
+    let context = TestContext::with_file("test.py", HELLO_TEST);
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update=no"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            FAIL [TIME] test::test_hello
+
+    diagnostics:
+
+    error[test-failure]: Test `test_hello` failed
+     --> test.py:4:5
+      |
+    4 | def test_hello():
+      |     ^^^^^^^^^^
+      |
+    info: Test failed here
+     --> test.py:5:5
+      |
+    5 |     karva.assert_snapshot('hello world')
+      |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+      |
+    info: New snapshot for 'test_hello'.
+          Snapshot updates are disabled, so no file was written.
+          Re-run with `--snapshot-update` to write it, or with `--snapshot-update=new` to review it first.
+
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    assert!(!context.root().join("snapshots").exists());
+}
+
+#[test]
+fn test_snapshot_update_no_keeps_mismatch_unwritten() {
+    let context = TestContext::with_file("test.py", HELLO_TEST);
+
+    let _ = context
+        .command_no_parallel()
+        .arg("--snapshot-update")
+        .output();
+
+    context.write_file(
+        "test.py",
+        r"
+import karva
+
+def test_hello():
+    karva.assert_snapshot('goodbye world')
+        ",
+    );
+
+    let output = context
+        .command_no_parallel()
+        .arg("--snapshot-update=no")
+        .output()
+        .expect("karva runs");
+    assert!(!output.status.success());
+
+    assert!(
+        !context
+            .root()
+            .join("snapshots/test__test_hello.snap.new")
+            .exists()
+    );
+    let content = context.read_file("snapshots/test__test_hello.snap");
+    insta::assert_snapshot!(content, @r"
+    ---
+    source: test.py::test_hello
+    assertion_line: 5
+    expression: 'hello world'
+    ---
+    hello world
+    ");
+}
+
+#[test]
+fn test_ci_defaults_to_no_snapshot_updates() {
+    let context = TestContext::with_file("test.py", HELLO_TEST);
+
+    let output = context
+        .command_no_parallel()
+        .env("CI", "true")
+        .output()
+        .expect("karva runs");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Snapshot updates are disabled"));
+
+    assert!(!context.root().join("snapshots").exists());
+}
+
+#[test]
+fn test_snapshot_update_new_overrides_ci() {
+    let context = TestContext::with_file("test.py", HELLO_TEST);
+
+    let _ = context
+        .command_no_parallel()
+        .env("CI", "true")
+        .arg("--snapshot-update=new")
+        .output();
+
+    assert!(
+        context
+            .root()
+            .join("snapshots/test__test_hello.snap.new")
+            .exists()
+    );
+}
+
+#[test]
+fn test_snapshot_update_unseen_writes_new_and_keeps_changed_pending() {
+    let context = TestContext::with_file("test.py", HELLO_TEST);
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update=unseen"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_hello
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    context.write_file(
+        "test.py",
+        r"
+import karva
+
+def test_hello():
+    karva.assert_snapshot('goodbye world')
+        ",
+    );
+
+    let output = context
+        .command_no_parallel()
+        .arg("--snapshot-update=unseen")
+        .output()
+        .expect("karva runs");
+    assert!(!output.status.success());
+
+    let content = context.read_file("snapshots/test__test_hello.snap");
+    insta::assert_snapshot!(content, @r"
+    ---
+    source: test.py::test_hello
+    assertion_line: 5
+    expression: 'hello world'
+    ---
+    hello world
+    ");
+    let pending = context.read_file("snapshots/test__test_hello.snap.new");
+    insta::assert_snapshot!(pending, @r"
+    ---
+    source: test.py::test_hello
+    assertion_line: 5
+    expression: 'goodbye world'
+    ---
+    goodbye world
+    ");
+}
+
+#[test]
+fn test_snapshot_update_unseen_fills_empty_inline_snapshot() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_hello():
+    karva.assert_snapshot("hello world", inline="")
+        "#,
+    );
+
+    let _ = context
+        .command_no_parallel()
+        .arg("--snapshot-update=unseen")
+        .output();
+
+    let source = context.read_file("test.py");
+    insta::assert_snapshot!(source, @r#"
+    import karva
+
+    def test_hello():
+        karva.assert_snapshot("hello world", inline="hello world")
+    "#);
+}
+
+#[test]
+fn test_snapshot_update_from_config() {
+    let context = TestContext::with_files([
+        (
+            "karva.toml",
+            r#"
+[profile.default.snapshot]
+update = "always"
+"#,
+        ),
+        ("test.py", HELLO_TEST),
+    ]);
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_hello
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    assert!(
+        context
+            .root()
+            .join("snapshots/test__test_hello.snap")
+            .exists()
+    );
+}
+
+#[test]
+fn test_snapshot_update_cli_overrides_config() {
+    let context = TestContext::with_files([
+        (
+            "karva.toml",
+            r#"
+[profile.default.snapshot]
+update = "always"
+"#,
+        ),
+        ("test.py", HELLO_TEST),
+    ]);
+
+    let _ = context
+        .command_no_parallel()
+        .arg("--snapshot-update=no")
+        .output();
+
+    assert!(!context.root().join("snapshots").exists());
+}
+
+#[test]
+fn test_snapshot_update_env_var() {
+    let context = TestContext::with_file("test.py", HELLO_TEST);
+
+    let _ = context
+        .command_no_parallel()
+        .env("KARVA_SNAPSHOT_UPDATE", "1")
+        .output();
+
+    assert!(
+        context
+            .root()
+            .join("snapshots/test__test_hello.snap")
+            .exists()
+    );
+}
@@ -29,7 +29,9 @@ def test_hello():
     let content = context.read_file("snapshots/test__test_hello--greeting.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_hello
+    source: test.py::test_hello
+    assertion_line: 5
+    expression: 'hello world'
     ---
     hello world
     ");
@@ -155,7 +157,9 @@ def test_page():
     let header = context.read_file("snapshots/test__test_page--header.snap");
     insta::assert_snapshot!(header, @r"
     ---
-    source: test.py:5::test_page
+    source: test.py::test_page
+    assertion_line: 5
+    expression: 'Welcome'
     ---
     Welcome
     ");
@@ -163,7 +167,9 @@ def test_page():
     let footer = context.read_file("snapshots/test__test_page--footer.snap");
     insta::assert_snapshot!(footer, @r"
     ---
-    source: test.py:6::test_page
+    source: test.py::test_page
+    assertion_line: 6
+    expression: 'Goodbye'
     ---
     Goodbye
     ");
@@ -197,7 +203,9 @@ def test_mixed():
     let unnamed = context.read_file("snapshots/test__test_mixed.snap");
     insta::assert_snapshot!(unnamed, @r"
     ---
-    source: test.py:5::test_mixed
+    source: test.py::test_mixed
+    assertion_line: 5
+    expression: 'unnamed value'
     ---
     unnamed value
     ");
@@ -205,7 +213,9 @@ def test_mixed():
     let named = context.read_file("snapshots/test__test_mixed--special.snap");
     insta::assert_snapshot!(named, @r"
     ---
-    source: test.py:6::test_mixed
+    source: test.py::test_mixed
+    assertion_line: 6
+    expression: 'named value'
     ---
     named value
     ");
@@ -372,7 +382,10 @@ def test_translate(lang):
     let en = context.read_file("snapshots/test__test_translate--greeting(lang=en).snap");
     insta::assert_snapshot!(en, @r"
     ---
-    source: test.py:6::test_translate(lang=en)
+    source: test.py::test_translate(lang=en)
+    assertion_line: 6
+    expression: f'hello_{lang}'
+    variant: lang=en
     ---
     hello_en
     ");
@@ -380,7 +393,10 @@ def test_translate(lang):
     let fr = context.read_file("snapshots/test__test_translate--greeting(lang=fr).snap");
     insta::assert_snapshot!(fr, @r"
     ---
-    source: test.py:6::test_translate(lang=fr)
+    source: test.py::test_translate(lang=fr)
+    assertion_line: 6
+    expression: f'hello_{lang}'
+    variant: lang=fr
     ---
     hello_fr
     ");
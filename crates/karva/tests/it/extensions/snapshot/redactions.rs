@@ -0,0 +1,287 @@
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+#[test]
+fn test_json_snapshot_static_redactions() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_order():
+    order = {
+        "created_at": "2024-01-15T10:32:00",
+        "items": [{"id": 812, "name": "pen"}, {"id": 977, "name": "ink"}],
+    }
+    karva.assert_json_snapshot(order, redactions={
+        ".created_at": "[timestamp]",
+        ".items[*].id": "[id]",
+    })
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_order
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_order.snap");
+    insta::assert_snapshot!(content, @r#"
+    ---
+    source: test.py::test_order
+    assertion_line: 9
+    expression: order
+    serializer: json
+    ---
+    {
+      "created_at": "[timestamp]",
+      "items": [
+        {
+          "id": "[id]",
+          "name": "pen"
+        },
+        {
+          "id": "[id]",
+          "name": "ink"
+        }
+      ]
+    }
+    "#);
+}
+
+#[test]
+fn test_json_snapshot_redactions_leave_value_untouched() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_copy():
+    data = {"token": "secret", "items": (1, 2)}
+    karva.assert_json_snapshot(data, redactions={".token": "[token]"})
+    assert data == {"token": "secret", "items": (1, 2)}
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_copy
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_json_snapshot_deep_redaction_from_settings() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_deep():
+    data = {
+        "uuid": "0b5e",
+        "owner": {"uuid": "77d1", "name": "Alice"},
+        "children": [{"uuid": "c3a0"}],
+    }
+    with karva.snapshot_settings(redactions={".**.uuid": "[uuid]"}):
+        karva.assert_json_snapshot(data)
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_deep
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_deep.snap");
+    insta::assert_snapshot!(content, @r#"
+    ---
+    source: test.py::test_deep
+    assertion_line: 11
+    expression: data
+    serializer: json
+    ---
+    {
+      "children": [
+        {
+          "uuid": "[uuid]"
+        }
+      ],
+      "owner": {
+        "name": "Alice",
+        "uuid": "[uuid]"
+      },
+      "uuid": "[uuid]"
+    }
+    "#);
+}
+
+#[test]
+fn test_json_snapshot_callable_redaction() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def check_token(value, path):
+    assert len(value) == 8
+    return f"[token at {path}]"
+
+def test_callable():
+    data = {"sessions": [{"token": "a1b2c3d4"}]}
+    karva.assert_json_snapshot(data, redactions={".sessions[*].token": check_token})
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_callable
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_callable.snap");
+    insta::assert_snapshot!(content, @r#"
+    ---
+    source: test.py::test_callable
+    assertion_line: 10
+    expression: data
+    serializer: json
+    ---
+    {
+      "sessions": [
+        {
+          "token": "[token at .sessions[0].token]"
+        }
+      ]
+    }
+    "#);
+}
+
+#[test]
+fn test_json_snapshot_sorted_redaction() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_sorted():
+    data = {
+        "tags": {"web", "api", "db"},
+        "users": [{"id": "[id]", "name": "Bob"}, {"id": 4, "name": "Alice"}],
+    }
+    karva.assert_json_snapshot(data, redactions={
+        ".tags": karva.sorted_redaction(),
+        ".users": karva.sorted_redaction(),
+        ".users[*].id": "[id]",
+    })
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_sorted
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_sorted.snap");
+    insta::assert_snapshot!(content, @r#"
+    ---
+    source: test.py::test_sorted
+    assertion_line: 9
+    expression: data
+    serializer: json
+    ---
+    {
+      "tags": [
+        "api",
+        "db",
+        "web"
+      ],
+      "users": [
+        {
+          "id": "[id]",
+          "name": "Alice"
+        },
+        {
+          "id": "[id]",
+          "name": "Bob"
+        }
+      ]
+    }
+    "#);
+}
+
+#[test]
+fn test_json_snapshot_invalid_redaction_selector() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_bad_selector():
+    karva.assert_json_snapshot({"a": 1}, redactions={"a[": "[a]"})
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            FAIL [TIME] test::test_bad_selector
+
+    diagnostics:
+
+    error[test-failure]: Test `test_bad_selector` failed
+     --> test.py:4:5
+      |
+    4 | def test_bad_selector():
+      |     ^^^^^^^^^^^^^^^^^
+      |
+    info: Test failed here
+     --> test.py:5:5
+      |
+    5 |     karva.assert_json_snapshot({"a": 1}, redactions={"a[": "[a]"})
+      |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+      |
+    info: Invalid redaction selector: a[
+
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    "#);
+}
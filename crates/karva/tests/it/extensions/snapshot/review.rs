@@ -27,6 +27,7 @@ def test_hello():
     Snapshot 1/1
     File: <temp_dir>/snapshots/test__test_hello.snap.new
     Source: test.py:5::test_hello
+    Expression: 'hello world'
 
     ────────────┬[LONG-LINE]
               1 │ +hello world
@@ -78,6 +79,7 @@ def test_hello():
     Snapshot 1/1
     File: <temp_dir>/snapshots/test__test_hello.snap.new
     Source: test.py:5::test_hello
+    Expression: 'hello world'
 
     ────────────┬[LONG-LINE]
               1 │ +hello world
@@ -129,6 +131,7 @@ def test_hello():
     Snapshot 1/1
     File: <temp_dir>/snapshots/test__test_hello.snap.new
     Source: test.py:5::test_hello
+    Expression: 'hello world'
 
     ────────────┬[LONG-LINE]
               1 │ +hello world
@@ -181,6 +184,7 @@ def test_two():
     Snapshot 1/2
     File: <temp_dir>/snapshots/test__test_one.snap.new
     Source: test.py:5::test_one
+    Expression: 'first'
 
     ────────────┬[LONG-LINE]
               1 │ +first
@@ -417,6 +421,7 @@ def test_hello():
     Snapshot 1/1
     File: <temp_dir>/snapshots/test__test_hello.snap.new
     Source: test.py:5::test_hello
+    Expression: 'goodbye world'
 
     ────────────┬[LONG-LINE]
         1       │ -hello world
@@ -441,7 +446,9 @@ def test_hello():
     let content = context.read_file("snapshots/test__test_hello.snap");
     insta::assert_snapshot!(content, @r"
     ---
-    source: test.py:5::test_hello
+    source: test.py::test_hello
+    assertion_line: 5
+    expression: 'goodbye world'
     ---
     goodbye world
     ");
@@ -0,0 +1,394 @@
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+#[test]
+fn test_yaml_snapshot() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_config():
+    config = {
+        "name": "karva",
+        "version": "0.1",
+        "tags": ["fast", "rust"],
+        "owner": {"name": "Alice", "admin": True},
+    }
+    karva.assert_yaml_snapshot(config)
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_config
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_config.snap");
+    insta::assert_snapshot!(content, @r#"
+    ---
+    source: test.py::test_config
+    assertion_line: 11
+    expression: config
+    serializer: yaml
+    ---
+    name: karva
+    owner:
+      admin: true
+      name: Alice
+    tags:
+      - fast
+      - rust
+    version: "0.1"
+    "#);
+}
+
+#[test]
+fn test_yaml_snapshot_redactions() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_user():
+    karva.assert_yaml_snapshot({"id": 812, "name": "Alice"}, redactions={".id": "[id]"})
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_user
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_user.snap");
+    insta::assert_snapshot!(content, @r#"
+    ---
+    source: test.py::test_user
+    assertion_line: 5
+    expression: {"id": 812, "name": "Alice"}
+    serializer: yaml
+    ---
+    id: "[id]"
+    name: Alice
+    "#);
+}
+
+#[test]
+fn test_toml_snapshot() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_manifest():
+    karva.assert_toml_snapshot({"owner": {"name": "Alice"}, "name": "karva"})
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_manifest
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_manifest.snap");
+    insta::assert_snapshot!(content, @r#"
+    ---
+    source: test.py::test_manifest
+    assertion_line: 5
+    expression: {"owner": {"name": "Alice"}, "name": "karva"}
+    serializer: toml
+    ---
+    name = "karva"
+
+    [owner]
+    name = "Alice"
+    "#);
+}
+
+#[test]
+fn test_toml_snapshot_non_serializable() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+def test_not_toml():
+    karva.assert_toml_snapshot(object())
+        ",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            FAIL [TIME] test::test_not_toml
+
+    diagnostics:
+
+    error[test-failure]: Test `test_not_toml` failed
+     --> test.py:4:5
+      |
+    4 | def test_not_toml():
+      |     ^^^^^^^^^^^^^
+      |
+    info: Test failed here
+     --> test.py:5:5
+      |
+    5 |     karva.assert_toml_snapshot(object())
+      |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+      |
+    info: assert_toml_snapshot() value is not TOML serializable
+
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_debug_snapshot() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_state():
+    state = {
+        "tags": {"rust", "fast"},
+        "point": (1, 2),
+        "owner": {"name": "Alice", "id": 7},
+        "name": "karva",
+    }
+    karva.assert_debug_snapshot(state)
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_state
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_state.snap");
+    insta::assert_snapshot!(content, @r"
+    ---
+    source: test.py::test_state
+    assertion_line: 11
+    expression: state
+    serializer: pprint
+    ---
+    {'name': 'karva',
+     'owner': {'id': 7, 'name': 'Alice'},
+     'point': (1, 2),
+     'tags': {'fast', 'rust'}}
+    ");
+}
+
+#[test]
+fn test_binary_snapshot() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_bytes():
+    karva.assert_binary_snapshot(b"hello", extension="bin")
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_bytes
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file("snapshots/test__test_bytes.snap");
+    insta::assert_snapshot!(content, @r#"
+    ---
+    source: test.py::test_bytes
+    assertion_line: 5
+    expression: b"hello"
+    serializer: binary
+    extension: bin
+    ---
+    .bin file, 5 bytes, sha256 2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824
+    "#);
+    assert_eq!(
+        context.read_file("snapshots/test__test_bytes.snap.bin"),
+        "hello"
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_bytes
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_binary_snapshot_mismatch_and_accept() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_bytes():
+    karva.assert_binary_snapshot(b"hello", extension="bin")
+        "#,
+    );
+
+    let _ = context
+        .command_no_parallel()
+        .arg("--snapshot-update")
+        .output();
+
+    context.write_file(
+        "test.py",
+        r#"
+import karva
+
+def test_bytes():
+    karva.assert_binary_snapshot(b"world", extension="bin")
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            FAIL [TIME] test::test_bytes
+
+    diagnostics:
+
+    error[test-failure]: Test `test_bytes` failed
+     --> test.py:4:5
+      |
+    4 | def test_bytes():
+      |     ^^^^^^^^^^
+      |
+    info: Test failed here
+     --> test.py:5:5
+      |
+    5 |     karva.assert_binary_snapshot(b"world", extension="bin")
+      |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+      |
+    info: Snapshot mismatch for 'test_bytes'.
+          Snapshot file: snapshots/test__test_bytes.snap
+          ────────────┬───────────────────────────
+              1       │ -.bin file, 5 bytes, sha256 2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824
+                    1 │ +.bin file, 5 bytes, sha256 486ea46224d1bb4fb680f34f7c9ad96a8f24ec88be73ea8e5a6c65260e9cb8a7
+          ────────────┴───────────────────────────
+
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    "#);
+
+    assert_eq!(
+        context.read_file("snapshots/test__test_bytes.snap.bin"),
+        "hello"
+    );
+    assert_eq!(
+        context.read_file("snapshots/test__test_bytes.snap.new.bin"),
+        "world"
+    );
+
+    let _ = context.snapshot("accept").output();
+
+    assert_eq!(
+        context.read_file("snapshots/test__test_bytes.snap.bin"),
+        "world"
+    );
+    assert!(
+        !context
+            .root()
+            .join("snapshots/test__test_bytes.snap.new.bin")
+            .exists()
+    );
+}
+
+#[test]
+fn test_binary_snapshot_invalid_extension() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+def test_bytes():
+    karva.assert_binary_snapshot(b"hello", extension="snap")
+        "#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--snapshot-update"), @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            FAIL [TIME] test::test_bytes
+
+    diagnostics:
+
+    error[test-failure]: Test `test_bytes` failed
+     --> test.py:4:5
+      |
+    4 | def test_bytes():
+      |     ^^^^^^^^^^
+      |
+    info: Test failed here
+     --> test.py:5:5
+      |
+    5 |     karva.assert_binary_snapshot(b"hello", extension="snap")
+      |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+      |
+    info: Invalid binary snapshot extension 'snap': use letters, digits, '-' and '_', other than 'snap' and 'new'
+
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    "#);
+}
@@ -0,0 +1,256 @@
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+#[test]
+fn test_unreferenced_warn_lists_removed_variants() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+@karva.tags.parametrize('x', [1, 2, 3])
+def test_param(x):
+    karva.assert_snapshot(str(x))
+        ",
+    );
+
+    let _ = context
+        .command_no_parallel()
+        .arg("--snapshot-update")
+        .output();
+
+    context.write_file(
+        "test.py",
+        r"
+import karva
+
+@karva.tags.parametrize('x', [1, 2])
+def test_param(x):
+    karva.assert_snapshot(str(x))
+        ",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--unreferenced=warn"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_param(x=1)
+            PASS [TIME] test::test_param(x=2)
+    ────────────
+         Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+    warning: 1 unreferenced snapshot:
+      snapshots/test__test_param(x=3).snap
+
+    ----- stderr -----
+    ");
+
+    assert!(
+        context
+            .root()
+            .join("snapshots/test__test_param(x=3).snap")
+            .exists()
+    );
+}
+
+#[test]
+fn test_unreferenced_delete_removes_renamed_named_snapshot() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+def test_named():
+    karva.assert_snapshot('hello', name='old')
+        ",
+    );
+
+    let _ = context
+        .command_no_parallel()
+        .arg("--snapshot-update")
+        .output();
+
+    context.write_file(
+        "test.py",
+        r"
+import karva
+
+def test_named():
+    karva.assert_snapshot('hello', name='new')
+        ",
+    );
+
+    assert_cmd_snapshot!(
+        context
+            .command_no_parallel()
+            .arg("--snapshot-update")
+            .arg("--unreferenced=delete"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_named
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    Removed 1 unreferenced snapshot:
+      snapshots/test__test_named--old.snap
+
+    ----- stderr -----
+    "
+    );
+
+    assert!(
+        !context
+            .root()
+            .join("snapshots/test__test_named--old.snap")
+            .exists()
+    );
+    assert!(
+        context
+            .root()
+            .join("snapshots/test__test_named--new.snap")
+            .exists()
+    );
+}
+
+#[test]
+fn test_unreferenced_reject_fails_on_extra_numbered_snapshot() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+def test_counter():
+    with karva.snapshot_settings(allow_duplicates=True):
+        karva.assert_snapshot('first')
+        karva.assert_snapshot('second')
+        ",
+    );
+
+    let _ = context
+        .command_no_parallel()
+        .arg("--snapshot-update")
+        .output();
+
+    context.write_file(
+        "test.py",
+        r"
+import karva
+
+def test_counter():
+    with karva.snapshot_settings(allow_duplicates=True):
+        karva.assert_snapshot('first')
+        ",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--unreferenced=reject"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_counter
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    error: 1 unreferenced snapshot:
+      snapshots/test__test_counter-1.snap
+    (hint: use `--unreferenced=delete` to remove them)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_unreferenced_keeps_snapshots_of_skipped_tests() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+def test_kept():
+    karva.assert_snapshot('kept')
+        ",
+    );
+
+    let _ = context
+        .command_no_parallel()
+        .arg("--snapshot-update")
+        .output();
+
+    context.write_file(
+        "test.py",
+        r"
+import karva
+
+@karva.tags.skip
+def test_kept():
+    karva.assert_snapshot('kept')
+        ",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().arg("--unreferenced=delete"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 skipped
+
+    ----- stderr -----
+    ");
+
+    assert!(
+        context
+            .root()
+            .join("snapshots/test__test_kept.snap")
+            .exists()
+    );
+}
+
+#[test]
+fn test_unreferenced_skipped_for_filtered_run() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+def test_a():
+    karva.assert_snapshot('a')
+
+def test_b():
+    karva.assert_snapshot('b')
+        ",
+    );
+
+    let _ = context
+        .command_no_parallel()
+        .arg("--snapshot-update")
+        .output();
+
+    assert_cmd_snapshot!(
+        context
+            .command_no_parallel()
+            .arg("--unreferenced=delete")
+            .arg("-E")
+            .arg("test(~test_a)"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test::test_a
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 1 skipped
+
+    warning: Skipped the unreferenced snapshot check because only some tests ran.
+
+    ----- stderr -----
+    "
+    );
+
+    assert!(context.root().join("snapshots/test__test_b.snap").exists());
+}
@@ -112,7 +112,10 @@ def test_user_data():
     let content = context.read_file("snapshots/test__test_user_data.snap");
     insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:9::test_user_data
+    source: test.py::test_user_data
+    assertion_line: 9
+    expression: result
+    serializer: json
     ---
     {
       "id": 1,
@@ -231,7 +234,10 @@ def test_user_data():
     let content = context.read_file("snapshots/test__test_user_data.snap");
     insta::assert_snapshot!(content, @r#"
     ---
-    source: test.py:9::test_user_data
+    source: test.py::test_user_data
+    assertion_line: 9
+    expression: result
+    serializer: json
     ---
     {
       "id": 1,
@@ -285,7 +291,9 @@ def test_hello():
     let content = context.read_file("snapshots/test__test_hello.snap");
     insta::assert_snapshot!(content, @"
     ---
-    source: test.py:5::test_hello
+    source: test.py::test_hello
+    assertion_line: 5
+    expression: 'second'
     ---
     second
     ");
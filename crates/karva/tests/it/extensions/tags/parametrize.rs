@@ -698,3 +698,214 @@ def test_invalid(x):
     ----- stderr -----
     ");
 }
+
+#[rstest]
+fn test_parametrize_ids(#[values("pytest", "karva")] framework: &str) {
+    let context = TestContext::with_file(
+        "test.py",
+        &format!(
+            r#"
+import {framework}
+
+@{parametrize_function}("config", [{{"debug": True}}, {{"debug": False}}, {{}}], ids=["debug", "release", None])
+def test_config(config):
+    assert isinstance(config, dict)
+"#,
+            parametrize_function = get_parametrize_function(framework),
+        ),
+    );
+
+    allow_duplicates! {
+        assert_cmd_snapshot!(context.command_no_parallel(), @"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+            Starting 1 test across 1 worker
+                PASS [TIME] test::test_config(config=debug)
+                PASS [TIME] test::test_config(config=release)
+                PASS [TIME] test::test_config(config={})
+        ────────────
+             Summary [TIME] 3 tests run: 3 passed, 0 skipped
+
+        ----- stderr -----
+        ");
+    }
+}
+
+/// The ids a callable returns for each value of a variant are joined with `-`.
+#[rstest]
+fn test_parametrize_ids_callable(#[values("pytest", "karva")] framework: &str) {
+    let context = TestContext::with_file(
+        "test.py",
+        &format!(
+            r#"
+import {framework}
+
+def value_id(value):
+    return f"v{{value}}" if value > 1 else None
+
+@{parametrize_function}("a,b", [(1, 2), (3, 4)], ids=value_id)
+def test_pair(a, b):
+    assert a < b
+"#,
+            parametrize_function = get_parametrize_function(framework),
+        ),
+    );
+
+    allow_duplicates! {
+        assert_cmd_snapshot!(context.command_no_parallel(), @"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+            Starting 1 test across 1 worker
+                PASS [TIME] test::test_pair(a,b=1-v2)
+                PASS [TIME] test::test_pair(a,b=v3-v4)
+        ────────────
+             Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+        ----- stderr -----
+        ");
+    }
+}
+
+/// An id given to a single param takes precedence over `ids`.
+#[test]
+fn test_parametrize_param_id() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+import pytest
+
+@karva.tags.parametrize("a", [karva.param(0, id="zero"), 1, 2], ids=[None, "one", "two"])
+def test_karva(a):
+    assert a >= 0
+
+@pytest.mark.parametrize("a", [pytest.param(0, id="zero"), 1], ids=[None, "one"])
+def test_pytest(a):
+    assert a >= 0
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test::test_karva(a=zero)
+            PASS [TIME] test::test_karva(a=one)
+            PASS [TIME] test::test_karva(a=two)
+            PASS [TIME] test::test_pytest(a=zero)
+            PASS [TIME] test::test_pytest(a=one)
+    ────────────
+         Summary [TIME] 5 tests run: 5 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_parametrize_duplicate_ids() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.tags.parametrize("x", [1, 2, 3], ids=["same", "same", "other"])
+def test_duplicate(x):
+    assert x > 0
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_duplicate(x=same)
+            PASS [TIME] test::test_duplicate(x=same)
+            PASS [TIME] test::test_duplicate(x=other)
+
+    diagnostics:
+
+    error[duplicate-parametrize-ids]: Test `test_duplicate` has duplicate parametrize ids
+     --> test.py:5:5
+      |
+    5 | def test_duplicate(x):
+      |     ^^^^^^^^^^^^^^
+      |
+    info: Duplicate ids: `same`
+
+    ────────────
+         Summary [TIME] 3 tests run: 3 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_parametrize_ids_length_mismatch() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import karva
+
+@karva.tags.parametrize("x", [1, 2], ids=["one", "two", "three"])
+def test_mismatch(x):
+    assert x > 0
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+    diagnostics:
+
+    error[failed-to-import-module]: Failed to import python module `test`: `ids` must have one id for each of the 2 parameter sets, got 3
+
+    ────────────
+         Summary [TIME] 0 tests run: 0 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_parametrize_pytest_invalid_ids() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import pytest
+
+@pytest.mark.parametrize("x", [1, 2], ids=["one"])
+def test_mismatch(x):
+    assert x > 0
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_mismatch(x=1)
+            PASS [TIME] test::test_mismatch(x=2)
+
+    diagnostics:
+
+    error[invalid-parametrize-ids]: Test `test_mismatch` has invalid parametrize ids
+     --> test.py:5:5
+      |
+    5 | def test_mismatch(x):
+      |     ^^^^^^^^^^^^^
+      |
+    info: ValueError: `ids` must have one id for each of the 2 parameter sets, got 1
+
+    ────────────
+         Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
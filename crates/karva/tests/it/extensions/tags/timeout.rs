@@ -174,6 +174,63 @@ def test_1():
     }
 }
 
+/// Timeouts too large for a `Duration` are rejected up front rather than
+/// crashing the worker when it reports the test's deadline.
+#[test]
+fn test_timeout_too_large_rejected() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+@karva.tags.timeout(1e20)
+def test_1():
+    assert True
+        ",
+    );
+
+    assert_cmd_snapshot!(context.command(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+    diagnostics:
+
+    error[failed-to-import-module]: Failed to import python module `test`: timeout seconds is too large
+
+    ────────────
+         Summary [TIME] 0 tests run: 0 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_timeout_too_large_pytest_mark_ignored() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import pytest
+
+@pytest.mark.timeout(1e20)
+def test_1():
+    assert True
+        ",
+    );
+
+    assert_cmd_snapshot!(context.command(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::test_1
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
 #[test]
 fn test_timeout_with_parametrize_each_case_gets_fresh_window() {
     let context = TestContext::with_file(
@@ -416,3 +473,81 @@ def test_slow():
     ----- stderr -----
     ");
 }
+
+/// With `--timeout-method=process` a test stuck in a busy loop is killed
+/// along with its worker, and a new worker runs the remaining tests.
+#[test]
+fn test_process_timeout_kills_hung_test() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import karva
+
+@karva.tags.timeout(0.2)
+def test_hang():
+    while True:
+        pass
+
+def test_after():
+    assert True
+        ",
+    );
+
+    assert_cmd_snapshot!(context.command().arg("--timeout-method=process"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+         TIMEOUT [TIME] test::test_hang
+            PASS [TIME] test::test_after
+
+    diagnostics:
+
+    error[test-timeout]: Test `test_hang` exceeded timeout of 0.2 seconds
+    info: Worker 0 was killed to stop the test
+
+    ────────────
+         Summary [TIME] 2 tests run: 1 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_config_process_timeout_kills_slow_test() {
+    let context = TestContext::with_files([
+        (
+            "pyproject.toml",
+            r#"
+[tool.karva.profile.default.test]
+timeout = 0.1
+timeout-method = "process"
+            "#,
+        ),
+        (
+            "test.py",
+            r"
+import time
+
+def test_slow():
+    time.sleep(5)
+            ",
+        ),
+    ]);
+
+    assert_cmd_snapshot!(context.command().arg("--output-format=concise"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+         TIMEOUT [TIME] test::test_slow
+
+    diagnostics:
+
+    error[test-timeout] Test `test_slow` exceeded timeout of 0.1 seconds: Worker 0 was killed to stop the test
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
@@ -0,0 +1,153 @@
+use insta::assert_snapshot;
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+fn bind_time_filter() -> insta::internals::SettingsBindDropGuard {
+    let mut settings = insta::Settings::clone_current();
+    settings.add_filter(r#"time="\d+\.\d+""#, r#"time="[TIME]""#);
+    settings.bind_to_scope()
+}
+
+#[test]
+fn junit_report_records_each_outcome() {
+    let context = TestContext::with_file(
+        "test_junit.py",
+        r#"
+import sys
+
+import karva
+
+def test_pass():
+    print("hello from test_pass")
+
+def test_fail():
+    print("oops", file=sys.stderr)
+    raise ValueError("boom")
+
+@karva.tags.skip(reason="not ready")
+def test_skip():
+    pass
+
+@karva.tags.parametrize("x", [1, 2])
+def test_param(x):
+    pass
+"#,
+    );
+
+    assert_cmd_snapshot!(
+        context
+            .command_no_parallel()
+            .args(["--output-format", "concise", "--junit-path", "reports/junit.xml"]),
+        @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 5 tests across 1 worker
+            PASS [TIME] test_junit::test_pass
+            FAIL [TIME] test_junit::test_fail
+            PASS [TIME] test_junit::test_param(x=1)
+            PASS [TIME] test_junit::test_param(x=2)
+
+    diagnostics:
+
+    test_junit.py:9:5: error[test-failure] Test `test_fail` failed
+    ────────────
+         Summary [TIME] 5 tests run: 3 passed, 1 failed, 1 skipped
+
+    ----- stderr -----
+    "
+    );
+
+    let _guard = bind_time_filter();
+    assert_snapshot!(context.read_file("reports/junit.xml"), @r#"
+    <?xml version="1.0" encoding="UTF-8"?>
+    <testsuites name="karva" tests="5" failures="1" errors="0" skipped="1" time="[TIME]">
+      <testsuite name="test_junit" tests="5" failures="1" errors="0" skipped="1" time="[TIME]">
+        <testcase name="test_fail" classname="test_junit" file="test_junit.py" time="[TIME]">
+          <failure message="ValueError: boom" type="ValueError">Traceback (most recent call last):
+      File &quot;<temp_dir>/test_junit.py&quot;, line 11, in test_fail
+        raise ValueError(&quot;boom&quot;)
+    ValueError: boom</failure>
+          <system-err>oops
+    </system-err>
+        </testcase>
+        <testcase name="test_param(x=1)" classname="test_junit" file="test_junit.py" time="[TIME]"/>
+        <testcase name="test_param(x=2)" classname="test_junit" file="test_junit.py" time="[TIME]"/>
+        <testcase name="test_pass" classname="test_junit" file="test_junit.py" time="[TIME]">
+          <system-out>hello from test_pass
+    </system-out>
+        </testcase>
+        <testcase name="test_skip" classname="test_junit" file="test_junit.py" time="[TIME]">
+          <skipped message="not ready"/>
+        </testcase>
+      </testsuite>
+    </testsuites>
+    "#);
+}
+
+#[test]
+fn junit_report_records_retried_attempts() {
+    let context = TestContext::with_file(
+        "test_retry.py",
+        r#"
+import os
+
+def test_flaky():
+    if os.environ["KARVA_ATTEMPT"] == "1":
+        raise RuntimeError("first attempt")
+"#,
+    );
+
+    let output = context
+        .command_no_parallel()
+        .args(["--retry", "1", "--junit-path", "junit.xml"])
+        .output()
+        .expect("failed to run karva");
+    assert!(output.status.success());
+
+    let _guard = bind_time_filter();
+    assert_snapshot!(context.read_file("junit.xml"), @r#"
+    <?xml version="1.0" encoding="UTF-8"?>
+    <testsuites name="karva" tests="1" failures="0" errors="0" skipped="0" time="[TIME]">
+      <testsuite name="test_retry" tests="1" failures="0" errors="0" skipped="0" time="[TIME]">
+        <testcase name="test_flaky" classname="test_retry" file="test_retry.py" time="[TIME]">
+          <flakyFailure message="RuntimeError: first attempt" type="RuntimeError">Traceback (most recent call last):
+      File &quot;<temp_dir>/test_retry.py&quot;, line 6, in test_flaky
+        raise RuntimeError(&quot;first attempt&quot;)
+    RuntimeError: first attempt</flakyFailure>
+        </testcase>
+      </testsuite>
+    </testsuites>
+    "#);
+}
+
+#[test]
+fn junit_report_from_config_is_written_relative_to_project_root() {
+    let context = TestContext::with_files([
+        (
+            "karva.toml",
+            r#"
+[profile.default.terminal]
+junit-path = "target/junit.xml"
+"#,
+        ),
+        ("test_config.py", "def test_one(): pass"),
+    ]);
+
+    let output = context
+        .command_no_parallel()
+        .output()
+        .expect("failed to run karva");
+    assert!(output.status.success());
+
+    let _guard = bind_time_filter();
+    assert_snapshot!(context.read_file("target/junit.xml"), @r#"
+    <?xml version="1.0" encoding="UTF-8"?>
+    <testsuites name="karva" tests="1" failures="0" errors="0" skipped="0" time="[TIME]">
+      <testsuite name="test_config" tests="1" failures="0" errors="0" skipped="0" time="[TIME]">
+        <testcase name="test_one" classname="test_config" file="test_config.py" time="[TIME]"/>
+      </testsuite>
+    </testsuites>
+    "#);
+}
@@ -9,8 +9,12 @@ mod discovery;
 mod durations;
 mod extensions;
 mod filterset;
+mod junit;
 mod last_failed;
+mod message_format;
 mod partition;
 mod run_ignored;
+mod unittest;
 mod version;
 mod watch;
+mod worker_crash;
@@ -0,0 +1,134 @@
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+fn bind_duration_filter() -> insta::internals::SettingsBindDropGuard {
+    let mut settings = insta::Settings::clone_current();
+    settings.add_filter(r#""duration":[0-9.e-]+"#, r#""duration":[DURATION]"#);
+    settings.bind_to_scope()
+}
+
+#[test]
+fn message_format_json_emits_events() {
+    let context = TestContext::with_file(
+        "test_events.py",
+        r#"
+import karva
+
+def test_pass():
+    pass
+
+def test_fail():
+    assert False
+
+@karva.tags.skip(reason="not ready")
+def test_skip():
+    pass
+
+@karva.tags.parametrize("x", [1])
+def test_param(x):
+    pass
+"#,
+    );
+
+    let _guard = bind_duration_filter();
+    assert_cmd_snapshot!(
+        context
+            .command_no_parallel()
+            .args(["--message-format", "json", "--output-format", "concise"]),
+        @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    {"type":"run-started","test_count":4,"worker_count":1}
+    {"type":"test-started","name":"test_events::test_pass","module":"test_events","function":"test_pass"}
+    {"type":"test-finished","name":"test_events::test_pass","module":"test_events","function":"test_pass","outcome":"passed","duration":[DURATION],"attempt":1,"flaky":false}
+    {"type":"test-started","name":"test_events::test_fail","module":"test_events","function":"test_fail"}
+    {"type":"test-finished","name":"test_events::test_fail","module":"test_events","function":"test_fail","outcome":"failed","duration":[DURATION],"attempt":1,"flaky":false}
+    {"type":"test-finished","name":"test_events::test_skip","module":"test_events","function":"test_skip","outcome":"skipped","duration":[DURATION],"attempt":1,"flaky":false,"skip_reason":"not ready"}
+    {"type":"test-started","name":"test_events::test_param(x=1)","module":"test_events","function":"test_param","params":"(x=1)"}
+    {"type":"test-finished","name":"test_events::test_param(x=1)","module":"test_events","function":"test_param","params":"(x=1)","outcome":"passed","duration":[DURATION],"attempt":1,"flaky":false}
+    {"type":"run-finished","duration":[DURATION],"success":false,"stats":{"total":4,"passed":2,"failed":1,"skipped":1,"flaky":0,"slow":0}}
+
+    ----- stderr -----
+    test_events.py:7:5: error[test-failure] Test `test_fail` failed
+    "#
+    );
+}
+
+#[test]
+fn message_format_json_reports_retries() {
+    let context = TestContext::with_file(
+        "test_retry.py",
+        r#"
+import os
+
+def test_flaky():
+    assert os.environ["KARVA_ATTEMPT"] == "2"
+"#,
+    );
+
+    let _guard = bind_duration_filter();
+    assert_cmd_snapshot!(
+        context
+            .command_no_parallel()
+            .args(["--message-format", "json", "--retry", "2"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"type":"run-started","test_count":1,"worker_count":1}
+    {"type":"test-started","name":"test_retry::test_flaky","module":"test_retry","function":"test_flaky"}
+    {"type":"test-attempt","name":"test_retry::test_flaky","module":"test_retry","function":"test_flaky","attempt":1,"outcome":"failed","duration":[DURATION]}
+    {"type":"test-attempt","name":"test_retry::test_flaky","module":"test_retry","function":"test_flaky","attempt":2,"outcome":"passed","duration":[DURATION]}
+    {"type":"test-finished","name":"test_retry::test_flaky","module":"test_retry","function":"test_flaky","outcome":"passed","duration":[DURATION],"attempt":2,"max_attempts":3,"flaky":true}
+    {"type":"run-finished","duration":[DURATION],"success":true,"stats":{"total":1,"passed":1,"failed":0,"skipped":0,"flaky":1,"slow":0}}
+
+    ----- stderr -----
+    "#
+    );
+}
+
+/// Output shown with `-s` goes to stderr, so stdout stays valid NDJSON.
+#[test]
+fn message_format_json_sends_test_output_to_stderr() {
+    let context = TestContext::with_file(
+        "test_print.py",
+        r#"
+def test_print():
+    print("hello from the test")
+"#,
+    );
+
+    let output = context
+        .command_no_parallel()
+        .args(["--message-format", "json", "-s"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(output.status.success(), "{stdout}{stderr}");
+    assert!(stdout.lines().all(|line| line.starts_with('{')), "{stdout}");
+    assert!(stderr.contains("hello from the test"), "{stderr}");
+}
+
+#[test]
+fn message_format_json_rejects_watch() {
+    let context = TestContext::with_file("test_watch.py", "def test_one(): pass");
+
+    assert_cmd_snapshot!(
+        context
+            .command_no_parallel()
+            .args(["--message-format", "json", "--watch"]),
+        @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Karva failed
+      Cause: `--message-format json` cannot be used with `--watch`
+    "
+    );
+}
@@ -0,0 +1,418 @@
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+/// `setUpClass`, `setUp`, `tearDown`, `tearDownClass` and cleanups run in
+/// the same order as under `unittest`. The class cleanup runs last and
+/// records the order to a file.
+#[test]
+fn test_unittest_lifecycle() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import unittest
+
+events = []
+
+def write_events():
+    events.append("class cleanup")
+    with open("events.txt", "w") as f:
+        f.write("\n".join(events))
+
+class LifecycleTests(unittest.TestCase):
+    @classmethod
+    def setUpClass(cls):
+        events.append("setUpClass")
+        cls.addClassCleanup(write_events)
+
+    @classmethod
+    def tearDownClass(cls):
+        events.append("tearDownClass")
+
+    def setUp(self):
+        events.append(f"setUp {self._testMethodName}")
+        self.addCleanup(events.append, f"cleanup {self._testMethodName}")
+
+    def tearDown(self):
+        events.append(f"tearDown {self._testMethodName}")
+
+    def test_first(self):
+        events.append("test_first")
+
+    def test_second(self):
+        events.append("test_second")
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test::LifecycleTests::test_first
+            PASS [TIME] test::LifecycleTests::test_second
+    ────────────
+         Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+
+    insta::assert_snapshot!(context.read_file("events.txt"), @"
+    setUpClass
+    setUp test_first
+    test_first
+    tearDown test_first
+    cleanup test_first
+    setUp test_second
+    test_second
+    tearDown test_second
+    cleanup test_second
+    tearDownClass
+    class cleanup
+    ");
+}
+
+#[test]
+fn test_unittest_outcomes() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import unittest
+
+class OutcomeTests(unittest.TestCase):
+    def test_pass(self):
+        self.assertTrue(True)
+
+    def test_fail(self):
+        self.assertEqual(1, 2)
+
+    def test_skip(self):
+        self.skipTest("not today")
+
+    @unittest.skip("decorated")
+    def test_skip_decorator(self):
+        self.fail()
+
+    @unittest.expectedFailure
+    def test_expected_failure(self):
+        self.assertEqual(1, 2)
+
+    @unittest.expectedFailure
+    def test_unexpected_success(self):
+        pass
+
+    def test_subtests(self):
+        for i in range(3):
+            with self.subTest(i=i):
+                self.assertNotEqual(i, 1)
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().args(["--output-format", "concise"]), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 7 tests across 1 worker
+            PASS [TIME] test::OutcomeTests::test_pass
+            FAIL [TIME] test::OutcomeTests::test_fail
+            PASS [TIME] test::OutcomeTests::test_expected_failure
+            FAIL [TIME] test::OutcomeTests::test_unexpected_success
+            FAIL [TIME] test::OutcomeTests::test_subtests
+
+    diagnostics:
+
+    test.py:8:9: error[test-failure] Test `test_fail` failed
+    test.py:23:9: error[test-pass-on-expect-failure] Test `test_unexpected_success` passes when expected to fail
+    test.py:26:9: error[test-failure] Test `test_subtests` failed
+    ────────────
+         Summary [TIME] 7 tests run: 2 passed, 3 failed, 2 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_unittest_failure_points_at_test() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import unittest
+
+class FailingTests(unittest.TestCase):
+    def test_fail(self):
+        self.assertEqual(1, 2)
+",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            FAIL [TIME] test::FailingTests::test_fail
+
+    diagnostics:
+
+    error[test-failure]: Test `test_fail` failed
+     --> test.py:5:9
+      |
+    5 |     def test_fail(self):
+      |         ^^^^^^^^^
+      |
+    info: Test failed here
+     --> test.py:6:9
+      |
+    6 |         self.assertEqual(1, 2)
+      |         ^^^^^^^^^^^^^^^^^^^^^^
+      |
+    info: 1 != 2
+
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_unittest_set_up_class_failure() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import unittest
+
+class BrokenSetUpTests(unittest.TestCase):
+    @classmethod
+    def setUpClass(cls):
+        raise RuntimeError("no database")
+
+    def test_one(self):
+        pass
+
+    def test_two(self):
+        pass
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel().args(["--output-format", "concise"]), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            FAIL [TIME] test::BrokenSetUpTests::test_one
+            FAIL [TIME] test::BrokenSetUpTests::test_two
+
+    diagnostics:
+
+    test.py:9:9: error[test-failure] Test `test_one` failed
+    test.py:12:9: error[test-failure] Test `test_two` failed
+    ────────────
+         Summary [TIME] 2 tests run: 0 passed, 2 failed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_unittest_set_up_class_skip() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import unittest
+
+class SkippedTests(unittest.TestCase):
+    @classmethod
+    def setUpClass(cls):
+        raise unittest.SkipTest("no database")
+
+    def test_one(self):
+        pass
+
+    def test_two(self):
+        pass
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+    ────────────
+         Summary [TIME] 2 tests run: 0 passed, 2 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_unittest_tear_down_class_failure() {
+    let context = TestContext::with_file(
+        "test.py",
+        r#"
+import unittest
+
+class BrokenTearDownTests(unittest.TestCase):
+    @classmethod
+    def tearDownClass(cls):
+        raise RuntimeError("connection lost")
+
+    def test_one(self):
+        pass
+"#,
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            PASS [TIME] test::BrokenTearDownTests::test_one
+
+    diagnostics:
+
+    error[class-teardown-failure]: Teardown of test class `BrokenTearDownTests` failed
+    info: connection lost
+
+    ────────────
+         Summary [TIME] 1 test run: 1 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+/// A `TestCase` subclass is collected whatever its name, as is a subclass
+/// of a test case class defined earlier in the module.
+#[test]
+fn test_unittest_subclasses_collected() {
+    let context = TestContext::with_file(
+        "test.py",
+        r"
+import unittest
+
+class Base(unittest.TestCase):
+    def test_base(self):
+        pass
+
+class Derived(Base):
+    def test_derived(self):
+        pass
+",
+    );
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test::Base::test_base
+            PASS [TIME] test::Derived::test_derived
+    ────────────
+         Summary [TIME] 2 tests run: 2 passed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_unittest_subclass_of_imported_base_collected() {
+    let context = TestContext::with_files([
+        (
+            "tests/base.py",
+            r"
+import unittest
+
+class BaseTest(unittest.TestCase):
+    def setUp(self):
+        self.value = 1
+
+class Helper:
+    pass
+",
+        ),
+        (
+            "tests/test.py",
+            r"
+from .base import BaseTest, Helper
+
+class FooTests(BaseTest):
+    def test_value(self):
+        self.assertEqual(self.value, 1)
+
+class FooChecks(Helper):
+    def test_not_collected(self):
+        raise AssertionError
+",
+        ),
+    ]);
+
+    let output = context.command_no_parallel().output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("Starting 1 test"), "{stdout}");
+    assert!(
+        stdout.contains("tests.test::FooTests::test_value"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("test_not_collected"), "{stdout}");
+    assert!(
+        stdout.contains("1 test run: 1 passed, 0 skipped"),
+        "{stdout}"
+    );
+}
+
+/// Bases are followed through package re-exports and module attributes, so
+/// the test count printed at the start of the run matches what runs.
+#[test]
+fn test_unittest_subclass_of_reexported_base_counted() {
+    let context = TestContext::with_files([
+        ("support/__init__.py", "from .cases import BaseTest, Helper"),
+        (
+            "support/cases.py",
+            r"
+import unittest
+
+class BaseTest(unittest.TestCase):
+    pass
+
+class Helper:
+    def __init__(self, value):
+        self.value = value
+",
+        ),
+        (
+            "test.py",
+            r"
+import support
+from support import BaseTest, Helper
+
+class Reexported(BaseTest):
+    def test_reexported(self):
+        pass
+
+class Attribute(support.BaseTest):
+    def test_attribute(self):
+        pass
+
+class TestHelper(Helper):
+    def __init__(self):
+        super().__init__(1)
+
+    def test_not_collected(self):
+        raise AssertionError
+",
+        ),
+    ]);
+
+    let output = context.command_no_parallel().output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("Starting 2 tests"), "{stdout}");
+    assert!(!stdout.contains("test_not_collected"), "{stdout}");
+    assert!(
+        stdout.contains("2 tests run: 2 passed, 0 skipped"),
+        "{stdout}"
+    );
+}
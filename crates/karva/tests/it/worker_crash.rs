@@ -0,0 +1,147 @@
+use insta_cmd::assert_cmd_snapshot;
+
+use crate::common::TestContext;
+
+const CRASHING_TESTS: &str = r"
+import os
+
+def test_before():
+    pass
+
+def test_crash():
+    os._exit(3)
+
+def test_after():
+    pass
+";
+
+#[test]
+fn worker_crash_reports_the_running_test() {
+    let context = TestContext::with_file("test_crash.py", CRASHING_TESTS);
+
+    assert_cmd_snapshot!(context.command_no_parallel(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 3 tests across 1 worker
+            PASS [TIME] test_crash::test_before
+            FAIL [TIME] test_crash::test_crash
+
+    diagnostics:
+
+    error[worker-crash]: Test `test_crash` crashed worker 0
+    info: The worker process exited with code 3
+
+    ────────────
+         Summary [TIME] 2 tests run: 1 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn worker_crash_respawns_worker_for_remaining_tests() {
+    let context = TestContext::with_file("test_crash.py", CRASHING_TESTS);
+
+    assert_cmd_snapshot!(
+        context
+            .command_no_parallel()
+            .args(["--respawn-on-crash", "--output-format", "concise"]),
+        @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 3 tests across 1 worker
+            PASS [TIME] test_crash::test_before
+            FAIL [TIME] test_crash::test_crash
+            PASS [TIME] test_crash::test_after
+
+    diagnostics:
+
+    error[worker-crash] Test `test_crash` crashed worker 0: The worker process exited with code 3
+    ────────────
+         Summary [TIME] 3 tests run: 2 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    "
+    );
+}
+
+#[test]
+fn worker_crash_respawns_worker_for_remaining_variants() {
+    let context = TestContext::with_file(
+        "test_crash.py",
+        r"
+import os
+import karva
+
+@karva.tags.parametrize('x', [1, 2, 3])
+def test_param(x):
+    if x == 2:
+        os._exit(3)
+
+def test_after():
+    pass
+",
+    );
+
+    assert_cmd_snapshot!(
+        context
+            .command_no_parallel()
+            .args(["--respawn-on-crash", "--output-format", "concise"]),
+        @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 2 tests across 1 worker
+            PASS [TIME] test_crash::test_param(x=1)
+            FAIL [TIME] test_crash::test_param(x=2)
+            PASS [TIME] test_crash::test_param(x=3)
+            PASS [TIME] test_crash::test_after
+
+    diagnostics:
+
+    error[worker-crash] Test `test_param(x=2)` crashed worker 0: The worker process exited with code 3
+    ────────────
+         Summary [TIME] 4 tests run: 3 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    "
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn worker_crash_reports_the_signal() {
+    let context = TestContext::with_file(
+        "test_signal.py",
+        r"
+import os
+import signal
+
+def test_killed():
+    os.kill(os.getpid(), signal.SIGKILL)
+",
+    );
+
+    assert_cmd_snapshot!(
+        context
+            .command_no_parallel()
+            .args(["--output-format", "concise"]),
+        @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+        Starting 1 test across 1 worker
+            FAIL [TIME] test_signal::test_killed
+
+    diagnostics:
+
+    error[worker-crash] Test `test_killed` crashed worker 0: The worker process was terminated by signal 9 (SIGKILL)
+    ────────────
+         Summary [TIME] 1 test run: 0 passed, 1 failed, 0 skipped
+
+    ----- stderr -----
+    "
+    );
+}
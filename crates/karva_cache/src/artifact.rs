@@ -26,10 +26,15 @@ pub enum CacheFile {
     FailedTests,
     /// Per-worker JSON: list of `FlakyTest` records.
     FlakyTests,
+    /// Per-worker JSON: list of `TestCaseRecord`s, one per test variant.
+    TestCases,
     /// Per-worker JSON: line-coverage data for sources tracked during the run.
+    /// Python subprocesses of the worker write theirs alongside, as
+    /// `coverage.<pid>.json`.
     Coverage,
-    /// Per-run empty sentinel marking that fail-fast was triggered.
-    FailFastSignal,
+    /// Per-worker JSON: list of the `.snap` files that snapshot assertions
+    /// compared against.
+    Snapshots,
     /// Cache-root JSON: list of last-run failed test names.
     LastFailed,
 }
@@ -43,8 +48,9 @@ impl CacheFile {
             Self::Durations => "durations.json",
             Self::FailedTests => "failed_tests.json",
             Self::FlakyTests => "flaky_tests.json",
+            Self::TestCases => "test_cases.json",
             Self::Coverage => "coverage.json",
-            Self::FailFastSignal => "fail-fast",
+            Self::Snapshots => "snapshots.json",
             Self::LastFailed => "last-failed.json",
         }
     }
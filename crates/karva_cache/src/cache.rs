@@ -1,10 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::time::Duration;
 
 use anyhow::Result;
 use camino::{Utf8Path, Utf8PathBuf};
-use karva_diagnostic::{FlakyTest, TestResultStats, TestRunResult};
+use karva_diagnostic::{FlakyTest, TestCaseRecord, TestResultStats, TestRunResult};
 use ruff_db::diagnostic::{DisplayDiagnosticConfig, DisplayDiagnostics, FileResolver};
 
 use crate::artifact::{CacheFile, read_json, read_text, write_json, write_json_if_nonempty};
@@ -18,6 +18,9 @@ pub struct AggregatedResults {
     pub failed_tests: Vec<String>,
     pub flaky_tests: Vec<FlakyTest>,
     pub durations: HashMap<String, Duration>,
+    pub test_cases: Vec<TestCaseRecord>,
+    /// The `.snap` files that snapshot assertions compared against.
+    pub snapshots: HashSet<Utf8PathBuf>,
 }
 
 /// Reads and writes test results in the cache directory for a specific run.
@@ -32,16 +35,12 @@ impl RunCache {
         Self { run_dir }
     }
 
-    /// Writes a fail-fast signal file to indicate a worker encountered a test failure.
-    pub fn write_fail_fast_signal(&self) -> Result<()> {
-        fs::create_dir_all(&self.run_dir)?;
-        fs::write(CacheFile::FailFastSignal.path_in(&self.run_dir), "")?;
-        Ok(())
-    }
-
-    /// Checks whether any worker has written a fail-fast signal file.
-    pub fn has_fail_fast_signal(&self) -> bool {
-        CacheFile::FailFastSignal.path_in(&self.run_dir).exists()
+    /// Constructs a cache handle for the most recent run in the cache
+    /// directory, or `None` if there are no runs.
+    pub fn most_recent(cache_dir: &Utf8Path) -> Result<Option<Self>> {
+        Ok(collect_run_dirs(cache_dir)?.last().map(|run| Self {
+            run_dir: cache_dir.join(run),
+        }))
     }
 
     /// Reads and merges test results from all worker directories for this run.
@@ -55,6 +54,13 @@ impl RunCache {
         Ok(results)
     }
 
+    /// Whether the given worker has saved its results for this run.
+    pub fn has_worker_results(&self, worker_id: usize) -> bool {
+        CacheFile::Stats
+            .path_in(&self.worker_dir(worker_id))
+            .exists()
+    }
+
     /// Path to the directory for a specific worker. Does not create it.
     pub fn worker_dir(&self, worker_id: usize) -> Utf8PathBuf {
         self.run_dir.join(worker_folder(worker_id))
@@ -67,20 +73,20 @@ impl RunCache {
         CacheFile::Coverage.path_in(&self.worker_dir(worker_id))
     }
 
-    /// Returns paths to every per-worker coverage file that exists for this
-    /// run, sorted by worker directory. Used to feed the coverage report.
+    /// Returns paths to every coverage file that exists for this run, sorted
+    /// by worker directory: each worker's own file, then the files of the
+    /// Python subprocesses its tests started. Used to feed the coverage
+    /// report.
     pub fn coverage_files(&self) -> Result<Vec<Utf8PathBuf>> {
         let mut files = Vec::new();
         for worker_dir in list_worker_dirs(&self.run_dir)? {
-            let path = CacheFile::Coverage.path_in(&worker_dir);
-            if path.exists() {
-                files.push(path);
-            }
+            files.extend(list_coverage_files(&worker_dir)?);
         }
         Ok(files)
     }
 
-    /// Persists a test run result (stats, diagnostics, durations, and failed tests) to disk.
+    /// Persists a test run result (stats, diagnostics, durations, failed tests,
+    /// per-test records, and referenced snapshots) to disk.
     pub fn write_result(
         &self,
         worker_id: usize,
@@ -102,6 +108,8 @@ impl RunCache {
             .collect();
         write_json_if_nonempty(&worker_dir, CacheFile::FailedTests, &failed_names)?;
         write_json_if_nonempty(&worker_dir, CacheFile::FlakyTests, result.flaky_tests())?;
+        write_json_if_nonempty(&worker_dir, CacheFile::TestCases, result.test_cases())?;
+        write_json_if_nonempty(&worker_dir, CacheFile::Snapshots, result.snapshots())?;
 
         Ok(())
     }
@@ -147,12 +155,20 @@ fn read_worker_results(worker_dir: &Utf8Path, results: &mut AggregatedResults) -
         results.flaky_tests.extend(flaky);
     }
 
+    if let Some(test_cases) = read_json::<Vec<TestCaseRecord>>(worker_dir, CacheFile::TestCases)? {
+        results.test_cases.extend(test_cases);
+    }
+
     if let Some(durations) =
         read_json::<HashMap<String, Duration>>(worker_dir, CacheFile::Durations)?
     {
         results.durations.extend(durations);
     }
 
+    if let Some(snapshots) = read_json::<Vec<Utf8PathBuf>>(worker_dir, CacheFile::Snapshots)? {
+        results.snapshots.extend(snapshots);
+    }
+
     Ok(())
 }
 
@@ -204,6 +220,37 @@ fn list_worker_dirs(run_dir: &Utf8Path) -> Result<Vec<Utf8PathBuf>> {
     Ok(dirs)
 }
 
+/// Returns the coverage files in a worker directory: the worker's own file,
+/// then the `coverage.<pid>.json` files of its subprocesses, sorted.
+fn list_coverage_files(worker_dir: &Utf8Path) -> Result<Vec<Utf8PathBuf>> {
+    let mut files = Vec::new();
+    let worker_file = CacheFile::Coverage.path_in(worker_dir);
+    if worker_file.exists() {
+        files.push(worker_file);
+    }
+
+    let mut subprocess_files = Vec::new();
+    for entry in fs::read_dir(worker_dir)? {
+        let entry = entry?;
+        let Ok(path) = Utf8PathBuf::try_from(entry.path()) else {
+            continue;
+        };
+        if path.file_name().is_some_and(is_subprocess_coverage_file) {
+            subprocess_files.push(path);
+        }
+    }
+    subprocess_files.sort();
+    files.extend(subprocess_files);
+    Ok(files)
+}
+
+/// Whether `name` is a `coverage.<pid>.json` file.
+fn is_subprocess_coverage_file(name: &str) -> bool {
+    name.strip_prefix("coverage.")
+        .and_then(|rest| rest.strip_suffix(".json"))
+        .is_some_and(|pid| !pid.is_empty() && pid.bytes().all(|b| b.is_ascii_digit()))
+}
+
 /// Returns `run-*` directory names sorted chronologically by their parsed timestamp.
 fn collect_run_dirs(cache_dir: &Utf8Path) -> Result<Vec<String>> {
     let mut run_dirs: Vec<String> = list_subdirs_with_prefix(cache_dir, RUN_PREFIX)?
@@ -264,6 +311,23 @@ pub fn prune_cache(cache_dir: &Utf8Path) -> Result<PruneResult> {
     Ok(PruneResult { removed })
 }
 
+/// Removes the coverage data files of every run in the cache, leaving the
+/// rest of each run in place.
+///
+/// Returns the number of files removed.
+pub fn erase_coverage(cache_dir: &Utf8Path) -> Result<usize> {
+    let mut removed = 0;
+    for run in collect_run_dirs(cache_dir)? {
+        for worker_dir in list_worker_dirs(&cache_dir.join(run))? {
+            for path in list_coverage_files(&worker_dir)? {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
 /// Removes the entire cache directory.
 ///
 /// Returns `true` if the directory existed and was removed.
@@ -282,6 +346,7 @@ mod tests {
 
     use camino::Utf8PathBuf;
     use insta::assert_debug_snapshot;
+    use karva_diagnostic::TestCaseOutcome;
 
     use super::*;
 
@@ -370,6 +435,35 @@ mod tests {
         assert!(results.diagnostics.is_empty());
     }
 
+    #[test]
+    fn has_worker_results_checks_the_worker_stats_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache_dir = Utf8PathBuf::try_from(tmp.path().to_path_buf()).unwrap();
+
+        let run_hash = RunHash::from_existing("run-650");
+        let run_name = run_hash.dir_name();
+        create_cache_with_stats(tmp.path(), &run_name, 1, r#"{"passed": 1}"#);
+
+        let cache = RunCache::new(&cache_dir, &run_hash);
+        assert!(!cache.has_worker_results(0));
+        assert!(cache.has_worker_results(1));
+    }
+
+    #[test]
+    fn most_recent_finds_the_newest_run() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache_dir = Utf8PathBuf::try_from(tmp.path().to_path_buf()).unwrap();
+
+        assert!(RunCache::most_recent(&cache_dir).unwrap().is_none());
+
+        create_cache_with_stats(tmp.path(), "run-9", 0, r#"{"passed": 1}"#);
+        create_cache_with_stats(tmp.path(), "run-100", 1, r#"{"passed": 1}"#);
+
+        let cache = RunCache::most_recent(&cache_dir).unwrap().unwrap();
+        assert!(!cache.has_worker_results(0));
+        assert!(cache.has_worker_results(1));
+    }
+
     #[test]
     fn write_last_failed_roundtrips_with_read() {
         let tmp = tempfile::tempdir().unwrap();
@@ -502,6 +596,56 @@ mod tests {
         assert!(!cache_dir.join("run-9").exists());
     }
 
+    #[test]
+    fn erase_coverage_removes_only_coverage_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache_dir = Utf8PathBuf::try_from(tmp.path().to_path_buf()).unwrap();
+
+        create_cache_with_stats(tmp.path(), "run-1", 0, r#"{"passed": 1}"#);
+        create_cache_with_stats(tmp.path(), "run-2", 0, r#"{"passed": 1}"#);
+        for run in ["run-1", "run-2"] {
+            let worker_dir = tmp.path().join(run).join("worker-0");
+            fs::write(worker_dir.join(CacheFile::Coverage.filename()), "{}").unwrap();
+        }
+
+        assert_eq!(erase_coverage(&cache_dir).unwrap(), 2);
+        assert_eq!(erase_coverage(&cache_dir).unwrap(), 0);
+
+        let cache = RunCache::most_recent(&cache_dir).unwrap().unwrap();
+        assert!(cache.coverage_files().unwrap().is_empty());
+        assert!(cache.has_worker_results(0));
+    }
+
+    #[test]
+    fn coverage_files_include_subprocess_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache_dir = Utf8PathBuf::try_from(tmp.path().to_path_buf()).unwrap();
+
+        create_cache_with_stats(tmp.path(), "run-1", 0, r#"{"passed": 1}"#);
+        let worker_dir = tmp.path().join("run-1").join("worker-0");
+        for name in [
+            "coverage.json",
+            "coverage.200.json",
+            "coverage.17.json",
+            "coverage.x.json",
+        ] {
+            fs::write(worker_dir.join(name), "{}").unwrap();
+        }
+
+        let cache = RunCache::most_recent(&cache_dir).unwrap().unwrap();
+        let names: Vec<String> = cache
+            .coverage_files()
+            .unwrap()
+            .iter()
+            .filter_map(|path| path.file_name().map(str::to_string))
+            .collect();
+        assert_eq!(
+            names,
+            vec!["coverage.json", "coverage.17.json", "coverage.200.json"]
+        );
+        assert_eq!(erase_coverage(&cache_dir).unwrap(), 3);
+    }
+
     #[test]
     fn clean_cache_removes_dir_and_returns_true() {
         let tmp = tempfile::tempdir().unwrap();
@@ -586,14 +730,76 @@ mod tests {
     }
 
     #[test]
-    fn fail_fast_signal_round_trip() {
+    fn aggregate_results_merges_test_cases_across_workers() {
         let tmp = tempfile::tempdir().unwrap();
         let cache_dir = Utf8PathBuf::try_from(tmp.path().to_path_buf()).unwrap();
-        let run_hash = RunHash::from_existing("run-800");
+        let run_hash = RunHash::from_existing("run-750");
+
+        let run_dir = tmp.path().join(run_hash.dir_name());
+        for (worker_id, name, outcome) in [(0, "test_a", "passed"), (1, "test_b", "failed")] {
+            let worker_dir = run_dir.join(format!("worker-{worker_id}"));
+            fs::create_dir_all(&worker_dir).unwrap();
+            fs::write(
+                worker_dir.join(CacheFile::TestCases.filename()),
+                format!(
+                    r#"[{{"module_name": "mod", "function_name": "{name}", "file": "mod.py", "outcome": "{outcome}", "duration": {{"secs": 0, "nanos": 5000000}}}}]"#
+                ),
+            )
+            .unwrap();
+        }
+
         let cache = RunCache::new(&cache_dir, &run_hash);
+        let results = cache.aggregate_results().unwrap();
 
-        assert!(!cache.has_fail_fast_signal());
-        cache.write_fail_fast_signal().unwrap();
-        assert!(cache.has_fail_fast_signal());
+        let summary: Vec<(String, TestCaseOutcome)> = results
+            .test_cases
+            .into_iter()
+            .map(|record| (record.function_name, record.outcome))
+            .collect();
+        assert_debug_snapshot!(summary, @r#"
+        [
+            (
+                "test_a",
+                Passed,
+            ),
+            (
+                "test_b",
+                Failed,
+            ),
+        ]
+        "#);
+    }
+
+    #[test]
+    fn aggregate_results_merges_snapshots_across_workers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache_dir = Utf8PathBuf::try_from(tmp.path().to_path_buf()).unwrap();
+        let run_hash = RunHash::from_existing("run-800");
+
+        let run_dir = tmp.path().join(run_hash.dir_name());
+        for (worker_id, snapshots) in [
+            (0, r#"["snapshots/test__a.snap", "snapshots/test__b.snap"]"#),
+            (1, r#"["snapshots/test__b.snap", "snapshots/test__c.snap"]"#),
+        ] {
+            let worker_dir = run_dir.join(format!("worker-{worker_id}"));
+            fs::create_dir_all(&worker_dir).unwrap();
+            fs::write(worker_dir.join(CacheFile::Snapshots.filename()), snapshots).unwrap();
+        }
+
+        let cache = RunCache::new(&cache_dir, &run_hash);
+        let mut snapshots: Vec<Utf8PathBuf> = cache
+            .aggregate_results()
+            .unwrap()
+            .snapshots
+            .into_iter()
+            .collect();
+        snapshots.sort();
+        assert_debug_snapshot!(snapshots, @r#"
+        [
+            "snapshots/test__a.snap",
+            "snapshots/test__b.snap",
+            "snapshots/test__c.snap",
+        ]
+        "#);
     }
 }
@@ -3,11 +3,11 @@ pub(crate) mod cache;
 pub(crate) mod hash;
 
 pub use cache::{
-    AggregatedResults, PruneResult, RunCache, clean_cache, prune_cache, read_last_failed,
-    read_recent_durations, write_last_failed,
+    AggregatedResults, PruneResult, RunCache, clean_cache, erase_coverage, prune_cache,
+    read_last_failed, read_recent_durations, write_last_failed,
 };
 pub use hash::RunHash;
-pub use karva_diagnostic::{DisplayFlakyTests, FlakyTest};
+pub use karva_diagnostic::{DisplayFlakyTests, FlakyTest, JunitReport, RunEvent, TestCaseOutcome};
 
 /// The directory name used for the cache, relative to the project root.
 pub const CACHE_DIR: &str = ".karva_cache";
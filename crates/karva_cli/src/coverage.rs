@@ -0,0 +1,170 @@
+use camino::Utf8PathBuf;
+use clap::Parser;
+use karva_metadata::{CovFailUnder, CoverageOptions, Options};
+
+use crate::enums::CovReport;
+use crate::test::{cov_report_options, parse_cov_fail_under, parse_cov_report};
+
+#[derive(Debug, Parser)]
+pub struct CoverageCommand {
+    #[command(subcommand)]
+    pub action: CoverageAction,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum CoverageAction {
+    /// Merge coverage data from several runs or machines into one file.
+    Combine(CombineArgs),
+
+    /// Report on saved coverage data without rerunning the tests.
+    Report(CoverageReportArgs),
+
+    /// Delete the coverage data of cached runs and the combined data file.
+    Erase,
+
+    /// List the tests that ran a line in the most recent coverage run.
+    WhoCovers(WhoCoversArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct CombineArgs {
+    /// Coverage data files to merge.
+    ///
+    /// Accepts the per-worker files of a run as well as files written by
+    /// an earlier `karva coverage combine`. Defaults to the data of the
+    /// most recent run.
+    #[clap(value_name = "FILE")]
+    pub files: Vec<Utf8PathBuf>,
+
+    /// Where to write the combined data.
+    ///
+    /// Defaults to `.karva_coverage.json`.
+    #[clap(short, long, value_name = "PATH")]
+    pub output: Option<Utf8PathBuf>,
+
+    /// Replace a path prefix recorded on another machine, as `OLD=NEW`.
+    ///
+    /// Every measured file whose path starts with `OLD` is moved under
+    /// `NEW`, resolved against the current directory. May be passed
+    /// multiple times; the first matching prefix wins.
+    #[clap(long = "remap", value_name = "OLD=NEW", value_parser = parse_path_remap)]
+    pub remaps: Vec<PathRemap>,
+}
+
+#[derive(Debug, Parser)]
+pub struct CoverageReportArgs {
+    /// Coverage data files to report on.
+    ///
+    /// Several files are combined before reporting. Defaults to the file
+    /// written by `karva coverage combine` if there is one, and to the data
+    /// of the most recent run otherwise.
+    #[clap(value_name = "FILE")]
+    pub files: Vec<Utf8PathBuf>,
+
+    /// Report type, as for `karva test --cov-report`.
+    ///
+    /// May be passed multiple times; the terminal table is always printed.
+    /// Adds to the reports set in the `[coverage]` configuration.
+    #[clap(
+        long = "report",
+        value_name = "TYPE",
+        value_parser = parse_cov_report,
+        action = clap::ArgAction::Append
+    )]
+    pub reports: Vec<CovReport>,
+
+    /// Fail if total coverage is below the given percentage.
+    #[clap(long, value_name = "PERCENT", value_parser = parse_cov_fail_under)]
+    pub fail_under: Option<f64>,
+
+    /// Report the coverage of the lines changed since this git ref.
+    #[clap(long, value_name = "REF")]
+    pub diff_base: Option<String>,
+
+    /// Report the coverage of the lines changed by this unified diff.
+    ///
+    /// Pass `-` to read the diff from stdin.
+    #[clap(long, value_name = "PATH", conflicts_with = "diff_base")]
+    pub diff_file: Option<Utf8PathBuf>,
+
+    /// Fail if coverage of the changed lines is below the given percentage.
+    #[clap(long, value_name = "PERCENT", value_parser = parse_cov_fail_under)]
+    pub fail_under_diff: Option<f64>,
+
+    /// The path to a `karva.toml` file to use for configuration.
+    #[arg(long, env = "KARVA_CONFIG_FILE", value_name = "PATH")]
+    pub config_file: Option<Utf8PathBuf>,
+
+    /// Configuration profile to read the `[coverage]` settings from.
+    ///
+    /// Defaults to `default`.
+    #[arg(short = 'P', long, env = "KARVA_PROFILE", value_name = "NAME")]
+    pub profile: Option<String>,
+}
+
+impl CoverageReportArgs {
+    /// The coverage options given on the command line, which take
+    /// precedence over the `[coverage]` configuration.
+    pub fn to_options(&self) -> Options {
+        Options {
+            coverage: Some(CoverageOptions {
+                fail_under: self.fail_under.map(CovFailUnder),
+                fail_under_diff: self.fail_under_diff.map(CovFailUnder),
+                diff_base: self.diff_base.clone(),
+                diff_file: self.diff_file.as_ref().map(ToString::to_string),
+                ..cov_report_options(&self.reports)
+            }),
+            ..Options::default()
+        }
+    }
+}
+
+/// A path prefix to replace when combining coverage data, written
+/// `OLD=NEW`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathRemap {
+    pub from: String,
+    pub to: Utf8PathBuf,
+}
+
+fn parse_path_remap(raw: &str) -> Result<PathRemap, String> {
+    let (from, to) = raw
+        .split_once('=')
+        .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+        .ok_or_else(|| format!("expected `OLD=NEW`, got `{raw}`"))?;
+    Ok(PathRemap {
+        from: from.to_string(),
+        to: Utf8PathBuf::from(to),
+    })
+}
+
+#[derive(Debug, Parser)]
+pub struct WhoCoversArgs {
+    /// The line to look up, as `FILE:LINE`.
+    ///
+    /// The tests are taken from the per-test contexts recorded by
+    /// `karva test --cov --cov-context`.
+    #[clap(value_name = "FILE:LINE", value_parser = parse_source_line)]
+    pub location: SourceLine,
+}
+
+/// A line of a source file, written `FILE:LINE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLine {
+    pub path: Utf8PathBuf,
+    pub line: u32,
+}
+
+fn parse_source_line(raw: &str) -> Result<SourceLine, String> {
+    let (path, line) = raw
+        .rsplit_once(':')
+        .filter(|(path, _)| !path.is_empty())
+        .ok_or_else(|| format!("expected `FILE:LINE`, got `{raw}`"))?;
+    let line = line
+        .parse::<u32>()
+        .map_err(|err| format!("`{line}` is not a valid line number: {err}"))?;
+    Ok(SourceLine {
+        path: Utf8PathBuf::from(path),
+        line,
+    })
+}
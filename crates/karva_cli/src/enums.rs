@@ -1,18 +1,28 @@
+use camino::Utf8PathBuf;
 use ruff_db::diagnostic::DiagnosticFormat;
 
-use karva_metadata::{NoTestsMode, RunIgnoredMode};
+use karva_metadata::{NoTestsMode, RunIgnoredMode, SnapshotUpdateMode};
 
-/// Coverage terminal report type.
-#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, PartialOrd, Ord, Default, clap::ValueEnum)]
+/// A coverage report requested with `--cov-report`.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CovReport {
     /// Compact terminal table (default).
-    #[default]
-    #[value(name = "term")]
     Term,
 
     /// Terminal table with a `Missing` column listing uncovered line numbers.
-    #[value(name = "term-missing")]
     TermMissing,
+
+    /// Cobertura XML report written to the given path.
+    Xml(Utf8PathBuf),
+
+    /// JSON report written to the given path.
+    Json(Utf8PathBuf),
+
+    /// LCOV report written to the given path.
+    Lcov(Utf8PathBuf),
+
+    /// HTML report written into the given directory.
+    Html(Utf8PathBuf),
 }
 
 /// The diagnostic output format.
@@ -46,13 +56,28 @@ impl From<OutputFormat> for karva_metadata::OutputFormat {
     }
 }
 
-impl From<CovReport> for karva_metadata::CovReport {
-    fn from(value: CovReport) -> Self {
-        match value {
-            CovReport::Term => Self::Term,
-            CovReport::TermMissing => Self::TermMissing,
+/// The format of the messages karva prints while running tests.
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum MessageFormat {
+    /// Human-readable status lines and summary (default).
+    #[default]
+    Human,
+
+    /// A stream of newline-delimited JSON events on stdout.
+    Json,
+}
+
+impl MessageFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Human => "human",
+            Self::Json => "json",
         }
     }
+
+    pub fn is_json(self) -> bool {
+        matches!(self, Self::Json)
+    }
 }
 
 /// Whether to run ignored/skipped tests.
@@ -110,3 +135,68 @@ impl From<NoTests> for NoTestsMode {
         }
     }
 }
+
+/// When snapshot assertions write snapshot files.
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum SnapshotUpdate {
+    /// `no` on CI, `new` otherwise.
+    Auto,
+
+    /// Write new and changed snapshots to pending `.snap.new` files.
+    New,
+
+    /// Write new and changed snapshots straight to `.snap` files.
+    #[value(alias = "1", alias = "true")]
+    Always,
+
+    /// Write new snapshots to `.snap` files and changed ones to `.snap.new` files.
+    Unseen,
+
+    /// Write no snapshot files.
+    #[value(alias = "0", alias = "false")]
+    No,
+}
+
+impl From<SnapshotUpdate> for SnapshotUpdateMode {
+    fn from(value: SnapshotUpdate) -> Self {
+        match value {
+            SnapshotUpdate::Auto => Self::Auto,
+            SnapshotUpdate::New => Self::New,
+            SnapshotUpdate::Always => Self::Always,
+            SnapshotUpdate::Unseen => Self::Unseen,
+            SnapshotUpdate::No => Self::No,
+        }
+    }
+}
+
+/// What to do with snapshot files that no assertion used during a run.
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Unreferenced {
+    /// List them after the run.
+    Warn,
+
+    /// List them and fail the run.
+    Reject,
+
+    /// Delete them.
+    Delete,
+}
+
+/// How hard per-test timeouts are enforced.
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum TimeoutMethod {
+    /// Run the test on a separate thread and abandon it once the timeout expires.
+    Thread,
+
+    /// Kill the worker running the test once the timeout expires.
+    Process,
+}
+
+impl From<TimeoutMethod> for karva_metadata::TimeoutMethod {
+    fn from(value: TimeoutMethod) -> Self {
+        match value {
+            TimeoutMethod::Thread => Self::Thread,
+            TimeoutMethod::Process => Self::Process,
+        }
+    }
+}
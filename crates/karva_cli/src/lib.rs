@@ -3,6 +3,7 @@ use clap::builder::Styles;
 use clap::builder::styling::{AnsiColor, Effects};
 
 mod cache;
+mod coverage;
 mod enums;
 mod partition;
 mod snapshot;
@@ -10,7 +11,14 @@ mod test;
 mod verbosity;
 
 pub use cache::{CacheAction, CacheCommand};
-pub use enums::{CovReport, NoTests, OutputFormat, RunIgnored};
+pub use coverage::{
+    CombineArgs, CoverageAction, CoverageCommand, CoverageReportArgs, PathRemap, SourceLine,
+    WhoCoversArgs,
+};
+pub use enums::{
+    CovReport, MessageFormat, NoTests, OutputFormat, RunIgnored, SnapshotUpdate, TimeoutMethod,
+    Unreferenced,
+};
 pub use partition::PartitionSelection;
 pub use snapshot::{
     SnapshotAction, SnapshotCommand, SnapshotDeleteArgs, SnapshotFilterArgs, SnapshotPruneArgs,
@@ -41,6 +49,9 @@ pub enum Command {
     /// Manage snapshots created by `karva.assert_snapshot()`.
     Snapshot(SnapshotCommand),
 
+    /// Combine, report and query saved coverage data.
+    Coverage(CoverageCommand),
+
     /// Manage the karva cache.
     Cache(CacheCommand),
 
@@ -4,11 +4,14 @@ use camino::Utf8PathBuf;
 use clap::Parser;
 use karva_logging::{FinalStatusLevel, StatusLevel, TerminalColor};
 use karva_metadata::{
-    CovFailUnder, CoverageOptions, MaxFail, Options, SlowTimeoutSecs, SrcOptions, TerminalOptions,
-    TestOptions, TestTimeoutSecs,
+    CovFailUnder, CoverageOptions, MaxFail, Options, SlowTimeoutSecs, SnapshotOptions, SrcOptions,
+    TerminalOptions, TestOptions, TestTimeoutSecs,
 };
 
-use crate::enums::{CovReport, NoTests, OutputFormat, RunIgnored};
+use crate::enums::{
+    CovReport, MessageFormat, NoTests, OutputFormat, RunIgnored, SnapshotUpdate, TimeoutMethod,
+    Unreferenced,
+};
 use crate::partition::PartitionSelection;
 use crate::verbosity::Verbosity;
 
@@ -33,6 +36,10 @@ pub struct SubTestCommand {
     #[clap(long, help_heading = "Filter options")]
     pub test_prefix: Option<String>,
 
+    /// The prefix of the test classes.
+    #[clap(long, help_heading = "Filter options")]
+    pub test_class_prefix: Option<String>,
+
     /// When set, .gitignore files will not be respected.
     #[clap(long, default_missing_value = "true", num_args=0..1, help_heading = "Filter options")]
     pub no_ignore: Option<bool>,
@@ -125,17 +132,44 @@ pub struct SubTestCommand {
     #[clap(long, value_name = "SECONDS", help_heading = "Runner options")]
     pub timeout: Option<f64>,
 
-    /// Update snapshots directly instead of creating pending `.snap.new` files.
-    ///
-    /// When set, `karva.assert_snapshot()` will write directly to `.snap` files,
-    /// accepting any changes automatically.
+    /// How hard per-test timeouts are enforced [default: thread]
+    #[clap(long, value_name = "METHOD", help_heading = "Runner options")]
+    pub timeout_method: Option<TimeoutMethod>,
+
+    /// Start a fresh worker when one crashes, to run the tests it had not reached yet.
     #[clap(long, default_missing_value = "true", num_args=0..1, help_heading = "Runner options")]
-    pub snapshot_update: Option<bool>,
+    pub respawn_on_crash: Option<bool>,
+
+    /// When snapshot assertions write snapshot files [default: auto]
+    ///
+    /// `new` writes new and changed snapshots to pending `.snap.new` files,
+    /// `always` writes them straight to `.snap` files, `unseen` writes new
+    /// snapshots to `.snap` files and keeps changed ones pending, and `no`
+    /// writes nothing. `auto` is `no` on CI and `new` otherwise. Without a
+    /// value, `--snapshot-update` means `always`.
+    #[clap(
+        long,
+        value_name = "MODE",
+        env = "KARVA_SNAPSHOT_UPDATE",
+        default_missing_value = "always",
+        num_args = 0..1,
+        help_heading = "Runner options"
+    )]
+    pub snapshot_update: Option<SnapshotUpdate>,
 
     /// The format to use for printing diagnostic messages.
     #[arg(long, help_heading = "Reporter options")]
     pub output_format: Option<OutputFormat>,
 
+    /// The format of the messages printed while tests run.
+    ///
+    /// `json` replaces the human-readable output with a stream of
+    /// newline-delimited JSON events on stdout, one per line, as tests start
+    /// and finish. Diagnostics, coverage tables and test output shown with
+    /// `--show-output` are written to stderr.
+    #[arg(long, value_name = "FORMAT", help_heading = "Reporter options")]
+    pub message_format: Option<MessageFormat>,
+
     /// Show Python stdout during test execution.
     #[clap(short = 's', long, default_missing_value = "true", num_args=0..1, help_heading = "Reporter options")]
     pub show_output: Option<bool>,
@@ -158,6 +192,13 @@ pub struct SubTestCommand {
     )]
     pub final_status_level: Option<FinalStatusLevel>,
 
+    /// Write a JUnit XML report of the run to the given path.
+    ///
+    /// Relative paths are resolved against the project root. Parent
+    /// directories are created if needed.
+    #[arg(long, value_name = "PATH", help_heading = "Reporter options")]
+    pub junit_path: Option<Utf8PathBuf>,
+
     /// Measure code coverage for the given source path.
     ///
     /// May be passed multiple times to measure several sources. Pass without
@@ -186,18 +227,24 @@ pub struct SubTestCommand {
     )]
     pub no_cov: bool,
 
-    /// Coverage terminal report type.
+    /// Coverage report type.
     ///
     /// `term` (default) prints a compact terminal table.
     /// `term-missing` extends it with a `Missing` column listing the
-    /// uncovered line numbers per file.
+    /// uncovered line numbers per file. `xml:PATH` writes a Cobertura XML
+    /// report, `json:PATH` a JSON report and `lcov:PATH` an LCOV report, to
+    /// `coverage.xml`, `coverage.json` and `coverage.lcov` when no path is
+    /// given. `html:DIR` writes an HTML
+    /// report into `DIR`, `htmlcov` by default. May be passed multiple
+    /// times; the terminal table is always printed.
     #[clap(
         long = "cov-report",
         value_name = "TYPE",
-        value_enum,
+        value_parser = parse_cov_report,
+        action = clap::ArgAction::Append,
         help_heading = "Coverage options"
     )]
-    pub cov_report: Option<CovReport>,
+    pub cov_report: Vec<CovReport>,
 
     /// Fail the run if total coverage is below the given percentage.
     ///
@@ -213,6 +260,113 @@ pub struct SubTestCommand {
     )]
     pub cov_fail_under: Option<f64>,
 
+    /// Fail the run if coverage of the changed lines is below the given
+    /// percentage.
+    ///
+    /// Only checked when a diff is given with `--cov-diff-base` or
+    /// `--cov-diff-file`. A diff that changes no executable line passes.
+    #[clap(
+        long = "cov-fail-under-diff",
+        value_name = "PERCENT",
+        value_parser = parse_cov_fail_under,
+        help_heading = "Coverage options"
+    )]
+    pub cov_fail_under_diff: Option<f64>,
+
+    /// Report the coverage of the lines changed since this git ref.
+    ///
+    /// The changed lines come from `git diff` in the project root, starting
+    /// from where `HEAD` branched off `<REF>` and including uncommitted
+    /// changes to tracked files. A range such as `main...HEAD` is passed to
+    /// `git diff` as given.
+    #[clap(
+        long = "cov-diff-base",
+        value_name = "REF",
+        help_heading = "Coverage options"
+    )]
+    pub cov_diff_base: Option<String>,
+
+    /// Report the coverage of the lines changed by this unified diff.
+    ///
+    /// Pass `-` to read the diff from stdin.
+    #[clap(
+        long = "cov-diff-file",
+        value_name = "PATH",
+        conflicts_with = "cov_diff_base",
+        help_heading = "Coverage options"
+    )]
+    pub cov_diff_file: Option<Utf8PathBuf>,
+
+    /// Measure branch coverage in addition to line coverage.
+    ///
+    /// Adds `Branch` and `BrPart` columns to the coverage report, and counts
+    /// branches towards the total percentage used by `--cov-fail-under`.
+    #[clap(
+        long = "cov-branch",
+        action = clap::ArgAction::SetTrue,
+        help_heading = "Coverage options"
+    )]
+    pub cov_branch: bool,
+
+    /// Record which tests run each line.
+    ///
+    /// Each executed line is tagged with the qualified names of the tests
+    /// that ran it. The JSON and HTML reports list them per line, and
+    /// `karva coverage who-covers` looks them up after the run.
+    #[clap(
+        long = "cov-context",
+        action = clap::ArgAction::SetTrue,
+        help_heading = "Coverage options"
+    )]
+    pub cov_context: bool,
+
+    /// Only measure files matching this glob.
+    ///
+    /// Matches the absolute path of a file or its path relative to the
+    /// project root. May be passed multiple times.
+    #[clap(
+        long = "cov-include",
+        value_name = "GLOB",
+        action = clap::ArgAction::Append,
+        help_heading = "Coverage options"
+    )]
+    pub cov_include: Vec<String>,
+
+    /// Do not measure files matching this glob.
+    ///
+    /// Takes precedence over `--cov-include`. May be passed multiple times.
+    #[clap(
+        long = "cov-omit",
+        value_name = "GLOB",
+        action = clap::ArgAction::Append,
+        help_heading = "Coverage options"
+    )]
+    pub cov_omit: Vec<String>,
+
+    /// Exclude source lines matching this regex from coverage.
+    ///
+    /// The block a matching line starts is excluded with it, like a line
+    /// marked `# pragma: no cover`. May be passed multiple times.
+    #[clap(
+        long = "cov-exclude-line",
+        value_name = "REGEX",
+        action = clap::ArgAction::Append,
+        help_heading = "Coverage options"
+    )]
+    pub cov_exclude_line: Vec<String>,
+
+    /// Do not measure the branches of lines matching this regex.
+    ///
+    /// Like a line marked `# pragma: no branch`. May be passed multiple
+    /// times.
+    #[clap(
+        long = "cov-partial-branch",
+        value_name = "REGEX",
+        action = clap::ArgAction::Append,
+        help_heading = "Coverage options"
+    )]
+    pub cov_partial_branch: Vec<String>,
+
     /// Internal: per-worker coverage data file path.
     ///
     /// Set automatically by the runner when `--cov` is enabled. Not intended
@@ -270,6 +424,18 @@ pub struct TestCommand {
     #[clap(long, help_heading = "Runner options")]
     pub watch: bool,
 
+    /// Report or remove snapshot files that no assertion used during the run.
+    ///
+    /// Looks at the `.snap` files of every test module that ran, so it
+    /// catches snapshots of parametrize variants that are no longer
+    /// generated, renamed named snapshots, and extra numbered snapshots.
+    /// `warn` lists them, `reject` lists them and fails the run, and
+    /// `delete` removes them. The check is skipped when only some tests
+    /// ran, through filters, `--last-failed`, `--partition` or test names
+    /// in the paths, and when a test failed.
+    #[clap(long, value_name = "ACTION", help_heading = "Runner options")]
+    pub unreferenced: Option<Unreferenced>,
+
     /// Show the N slowest tests after the run completes.
     #[clap(long, value_name = "N", help_heading = "Reporter options")]
     pub durations: Option<usize>,
@@ -338,9 +504,11 @@ impl SubTestCommand {
                 show_python_output: self.show_output,
                 status_level: self.status_level,
                 final_status_level: self.final_status_level,
+                junit_path: self.junit_path.map(Utf8PathBuf::into_string),
             }),
             test: Some(TestOptions {
                 test_function_prefix: self.test_prefix,
+                test_class_prefix: self.test_class_prefix,
                 fail_fast,
                 max_fail,
                 try_import_fixtures: self.try_import_fixtures,
@@ -348,12 +516,27 @@ impl SubTestCommand {
                 no_tests: self.no_tests.map(Into::into),
                 slow_timeout: self.slow_timeout.map(SlowTimeoutSecs),
                 timeout: self.timeout.map(TestTimeoutSecs),
+                timeout_method: self.timeout_method.map(Into::into),
+                respawn_on_crash: self.respawn_on_crash,
             }),
             coverage: Some(CoverageOptions {
                 sources: (!self.cov.is_empty()).then(|| self.cov.clone()),
-                report: self.cov_report.map(Into::into),
                 fail_under: self.cov_fail_under.map(CovFailUnder),
+                fail_under_diff: self.cov_fail_under_diff.map(CovFailUnder),
+                diff_base: self.cov_diff_base,
+                diff_file: self.cov_diff_file.map(Utf8PathBuf::into_string),
+                branch: self.cov_branch.then_some(true),
+                context: self.cov_context.then_some(true),
+                include: (!self.cov_include.is_empty()).then_some(self.cov_include),
+                omit: (!self.cov_omit.is_empty()).then_some(self.cov_omit),
+                exclude_lines: (!self.cov_exclude_line.is_empty()).then_some(self.cov_exclude_line),
+                partial_branches: (!self.cov_partial_branch.is_empty())
+                    .then_some(self.cov_partial_branch),
                 disabled: self.no_cov.then_some(true),
+                ..cov_report_options(&self.cov_report)
+            }),
+            snapshot: Some(SnapshotOptions {
+                update: self.snapshot_update.map(Into::into),
             }),
         }
     }
@@ -369,10 +552,57 @@ impl TestCommand {
     }
 }
 
+/// The coverage options set by `--cov-report` arguments. Each sets one kind
+/// of report; the last of a kind wins.
+pub(crate) fn cov_report_options(reports: &[CovReport]) -> CoverageOptions {
+    let mut options = CoverageOptions::default();
+    for report in reports {
+        match report {
+            CovReport::Term => options.report = Some(karva_metadata::CovReport::Term),
+            CovReport::TermMissing => {
+                options.report = Some(karva_metadata::CovReport::TermMissing);
+            }
+            CovReport::Xml(path) => options.xml_path = Some(path.to_string()),
+            CovReport::Json(path) => options.json_path = Some(path.to_string()),
+            CovReport::Lcov(path) => options.lcov_path = Some(path.to_string()),
+            CovReport::Html(dir) => options.html_dir = Some(dir.to_string()),
+        }
+    }
+    options
+}
+
+/// Parse a `--cov-report` argument: `term`, `term-missing`, or `xml`,
+/// `json`, `lcov` or `html` with an optional `:PATH`.
+pub(crate) fn parse_cov_report(raw: &str) -> Result<CovReport, String> {
+    let (kind, path) = match raw.split_once(':') {
+        Some((kind, path)) => (kind, Some(path).filter(|path| !path.is_empty())),
+        None => (raw, None),
+    };
+    match (kind, path) {
+        ("term", None) => Ok(CovReport::Term),
+        ("term-missing", None) => Ok(CovReport::TermMissing),
+        ("xml", path) => Ok(CovReport::Xml(Utf8PathBuf::from(
+            path.unwrap_or("coverage.xml"),
+        ))),
+        ("json", path) => Ok(CovReport::Json(Utf8PathBuf::from(
+            path.unwrap_or("coverage.json"),
+        ))),
+        ("lcov", path) => Ok(CovReport::Lcov(Utf8PathBuf::from(
+            path.unwrap_or("coverage.lcov"),
+        ))),
+        ("html", path) => Ok(CovReport::Html(Utf8PathBuf::from(
+            path.unwrap_or("htmlcov"),
+        ))),
+        _ => Err(format!(
+            "expected `term`, `term-missing`, `xml[:PATH]`, `json[:PATH]`, `lcov[:PATH]` or `html[:DIR]`, got `{raw}`"
+        )),
+    }
+}
+
 /// Parse and validate a `--cov-fail-under=N` argument.
 ///
 /// Accepts any finite percentage in `0..=100`.
-fn parse_cov_fail_under(raw: &str) -> Result<f64, String> {
+pub(crate) fn parse_cov_fail_under(raw: &str) -> Result<f64, String> {
     let value: f64 = raw
         .parse()
         .map_err(|err| format!("`{raw}` is not a valid number: {err}"))?;
@@ -1,13 +1,16 @@
 use camino::{Utf8Path, Utf8PathBuf};
-use ruff_python_ast::{PythonVersion, Stmt};
+use ruff_python_ast::{PythonVersion, Stmt, StmtClassDef};
 use ruff_python_parser::{Mode, ParseOptions, parse_unchecked};
 
 use karva_python_semantic::ModulePath;
 use karva_python_semantic::is_fixture_function;
 
 mod models;
+mod test_case;
 
-pub use models::{CollectedModule, CollectedPackage, ModuleType};
+pub use models::{CollectedClass, CollectedModule, CollectedPackage, ModuleType};
+
+use crate::test_case::ModuleScope;
 
 /// Settings that control how test files are collected and parsed.
 pub struct CollectionSettings<'a> {
@@ -15,6 +18,8 @@ pub struct CollectionSettings<'a> {
     pub python_version: PythonVersion,
     /// The prefix used to identify test functions (e.g., `"test_"`).
     pub test_function_prefix: &'a str,
+    /// The prefix used to identify test classes (e.g., `"Test"`).
+    pub test_class_prefix: &'a str,
     /// Whether to respect `.gitignore` and similar ignore files during file discovery.
     pub respect_ignore_files: bool,
     /// Whether to collect fixture function definitions in addition to test functions.
@@ -23,8 +28,11 @@ pub struct CollectionSettings<'a> {
 
 /// Collects test functions and fixtures from a Python file.
 ///
-/// If `function_names` is empty, all test functions matching the configured prefix are collected.
+/// If `function_names` is empty, all test functions matching the configured prefix are collected,
+/// along with the test methods of every class matching the class prefix and of every
+/// `unittest.TestCase` subclass.
 /// If `function_names` is non-empty, only test functions with names in the list are collected.
+/// Methods are named `Class::method`, and a bare `Class` selects every test method of the class.
 /// Fixtures are always collected regardless of the filter.
 pub fn collect_file(
     path: &Utf8PathBuf,
@@ -46,26 +54,114 @@ pub fn collect_file(
 
     let mut collected_module = CollectedModule::new(module_path, module_type, source_text);
 
+    let mut scope = ModuleScope::new(path, cwd, settings.python_version);
+
     for stmt in parsed.into_syntax().body {
-        if let Stmt::FunctionDef(function_def) = stmt {
-            if settings.collect_fixtures && is_fixture_function(&function_def) {
-                collected_module.add_fixture_function_def(function_def);
-                continue;
+        match stmt {
+            Stmt::FunctionDef(function_def) => {
+                if settings.collect_fixtures && is_fixture_function(&function_def) {
+                    collected_module.add_fixture_function_def(function_def);
+                    continue;
+                }
+
+                if is_test_function_to_collect(
+                    &function_def.name,
+                    function_names,
+                    settings.test_function_prefix,
+                ) {
+                    collected_module.add_test_function_def(function_def);
+                }
             }
+            Stmt::ClassDef(class_def) => {
+                let is_test_case = scope.add_class(&class_def);
 
-            if is_test_function_to_collect(
-                &function_def.name,
-                function_names,
-                settings.test_function_prefix,
-            ) {
-                collected_module.add_test_function_def(function_def);
+                if let Some(class) =
+                    collect_class(class_def, is_test_case, settings, function_names)
+                {
+                    collected_module.add_test_class(class);
+                }
             }
+            Stmt::Import(import) => scope.add_import(&import),
+            Stmt::ImportFrom(import) => scope.add_import_from(&import),
+            _ => {}
         }
     }
 
     Some(collected_module)
 }
 
+/// Collects the test methods and fixture methods of a test class.
+///
+/// Returns `None` when the class is not a test class, or when none of its
+/// methods are selected. Like pytest, classes that define `__init__` are
+/// not collected, since they can't be instantiated without arguments.
+/// `unittest.TestCase` subclasses are collected whatever their name, and
+/// may define `__init__`.
+fn collect_class(
+    class_def: StmtClassDef,
+    is_test_case: bool,
+    settings: &CollectionSettings,
+    function_names: &[String],
+) -> Option<CollectedClass> {
+    let class_name = class_def.name.to_string();
+
+    if !is_test_case
+        && !class_def.name.starts_with(settings.test_class_prefix)
+        && function_names.is_empty()
+    {
+        return None;
+    }
+
+    let method_names = selected_method_names(&class_name, function_names)?;
+
+    let mut class = CollectedClass::new(class_name);
+
+    for stmt in class_def.body {
+        let Stmt::FunctionDef(function_def) = stmt else {
+            continue;
+        };
+
+        if !is_test_case && function_def.name.as_str() == "__init__" {
+            return None;
+        }
+
+        if settings.collect_fixtures && is_fixture_function(&function_def) {
+            class.add_fixture_function_def(function_def);
+            continue;
+        }
+
+        if is_test_function_to_collect(
+            &function_def.name,
+            &method_names,
+            settings.test_function_prefix,
+        ) {
+            class.add_test_function_def(function_def);
+        }
+    }
+
+    (!class.is_empty()).then_some(class)
+}
+
+/// Returns the explicitly selected method names of a class.
+///
+/// An empty list selects every test method. Returns `None` when names were
+/// given but none of them refer to the class.
+fn selected_method_names(class_name: &str, explicit_names: &[String]) -> Option<Vec<String>> {
+    if explicit_names.is_empty() || explicit_names.iter().any(|name| name == class_name) {
+        return Some(Vec::new());
+    }
+
+    let method_names: Vec<String> = explicit_names
+        .iter()
+        .filter_map(|name| {
+            let (class, method) = name.split_once("::")?;
+            (class == class_name).then(|| method.to_string())
+        })
+        .collect();
+
+    (!method_names.is_empty()).then_some(method_names)
+}
+
 /// Returns `true` if a function should be collected as a test.
 ///
 /// When `explicit_names` is empty, any function whose name starts with
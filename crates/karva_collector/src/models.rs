@@ -18,6 +18,8 @@ pub struct CollectedModule {
     pub test_function_defs: Vec<StmtFunctionDef>,
     /// Fixture function definitions (functions with fixture decorators)
     pub fixture_function_defs: Vec<StmtFunctionDef>,
+    /// Test classes (classes starting with the test class prefix)
+    pub test_classes: Vec<CollectedClass>,
 }
 
 impl CollectedModule {
@@ -28,6 +30,7 @@ impl CollectedModule {
             source_text,
             test_function_defs: Vec::new(),
             fixture_function_defs: Vec::new(),
+            test_classes: Vec::new(),
         }
     }
 
@@ -39,6 +42,10 @@ impl CollectedModule {
         self.fixture_function_defs.push(function_def);
     }
 
+    pub(crate) fn add_test_class(&mut self, class: CollectedClass) {
+        self.test_classes.push(class);
+    }
+
     pub(crate) fn file_path(&self) -> &Utf8PathBuf {
         self.path.path()
     }
@@ -48,7 +55,58 @@ impl CollectedModule {
     }
 
     pub(crate) fn is_empty(&self) -> bool {
-        self.test_function_defs.is_empty() && self.fixture_function_defs.is_empty()
+        self.test_function_defs.is_empty()
+            && self.fixture_function_defs.is_empty()
+            && self.test_classes.is_empty()
+    }
+
+    /// Returns the number of tests in this module, including class methods.
+    pub fn test_count(&self) -> usize {
+        self.test_function_defs.len()
+            + self
+                .test_classes
+                .iter()
+                .map(|class| class.test_function_defs.len())
+                .sum::<usize>()
+    }
+}
+
+/// A test class containing raw AST method definitions.
+#[derive(Debug, Clone)]
+pub struct CollectedClass {
+    /// The name of the class.
+    pub name: String,
+    /// Test method definitions (methods starting with test prefix)
+    pub test_function_defs: Vec<StmtFunctionDef>,
+    /// Fixture method definitions (methods with fixture decorators)
+    pub fixture_function_defs: Vec<StmtFunctionDef>,
+}
+
+impl CollectedClass {
+    pub(crate) fn new(name: String) -> Self {
+        Self {
+            name,
+            test_function_defs: Vec::new(),
+            fixture_function_defs: Vec::new(),
+        }
+    }
+
+    pub(crate) fn add_test_function_def(&mut self, function_def: StmtFunctionDef) {
+        self.test_function_defs.push(function_def);
+    }
+
+    pub(crate) fn add_fixture_function_def(&mut self, function_def: StmtFunctionDef) {
+        self.fixture_function_defs.push(function_def);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.test_function_defs.is_empty()
+    }
+
+    /// Merges method definitions from another collection of the same class.
+    fn update(&mut self, class: Self) {
+        add_unique_definitions(&mut self.test_function_defs, class.test_function_defs);
+        add_unique_definitions(&mut self.fixture_function_defs, class.fixture_function_defs);
     }
 }
 
@@ -194,11 +252,7 @@ impl CollectedPackage {
 
     /// Returns the total number of tests in this package and all subpackages.
     pub fn test_count(&self) -> usize {
-        let module_tests: usize = self
-            .modules
-            .values()
-            .map(|m| m.test_function_defs.len())
-            .sum();
+        let module_tests: usize = self.modules.values().map(CollectedModule::test_count).sum();
         let package_tests: usize = self.packages.values().map(Self::test_count).sum();
         module_tests + package_tests
     }
@@ -229,6 +283,17 @@ impl CollectedModule {
                 &mut self.fixture_function_defs,
                 module.fixture_function_defs,
             );
+            for class in module.test_classes {
+                if let Some(existing) = self
+                    .test_classes
+                    .iter_mut()
+                    .find(|existing| existing.name == class.name)
+                {
+                    existing.update(class);
+                } else {
+                    self.test_classes.push(class);
+                }
+            }
         }
     }
 }
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ruff_python_ast::{Expr, PythonVersion, Stmt, StmtClassDef, StmtImport, StmtImportFrom};
+use ruff_python_parser::{Mode, ParseOptions, parse_unchecked};
+
+/// How many modules deep imported bases are followed. Also stops import
+/// cycles.
+const MAX_IMPORT_DEPTH: usize = 8;
+
+/// The names a module binds that tell whether its classes derive from
+/// `unittest.TestCase`.
+///
+/// A base named like `TestCase` (such as `unittest.TestCase` or
+/// `IsolatedAsyncioTestCase`) is a test case class. Any other base is
+/// looked up among the classes defined earlier in the module, or in the
+/// project module it was imported from. Bases that can't be found, such as
+/// classes from installed packages, are not test case classes.
+pub(crate) struct ModuleScope<'a> {
+    path: Utf8PathBuf,
+    cwd: &'a Utf8Path,
+    python_version: PythonVersion,
+    depth: usize,
+    /// Whether each class defined so far derives from `TestCase`.
+    classes: HashMap<String, bool>,
+    /// Names bound by `from module import name`.
+    imported_names: HashMap<String, ImportedName>,
+    /// Modules bound by `import module`, by their dotted name or alias.
+    imported_modules: HashMap<String, String>,
+}
+
+/// A name bound by `from module import name`.
+struct ImportedName {
+    /// The module it was imported from, without leading dots.
+    module: String,
+    /// The number of leading dots of a relative import.
+    level: u32,
+    /// The name in the module it was imported from.
+    name: String,
+}
+
+impl ImportedName {
+    /// The dotted name of the imported name as a submodule.
+    fn submodule(&self) -> String {
+        if self.module.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.module, self.name)
+        }
+    }
+}
+
+impl<'a> ModuleScope<'a> {
+    pub(crate) fn new(path: &Utf8Path, cwd: &'a Utf8Path, python_version: PythonVersion) -> Self {
+        Self::with_depth(path, cwd, python_version, 0)
+    }
+
+    fn with_depth(
+        path: &Utf8Path,
+        cwd: &'a Utf8Path,
+        python_version: PythonVersion,
+        depth: usize,
+    ) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            cwd,
+            python_version,
+            depth,
+            classes: HashMap::new(),
+            imported_names: HashMap::new(),
+            imported_modules: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn add_import(&mut self, import: &StmtImport) {
+        for alias in &import.names {
+            let bound = alias.asname.as_ref().unwrap_or(&alias.name);
+            self.imported_modules
+                .insert(bound.to_string(), alias.name.to_string());
+        }
+    }
+
+    pub(crate) fn add_import_from(&mut self, import: &StmtImportFrom) {
+        let module = import
+            .module
+            .as_ref()
+            .map_or_else(String::new, ToString::to_string);
+        for alias in &import.names {
+            let bound = alias.asname.as_ref().unwrap_or(&alias.name);
+            self.imported_names.insert(
+                bound.to_string(),
+                ImportedName {
+                    module: module.clone(),
+                    level: import.level,
+                    name: alias.name.to_string(),
+                },
+            );
+        }
+    }
+
+    /// Whether `class_def` derives from `TestCase`. The class is remembered
+    /// so that later classes in the module can derive from it.
+    pub(crate) fn add_class(&mut self, class_def: &StmtClassDef) -> bool {
+        let is_test_case = class_def
+            .bases()
+            .iter()
+            .any(|base| self.is_test_case_base(base));
+        self.classes
+            .insert(class_def.name.to_string(), is_test_case);
+        is_test_case
+    }
+
+    fn is_test_case_base(&self, base: &Expr) -> bool {
+        match base {
+            Expr::Name(name) => self.is_test_case_name(name.id.as_str()),
+            Expr::Attribute(attr) => {
+                let name = attr.attr.as_str();
+                if name.ends_with("TestCase") {
+                    return true;
+                }
+                let Some(module) = dotted_name(&attr.value) else {
+                    return false;
+                };
+                let file = if let Some(module) = self.imported_modules.get(&module) {
+                    self.module_file(module, 0)
+                } else if let Some(imported) = self.imported_names.get(&module) {
+                    self.module_file(&imported.submodule(), imported.level)
+                } else {
+                    None
+                };
+                file.is_some_and(|file| self.is_test_case_in(&file, name))
+            }
+            _ => false,
+        }
+    }
+
+    fn is_test_case_name(&self, name: &str) -> bool {
+        if name.ends_with("TestCase") {
+            return true;
+        }
+        if let Some(is_test_case) = self.classes.get(name) {
+            return *is_test_case;
+        }
+        self.imported_names.get(name).is_some_and(|imported| {
+            self.module_file(&imported.module, imported.level)
+                .is_some_and(|file| self.is_test_case_in(&file, &imported.name))
+        })
+    }
+
+    /// Whether the class `name` defined or imported by the module in
+    /// `file` derives from `TestCase`.
+    fn is_test_case_in(&self, file: &Utf8Path, name: &str) -> bool {
+        if self.depth >= MAX_IMPORT_DEPTH {
+            return false;
+        }
+        let Ok(source) = std::fs::read_to_string(file) else {
+            return false;
+        };
+        let parse_options =
+            ParseOptions::from(Mode::Module).with_target_version(self.python_version);
+        let Some(parsed) = parse_unchecked(&source, parse_options).try_into_module() else {
+            return false;
+        };
+
+        let mut scope = Self::with_depth(file, self.cwd, self.python_version, self.depth + 1);
+        for stmt in &parsed.syntax().body {
+            match stmt {
+                Stmt::Import(import) => scope.add_import(import),
+                Stmt::ImportFrom(import) => scope.add_import_from(import),
+                Stmt::ClassDef(class_def) => {
+                    scope.add_class(class_def);
+                }
+                _ => {}
+            }
+        }
+        scope.is_test_case_name(name)
+    }
+
+    /// The file of the project module `module`, imported with `level`
+    /// leading dots.
+    ///
+    /// Absolute imports are looked up from the project root and from the
+    /// directory of the importing module.
+    fn module_file(&self, module: &str, level: u32) -> Option<Utf8PathBuf> {
+        let parent = self.path.parent()?;
+        let roots = if level == 0 {
+            vec![self.cwd, parent]
+        } else {
+            let mut root = parent;
+            for _ in 1..level {
+                root = root.parent()?;
+            }
+            vec![root]
+        };
+
+        roots.into_iter().find_map(|root| {
+            let path = module
+                .split('.')
+                .filter(|part| !part.is_empty())
+                .fold(root.to_path_buf(), |path, part| path.join(part));
+            if path == root {
+                return Some(path.join("__init__.py")).filter(|file| file.is_file());
+            }
+            [path.with_extension("py"), path.join("__init__.py")]
+                .into_iter()
+                .find(|file| file.is_file())
+        })
+    }
+}
+
+/// The dotted name of an expression such as `unittest.mock`.
+fn dotted_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Name(name) => Some(name.id.to_string()),
+        Expr::Attribute(attr) => Some(format!(
+            "{}.{}",
+            dotted_name(&attr.value)?,
+            attr.attr.as_str()
+        )),
+        _ => None,
+    }
+}
@@ -0,0 +1,214 @@
+//! Cobertura XML rendering of combined coverage data.
+//!
+//! The layout follows the one coverage.py writes, which code-coverage
+//! services and review tools read: one `<package>` per directory, one
+//! `<class>` per source file, and one `<line>` per executable line.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use camino::Utf8Path;
+use karva_diagnostic::escape_xml;
+
+use crate::report::{CombinedFile, canonical_cwd, display_path};
+
+/// Line and branch counts of a file, a package or the whole report.
+#[derive(Debug, Default, Clone, Copy)]
+struct Counts {
+    lines_valid: u32,
+    lines_covered: u32,
+    branches_valid: u32,
+    branches_covered: u32,
+}
+
+impl Counts {
+    fn of_file(data: &CombinedFile) -> Self {
+        let mut counts = Self {
+            lines_valid: saturating_len(data.executable.len()),
+            lines_covered: saturating_len(data.executable.intersection(&data.executed).count()),
+            ..Self::default()
+        };
+        for branch_line in data.branch_lines().values() {
+            counts.branches_valid += saturating_len(branch_line.destinations.len());
+            counts.branches_covered += saturating_len(branch_line.taken.len());
+        }
+        counts
+    }
+
+    fn add(&mut self, other: Self) {
+        self.lines_valid = self.lines_valid.saturating_add(other.lines_valid);
+        self.lines_covered = self.lines_covered.saturating_add(other.lines_covered);
+        self.branches_valid = self.branches_valid.saturating_add(other.branches_valid);
+        self.branches_covered = self.branches_covered.saturating_add(other.branches_covered);
+    }
+
+    fn line_rate(self) -> String {
+        rate(self.lines_covered, self.lines_valid)
+    }
+
+    /// Without branch coverage the rate is `0`, as coverage.py writes it.
+    fn branch_rate(self, branch: bool) -> String {
+        if branch {
+            rate(self.branches_covered, self.branches_valid)
+        } else {
+            "0".to_string()
+        }
+    }
+}
+
+/// Render `combined` as a Cobertura XML document. File names are relative
+/// to `cwd`, which is listed as the only `<source>`.
+pub(crate) fn render(
+    cwd: &Utf8Path,
+    combined: &BTreeMap<String, CombinedFile>,
+    branch: bool,
+) -> String {
+    let cwd_real = canonical_cwd(cwd);
+
+    let mut packages: BTreeMap<String, Vec<(String, &CombinedFile)>> = BTreeMap::new();
+    for (filename, data) in combined {
+        let name = display_path(filename, &cwd_real);
+        let package = match name.rsplit_once('/') {
+            Some((directory, _)) => directory.trim_start_matches('/').replace('/', "."),
+            None => ".".to_string(),
+        };
+        packages.entry(package).or_default().push((name, data));
+    }
+
+    let mut total = Counts::default();
+    let mut body = String::new();
+    for (package, files) in &packages {
+        let mut package_counts = Counts::default();
+        let mut classes = String::new();
+        for (name, data) in files {
+            let counts = Counts::of_file(data);
+            package_counts.add(counts);
+            write_class(&mut classes, name, data, counts, branch);
+        }
+        total.add(package_counts);
+
+        let _ = writeln!(
+            body,
+            r#"    <package name="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+            escape_xml(package),
+            package_counts.line_rate(),
+            package_counts.branch_rate(branch),
+        );
+        body.push_str("      <classes>\n");
+        body.push_str(&classes);
+        body.push_str("      </classes>\n");
+        body.push_str("    </package>\n");
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" ?>\n");
+    let _ = writeln!(
+        out,
+        r#"<coverage version="{}" timestamp="{timestamp}" lines-valid="{}" lines-covered="{}" line-rate="{}" branches-valid="{}" branches-covered="{}" branch-rate="{}" complexity="0">"#,
+        karva_version::version(),
+        total.lines_valid,
+        total.lines_covered,
+        total.line_rate(),
+        total.branches_valid,
+        total.branches_covered,
+        total.branch_rate(branch),
+    );
+    out.push_str("  <sources>\n");
+    let _ = writeln!(
+        out,
+        "    <source>{}</source>",
+        escape_xml(&cwd_real.to_string_lossy())
+    );
+    out.push_str("  </sources>\n");
+    out.push_str("  <packages>\n");
+    out.push_str(&body);
+    out.push_str("  </packages>\n");
+    out.push_str("</coverage>\n");
+    out
+}
+
+fn write_class(out: &mut String, name: &str, data: &CombinedFile, counts: Counts, branch: bool) {
+    let class_name = name.rsplit_once('/').map_or(name, |(_, file)| file);
+    let _ = writeln!(
+        out,
+        r#"        <class name="{}" filename="{}" complexity="0" line-rate="{}" branch-rate="{}">"#,
+        escape_xml(class_name),
+        escape_xml(name),
+        counts.line_rate(),
+        counts.branch_rate(branch),
+    );
+    out.push_str("          <methods/>\n");
+    out.push_str("          <lines>\n");
+
+    let branch_lines = data.branch_lines();
+    for &line in &data.executable {
+        let hits = u32::from(data.executed.contains(&line));
+        let _ = write!(out, r#"            <line number="{line}" hits="{hits}""#);
+        if let Some(branch_line) = i32::try_from(line)
+            .ok()
+            .and_then(|line| branch_lines.get(&line))
+        {
+            let valid = saturating_len(branch_line.destinations.len());
+            let covered = saturating_len(branch_line.taken.len());
+            let percent = covered * 100 / valid.max(1);
+            let _ = write!(
+                out,
+                r#" branch="true" condition-coverage="{percent}% ({covered}/{valid})""#
+            );
+            let missing: Vec<String> = branch_line
+                .destinations
+                .difference(&branch_line.taken)
+                .map(|&to| {
+                    if to < 0 {
+                        "exit".to_string()
+                    } else {
+                        to.to_string()
+                    }
+                })
+                .collect();
+            if !missing.is_empty() {
+                let _ = write!(out, r#" missing-branches="{}""#, missing.join(","));
+            }
+        }
+        out.push_str("/>\n");
+    }
+
+    out.push_str("          </lines>\n");
+    out.push_str("        </class>\n");
+}
+
+/// Format `hit / total` with up to four decimals, like coverage.py. An
+/// empty total counts as fully covered.
+fn rate(hit: u32, total: u32) -> String {
+    if total == 0 {
+        return "1".to_string();
+    }
+    let formatted = format!("{:.4}", f64::from(hit) / f64::from(total));
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn saturating_len(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_trims_trailing_zeros() {
+        assert_eq!(rate(5, 6), "0.8333");
+        assert_eq!(rate(7, 8), "0.875");
+        assert_eq!(rate(3, 3), "1");
+        assert_eq!(rate(0, 3), "0");
+        assert_eq!(rate(0, 0), "1");
+    }
+}
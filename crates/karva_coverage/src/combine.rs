@@ -0,0 +1,182 @@
+//! Merge saved coverage data into a single data file.
+//!
+//! The combined file uses the per-worker [`WorkerFile`] format, so it can be
+//! reported on, or combined again, like the data of a single worker. Data
+//! recorded on another machine, such as a CI shard, is moved onto local
+//! paths with [`PathRemap`]s.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, btree_map};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use camino::Utf8Path;
+
+use crate::data::{FileEntry, WorkerFile};
+use crate::report::{CombinedFile, combine, write_report};
+
+/// The default path of the combined data file, relative to the project root.
+pub const DATA_FILE: &str = ".karva_coverage.json";
+
+/// Replaces a path prefix recorded on another machine.
+#[derive(Debug, Clone)]
+pub struct PathRemap {
+    from: PathBuf,
+    to: PathBuf,
+}
+
+impl PathRemap {
+    /// Move files under `from` to `to`. `to` is canonicalized when it
+    /// exists, to match the paths the tracer records.
+    pub fn new(from: impl Into<PathBuf>, to: &Path) -> Self {
+        Self {
+            from: from.into(),
+            to: std::fs::canonicalize(to).unwrap_or_else(|_| to.to_path_buf()),
+        }
+    }
+
+    fn apply(&self, path: &Path) -> Option<PathBuf> {
+        let rest = path.strip_prefix(&self.from).ok()?;
+        Some(self.to.join(rest))
+    }
+}
+
+/// Combine the data files in `files` into `output`, moving each measured
+/// file with the first of `remaps` that matches its path. Files that end
+/// up on the same path are merged.
+///
+/// Returns the number of measured files in the combined data.
+pub fn combine_data_files(
+    files: &[impl AsRef<Utf8Path>],
+    remaps: &[PathRemap],
+    output: &Utf8Path,
+) -> Result<usize> {
+    let (branch, combined) = combine(files)?;
+    let remapped = remap(combined, remaps);
+    write_report(
+        output,
+        &serde_json::to_string(&worker_file(branch, &remapped))?,
+    )?;
+    Ok(remapped.len())
+}
+
+fn remap(
+    combined: BTreeMap<String, CombinedFile>,
+    remaps: &[PathRemap],
+) -> BTreeMap<String, CombinedFile> {
+    let mut remapped: BTreeMap<String, CombinedFile> = BTreeMap::new();
+    for (filename, data) in combined {
+        let filename = remaps
+            .iter()
+            .find_map(|remap| remap.apply(Path::new(&filename)))
+            .map_or(filename, |path| path.to_string_lossy().into_owned());
+        match remapped.entry(filename) {
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(data);
+            }
+            btree_map::Entry::Occupied(mut entry) => entry.get_mut().merge(data),
+        }
+    }
+    remapped
+}
+
+/// The combined data in the data file format, with the context names
+/// numbered in sorted order.
+fn worker_file(branch: bool, combined: &BTreeMap<String, CombinedFile>) -> WorkerFile {
+    let names: BTreeSet<&str> = combined
+        .values()
+        .flat_map(|data| data.contexts.values().flatten())
+        .map(String::as_str)
+        .collect();
+    let ids: HashMap<&str, u32> = names
+        .iter()
+        .enumerate()
+        .map(|(id, &name)| (name, u32::try_from(id).unwrap_or(u32::MAX)))
+        .collect();
+
+    let files = combined
+        .iter()
+        .map(|(filename, data)| {
+            let entry = FileEntry {
+                executable: data.executable.iter().copied().collect(),
+                executed: data.executed.iter().copied().collect(),
+                excluded: data.excluded.iter().copied().collect(),
+                arcs: data.arcs.iter().copied().collect(),
+                executed_arcs: data.executed_arcs.iter().copied().collect(),
+                contexts: data
+                    .contexts
+                    .iter()
+                    .map(|(&line, tests)| {
+                        (line, tests.iter().map(|test| ids[test.as_str()]).collect())
+                    })
+                    .collect(),
+            };
+            (filename.clone(), entry)
+        })
+        .collect();
+
+    WorkerFile {
+        branch,
+        contexts: names.into_iter().map(ToString::to_string).collect(),
+        files,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cf(executable: &[u32], executed: &[u32]) -> CombinedFile {
+        CombinedFile {
+            executable: executable.iter().copied().collect(),
+            executed: executed.iter().copied().collect(),
+            ..CombinedFile::default()
+        }
+    }
+
+    #[test]
+    fn remap_moves_files_under_the_new_prefix() {
+        let combined = BTreeMap::from([
+            ("/ci/build/src/app.py".to_string(), cf(&[1, 2], &[1])),
+            ("/other/lib.py".to_string(), cf(&[1], &[1])),
+        ]);
+        let remaps = [PathRemap::new("/ci/build", Path::new("/project"))];
+        let remapped = remap(combined, &remaps);
+        assert_eq!(
+            remapped.keys().collect::<Vec<_>>(),
+            vec!["/other/lib.py", "/project/src/app.py"]
+        );
+    }
+
+    #[test]
+    fn remap_merges_files_landing_on_the_same_path() {
+        let combined = BTreeMap::from([
+            ("/ci/one/app.py".to_string(), cf(&[1, 2, 3], &[1])),
+            ("/ci/two/app.py".to_string(), cf(&[1, 2, 3], &[2])),
+        ]);
+        let remaps = [
+            PathRemap::new("/ci/one", Path::new("/project")),
+            PathRemap::new("/ci/two", Path::new("/project")),
+        ];
+        let remapped = remap(combined, &remaps);
+        assert_eq!(remapped["/project/app.py"].executed, BTreeSet::from([1, 2]));
+    }
+
+    #[test]
+    fn worker_file_numbers_contexts() {
+        let mut data = cf(&[1, 2], &[1, 2]);
+        data.contexts
+            .entry(1)
+            .or_default()
+            .extend(["test::b".to_string(), "test::a".to_string()]);
+        data.contexts
+            .entry(2)
+            .or_default()
+            .insert("test::b".to_string());
+        let file = worker_file(false, &BTreeMap::from([("/app.py".to_string(), data)]));
+        assert_eq!(file.contexts, vec!["test::a", "test::b"]);
+        assert_eq!(
+            file.files["/app.py"].contexts,
+            BTreeMap::from([(1, vec![0, 1]), (2, vec![1])])
+        );
+    }
+}
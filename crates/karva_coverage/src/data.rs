@@ -7,6 +7,14 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WorkerFile {
+    /// Whether branch coverage was measured, in which case every file
+    /// carries its arcs.
+    #[serde(default)]
+    pub branch: bool,
+    /// Names of the tests that ran lines, indexed by the context ids in
+    /// [`FileEntry::contexts`]. Empty unless contexts were recorded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contexts: Vec<String>,
     pub files: BTreeMap<String, FileEntry>,
 }
 
@@ -14,4 +22,19 @@ pub struct WorkerFile {
 pub struct FileEntry {
     pub executable: Vec<u32>,
     pub executed: Vec<u32>,
+    /// Lines excluded with `# pragma: no cover` or an `exclude-lines`
+    /// pattern.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded: Vec<u32>,
+    /// Possible arcs out of the file's branch lines, as `(from, to)` line
+    /// pairs. A negative `to` exits the code object starting on line `-to`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arcs: Vec<(i32, i32)>,
+    /// Arcs taken out of the file's branch lines.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub executed_arcs: Vec<(i32, i32)>,
+    /// Ids of the contexts that ran each executed line, indexing
+    /// [`WorkerFile::contexts`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: BTreeMap<u32, Vec<u32>>,
 }
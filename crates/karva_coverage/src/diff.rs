@@ -0,0 +1,292 @@
+//! The lines changed by a unified diff.
+//!
+//! Diff coverage only measures the executable lines a diff adds or
+//! modifies. The diff comes from a file, from stdin, or from `git diff`
+//! against a base ref.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use camino::Utf8Path;
+
+/// The lines added or modified by a diff, per file.
+#[derive(Debug, Clone, Default)]
+pub struct DiffLines {
+    /// Canonical file paths with the line numbers of their new side.
+    files: BTreeMap<PathBuf, BTreeSet<u32>>,
+}
+
+impl DiffLines {
+    /// Read the diff to measure: the unified diff in `file`, or stdin when
+    /// `file` is `-`, or the changes since the git ref `base` in `cwd`.
+    /// Returns `None` when neither is given.
+    pub fn read(
+        cwd: &Utf8Path,
+        file: Option<&Utf8Path>,
+        base: Option<&str>,
+    ) -> Result<Option<Self>> {
+        let diff = match (file, base) {
+            (Some(_), Some(_)) => bail!("a diff file and a diff base cannot be used together"),
+            (Some(file), None) if file == "-" => {
+                let mut diff = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut diff)
+                    .context("failed to read the diff from stdin")?;
+                diff
+            }
+            (Some(file), None) => {
+                let path = cwd.join(file);
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("failed to read the diff file {path}"))?
+            }
+            (None, Some(base)) => git_diff(cwd, base)?,
+            (None, None) => return Ok(None),
+        };
+        Ok(Some(Self::parse(&diff, cwd.as_std_path())))
+    }
+
+    /// Parse a unified diff whose paths are relative to `root`. Deleted
+    /// files and removed lines are ignored.
+    pub fn parse(diff: &str, root: &Path) -> Self {
+        let mut files: BTreeMap<PathBuf, BTreeSet<u32>> = BTreeMap::new();
+        let mut current: Option<PathBuf> = None;
+        let mut hunk = Hunk::default();
+
+        for line in diff.lines() {
+            // Inside a hunk, `+++ ` and `--- ` are added and removed lines,
+            // so headers are only looked for between hunks.
+            if !hunk.is_done() {
+                match line.as_bytes().first() {
+                    Some(b'+') => {
+                        if let Some(path) = &current {
+                            files.entry(path.clone()).or_default().insert(hunk.new_line);
+                        }
+                        hunk.new_line += 1;
+                        hunk.new_left = hunk.new_left.saturating_sub(1);
+                    }
+                    Some(b'-') => hunk.old_left = hunk.old_left.saturating_sub(1),
+                    Some(b'\\') => {}
+                    _ => {
+                        hunk.new_line += 1;
+                        hunk.new_left = hunk.new_left.saturating_sub(1);
+                        hunk.old_left = hunk.old_left.saturating_sub(1);
+                    }
+                }
+            } else if let Some(target) = line.strip_prefix("+++ ") {
+                current = diff_target(target).map(|path| {
+                    let path = root.join(path);
+                    std::fs::canonicalize(&path).unwrap_or(path)
+                });
+            } else if let Some(header) = line.strip_prefix("@@ ") {
+                hunk = Hunk::parse(header).unwrap_or_default();
+            }
+        }
+
+        Self { files }
+    }
+
+    /// The changed lines of the canonical `path`, if it was changed.
+    pub(crate) fn changed_lines(&self, path: &Path) -> Option<&BTreeSet<u32>> {
+        self.files.get(path)
+    }
+}
+
+/// The path of a `+++` header, without git's `b/` prefix. `None` for a
+/// deleted file.
+fn diff_target(target: &str) -> Option<&str> {
+    // Git appends a tab and a timestamp in some modes.
+    let target = target.split('\t').next().unwrap_or(target).trim_end();
+    if target == "/dev/null" {
+        return None;
+    }
+    Some(target.strip_prefix("b/").unwrap_or(target))
+}
+
+/// The position in the hunk being read.
+#[derive(Debug, Default)]
+struct Hunk {
+    /// The new-side line number of the next added or context line.
+    new_line: u32,
+    /// The old-side lines still to come.
+    old_left: u32,
+    /// The new-side lines still to come.
+    new_left: u32,
+}
+
+impl Hunk {
+    /// Parse a hunk header such as `-1,3 +1,4 @@ def f():`, without the
+    /// leading `@@ `. A missing count means one line.
+    fn parse(header: &str) -> Option<Self> {
+        let mut sides = header.split_whitespace();
+        let (_, old_left) = range(sides.next()?.strip_prefix('-')?)?;
+        let (new_line, new_left) = range(sides.next()?.strip_prefix('+')?)?;
+        Some(Self {
+            new_line,
+            old_left,
+            new_left,
+        })
+    }
+
+    fn is_done(&self) -> bool {
+        self.old_left == 0 && self.new_left == 0
+    }
+}
+
+/// A `start,count` range of a hunk header.
+fn range(raw: &str) -> Option<(u32, u32)> {
+    match raw.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((raw.parse().ok()?, 1)),
+    }
+}
+
+/// Run `git diff` in `cwd` for the changes made since `base`, with paths
+/// relative to `cwd`.
+///
+/// A single ref is compared from its merge base with `HEAD`, so commits
+/// that landed on `base` after the branch left it don't count as changes;
+/// uncommitted changes to tracked files do. A range such as
+/// `main...HEAD` is passed to git as given.
+fn git_diff(cwd: &Utf8Path, base: &str) -> Result<String> {
+    let from = if base.contains("..") {
+        base.to_string()
+    } else {
+        git(cwd, &["merge-base", base, "HEAD"])?.trim().to_string()
+    };
+    git(
+        cwd,
+        &[
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "--relative",
+            "--unified=0",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+            &from,
+            "--",
+        ],
+    )
+}
+
+/// Run git with `args` in `cwd` and return what it printed.
+fn git(cwd: &Utf8Path, args: &[&str]) -> Result<String> {
+    let command = args.first().copied().unwrap_or_default();
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .with_context(|| format!("failed to run `git {command}`"))?;
+    if !output.status.success() {
+        bail!(
+            "`git {command}` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8(output.stdout)
+        .with_context(|| format!("`git {command}` printed invalid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changed(diff: &str) -> Vec<(String, Vec<u32>)> {
+        DiffLines::parse(diff, Path::new("/project"))
+            .files
+            .into_iter()
+            .map(|(path, lines)| {
+                (
+                    path.to_string_lossy().into_owned(),
+                    lines.into_iter().collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_added_lines_of_git_diff() {
+        let diff = "\
+diff --git a/src/app.py b/src/app.py
+index 1111111..2222222 100644
+--- a/src/app.py
++++ b/src/app.py
+@@ -1,3 +1,4 @@
+ def f():
+-    return 1
++    x = 1
++    return x
+
+@@ -10,0 +12,1 @@ def g():
++    pass
+";
+        assert_eq!(
+            changed(diff),
+            vec![("/project/src/app.py".to_string(), vec![2, 3, 12])]
+        );
+    }
+
+    #[test]
+    fn parses_new_and_deleted_files() {
+        let diff = "\
+--- /dev/null
++++ b/new.py
+@@ -0,0 +1,2 @@
++a = 1
++b = 2
+--- a/old.py
++++ /dev/null
+@@ -1,1 +0,0 @@
+-gone = 1
+";
+        assert_eq!(
+            changed(diff),
+            vec![("/project/new.py".to_string(), vec![1, 2])]
+        );
+    }
+
+    #[test]
+    fn parses_diff_without_prefixes() {
+        let diff = "\
+--- app.py\t2024-01-01 00:00:00
++++ app.py\t2024-01-02 00:00:00
+@@ -1 +1 @@
+-a = 1
++a = 2
+";
+        assert_eq!(
+            changed(diff),
+            vec![("/project/app.py".to_string(), vec![1])]
+        );
+    }
+
+    #[test]
+    fn header_like_lines_inside_a_hunk_are_content() {
+        let diff = "\
+--- a/app.py
++++ b/app.py
+@@ -1,2 +1,2 @@
+--- removed comment
++++ added comment
+ x = 1
+";
+        assert_eq!(
+            changed(diff),
+            vec![("/project/app.py".to_string(), vec![1])]
+        );
+    }
+
+    #[test]
+    fn a_file_and_a_base_conflict() {
+        let err = DiffLines::read(
+            Utf8Path::new("."),
+            Some(Utf8Path::new("x.diff")),
+            Some("main"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("cannot be used together"));
+    }
+}
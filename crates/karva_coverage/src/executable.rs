@@ -6,55 +6,177 @@
 //! case) for free, and a single hook (`visit_body`) for skipping the
 //! leading docstring of every body — `CPython` stores docstrings as
 //! bytecode constants rather than executable statements.
+//!
+//! For branch coverage, [`branch_arcs`] also derives the arcs out of every
+//! line that can continue on more than one line.
+//!
+//! Lines marked `# pragma: no cover`, or matching one of the configured
+//! [`Exclusions`], are left out along with the block they start.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::path::Path;
 
+use anyhow::{Context, Result};
+use regex::RegexSet;
+
 use ruff_python_ast::helpers::is_docstring_stmt;
 use ruff_python_ast::token::TokenKind;
 use ruff_python_ast::visitor::source_order::{
     SourceOrderVisitor, walk_decorator, walk_elif_else_clause, walk_except_handler,
     walk_match_case, walk_stmt,
 };
-use ruff_python_ast::{Decorator, ElifElseClause, ExceptHandler, MatchCase, Stmt};
+use ruff_python_ast::{Decorator, ElifElseClause, ExceptHandler, Expr, MatchCase, Pattern, Stmt};
 use ruff_python_parser::{Mode, ParseOptions, parse_unchecked};
 use ruff_source_file::LineIndex;
-use ruff_text_size::{Ranged, TextSize};
+use ruff_text_size::{Ranged, TextRange, TextSize};
+
+/// Regexes matched against each source line to leave lines out of the
+/// measurement, on top of the `# pragma: no cover` and `# pragma: no
+/// branch` comments that always apply.
+#[derive(Debug, Clone, Default)]
+pub struct Exclusions {
+    /// Lines excluded from coverage, along with the block they start. On a
+    /// decorator, the whole decorated definition is excluded.
+    exclude_lines: Option<RegexSet>,
+
+    /// Branch lines whose arcs are not measured.
+    partial_branches: Option<RegexSet>,
+}
+
+impl Exclusions {
+    pub fn new(exclude_lines: &[String], partial_branches: &[String]) -> Result<Self> {
+        Ok(Self {
+            exclude_lines: regex_set(exclude_lines).context("invalid `exclude-lines` pattern")?,
+            partial_branches: regex_set(partial_branches)
+                .context("invalid `partial-branches` pattern")?,
+        })
+    }
+}
+
+fn regex_set(patterns: &[String]) -> Result<Option<RegexSet>, regex::Error> {
+    if patterns.is_empty() {
+        Ok(None)
+    } else {
+        RegexSet::new(patterns).map(Some)
+    }
+}
 
 /// Parse `path` and return the set of line numbers that contain a statement.
-pub fn executable_lines(path: &Path) -> HashSet<u32> {
+pub fn executable_lines(path: &Path, exclusions: &Exclusions) -> HashSet<u32> {
     let Ok(source) = std::fs::read_to_string(path) else {
         return HashSet::new();
     };
-    executable_lines_for_source(&source)
+    executable_lines_for_source(&source, exclusions)
 }
 
 /// Compute executable line numbers from a source string. Exposed separately
 /// so unit tests can avoid touching the filesystem.
-pub fn executable_lines_for_source(source: &str) -> HashSet<u32> {
+pub fn executable_lines_for_source(source: &str, exclusions: &Exclusions) -> HashSet<u32> {
+    line_sets(source, exclusions).0
+}
+
+/// Parse `path` and return the lines excluded with `# pragma: no cover` or
+/// an `exclude-lines` pattern.
+pub fn excluded_lines(path: &Path, exclusions: &Exclusions) -> HashSet<u32> {
+    let Ok(source) = std::fs::read_to_string(path) else {
+        return HashSet::new();
+    };
+    excluded_lines_for_source(&source, exclusions)
+}
+
+/// Compute the excluded lines of a source string: every line of each
+/// statement or clause whose head carries `# pragma: no cover` or matches
+/// an `exclude-lines` pattern, including the lines of its body.
+pub fn excluded_lines_for_source(source: &str, exclusions: &Exclusions) -> HashSet<u32> {
+    line_sets(source, exclusions).1
+}
+
+/// The executable and the excluded lines of a source string.
+fn line_sets(source: &str, exclusions: &Exclusions) -> (HashSet<u32>, HashSet<u32>) {
     let Some(parsed) = parse_unchecked(source, ParseOptions::from(Mode::Module)).try_into_module()
     else {
-        return HashSet::new();
+        return (HashSet::new(), HashSet::new());
     };
     let line_index = LineIndex::from_source_text(source);
-    let pragma_lines = pragma_no_cover_lines(&parsed, source, &line_index);
+    let pattern_lines = matching_lines(source, exclusions.exclude_lines.as_ref());
+    let mut marked_lines = pragma_lines(&parsed, source, &line_index, "pragma: no cover");
+    marked_lines.extend(&pattern_lines);
     let module = parsed.into_syntax();
     let mut visitor = ExecutableLineVisitor {
         line_index: &line_index,
-        pragma_lines: &pragma_lines,
+        marked_lines: &marked_lines,
+        pattern_lines: &pattern_lines,
         lines: HashSet::new(),
+        excluded: HashSet::new(),
     };
     visitor.visit_body(&module.body);
-    visitor.lines
+    (visitor.lines, visitor.excluded)
+}
+
+/// Parse `path` and return the possible arcs out of its branch lines.
+pub fn branch_arcs(path: &Path, exclusions: &Exclusions) -> BTreeSet<(i32, i32)> {
+    let Ok(source) = std::fs::read_to_string(path) else {
+        return BTreeSet::new();
+    };
+    branch_arcs_for_source(&source, exclusions)
+}
+
+/// Compute the possible arcs out of the branch lines of a source string.
+///
+/// An arc is a `(from, to)` pair of line numbers. A branch line is an
+/// executable line that can continue on more than one line: the head of an
+/// `if`, `elif`, `while`, `for` or refutable `case`. A negative `to` is an
+/// exit from the function, class or module whose first line is `-to`,
+/// matching the arcs the tracer records for returns.
+///
+/// Arcs into excluded lines are dropped, and a line left with a single arc
+/// is no longer a branch. Lines marked `# pragma: no branch` or matching a
+/// `partial-branches` pattern are not branches either.
+pub fn branch_arcs_for_source(source: &str, exclusions: &Exclusions) -> BTreeSet<(i32, i32)> {
+    let Some(parsed) = parse_unchecked(source, ParseOptions::from(Mode::Module)).try_into_module()
+    else {
+        return BTreeSet::new();
+    };
+    let line_index = LineIndex::from_source_text(source);
+    let executable = executable_lines_for_source(source, exclusions);
+    let mut no_branch = pragma_lines(&parsed, source, &line_index, "pragma: no branch");
+    no_branch.extend(matching_lines(source, exclusions.partial_branches.as_ref()));
+    let module = parsed.into_syntax();
+
+    let mut visitor = BranchArcVisitor {
+        line_index: &line_index,
+        branches: BTreeMap::new(),
+    };
+    visitor.visit_scope_body(&module.body, 1);
+
+    let is_executable =
+        |line: i32| u32::try_from(line).is_ok_and(|line| executable.contains(&line));
+
+    let mut arcs = BTreeSet::new();
+    for (from, destinations) in visitor.branches {
+        if !is_executable(from) || u32::try_from(from).is_ok_and(|from| no_branch.contains(&from)) {
+            continue;
+        }
+        let destinations: Vec<i32> = destinations
+            .into_iter()
+            .filter(|&to| to < 0 || is_executable(to))
+            .collect();
+        if destinations.len() > 1 {
+            arcs.extend(destinations.into_iter().map(|to| (from, to)));
+        }
+    }
+    arcs
 }
 
-/// Collect the set of line numbers carrying a `# pragma: no cover` comment.
-/// Match is case-insensitive and tolerant of surrounding whitespace, mirroring
-/// coverage.py's default `exclude_lines` regex.
-fn pragma_no_cover_lines<T>(
+/// Collect the set of line numbers carrying a comment with `pragma`, such
+/// as `pragma: no cover`. Match is case-insensitive and tolerant of
+/// surrounding whitespace, mirroring coverage.py's default `exclude_lines`
+/// regex.
+fn pragma_lines<T>(
     parsed: &ruff_python_parser::Parsed<T>,
     source: &str,
     line_index: &LineIndex,
+    pragma: &str,
 ) -> HashSet<u32> {
     let mut lines = HashSet::new();
     for token in parsed.tokens() {
@@ -65,7 +187,7 @@ fn pragma_no_cover_lines<T>(
         let Some(text) = source.get(range.start().to_usize()..range.end().to_usize()) else {
             continue;
         };
-        if is_pragma_no_cover(text)
+        if has_pragma(text, pragma)
             && let Ok(line) = u32::try_from(line_index.line_index(range.start()).get())
         {
             lines.insert(line);
@@ -74,17 +196,36 @@ fn pragma_no_cover_lines<T>(
     lines
 }
 
-fn is_pragma_no_cover(comment: &str) -> bool {
+fn has_pragma(comment: &str, pragma: &str) -> bool {
     // `comment` includes the leading `#`. Strip it and compare
-    // case-insensitively against the canonical `pragma: no cover` token.
+    // case-insensitively against the canonical pragma token.
     let body = comment.strip_prefix('#').unwrap_or(comment).trim();
-    body.to_ascii_lowercase().contains("pragma: no cover")
+    body.to_ascii_lowercase().contains(pragma)
+}
+
+/// The line numbers of the lines of `source` that match any of `patterns`.
+fn matching_lines(source: &str, patterns: Option<&RegexSet>) -> HashSet<u32> {
+    let Some(patterns) = patterns else {
+        return HashSet::new();
+    };
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| patterns.is_match(line))
+        .filter_map(|(index, _)| u32::try_from(index + 1).ok())
+        .collect()
 }
 
 struct ExecutableLineVisitor<'a> {
     line_index: &'a LineIndex,
-    pragma_lines: &'a HashSet<u32>,
+    /// Lines marked `# pragma: no cover` or matching an `exclude-lines`
+    /// pattern.
+    marked_lines: &'a HashSet<u32>,
+    /// Lines matching an `exclude-lines` pattern.
+    pattern_lines: &'a HashSet<u32>,
     lines: HashSet<u32>,
+    /// Every line of the nodes skipped because of a pragma or pattern.
+    excluded: HashSet<u32>,
 }
 
 impl ExecutableLineVisitor<'_> {
@@ -94,26 +235,42 @@ impl ExecutableLineVisitor<'_> {
         }
     }
 
-    /// Whether `offset` falls on a line marked with `# pragma: no cover`.
-    /// Used to decide whether to skip a statement (or clause) outright.
+    /// Whether `offset` falls on a line marked with `# pragma: no cover` or
+    /// matching an `exclude-lines` pattern. Used to decide whether to skip
+    /// a statement (or clause) outright.
     fn line_has_pragma(&self, offset: TextSize) -> bool {
-        if let Ok(line) = u32::try_from(self.line_index.line_index(offset).get()) {
-            self.pragma_lines.contains(&line)
-        } else {
-            false
-        }
+        Self::line_in(self.line_index, self.marked_lines, offset)
+    }
+
+    /// Whether `offset` falls on a line matching an `exclude-lines` pattern.
+    fn line_matches_pattern(&self, offset: TextSize) -> bool {
+        Self::line_in(self.line_index, self.pattern_lines, offset)
+    }
+
+    fn line_in(line_index: &LineIndex, lines: &HashSet<u32>, offset: TextSize) -> bool {
+        u32::try_from(line_index.line_index(offset).get()).is_ok_and(|line| lines.contains(&line))
     }
 
-    /// Record `offset` unless its line is marked `# pragma: no cover`.
+    /// Record `offset` unless its line is marked for exclusion.
     /// Returns `false` when the pragma applies, signalling the caller to
-    /// stop walking the subtree rooted at this node.
-    fn record_unless_pragma(&mut self, offset: TextSize) -> bool {
+    /// stop walking the subtree rooted at this node, whose lines span
+    /// `range`.
+    fn record_unless_pragma(&mut self, offset: TextSize, range: TextRange) -> bool {
         if self.line_has_pragma(offset) {
+            self.exclude(range);
             return false;
         }
         self.record(offset);
         true
     }
+
+    /// Mark every line of `range` as excluded.
+    fn exclude(&mut self, range: TextRange) {
+        let start = self.line_index.line_index(range.start()).get();
+        let end = self.line_index.line_index(range.end()).get();
+        self.excluded
+            .extend((start..=end).filter_map(|line| u32::try_from(line).ok()));
+    }
 }
 
 impl<'a> SourceOrderVisitor<'a> for ExecutableLineVisitor<'_> {
@@ -134,20 +291,28 @@ impl<'a> SourceOrderVisitor<'a> for ExecutableLineVisitor<'_> {
     ///
     /// A `# pragma: no cover` on the statement's head line excludes both
     /// the head and the entire body — we skip recording and stop walking
-    /// the subtree.
+    /// the subtree. So does an `exclude-lines` pattern matching the head
+    /// line or, for a definition, one of its decorators.
     fn visit_stmt(&mut self, stmt: &'a Stmt) {
-        let offset = match stmt {
-            Stmt::FunctionDef(s) => s.name.range().start(),
-            Stmt::ClassDef(s) => s.name.range().start(),
-            _ => stmt.range().start(),
+        let (offset, decorators) = match stmt {
+            Stmt::FunctionDef(s) => (s.name.range().start(), &s.decorator_list[..]),
+            Stmt::ClassDef(s) => (s.name.range().start(), &s.decorator_list[..]),
+            _ => (stmt.range().start(), &[][..]),
         };
-        if self.record_unless_pragma(offset) {
+        if decorators
+            .iter()
+            .any(|decorator| self.line_matches_pattern(decorator.range().start()))
+        {
+            self.exclude(stmt.range());
+            return;
+        }
+        if self.record_unless_pragma(offset, stmt.range()) {
             walk_stmt(self, stmt);
         }
     }
 
     fn visit_decorator(&mut self, decorator: &'a Decorator) {
-        if self.record_unless_pragma(decorator.range().start()) {
+        if self.record_unless_pragma(decorator.range().start(), decorator.range()) {
             walk_decorator(self, decorator);
         }
     }
@@ -162,6 +327,7 @@ impl<'a> SourceOrderVisitor<'a> for ExecutableLineVisitor<'_> {
     fn visit_elif_else_clause(&mut self, clause: &'a ElifElseClause) {
         let offset = clause.range().start();
         if self.line_has_pragma(offset) {
+            self.exclude(clause.range());
             return;
         }
         if clause.test.is_some() {
@@ -171,24 +337,190 @@ impl<'a> SourceOrderVisitor<'a> for ExecutableLineVisitor<'_> {
     }
 
     fn visit_except_handler(&mut self, handler: &'a ExceptHandler) {
-        if self.record_unless_pragma(handler.range().start()) {
+        if self.record_unless_pragma(handler.range().start(), handler.range()) {
             walk_except_handler(self, handler);
         }
     }
 
     fn visit_match_case(&mut self, case: &'a MatchCase) {
-        if self.record_unless_pragma(case.range().start()) {
+        if self.record_unless_pragma(case.range().start(), case.range()) {
             walk_match_case(self, case);
         }
     }
 }
 
+/// Collects the destinations of every branch line.
+///
+/// Each body is walked with the line execution continues on once the body
+/// is done: the next statement, the head of the enclosing loop, or the exit
+/// of the enclosing scope.
+struct BranchArcVisitor<'a> {
+    line_index: &'a LineIndex,
+    branches: BTreeMap<i32, BTreeSet<i32>>,
+}
+
+impl BranchArcVisitor<'_> {
+    fn line(&self, offset: TextSize) -> i32 {
+        i32::try_from(self.line_index.line_index(offset).get()).unwrap_or(i32::MAX)
+    }
+
+    /// The line a body starts executing on, or `next` for an empty body.
+    fn first_line(&self, body: &[Stmt], next: i32) -> i32 {
+        body.first().map_or(next, |stmt| self.line(stmt.start()))
+    }
+
+    fn add_branch(&mut self, from: i32, destinations: [i32; 2]) {
+        if destinations[0] != destinations[1] {
+            self.branches.entry(from).or_default().extend(destinations);
+        }
+    }
+
+    /// Walk the body of a function, class or module starting on
+    /// `first_line`, which exits to `-first_line`.
+    fn visit_scope_body(&mut self, body: &[Stmt], first_line: i32) {
+        let start = usize::from(body.first().is_some_and(is_docstring_stmt));
+        self.visit_body(&body[start..], -first_line);
+    }
+
+    fn visit_body(&mut self, body: &[Stmt], next: i32) {
+        for (index, stmt) in body.iter().enumerate() {
+            let following = body
+                .get(index + 1)
+                .map_or(next, |stmt| self.line(stmt.start()));
+            self.visit_stmt(stmt, following);
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt, next: i32) {
+        match stmt {
+            // The code object of a decorated function or class starts on its
+            // first decorator, which is where the statement's range starts.
+            Stmt::FunctionDef(function) => {
+                self.visit_scope_body(&function.body, self.line(stmt.start()));
+            }
+            Stmt::ClassDef(class) => {
+                self.visit_scope_body(&class.body, self.line(stmt.start()));
+            }
+            Stmt::If(if_stmt) => {
+                // The line each clause starts on: an `elif` evaluates its
+                // test on its own line, an `else` goes straight to its body.
+                let clause_lines: Vec<i32> = if_stmt
+                    .elif_else_clauses
+                    .iter()
+                    .map(|clause| {
+                        if clause.test.is_some() {
+                            self.line(clause.start())
+                        } else {
+                            self.first_line(&clause.body, next)
+                        }
+                    })
+                    .collect();
+
+                if !is_constant_true(&if_stmt.test) {
+                    self.add_branch(
+                        self.line(stmt.start()),
+                        [
+                            self.first_line(&if_stmt.body, next),
+                            clause_lines.first().copied().unwrap_or(next),
+                        ],
+                    );
+                }
+                self.visit_body(&if_stmt.body, next);
+
+                for (index, clause) in if_stmt.elif_else_clauses.iter().enumerate() {
+                    if clause.test.is_some() {
+                        self.add_branch(
+                            clause_lines[index],
+                            [
+                                self.first_line(&clause.body, next),
+                                clause_lines.get(index + 1).copied().unwrap_or(next),
+                            ],
+                        );
+                    }
+                    self.visit_body(&clause.body, next);
+                }
+            }
+            Stmt::While(while_stmt) => {
+                let line = self.line(stmt.start());
+                if !is_constant_true(&while_stmt.test) {
+                    self.add_branch(
+                        line,
+                        [
+                            self.first_line(&while_stmt.body, line),
+                            self.first_line(&while_stmt.orelse, next),
+                        ],
+                    );
+                }
+                self.visit_body(&while_stmt.body, line);
+                self.visit_body(&while_stmt.orelse, next);
+            }
+            Stmt::For(for_stmt) => {
+                let line = self.line(stmt.start());
+                self.add_branch(
+                    line,
+                    [
+                        self.first_line(&for_stmt.body, line),
+                        self.first_line(&for_stmt.orelse, next),
+                    ],
+                );
+                self.visit_body(&for_stmt.body, line);
+                self.visit_body(&for_stmt.orelse, next);
+            }
+            Stmt::Match(match_stmt) => {
+                for (index, case) in match_stmt.cases.iter().enumerate() {
+                    if !is_irrefutable(case) {
+                        let otherwise = match_stmt
+                            .cases
+                            .get(index + 1)
+                            .map_or(next, |case| self.line(case.start()));
+                        self.add_branch(
+                            self.line(case.start()),
+                            [self.first_line(&case.body, next), otherwise],
+                        );
+                    }
+                    self.visit_body(&case.body, next);
+                }
+            }
+            Stmt::Try(try_stmt) => {
+                let after_finally = self.first_line(&try_stmt.finalbody, next);
+                let after_body = self.first_line(&try_stmt.orelse, after_finally);
+                self.visit_body(&try_stmt.body, after_body);
+                for handler in &try_stmt.handlers {
+                    let ExceptHandler::ExceptHandler(handler) = handler;
+                    self.visit_body(&handler.body, after_finally);
+                }
+                self.visit_body(&try_stmt.orelse, after_finally);
+                self.visit_body(&try_stmt.finalbody, next);
+            }
+            // Leaving a `with` block calls `__exit__` on the `with` line.
+            Stmt::With(with_stmt) => {
+                self.visit_body(&with_stmt.body, self.line(stmt.start()));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether `expr` is the literal `True`, which `CPython` compiles without a
+/// branch, as in `while True:`.
+fn is_constant_true(expr: &Expr) -> bool {
+    matches!(expr, Expr::BooleanLiteral(literal) if literal.value)
+}
+
+/// Whether a `case` matches every subject, like `case _:`.
+fn is_irrefutable(case: &MatchCase) -> bool {
+    case.guard.is_none()
+        && matches!(&case.pattern, Pattern::MatchAs(pattern) if pattern.pattern.is_none())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     fn lines(source: &str) -> Vec<u32> {
-        let mut v: Vec<u32> = executable_lines_for_source(source).into_iter().collect();
+        let mut v: Vec<u32> = executable_lines_for_source(source, &Exclusions::default())
+            .into_iter()
+            .collect();
         v.sort_unstable();
         v
     }
@@ -198,7 +530,7 @@ mod tests {
     /// snapshots line up next to the original Python so a reviewer can
     /// scan and confirm the markers fall on the right keywords.
     fn annotate(source: &str) -> String {
-        let recorded = executable_lines_for_source(source);
+        let recorded = executable_lines_for_source(source, &Exclusions::default());
         source
             .lines()
             .enumerate()
@@ -215,6 +547,12 @@ mod tests {
             .join("\n")
     }
 
+    fn arcs(source: &str) -> Vec<(i32, i32)> {
+        branch_arcs_for_source(source, &Exclusions::default())
+            .into_iter()
+            .collect()
+    }
+
     #[test]
     fn skips_module_docstring() {
         let src = "\
@@ -516,6 +854,28 @@ z = 3
         assert_eq!(lines(src), vec![1, 3]);
     }
 
+    #[test]
+    fn pragma_excluded_lines_cover_the_whole_block() {
+        let src = "\
+def kept():
+    return 1
+
+def excluded():  # pragma: no cover
+    a = 1
+    return a
+
+if kept():
+    pass
+else:  # pragma: no cover
+    raise
+";
+        let mut excluded: Vec<u32> = excluded_lines_for_source(src, &Exclusions::default())
+            .into_iter()
+            .collect();
+        excluded.sort_unstable();
+        assert_eq!(excluded, vec![4, 5, 6, 10, 11]);
+    }
+
     #[test]
     fn pragma_on_function_head_excludes_body() {
         let src = "\
@@ -637,4 +997,151 @@ y = 2
         // a comment and must not exclude line 1.
         assert_eq!(lines(src), vec![1, 2]);
     }
+
+    #[test]
+    fn arcs_for_if_else() {
+        let src = "\
+def f(x):
+    if x:
+        a = 1
+    else:
+        a = 2
+    return a
+";
+        assert_eq!(arcs(src), vec![(2, 3), (2, 5)]);
+    }
+
+    #[test]
+    fn arcs_for_if_without_else_exit_the_function() {
+        let src = "\
+def f(x):
+    if x:
+        return 1
+";
+        // Skipping the body leaves the function, whose code starts on line 1.
+        assert_eq!(arcs(src), vec![(2, -1), (2, 3)]);
+    }
+
+    #[test]
+    fn arcs_for_loops_return_to_the_loop_head() {
+        let src = "\
+for i in range(3):
+    if i:
+        continue
+while x:
+    x -= 1
+";
+        assert_eq!(
+            arcs(src),
+            vec![(1, 2), (1, 4), (2, 1), (2, 3), (4, -1), (4, 5)]
+        );
+    }
+
+    #[test]
+    fn arcs_for_elif_and_match_cases() {
+        let src = "\
+if a:
+    x = 1
+elif b:
+    x = 2
+while True:
+    break
+match x:
+    case 1:
+        pass
+    case _:
+        pass
+";
+        // `while True:` and the irrefutable `case _:` only go one way.
+        assert_eq!(
+            arcs(src),
+            vec![(1, 2), (1, 3), (3, 4), (3, 5), (8, 9), (8, 10)]
+        );
+    }
+
+    #[test]
+    fn pragma_removes_excluded_branch() {
+        let src = "\
+def f(x):
+    if x:
+        return 1
+    else:  # pragma: no cover
+        return 2
+";
+        assert_eq!(arcs(src), Vec::<(i32, i32)>::new());
+    }
+
+    fn exclusions(exclude_lines: &[&str], partial_branches: &[&str]) -> Exclusions {
+        let strings =
+            |patterns: &[&str]| patterns.iter().map(ToString::to_string).collect::<Vec<_>>();
+        Exclusions::new(&strings(exclude_lines), &strings(partial_branches)).unwrap()
+    }
+
+    #[test]
+    fn exclude_lines_pattern_excludes_the_block_it_starts() {
+        let src = "\
+from typing import TYPE_CHECKING
+
+if TYPE_CHECKING:
+    import os
+
+def f():
+    raise NotImplementedError
+";
+        let exclusions = exclusions(&["if TYPE_CHECKING:", "raise NotImplementedError"], &[]);
+        let mut executable: Vec<u32> = executable_lines_for_source(src, &exclusions)
+            .into_iter()
+            .collect();
+        executable.sort_unstable();
+        assert_eq!(executable, vec![1, 6]);
+
+        let mut excluded: Vec<u32> = excluded_lines_for_source(src, &exclusions)
+            .into_iter()
+            .collect();
+        excluded.sort_unstable();
+        assert_eq!(excluded, vec![3, 4, 7]);
+    }
+
+    #[test]
+    fn exclude_lines_pattern_on_decorator_excludes_the_definition() {
+        let src = "\
+from typing import overload
+
+@overload
+def f(x: int) -> int: ...
+
+def f(x):
+    return x
+";
+        let exclusions = exclusions(&["@overload"], &[]);
+        let mut executable: Vec<u32> = executable_lines_for_source(src, &exclusions)
+            .into_iter()
+            .collect();
+        executable.sort_unstable();
+        assert_eq!(executable, vec![1, 6, 7]);
+    }
+
+    #[test]
+    fn invalid_exclude_lines_pattern_is_an_error() {
+        let err = Exclusions::new(&["(".to_string()], &[]).unwrap_err();
+        assert!(err.to_string().contains("invalid `exclude-lines` pattern"));
+    }
+
+    #[test]
+    fn no_branch_pragma_and_partial_branches_pattern_drop_the_arcs() {
+        let src = "\
+def f(x):
+    if x:  # pragma: no branch
+        x = 1
+    while x > 0:
+        x -= 1
+    for i in range(x):
+        pass
+";
+        let exclusions = exclusions(&[], &["while x > 0:"]);
+        let arcs: Vec<(i32, i32)> = branch_arcs_for_source(src, &exclusions)
+            .into_iter()
+            .collect();
+        assert_eq!(arcs, vec![(6, -1), (6, 7)]);
+    }
 }
@@ -0,0 +1,103 @@
+//! Selection of the source files to measure.
+//!
+//! On top of the source roots, the `include` and `omit` globs narrow the
+//! files measured. A pattern matches either the absolute path of a file or
+//! its path relative to the working directory, so both `src/*` and
+//! `*/migrations/*` work as expected.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::report::canonical_cwd;
+
+/// The `include` and `omit` globs, compiled.
+#[derive(Debug, Clone, Default)]
+pub struct SourceFilter {
+    /// Canonical working directory that relative paths are taken from.
+    cwd: PathBuf,
+
+    /// Only files matching one of these are measured. `None` measures
+    /// every file.
+    include: Option<GlobSet>,
+
+    /// Files matching one of these are not measured.
+    omit: Option<GlobSet>,
+}
+
+impl SourceFilter {
+    pub fn new(cwd: &Utf8Path, include: &[String], omit: &[String]) -> Result<Self> {
+        Ok(Self {
+            cwd: canonical_cwd(cwd),
+            include: glob_set(include).context("invalid `include` pattern")?,
+            omit: glob_set(omit).context("invalid `omit` pattern")?,
+        })
+    }
+
+    /// Whether the file at the canonical `path` is measured.
+    pub fn is_measured(&self, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.cwd).ok();
+        let matches = |set: &GlobSet| {
+            set.is_match(path) || relative.is_some_and(|relative| set.is_match(relative))
+        };
+        self.include.as_ref().is_none_or(matches) && !self.omit.as_ref().is_some_and(matches)
+    }
+}
+
+fn glob_set(patterns: &[String]) -> Result<Option<GlobSet>, globset::Error> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+    builder.build().map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A filter for `/project`, which does not exist and so is used as is.
+    fn filter(include: &[&str], omit: &[&str]) -> SourceFilter {
+        let strings =
+            |patterns: &[&str]| patterns.iter().map(ToString::to_string).collect::<Vec<_>>();
+        SourceFilter::new(Utf8Path::new("/project"), &strings(include), &strings(omit)).unwrap()
+    }
+
+    #[test]
+    fn everything_is_measured_by_default() {
+        assert!(SourceFilter::default().is_measured(Path::new("/project/src/app.py")));
+    }
+
+    #[test]
+    fn include_matches_relative_paths() {
+        let filter = filter(&["src/*"], &[]);
+        assert!(filter.is_measured(Path::new("/project/src/app.py")));
+        assert!(!filter.is_measured(Path::new("/project/scripts/run.py")));
+    }
+
+    #[test]
+    fn omit_wins_over_include() {
+        let filter = filter(&["src/*"], &["*/migrations/*"]);
+        assert!(filter.is_measured(Path::new("/project/src/app.py")));
+        assert!(!filter.is_measured(Path::new("/project/src/migrations/0001.py")));
+    }
+
+    #[test]
+    fn omit_matches_absolute_paths() {
+        let filter = filter(&[], &["/project/generated/*"]);
+        assert!(!filter.is_measured(Path::new("/project/generated/schema.py")));
+        assert!(filter.is_measured(Path::new("/project/app.py")));
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        let err =
+            SourceFilter::new(Utf8Path::new("/project"), &["[".to_string()], &[]).unwrap_err();
+        assert!(err.to_string().contains("invalid `include` pattern"));
+    }
+}
@@ -0,0 +1,463 @@
+//! Static HTML rendering of combined coverage data.
+//!
+//! Writes a self-contained directory: an `index.html` listing every file
+//! from the least to the most covered, one page per source file with the
+//! source highlighted and each line marked as run, missing, excluded or a
+//! partial branch, and the `style.css` the pages share. When per-test
+//! contexts were recorded, each executed line lists the tests that ran it.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use karva_diagnostic::escape_xml;
+use ruff_python_ast::token::TokenKind;
+use ruff_python_parser::{Mode, ParseOptions, parse_unchecked};
+use ruff_text_size::Ranged;
+
+use crate::report::{
+    BranchStats, CombinedFile, canonical_cwd, display_path, format_percent, percent,
+};
+
+const STYLE: &str = r#"body {
+  font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif;
+  margin: 2em;
+  color: #24292e;
+}
+a { color: #0366d6; text-decoration: none; }
+a:hover { text-decoration: underline; }
+table.index { border-collapse: collapse; }
+table.index th, table.index td { padding: 0.3em 1em; text-align: right; border-bottom: 1px solid #e1e4e8; }
+table.index .name { text-align: left; }
+table.index tfoot td { font-weight: bold; }
+.stats span { padding: 0.1em 0.4em; }
+.source { font-family: SFMono-Regular, Consolas, "Liberation Mono", Menlo, monospace; font-size: 0.85em; }
+.line { display: flex; white-space: pre-wrap; border-left: 4px solid transparent; }
+.line .n { flex: 0 0 4em; padding-right: 1em; text-align: right; color: #959da5; }
+.line code { flex: 1; font-family: inherit; }
+.line .annotate { padding: 0 1em; color: #586069; }
+.line .ctx { padding: 0 1em; color: #586069; }
+.line .ctx summary { cursor: pointer; }
+.line .ctx div { padding-left: 1em; }
+.run { background: #e6ffed; border-left-color: #2cbe4e; }
+.mis { background: #ffeef0; border-left-color: #cb2431; }
+.par { background: #fff8c5; border-left-color: #dbab09; }
+.exc { color: #959da5; border-left-color: #d1d5da; }
+.kw { color: #d73a49; font-weight: bold; }
+.str { color: #032f62; }
+.num { color: #005cc5; }
+.com { color: #6a737d; font-style: italic; }
+"#;
+
+/// A source file's counts, and the name of its page.
+struct FilePage<'a> {
+    /// Absolute path of the source file.
+    path: &'a str,
+    /// Path shown in the report, relative to the working directory.
+    name: String,
+    /// File name of the page within the report directory.
+    page: String,
+    data: &'a CombinedFile,
+    stmts: u32,
+    miss: u32,
+    branches: BranchStats,
+}
+
+impl FilePage<'_> {
+    /// Statements and branches, the denominator of the coverage percentage.
+    fn total(&self) -> u32 {
+        self.stmts.saturating_add(self.branches.branches)
+    }
+
+    /// Statements and branches that never ran.
+    fn missed(&self) -> u32 {
+        self.miss.saturating_add(self.branches.missing)
+    }
+
+    fn excluded(&self) -> u32 {
+        saturating_len(self.data.excluded.len())
+    }
+}
+
+/// Write the HTML report for `combined` into `dir`, creating it if needed.
+pub(crate) fn write(
+    dir: &Utf8Path,
+    cwd: &Utf8Path,
+    combined: &BTreeMap<String, CombinedFile>,
+    branch: bool,
+) -> Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create directory {dir}"))?;
+    let cwd_real = canonical_cwd(cwd);
+
+    let mut used_pages = HashSet::from(["index.html".to_string(), "style.css".to_string()]);
+    let mut pages: Vec<FilePage> = combined
+        .iter()
+        .map(|(path, data)| {
+            let name = display_path(path, &cwd_real);
+            let stmts = saturating_len(data.executable.len());
+            let miss = stmts.saturating_sub(saturating_len(data.executed.len()));
+            FilePage {
+                path,
+                page: page_name(&name, &mut used_pages),
+                name,
+                data,
+                stmts,
+                miss,
+                branches: if branch {
+                    data.branch_stats()
+                } else {
+                    BranchStats::default()
+                },
+            }
+        })
+        .collect();
+    pages.sort_by(|a, b| {
+        percent(a.total(), a.missed())
+            .total_cmp(&percent(b.total(), b.missed()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    write_file(dir, "style.css", STYLE)?;
+    write_file(dir, "index.html", &render_index(&pages, branch))?;
+    for page in &pages {
+        // A file removed since the run still gets a page, without source.
+        let source = std::fs::read_to_string(page.path).unwrap_or_default();
+        write_file(dir, &page.page, &render_file(page, &source, branch))?;
+    }
+    Ok(())
+}
+
+fn write_file(dir: &Utf8Path, name: &str, contents: &str) -> Result<()> {
+    let path = dir.join(name);
+    std::fs::write(&path, contents).with_context(|| format!("failed to write {path}"))
+}
+
+/// The page of a source file, named after its path: `pkg/mod.py` is
+/// rendered to `pkg_mod_py.html`. A path that flattens to a name in
+/// `used`, such as `pkg_mod.py`, gets a numbered suffix instead, like
+/// `pkg_mod_py_2.html`. Names are compared ignoring case, for file systems
+/// that do.
+fn page_name(name: &str, used: &mut HashSet<String>) -> String {
+    let stem = name.replace(['/', '\\', '.'], "_");
+    let mut page = format!("{stem}.html");
+    let mut suffix = 1;
+    while !used.insert(page.to_lowercase()) {
+        suffix += 1;
+        page = format!("{stem}_{suffix}.html");
+    }
+    page
+}
+
+fn render_index(pages: &[FilePage<'_>], branch: bool) -> String {
+    let mut rows = String::new();
+    let (mut stmts, mut miss, mut excluded) = (0u32, 0u32, 0u32);
+    let (mut branches, mut partial, mut total, mut missed) = (0u32, 0u32, 0u32, 0u32);
+
+    for page in pages {
+        let _ = write!(
+            rows,
+            r#"<tr><td class="name"><a href="{}">{}</a></td>"#,
+            escape_xml(&page.page),
+            escape_xml(&page.name),
+        );
+        write_counts(
+            &mut rows,
+            branch,
+            [
+                page.stmts,
+                page.miss,
+                page.excluded(),
+                page.branches.branches,
+                page.branches.partial,
+            ],
+            &format_percent(page.total(), page.missed()),
+        );
+        rows.push_str("</tr>\n");
+
+        stmts = stmts.saturating_add(page.stmts);
+        miss = miss.saturating_add(page.miss);
+        excluded = excluded.saturating_add(page.excluded());
+        branches = branches.saturating_add(page.branches.branches);
+        partial = partial.saturating_add(page.branches.partial);
+        total = total.saturating_add(page.total());
+        missed = missed.saturating_add(page.missed());
+    }
+
+    let total_cover = format_percent(total, missed);
+    let mut totals = String::from(r#"<tr><td class="name">Total</td>"#);
+    write_counts(
+        &mut totals,
+        branch,
+        [stmts, miss, excluded, branches, partial],
+        &total_cover,
+    );
+    totals.push_str("</tr>\n");
+
+    let branch_headers = if branch {
+        "<th>Branches</th><th>Partial</th>"
+    } else {
+        ""
+    };
+
+    let mut out = page_header(&format!("Coverage report: {total_cover}"));
+    let _ = write!(
+        out,
+        r#"<h1>Coverage report: <span class="pc">{total_cover}</span></h1>
+<table class="index">
+<thead>
+<tr><th class="name">File</th><th>Statements</th><th>Missing</th><th>Excluded</th>{branch_headers}<th>Coverage</th></tr>
+</thead>
+<tbody>
+{rows}</tbody>
+<tfoot>
+{totals}</tfoot>
+</table>
+</body>
+</html>
+"#
+    );
+    out
+}
+
+/// Write the count cells of an index row: statements, missing, excluded,
+/// then branches and partial branches when `branch` is set, and coverage.
+fn write_counts(out: &mut String, branch: bool, counts: [u32; 5], cover: &str) {
+    let shown = if branch { &counts[..] } else { &counts[..3] };
+    for count in shown {
+        let _ = write!(out, "<td>{count}</td>");
+    }
+    let _ = write!(out, "<td>{cover}</td>");
+}
+
+fn render_file(page: &FilePage<'_>, source: &str, branch: bool) -> String {
+    let data = page.data;
+    let cover = format_percent(page.total(), page.missed());
+
+    let mut partial: BTreeMap<i32, Vec<i32>> = BTreeMap::new();
+    for &(from, to) in &page.branches.partial_arcs {
+        partial.entry(from).or_default().push(to);
+    }
+
+    let mut out = page_header(&format!("Coverage for {}: {cover}", page.name));
+    let _ = writeln!(
+        out,
+        r#"<p><a href="index.html">&larr; Index</a></p>
+<h1>Coverage for <b>{}</b>: <span class="pc">{cover}</span></h1>"#,
+        escape_xml(&page.name),
+    );
+    let _ = write!(
+        out,
+        r#"<p class="stats"><span class="run">{} run</span> <span class="mis">{} missing</span> <span class="exc">{} excluded</span>"#,
+        page.stmts.saturating_sub(page.miss),
+        page.miss,
+        page.excluded(),
+    );
+    if branch {
+        let _ = write!(
+            out,
+            r#" <span class="par">{} partial</span>"#,
+            page.branches.partial
+        );
+    }
+    out.push_str("</p>\n<div class=\"source\">\n");
+
+    for (index, code) in highlight(source).iter().enumerate() {
+        let line = u32::try_from(index + 1).unwrap_or(u32::MAX);
+        let partial_destinations = i32::try_from(line).ok().and_then(|line| partial.get(&line));
+        let class = if data.excluded.contains(&line) {
+            " exc"
+        } else if !data.executable.contains(&line) {
+            ""
+        } else if !data.executed.contains(&line) {
+            " mis"
+        } else if partial_destinations.is_some() {
+            " par"
+        } else {
+            " run"
+        };
+
+        let _ = write!(
+            out,
+            r##"<div class="line{class}" id="t{line}"><a class="n" href="#t{line}">{line}</a><code>{code}</code>"##
+        );
+        if let Some(destinations) = partial_destinations {
+            let annotation: Vec<String> = destinations
+                .iter()
+                .map(|&to| {
+                    if to < 0 {
+                        format!("{line} &#x219B; exit")
+                    } else {
+                        format!("{line} &#x219B; {to}")
+                    }
+                })
+                .collect();
+            let _ = write!(
+                out,
+                r#"<span class="annotate">{}</span>"#,
+                annotation.join(", ")
+            );
+        }
+        if let Some(tests) = data.contexts.get(&line) {
+            write_contexts(&mut out, tests);
+        }
+        out.push_str("</div>\n");
+    }
+
+    out.push_str("</div>\n</body>\n</html>\n");
+    out
+}
+
+/// Write a collapsed list of the tests that ran a line.
+fn write_contexts(out: &mut String, tests: &BTreeSet<String>) {
+    let count = tests.len();
+    let noun = if count == 1 { "test" } else { "tests" };
+    let _ = write!(
+        out,
+        r#"<details class="ctx"><summary>{count} {noun}</summary>"#
+    );
+    for test in tests {
+        let _ = write!(out, "<div>{}</div>", escape_xml(test));
+    }
+    out.push_str("</details>");
+}
+
+fn page_header(title: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{}</title>
+<link rel="stylesheet" href="style.css">
+</head>
+<body>
+"#,
+        escape_xml(title)
+    )
+}
+
+/// Render each line of `source` as HTML, with keywords, strings, numbers
+/// and comments wrapped in classed `<span>`s.
+fn highlight(source: &str) -> Vec<String> {
+    let parsed = parse_unchecked(source, ParseOptions::from(Mode::Module));
+
+    let mut lines = vec![String::new()];
+    let mut position = 0;
+    for token in parsed.tokens() {
+        let Some(class) = token_class(token.kind()) else {
+            continue;
+        };
+        let start = token.start().to_usize();
+        let end = token.end().to_usize();
+        if start < position {
+            continue;
+        }
+        push_text(&mut lines, &source[position..start], None);
+        push_text(&mut lines, &source[start..end], Some(class));
+        position = end;
+    }
+    push_text(&mut lines, &source[position..], None);
+
+    lines.truncate(source.lines().count());
+    lines
+}
+
+fn token_class(kind: TokenKind) -> Option<&'static str> {
+    match kind {
+        TokenKind::Comment => Some("com"),
+        TokenKind::String
+        | TokenKind::FStringStart
+        | TokenKind::FStringMiddle
+        | TokenKind::FStringEnd => Some("str"),
+        TokenKind::Int | TokenKind::Float | TokenKind::Complex => Some("num"),
+        kind if kind.is_keyword() => Some("kw"),
+        _ => None,
+    }
+}
+
+/// Append `text` to the last of `lines`, starting a new line at each
+/// newline so tokens spanning several lines are split between them.
+fn push_text(lines: &mut Vec<String>, text: &str, class: Option<&str>) {
+    for (index, part) in text.split('\n').enumerate() {
+        if index > 0 {
+            lines.push(String::new());
+        }
+        let part = part.strip_suffix('\r').unwrap_or(part);
+        if part.is_empty() {
+            continue;
+        }
+        let Some(line) = lines.last_mut() else {
+            continue;
+        };
+        match class {
+            Some(class) => {
+                let _ = write!(line, r#"<span class="{class}">{}</span>"#, escape_xml(part));
+            }
+            None => line.push_str(&escape_xml(part)),
+        }
+    }
+}
+
+fn saturating_len(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_wraps_tokens_per_line() {
+        let lines = highlight("if x > 1:  # big\n    s = \"<a>\"\n");
+        assert_eq!(
+            lines,
+            vec![
+                r#"<span class="kw">if</span> x &gt; <span class="num">1</span>:  <span class="com"># big</span>"#,
+                r#"    s = <span class="str">&quot;&lt;a&gt;&quot;</span>"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn highlight_splits_multiline_strings() {
+        let lines = highlight("s = \"\"\"a\nb\"\"\"\n");
+        assert_eq!(
+            lines,
+            vec![
+                r#"s = <span class="str">&quot;&quot;&quot;a</span>"#,
+                r#"<span class="str">b&quot;&quot;&quot;</span>"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn contexts_list_the_tests_of_a_line() {
+        let mut out = String::new();
+        write_contexts(
+            &mut out,
+            &BTreeSet::from(["test::a".to_string(), "test::b[<x>]".to_string()]),
+        );
+        assert_eq!(
+            out,
+            r#"<details class="ctx"><summary>2 tests</summary><div>test::a</div><div>test::b[&lt;x&gt;]</div></details>"#
+        );
+    }
+
+    #[test]
+    fn page_names_flatten_the_path() {
+        let mut used = HashSet::new();
+        assert_eq!(page_name("pkg/mod.py", &mut used), "pkg_mod_py.html");
+        assert_eq!(page_name("test_a.py", &mut used), "test_a_py.html");
+    }
+
+    #[test]
+    fn page_names_are_unique() {
+        let mut used = HashSet::from(["index.html".to_string()]);
+        assert_eq!(page_name("pkg/mod.py", &mut used), "pkg_mod_py.html");
+        assert_eq!(page_name("pkg_mod.py", &mut used), "pkg_mod_py_2.html");
+        assert_eq!(page_name("pkg.mod.py", &mut used), "pkg_mod_py_3.html");
+        assert_eq!(page_name("Pkg/Mod.py", &mut used), "Pkg_Mod_py_4.html");
+        assert_eq!(page_name("index", &mut used), "index_2.html");
+    }
+}
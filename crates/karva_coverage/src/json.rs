@@ -0,0 +1,243 @@
+//! JSON rendering of combined coverage data.
+//!
+//! The layout follows coverage.py's JSON report: a `meta` object, then per
+//! file the executed, missing and excluded lines with a `summary` of the
+//! counts, and the `totals` over every file. When per-test contexts were
+//! recorded, each file also maps its executed lines to the tests that ran
+//! them.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use camino::Utf8Path;
+use serde::Serialize;
+
+use crate::report::{CombinedFile, canonical_cwd, display_path, percent};
+
+#[derive(Serialize)]
+struct Report<'a> {
+    meta: Meta,
+    files: BTreeMap<String, FileReport<'a>>,
+    totals: Summary,
+}
+
+#[derive(Serialize)]
+struct Meta {
+    version: &'static str,
+    branch_coverage: bool,
+    show_contexts: bool,
+}
+
+#[derive(Serialize)]
+struct FileReport<'a> {
+    executed_lines: Vec<u32>,
+    summary: Summary,
+    missing_lines: Vec<u32>,
+    excluded_lines: Vec<u32>,
+    /// The tests that ran each executed line.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    contexts: BTreeMap<u32, Vec<&'a str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    executed_branches: Option<Vec<(i32, i32)>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    missing_branches: Option<Vec<(i32, i32)>>,
+}
+
+/// Line counts, and branch counts when branch coverage was measured.
+#[derive(Serialize, Default)]
+struct Summary {
+    covered_lines: u32,
+    num_statements: u32,
+    percent_covered: f64,
+    missing_lines: u32,
+    excluded_lines: u32,
+    #[serde(flatten)]
+    branches: Option<BranchSummary>,
+}
+
+#[derive(Serialize, Default, Clone, Copy)]
+struct BranchSummary {
+    num_branches: u32,
+    num_partial_branches: u32,
+    covered_branches: u32,
+    missing_branches: u32,
+}
+
+impl Summary {
+    /// Add `other`'s counts. The percentage is left for
+    /// [`Self::update_percent`].
+    fn add(&mut self, other: &Self) {
+        self.covered_lines = self.covered_lines.saturating_add(other.covered_lines);
+        self.num_statements = self.num_statements.saturating_add(other.num_statements);
+        self.missing_lines = self.missing_lines.saturating_add(other.missing_lines);
+        self.excluded_lines = self.excluded_lines.saturating_add(other.excluded_lines);
+        if let Some(other) = other.branches {
+            let branches = self.branches.get_or_insert_default();
+            branches.num_branches = branches.num_branches.saturating_add(other.num_branches);
+            branches.num_partial_branches = branches
+                .num_partial_branches
+                .saturating_add(other.num_partial_branches);
+            branches.covered_branches = branches
+                .covered_branches
+                .saturating_add(other.covered_branches);
+            branches.missing_branches = branches
+                .missing_branches
+                .saturating_add(other.missing_branches);
+        }
+    }
+
+    /// Set the percentage from the counts. Branches count alongside
+    /// statements, as in the terminal report.
+    fn update_percent(&mut self) {
+        let (branches, missing_branches) = self
+            .branches
+            .map_or((0, 0), |b| (b.num_branches, b.missing_branches));
+        self.percent_covered = percent(
+            self.num_statements.saturating_add(branches),
+            self.missing_lines.saturating_add(missing_branches),
+        );
+    }
+}
+
+/// Render `combined` as a JSON document. File names are relative to `cwd`.
+pub(crate) fn render(
+    cwd: &Utf8Path,
+    combined: &BTreeMap<String, CombinedFile>,
+    branch: bool,
+) -> Result<String> {
+    let cwd_real = canonical_cwd(cwd);
+
+    let mut totals = Summary::default();
+    let mut files = BTreeMap::new();
+    for (filename, data) in combined {
+        let file = file_report(data, branch);
+        totals.add(&file.summary);
+        files.insert(display_path(filename, &cwd_real), file);
+    }
+    totals.update_percent();
+
+    let report = Report {
+        meta: Meta {
+            version: karva_version::version(),
+            branch_coverage: branch,
+            show_contexts: combined.values().any(|data| !data.contexts.is_empty()),
+        },
+        files,
+        totals,
+    };
+    let mut out = serde_json::to_string_pretty(&report)?;
+    out.push('\n');
+    Ok(out)
+}
+
+fn file_report(data: &CombinedFile, branch: bool) -> FileReport<'_> {
+    let executed_lines: Vec<u32> = data.executed.iter().copied().collect();
+    let missing_lines: Vec<u32> = data
+        .executable
+        .difference(&data.executed)
+        .copied()
+        .collect();
+    let excluded_lines: Vec<u32> = data.excluded.iter().copied().collect();
+
+    let (branches, executed_branches, missing_branches) = if branch {
+        let stats = data.branch_stats();
+        let mut executed_branches = Vec::new();
+        let mut missing_branches = Vec::new();
+        for (from, line) in data.branch_lines() {
+            for &to in &line.destinations {
+                if line.taken.contains(&to) {
+                    executed_branches.push((from, to));
+                } else {
+                    missing_branches.push((from, to));
+                }
+            }
+        }
+        (
+            Some(BranchSummary {
+                num_branches: stats.branches,
+                num_partial_branches: stats.partial,
+                covered_branches: stats.branches.saturating_sub(stats.missing),
+                missing_branches: stats.missing,
+            }),
+            Some(executed_branches),
+            Some(missing_branches),
+        )
+    } else {
+        (None, None, None)
+    };
+
+    let mut summary = Summary {
+        covered_lines: saturating_len(executed_lines.len()),
+        num_statements: saturating_len(data.executable.len()),
+        percent_covered: 0.0,
+        missing_lines: saturating_len(missing_lines.len()),
+        excluded_lines: saturating_len(excluded_lines.len()),
+        branches,
+    };
+    summary.update_percent();
+
+    FileReport {
+        executed_lines,
+        summary,
+        missing_lines,
+        excluded_lines,
+        contexts: data
+            .contexts
+            .iter()
+            .map(|(&line, tests)| (line, tests.iter().map(String::as_str).collect()))
+            .collect(),
+        executed_branches,
+        missing_branches,
+    }
+}
+
+fn saturating_len(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cf(executable: &[u32], executed: &[u32]) -> CombinedFile {
+        CombinedFile {
+            executable: executable.iter().copied().collect(),
+            executed: executed.iter().copied().collect(),
+            ..CombinedFile::default()
+        }
+    }
+
+    #[test]
+    fn summary_counts_lines() {
+        let report = file_report(&cf(&[1, 2, 3, 4], &[1, 2, 3]), false);
+        assert_eq!(report.missing_lines, vec![4]);
+        assert_eq!(report.summary.covered_lines, 3);
+        assert!((report.summary.percent_covered - 75.0).abs() < f64::EPSILON);
+        assert!(report.summary.branches.is_none());
+    }
+
+    #[test]
+    fn totals_sum_files() {
+        let mut totals = Summary::default();
+        totals.add(&file_report(&cf(&[1, 2], &[1, 2]), false).summary);
+        totals.add(&file_report(&cf(&[1, 2], &[]), false).summary);
+        totals.update_percent();
+        assert_eq!(totals.num_statements, 4);
+        assert_eq!(totals.missing_lines, 2);
+        assert!((totals.percent_covered - 50.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn contexts_list_tests_per_line() {
+        let mut data = cf(&[1, 2], &[1, 2]);
+        data.contexts
+            .entry(2)
+            .or_default()
+            .extend(["test::b".to_string(), "test::a".to_string()]);
+        let report = file_report(&data, false);
+        assert_eq!(
+            report.contexts,
+            BTreeMap::from([(2, vec!["test::a", "test::b"])])
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! LCOV tracefile rendering of combined coverage data.
+//!
+//! Each source file is one record: an `SF:` line, a `DA:` line per
+//! executable line, a `BRDA:` line per branch destination when branch
+//! coverage was measured, and the `LF`/`LH` (and `BRF`/`BRH`) totals.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use camino::Utf8Path;
+
+use crate::report::{CombinedFile, canonical_cwd, display_path};
+
+/// Render `combined` as an LCOV tracefile. File names are relative to
+/// `cwd`.
+pub(crate) fn render(cwd: &Utf8Path, combined: &BTreeMap<String, CombinedFile>) -> String {
+    let cwd_real = canonical_cwd(cwd);
+
+    let mut out = String::new();
+    for (filename, data) in combined {
+        let _ = writeln!(out, "SF:{}", display_path(filename, &cwd_real));
+
+        let mut lines_hit = 0u32;
+        for &line in &data.executable {
+            let hit = data.executed.contains(&line);
+            lines_hit += u32::from(hit);
+            let _ = writeln!(out, "DA:{line},{}", u32::from(hit));
+        }
+
+        let mut branches_found = 0u32;
+        let mut branches_hit = 0u32;
+        for (line, branch_line) in data.branch_lines() {
+            let executed = data.is_executed(line);
+            for (index, destination) in branch_line.destinations.iter().enumerate() {
+                // LCOV writes `-` for the branches of lines that never ran.
+                let taken = if !executed {
+                    "-"
+                } else if branch_line.taken.contains(destination) {
+                    branches_hit += 1;
+                    "1"
+                } else {
+                    "0"
+                };
+                branches_found += 1;
+                let _ = writeln!(out, "BRDA:{line},0,{index},{taken}");
+            }
+        }
+        if branches_found > 0 {
+            let _ = writeln!(out, "BRF:{branches_found}");
+            let _ = writeln!(out, "BRH:{branches_hit}");
+        }
+
+        let _ = writeln!(out, "LF:{}", data.executable.len());
+        let _ = writeln!(out, "LH:{lines_hit}");
+        out.push_str("end_of_record\n");
+    }
+    out
+}
@@ -1,22 +1,41 @@
-//! Line-coverage measurement for karva.
+//! Line and branch coverage measurement for karva.
 //!
 //! Two halves live here:
 //!
 //! * [`tracer`] runs in the worker process. It installs a Python tracer
 //!   (`sys.monitoring` on 3.12+, `sys.settrace` otherwise), records every
 //!   executed line under the configured source roots, computes executable
-//!   lines via the AST, and writes a per-worker JSON file.
+//!   lines via the AST, and writes a per-worker JSON file. [`filter`] narrows
+//!   the files measured with the `include` and `omit` globs, and [`process`]
+//!   extends the measurement to the Python subprocesses tests start.
 //! * [`report`] runs in the main process. It reads each worker's JSON
 //!   file, unions the line sets per source file, and prints a terminal
-//!   `Name / Stmts / Miss / Cover` table.
+//!   `Name / Stmts / Miss / Cover` table. It can also write the combined
+//!   data as Cobertura XML ([`cobertura`]), JSON ([`json`]), LCOV
+//!   ([`lcov`]) or a static HTML site ([`html`]). With a diff from
+//!   [`diff`], it also reports the coverage of the changed lines.
 //!
 //! The two halves communicate only through the JSON file format, defined
-//! in [`data`].
+//! in [`data`]. [`combine`] merges data files, possibly from other machines,
+//! into a single file in the same format.
 
+mod cobertura;
+mod combine;
 pub mod data;
+mod diff;
 pub mod executable;
+mod filter;
+mod html;
+mod json;
+mod lcov;
+mod process;
 pub mod report;
 pub mod tracer;
 
-pub use report::combine_and_report;
+pub use combine::{DATA_FILE, PathRemap, combine_data_files};
+pub use diff::DiffLines;
+pub use executable::Exclusions;
+pub use filter::SourceFilter;
+pub use process::{measure_subprocesses, start_process_coverage};
+pub use report::{CoverageTotals, ReportOptions, combine_and_report, tests_covering};
 pub use tracer::{CoverageConfig, CoverageSession};
@@ -0,0 +1,247 @@
+//! Coverage of the Python subprocesses tests start.
+//!
+//! [`measure_subprocesses`] exports the worker's configuration in
+//! `KARVA_COVERAGE_PROCESS` and puts a `sitecustomize` hook first on
+//! `PYTHONPATH`, so every Python process started from a test inherits both.
+//! When karva is installed in the child's environment, the hook calls
+//! [`start_process_coverage`], which measures the child like the worker and
+//! writes its own data file, `coverage.<pid>.json`, next to the worker's
+//! when the child exits. The hook then runs any other `sitecustomize`
+//! module it shadowed.
+//!
+//! `multiprocessing` children are measured from
+//! `BaseProcess._bootstrap`, which both the worker and measured children
+//! wrap: a forked child inherits the parent's tracer but not a way to save
+//! it, and exits with `os._exit`, skipping `atexit`. The wrapper starts the
+//! child's own session and saves it when the process target returns.
+
+use std::sync::{Mutex, PoisonError};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use karva_static::WorkerEnvVars;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::tracer::{CoverageConfig, CoverageSession, MONITORING_TOOL};
+
+/// The directory holding the hook, next to the worker's data file.
+const HOOK_DIR: &str = "coverage-hook";
+
+const HOOK: &str = r#"# Written by karva to measure coverage in subprocesses of a test run.
+import os
+import sys
+
+if "KARVA_COVERAGE_PROCESS" in os.environ:
+    try:
+        from karva._karva import _start_process_coverage
+    except ImportError:
+        pass
+    else:
+        _start_process_coverage()
+
+
+def _run_shadowed_sitecustomize():
+    import importlib.machinery
+    import importlib.util
+
+    hook_dir = os.path.dirname(os.path.abspath(__file__))
+    sys.path[:] = [p for p in sys.path if os.path.abspath(p or ".") != hook_dir]
+    spec = importlib.machinery.PathFinder.find_spec("sitecustomize", sys.path)
+    if spec is not None and spec.loader is not None:
+        module = importlib.util.module_from_spec(spec)
+        spec.loader.exec_module(module)
+
+
+_run_shadowed_sitecustomize()
+del _run_shadowed_sitecustomize
+"#;
+
+/// Wraps `multiprocessing`'s process bootstrap with the functions that
+/// start and save the measurement of a child.
+const MULTIPROCESSING_PATCH: &str = r#"
+def _patch_bootstrap(start, stop):
+    from multiprocessing.process import BaseProcess
+
+    original = BaseProcess._bootstrap
+    if getattr(original, "_karva_coverage", False):
+        return
+
+    def _bootstrap(self, *args, **kwargs):
+        start()
+        try:
+            return original(self, *args, **kwargs)
+        finally:
+            stop()
+
+    _bootstrap._karva_coverage = True
+    BaseProcess._bootstrap = _bootstrap
+"#;
+
+/// The measurement of this process with the id of the process that started
+/// it. After a `fork` the child holds a copy of its parent's session, which
+/// it must neither reuse nor save.
+static PROCESS_SESSION: Mutex<Option<(u32, CoverageSession)>> = Mutex::new(None);
+
+/// What a subprocess needs to measure itself like the worker.
+#[derive(Serialize, Deserialize)]
+struct ProcessConfig {
+    cwd: Utf8PathBuf,
+    config: CoverageConfig,
+}
+
+/// Make the Python subprocesses of this process measure their coverage
+/// with `config`, by exporting it and installing the `sitecustomize` hook.
+///
+/// Goes through Python's `os.environ`, so processes started with
+/// `subprocess` and from Rust both inherit the variables.
+pub fn measure_subprocesses(
+    py: Python<'_>,
+    cwd: &Utf8Path,
+    config: &CoverageConfig,
+) -> PyResult<()> {
+    let hook_dir = config
+        .data_file
+        .parent()
+        .unwrap_or_else(|| Utf8Path::new("."))
+        .join(HOOK_DIR);
+    std::fs::create_dir_all(&hook_dir)?;
+    std::fs::write(hook_dir.join("sitecustomize.py"), HOOK)?;
+
+    let exported = serde_json::to_string(&ProcessConfig {
+        cwd: cwd.to_path_buf(),
+        config: config.clone(),
+    })
+    .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    let os = py.import("os")?;
+    let environ = os.getattr("environ")?;
+    let existing: Option<String> = environ.call_method1("get", ("PYTHONPATH",))?.extract()?;
+    let python_path = match existing.filter(|existing| !existing.is_empty()) {
+        Some(existing) => {
+            let separator: String = os.getattr("pathsep")?.extract()?;
+            format!("{hook_dir}{separator}{existing}")
+        }
+        None => hook_dir.to_string(),
+    };
+    environ.set_item("PYTHONPATH", python_path)?;
+    environ.set_item(WorkerEnvVars::KARVA_COVERAGE_PROCESS, exported)?;
+    patch_multiprocessing(py)
+}
+
+/// Start measuring this process as configured in `KARVA_COVERAGE_PROCESS`,
+/// and save the data when the interpreter exits. Called by the
+/// `sitecustomize` hook and by `multiprocessing` children; does nothing
+/// when the variable is unset or this process is already measured.
+///
+/// Lines are recorded in the context of the test that started the
+/// process, when contexts are recorded.
+#[pyfunction]
+#[pyo3(name = "_start_process_coverage")]
+pub fn start_process_coverage(py: Python<'_>) -> PyResult<()> {
+    let Ok(exported) = std::env::var(WorkerEnvVars::KARVA_COVERAGE_PROCESS) else {
+        return Ok(());
+    };
+    let pid = std::process::id();
+    {
+        let mut current = PROCESS_SESSION
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if current.as_ref().is_some_and(|(owner, _)| *owner == pid) {
+            return Ok(());
+        }
+        *current = None;
+    }
+    release_inherited_tools(py)?;
+
+    let ProcessConfig { cwd, mut config } = serde_json::from_str(&exported).map_err(|err| {
+        PyValueError::new_err(format!(
+            "invalid `{}`: {err}",
+            WorkerEnvVars::KARVA_COVERAGE_PROCESS
+        ))
+    })?;
+    config.data_file = process_data_file(&config.data_file, pid);
+
+    let session = CoverageSession::start(py, &cwd, &config)?;
+    if let Ok(test_name) = std::env::var(WorkerEnvVars::KARVA_TEST_NAME) {
+        session.set_context(py, Some(&test_name))?;
+    }
+    *PROCESS_SESSION
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = Some((pid, session));
+
+    py.import("atexit")?
+        .call_method1("register", (wrap_pyfunction!(stop_process_coverage, py)?,))?;
+    patch_multiprocessing(py)
+}
+
+/// Stop the measurement started by [`start_process_coverage`] in this
+/// process and write its data file.
+#[pyfunction]
+fn stop_process_coverage(py: Python<'_>) -> PyResult<()> {
+    let session = PROCESS_SESSION
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    match session {
+        Some((pid, session)) if pid == std::process::id() => session.stop_and_save(py),
+        _ => Ok(()),
+    }
+}
+
+/// Measure the `multiprocessing` children of this process, see the module
+/// docs.
+fn patch_multiprocessing(py: Python<'_>) -> PyResult<()> {
+    let locals = pyo3::types::PyDict::new(py);
+    py.run(
+        &std::ffi::CString::new(MULTIPROCESSING_PATCH).expect("valid CString"),
+        None,
+        Some(&locals),
+    )?;
+    locals
+        .get_item("_patch_bootstrap")?
+        .expect("_patch_bootstrap is defined by the patch")
+        .call1((
+            wrap_pyfunction!(start_process_coverage, py)?,
+            wrap_pyfunction!(stop_process_coverage, py)?,
+        ))?;
+    Ok(())
+}
+
+/// Disable the `sys.monitoring` tools a forked child inherited from a
+/// measured parent, so the child only records into its own session.
+/// `sys.settrace` needs nothing: the child's session replaces the function.
+fn release_inherited_tools(py: Python<'_>) -> PyResult<()> {
+    let Ok(mon) = py.import("sys")?.getattr("monitoring") else {
+        return Ok(());
+    };
+    for tool_id in 0u8..6u8 {
+        let tool: Option<String> = mon.call_method1("get_tool", (tool_id,))?.extract()?;
+        if tool.as_deref() == Some(MONITORING_TOOL) {
+            mon.call_method1("set_events", (tool_id, 0))?;
+            mon.call_method1("free_tool_id", (tool_id,))?;
+        }
+    }
+    Ok(())
+}
+
+/// The data file of the subprocess `pid`: `coverage.<pid>.json` next to
+/// the worker's `coverage.json`.
+pub(crate) fn process_data_file(data_file: &Utf8Path, pid: u32) -> Utf8PathBuf {
+    let stem = data_file.file_stem().unwrap_or("coverage");
+    let extension = data_file.extension().unwrap_or("json");
+    data_file.with_file_name(format!("{stem}.{pid}.{extension}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn process_data_file_is_next_to_the_worker_file() {
+        assert_eq!(
+            process_data_file(Utf8Path::new("/cache/run-1/worker-0/coverage.json"), 42),
+            Utf8PathBuf::from("/cache/run-1/worker-0/coverage.42.json")
+        );
+    }
+}
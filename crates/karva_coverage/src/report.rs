@@ -3,51 +3,260 @@
 //! Pure Rust — runs in the main process, never touches Python. Reads each
 //! per-worker JSON file written by the [`tracer`](crate::tracer), unions the
 //! per-file line sets, and prints a `Name / Stmts / Miss / Cover` table
-//! sorted alphabetically with a `TOTAL` row.
+//! sorted alphabetically with a `TOTAL` row. When the workers measured
+//! branch coverage, the table gains `Branch / BrPart` columns and the
+//! coverage percentage counts branches alongside statements.
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
 use colored::Colorize;
 
 use crate::data::WorkerFile;
+use crate::diff::DiffLines;
+use crate::filter::SourceFilter;
+use crate::{cobertura, html, json, lcov};
 
 /// Combine the per-worker data files in `files` and print a terminal report
-/// to stdout. No-ops if there is no data to report.
+/// to stdout, writing any file reports requested in `options`. No-ops if
+/// there is no data to report.
 ///
 /// `files` is the list of per-worker `coverage.json` paths to merge. The
 /// caller (typically [`karva_cache::RunCache::coverage_files`]) is responsible
 /// for resolving the paths; this function only reads them.
 ///
-/// When `show_missing` is true, the report includes a final `Missing` column
+/// When `options.show_missing` is true, the report includes a final `Missing` column
 /// listing the uncovered line numbers per file (consecutive lines collapsed
-/// into `a-b` ranges).
+/// into `a-b` ranges), and with branch coverage the branches never taken
+/// out of executed lines (`a->b`, or `a->exit` for leaving the function).
+///
+/// The table is written to `out`.
+///
+/// When `options.diff` is set, a second table lists the executable lines
+/// the diff changed in each measured file and which of them never ran.
 ///
 /// Returns the total coverage percentage (`0.0..=100.0`) shown in the
-/// `TOTAL` row, or `None` if there was no data to report. Files with zero
-/// executable lines do not contribute to the total.
+/// `TOTAL` row, and the diff coverage if there was one, or `None` if there
+/// was no data to report. Files with zero executable lines do not
+/// contribute to the total. With branch coverage the percentage counts
+/// branches alongside statements.
 pub fn combine_and_report(
     cwd: &Utf8Path,
     files: &[impl AsRef<Utf8Path>],
-    show_missing: bool,
-) -> Result<Option<f64>> {
-    let combined = combine(files)?;
+    options: &ReportOptions,
+    out: &mut dyn Write,
+) -> Result<Option<CoverageTotals>> {
+    let (branch, mut combined) = combine(files)?;
+    combined.retain(|filename, _| options.filter.is_measured(Path::new(filename)));
     if combined.is_empty() {
         return Ok(None);
     }
-    let total = print_report(cwd, &combined, show_missing, &mut std::io::stdout().lock())?;
-    Ok(Some(total))
+    let total = print_report(cwd, &combined, branch, options.show_missing, out)?;
+    let diff = options
+        .diff
+        .as_ref()
+        .map(|diff| print_diff_report(cwd, &combined, diff, out))
+        .transpose()?
+        .flatten();
+    if let Some(path) = &options.xml_path {
+        write_report(path, &cobertura::render(cwd, &combined, branch))?;
+    }
+    if let Some(path) = &options.json_path {
+        write_report(path, &json::render(cwd, &combined, branch)?)?;
+    }
+    if let Some(path) = &options.lcov_path {
+        write_report(path, &lcov::render(cwd, &combined))?;
+    }
+    if let Some(dir) = &options.html_dir {
+        html::write(dir, cwd, &combined, branch)?;
+    }
+    Ok(Some(CoverageTotals { total, diff }))
+}
+
+/// The coverage percentages measured by [`combine_and_report`].
+#[derive(Debug, Clone, Copy)]
+pub struct CoverageTotals {
+    /// The total coverage shown in the `TOTAL` row.
+    pub total: f64,
+
+    /// The coverage of the executable lines changed by the diff, or `None`
+    /// if there was no diff or it changed no executable line.
+    pub diff: Option<f64>,
+}
+
+/// Write a report file, creating its parent directories if needed.
+pub(crate) fn write_report(path: &Utf8Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {parent}"))?;
+    }
+    std::fs::write(path, contents)
+        .with_context(|| format!("failed to write coverage report to {path}"))
+}
+
+/// The reports [`combine_and_report`] writes in addition to the terminal
+/// table.
+#[derive(Debug, Default, Clone)]
+pub struct ReportOptions {
+    /// Whether the terminal table has a `Missing` column.
+    pub show_missing: bool,
+
+    /// Where to write a Cobertura XML report, if anywhere.
+    pub xml_path: Option<Utf8PathBuf>,
+
+    /// Where to write a JSON report, if anywhere.
+    pub json_path: Option<Utf8PathBuf>,
+
+    /// Where to write an LCOV report, if anywhere.
+    pub lcov_path: Option<Utf8PathBuf>,
+
+    /// The directory to write an HTML report into, if any.
+    pub html_dir: Option<Utf8PathBuf>,
+
+    /// The files to report on. Files the filter leaves out are dropped
+    /// from every report.
+    pub filter: SourceFilter,
+
+    /// The changed lines to report diff coverage for, if any.
+    pub diff: Option<DiffLines>,
+}
+
+/// The union of every worker's data for one source file.
+#[derive(Debug, Default)]
+pub(crate) struct CombinedFile {
+    pub(crate) executable: BTreeSet<u32>,
+    pub(crate) executed: BTreeSet<u32>,
+    pub(crate) excluded: BTreeSet<u32>,
+    pub(crate) arcs: BTreeSet<(i32, i32)>,
+    pub(crate) executed_arcs: BTreeSet<(i32, i32)>,
+    /// The tests that ran each line. Empty unless contexts were recorded.
+    pub(crate) contexts: BTreeMap<u32, BTreeSet<String>>,
+}
+
+/// The arcs out of one branch line.
+#[derive(Debug)]
+pub(crate) struct BranchLine {
+    /// Every line the branch can go to. Negative for leaving the function.
+    pub(crate) destinations: BTreeSet<i32>,
+    /// The destinations that were taken.
+    pub(crate) taken: BTreeSet<i32>,
 }
 
+/// Branch coverage of a single file.
 #[derive(Debug, Default)]
-struct CombinedFile {
-    executable: BTreeSet<u32>,
-    executed: BTreeSet<u32>,
+pub(crate) struct BranchStats {
+    /// Number of possible arcs out of branch lines.
+    pub(crate) branches: u32,
+    /// Number of those arcs never taken.
+    pub(crate) missing: u32,
+    /// Number of executed branch lines with an arc never taken.
+    pub(crate) partial: u32,
+    /// The arcs never taken out of executed branch lines.
+    pub(crate) partial_arcs: Vec<(i32, i32)>,
+}
+
+impl CombinedFile {
+    /// Add `other`'s data for the same file.
+    pub(crate) fn merge(&mut self, other: Self) {
+        self.executable.extend(other.executable);
+        self.executed.extend(other.executed);
+        self.excluded.extend(other.excluded);
+        self.arcs.extend(other.arcs);
+        self.executed_arcs.extend(other.executed_arcs);
+        for (line, tests) in other.contexts {
+            self.contexts.entry(line).or_default().extend(tests);
+        }
+    }
+
+    /// Whether `line` ran.
+    pub(crate) fn is_executed(&self, line: i32) -> bool {
+        u32::try_from(line).is_ok_and(|line| self.executed.contains(&line))
+    }
+
+    /// The file's branch lines by line number. Empty unless branch coverage
+    /// was measured.
+    pub(crate) fn branch_lines(&self) -> BTreeMap<i32, BranchLine> {
+        let mut destinations: BTreeMap<i32, BTreeSet<i32>> = BTreeMap::new();
+        for &(from, to) in &self.arcs {
+            destinations.entry(from).or_default().insert(to);
+        }
+
+        destinations
+            .into_iter()
+            .map(|(from, destinations)| {
+                let measured: BTreeSet<i32> = self
+                    .executed_arcs
+                    .range((from, i32::MIN)..=(from, i32::MAX))
+                    .map(|&(_, to)| to)
+                    .collect();
+                let taken = taken_destinations(from, &destinations, &measured);
+                (
+                    from,
+                    BranchLine {
+                        destinations,
+                        taken,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    pub(crate) fn branch_stats(&self) -> BranchStats {
+        let mut stats = BranchStats::default();
+        for (from, line) in self.branch_lines() {
+            let missing: Vec<i32> = line.destinations.difference(&line.taken).copied().collect();
+
+            stats.branches = stats
+                .branches
+                .saturating_add(u32::try_from(line.destinations.len()).unwrap_or(u32::MAX));
+            stats.missing = stats
+                .missing
+                .saturating_add(u32::try_from(missing.len()).unwrap_or(u32::MAX));
+
+            if self.is_executed(from) && !missing.is_empty() {
+                stats.partial += 1;
+                stats
+                    .partial_arcs
+                    .extend(missing.into_iter().map(|to| (from, to)));
+            }
+        }
+        stats
+    }
+}
+
+/// The destinations of a branch line that were taken, given the
+/// destinations the tracer measured.
+///
+/// `CPython` doesn't always attribute the jump out of a block to the line
+/// the AST predicts: the implicit return at the end of a function, or the
+/// jump back to a loop head, can carry the line of a neighbouring
+/// statement. A measured destination the AST doesn't predict therefore
+/// counts towards the branch's arcs that leave the block, to an exit or to
+/// an earlier line.
+fn taken_destinations(
+    from: i32,
+    possible: &BTreeSet<i32>,
+    measured: &BTreeSet<i32>,
+) -> BTreeSet<i32> {
+    let mut taken: BTreeSet<i32> = possible.intersection(measured).copied().collect();
+    if !measured.is_subset(possible) {
+        taken.extend(possible.iter().filter(|&&to| to < 0 || to <= from));
+    }
+    taken
 }
 
-fn combine(files: &[impl AsRef<Utf8Path>]) -> Result<BTreeMap<String, CombinedFile>> {
+/// Union the per-worker data files. Returns whether any of them measured
+/// branch coverage, and the combined data per source file.
+pub(crate) fn combine(
+    files: &[impl AsRef<Utf8Path>],
+) -> Result<(bool, BTreeMap<String, CombinedFile>)> {
+    let mut branch = false;
     let mut combined: BTreeMap<String, CombinedFile> = BTreeMap::new();
 
     for path in files {
@@ -57,20 +266,56 @@ fn combine(files: &[impl AsRef<Utf8Path>]) -> Result<BTreeMap<String, CombinedFi
         let parsed: WorkerFile = serde_json::from_slice(&bytes)
             .with_context(|| format!("failed to parse coverage file {path}"))?;
 
+        branch |= parsed.branch;
         for (filename, file_entry) in parsed.files {
             let bucket = combined.entry(filename).or_default();
             bucket.executable.extend(file_entry.executable);
             bucket.executed.extend(file_entry.executed);
+            bucket.excluded.extend(file_entry.excluded);
+            bucket.arcs.extend(file_entry.arcs);
+            bucket.executed_arcs.extend(file_entry.executed_arcs);
+            for (line, ids) in file_entry.contexts {
+                let names = ids
+                    .into_iter()
+                    .filter_map(|id| parsed.contexts.get(usize::try_from(id).ok()?))
+                    .cloned();
+                bucket.contexts.entry(line).or_default().extend(names);
+            }
         }
     }
 
-    Ok(combined)
+    Ok((branch, combined))
+}
+
+/// The tests that ran `line` of `file`, from the contexts recorded in the
+/// per-worker data `files`.
+///
+/// Returns `None` if no contexts were recorded, so there is no telling
+/// which tests ran the line.
+pub fn tests_covering(
+    files: &[impl AsRef<Utf8Path>],
+    file: &Path,
+    line: u32,
+) -> Result<Option<BTreeSet<String>>> {
+    let (_, mut combined) = combine(files)?;
+    if combined.values().all(|data| data.contexts.is_empty()) {
+        return Ok(None);
+    }
+    let file = std::fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+    Ok(Some(
+        combined
+            .get_mut(&*file.to_string_lossy())
+            .and_then(|data| data.contexts.remove(&line))
+            .unwrap_or_default(),
+    ))
 }
 
 struct Row<'a> {
     name: &'a str,
     stmts: &'a str,
     miss: &'a str,
+    branch: &'a str,
+    brpart: &'a str,
     cover: &'a str,
     missing: &'a str,
 }
@@ -79,16 +324,18 @@ struct FileRow {
     name: String,
     stmts: u32,
     miss: u32,
+    branches: BranchStats,
     missing: String,
 }
 
 fn print_report(
     cwd: &Utf8Path,
     combined: &BTreeMap<String, CombinedFile>,
+    branch: bool,
     show_missing: bool,
     out: &mut dyn Write,
 ) -> Result<f64> {
-    let cwd_real = std::fs::canonicalize(cwd.as_std_path()).unwrap_or_else(|_| cwd.into());
+    let cwd_real = canonical_cwd(cwd);
 
     let rows: Vec<FileRow> = combined
         .iter()
@@ -96,13 +343,18 @@ fn print_report(
             let stmts = u32::try_from(data.executable.len()).unwrap_or(u32::MAX);
             let hit = u32::try_from(data.executed.len()).unwrap_or(u32::MAX);
             let miss = stmts.saturating_sub(hit);
+            let branches = if branch {
+                data.branch_stats()
+            } else {
+                BranchStats::default()
+            };
             let missing = if show_missing {
                 let uncovered: BTreeSet<u32> = data
                     .executable
                     .difference(&data.executed)
                     .copied()
                     .collect();
-                collapse_ranges(&uncovered)
+                collapse_ranges(&uncovered, &branches.partial_arcs)
             } else {
                 String::new()
             };
@@ -110,6 +362,7 @@ fn print_report(
                 name: display_path(filename, &cwd_real),
                 stmts,
                 miss,
+                branches,
                 missing,
             }
         })
@@ -126,10 +379,13 @@ fn print_report(
     let header = format_row(
         name_width,
         show_missing,
+        branch,
         &Row {
             name: "Name",
             stmts: "Stmts",
             miss: "Miss",
+            branch: "Branch",
+            brpart: "BrPart",
             cover: "Cover",
             missing: "Missing",
         },
@@ -143,21 +399,32 @@ fn print_report(
 
     let mut total_stmts: u32 = 0;
     let mut total_miss: u32 = 0;
+    let mut total_branches: u32 = 0;
+    let mut total_missing_branches: u32 = 0;
+    let mut total_partial: u32 = 0;
 
     for row in &rows {
-        let cover = format_percent(row.stmts, row.miss);
+        let cover = format_percent(
+            row.stmts.saturating_add(row.branches.branches),
+            row.miss.saturating_add(row.branches.missing),
+        );
         let stmts_str = row.stmts.to_string();
         let miss_str = row.miss.to_string();
+        let branch_str = row.branches.branches.to_string();
+        let brpart_str = row.branches.partial.to_string();
         writeln!(
             out,
             "{}",
             format_row(
                 name_width,
                 show_missing,
+                branch,
                 &Row {
                     name: &row.name,
                     stmts: &stmts_str,
                     miss: &miss_str,
+                    branch: &branch_str,
+                    brpart: &brpart_str,
                     cover: &cover,
                     missing: &row.missing,
                 },
@@ -165,23 +432,33 @@ fn print_report(
         )?;
         total_stmts = total_stmts.saturating_add(row.stmts);
         total_miss = total_miss.saturating_add(row.miss);
+        total_branches = total_branches.saturating_add(row.branches.branches);
+        total_missing_branches = total_missing_branches.saturating_add(row.branches.missing);
+        total_partial = total_partial.saturating_add(row.branches.partial);
     }
 
     writeln!(out, "{rule}")?;
-    let total_pct = percent(total_stmts, total_miss);
-    let total_cover = format_percent(total_stmts, total_miss);
+    let total = total_stmts.saturating_add(total_branches);
+    let total_missed = total_miss.saturating_add(total_missing_branches);
+    let total_pct = percent(total, total_missed);
+    let total_cover = format_percent(total, total_missed);
     let total_stmts_str = total_stmts.to_string();
     let total_miss_str = total_miss.to_string();
+    let total_branch_str = total_branches.to_string();
+    let total_brpart_str = total_partial.to_string();
     writeln!(
         out,
         "{}",
         format_row(
             name_width,
             show_missing,
+            branch,
             &Row {
                 name: "TOTAL",
                 stmts: &total_stmts_str,
                 miss: &total_miss_str,
+                branch: &total_branch_str,
+                brpart: &total_brpart_str,
                 cover: &total_cover,
                 missing: "",
             },
@@ -191,40 +468,186 @@ fn print_report(
     Ok(total_pct)
 }
 
-fn format_row(name_width: usize, show_missing: bool, row: &Row<'_>) -> String {
-    let base = format!(
-        "{name:<name_width$}   {stmts:>stmts_w$}   {miss:>miss_w$}   {cover:>cover_w$}",
+/// Print the diff coverage table: per measured file, the executable lines
+/// `diff` changed and the ones among them that never ran. Branches are not
+/// counted. Returns the diff coverage, or `None` if no executable line
+/// changed.
+fn print_diff_report(
+    cwd: &Utf8Path,
+    combined: &BTreeMap<String, CombinedFile>,
+    diff: &DiffLines,
+    out: &mut dyn Write,
+) -> Result<Option<f64>> {
+    let cwd_real = canonical_cwd(cwd);
+
+    let rows: Vec<(String, u32, BTreeSet<u32>)> = combined
+        .iter()
+        .filter_map(|(filename, data)| {
+            let changed: BTreeSet<u32> = diff
+                .changed_lines(Path::new(filename))?
+                .intersection(&data.executable)
+                .copied()
+                .collect();
+            if changed.is_empty() {
+                return None;
+            }
+            let uncovered = changed.difference(&data.executed).copied().collect();
+            let lines = u32::try_from(changed.len()).unwrap_or(u32::MAX);
+            Some((display_path(filename, &cwd_real), lines, uncovered))
+        })
+        .collect();
+
+    writeln!(out)?;
+    if rows.is_empty() {
+        writeln!(out, "Diff coverage: no changed lines to measure")?;
+        return Ok(None);
+    }
+
+    let name_width = rows
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len())
+        .max("TOTAL".len());
+
+    let header = format_row(
+        name_width,
+        true,
+        false,
+        &Row {
+            name: "Name",
+            stmts: "Stmts",
+            miss: "Miss",
+            branch: "",
+            brpart: "",
+            cover: "Cover",
+            missing: "Missing",
+        },
+    );
+    let rule = "-".repeat(header.chars().count());
+
+    writeln!(out, "{}", "Diff coverage".bold())?;
+    writeln!(out, "{}", header.bold())?;
+    writeln!(out, "{rule}")?;
+
+    let mut total_lines: u32 = 0;
+    let mut total_miss: u32 = 0;
+    for (name, lines, uncovered) in &rows {
+        let miss = u32::try_from(uncovered.len()).unwrap_or(u32::MAX);
+        writeln!(
+            out,
+            "{}",
+            format_row(
+                name_width,
+                true,
+                false,
+                &Row {
+                    name,
+                    stmts: &lines.to_string(),
+                    miss: &miss.to_string(),
+                    branch: "",
+                    brpart: "",
+                    cover: &format_percent(*lines, miss),
+                    missing: &collapse_ranges(uncovered, &[]),
+                },
+            )
+        )?;
+        total_lines = total_lines.saturating_add(*lines);
+        total_miss = total_miss.saturating_add(miss);
+    }
+
+    writeln!(out, "{rule}")?;
+    writeln!(
+        out,
+        "{}",
+        format_row(
+            name_width,
+            true,
+            false,
+            &Row {
+                name: "TOTAL",
+                stmts: &total_lines.to_string(),
+                miss: &total_miss.to_string(),
+                branch: "",
+                brpart: "",
+                cover: &format_percent(total_lines, total_miss),
+                missing: "",
+            },
+        )
+    )?;
+
+    Ok(Some(percent(total_lines, total_miss)))
+}
+
+fn format_row(name_width: usize, show_missing: bool, branch: bool, row: &Row<'_>) -> String {
+    let mut line = format!(
+        "{name:<name_width$}   {stmts:>stmts_w$}   {miss:>miss_w$}",
         name = row.name,
         stmts = row.stmts,
         miss = row.miss,
-        cover = row.cover,
         stmts_w = "Stmts".len(),
         miss_w = "Miss".len(),
+    );
+    if branch {
+        let _ = write!(
+            line,
+            "   {branch:>branch_w$}   {brpart:>brpart_w$}",
+            branch = row.branch,
+            brpart = row.brpart,
+            branch_w = "Branch".len(),
+            brpart_w = "BrPart".len(),
+        );
+    }
+    let _ = write!(
+        line,
+        "   {cover:>cover_w$}",
+        cover = row.cover,
         cover_w = "Cover".len(),
     );
     if show_missing && !row.missing.is_empty() {
-        format!("{base}   {missing}", missing = row.missing)
-    } else {
-        base
+        let _ = write!(line, "   {missing}", missing = row.missing);
     }
+    line
 }
 
-fn collapse_ranges(lines: &BTreeSet<u32>) -> String {
-    let mut parts: Vec<String> = Vec::new();
+/// Render the `Missing` column: runs of uncovered lines collapsed into
+/// `a-b` ranges, and the partial branch arcs as `a->b`, ordered by line.
+fn collapse_ranges(lines: &BTreeSet<u32>, partial_arcs: &[(i32, i32)]) -> String {
+    let mut parts: Vec<(i64, String)> = Vec::new();
     let mut iter = lines.iter().copied();
-    let Some(mut start) = iter.next() else {
-        return String::new();
-    };
-    let mut end = start;
-    for line in iter {
-        if line != end + 1 {
-            parts.push(format_range(start, end));
-            start = line;
+    if let Some(mut start) = iter.next() {
+        let mut end = start;
+        for line in iter {
+            if line != end + 1 {
+                parts.push((i64::from(start), format_range(start, end)));
+                start = line;
+            }
+            end = line;
         }
-        end = line;
+        parts.push((i64::from(start), format_range(start, end)));
+    }
+
+    parts.extend(
+        partial_arcs
+            .iter()
+            .map(|&(from, to)| (i64::from(from), format_arc(from, to))),
+    );
+    parts.sort_by_key(|(line, _)| *line);
+
+    parts
+        .into_iter()
+        .map(|(_, part)| part)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_arc(from: i32, to: i32) -> String {
+    if to < 0 {
+        format!("{from}->exit")
+    } else {
+        format!("{from}->{to}")
     }
-    parts.push(format_range(start, end));
-    parts.join(", ")
 }
 
 fn format_range(start: u32, end: u32) -> String {
@@ -235,7 +658,7 @@ fn format_range(start: u32, end: u32) -> String {
     }
 }
 
-fn percent(total: u32, miss: u32) -> f64 {
+pub(crate) fn percent(total: u32, miss: u32) -> f64 {
     if total == 0 {
         return 100.0;
     }
@@ -243,13 +666,20 @@ fn percent(total: u32, miss: u32) -> f64 {
     f64::from(hit) / f64::from(total) * 100.0
 }
 
-fn format_percent(total: u32, miss: u32) -> String {
+pub(crate) fn format_percent(total: u32, miss: u32) -> String {
     let pct = percent(total, miss);
     format!("{pct:.0}%")
 }
 
-fn display_path(absolute: &str, cwd: &std::path::Path) -> String {
-    if let Ok(rel) = std::path::Path::new(absolute).strip_prefix(cwd) {
+/// The working directory with symlinks resolved, to match the canonical
+/// paths the tracer records.
+pub(crate) fn canonical_cwd(cwd: &Utf8Path) -> PathBuf {
+    std::fs::canonicalize(cwd.as_std_path()).unwrap_or_else(|_| cwd.into())
+}
+
+/// `absolute` relative to `cwd`, or unchanged if it lies outside it.
+pub(crate) fn display_path(absolute: &str, cwd: &Path) -> String {
+    if let Ok(rel) = Path::new(absolute).strip_prefix(cwd) {
         rel.to_string_lossy().into_owned()
     } else {
         absolute.to_string()
@@ -264,6 +694,20 @@ mod tests {
         CombinedFile {
             executable: executable.iter().copied().collect(),
             executed: executed.iter().copied().collect(),
+            ..CombinedFile::default()
+        }
+    }
+
+    fn cf_branch(
+        executable: &[u32],
+        executed: &[u32],
+        arcs: &[(i32, i32)],
+        executed_arcs: &[(i32, i32)],
+    ) -> CombinedFile {
+        CombinedFile {
+            arcs: arcs.iter().copied().collect(),
+            executed_arcs: executed_arcs.iter().copied().collect(),
+            ..cf(executable, executed)
         }
     }
 
@@ -289,7 +733,7 @@ mod tests {
         data.insert("/proj/b.py".to_string(), cf(&[1, 2], &[1, 2]));
 
         let mut buf: Vec<u8> = Vec::new();
-        let total = print_report(Utf8Path::new("/proj"), &data, false, &mut buf).unwrap();
+        let total = print_report(Utf8Path::new("/proj"), &data, false, false, &mut buf).unwrap();
         let out = String::from_utf8(buf).unwrap();
 
         assert!(out.contains("a.py"));
@@ -311,34 +755,116 @@ mod tests {
         );
 
         let mut buf: Vec<u8> = Vec::new();
-        print_report(Utf8Path::new("/proj"), &data, true, &mut buf).unwrap();
+        print_report(Utf8Path::new("/proj"), &data, false, true, &mut buf).unwrap();
         let out = String::from_utf8(buf).unwrap();
 
         assert!(out.contains("Missing"));
         assert!(out.contains("2-4, 6-8"));
     }
 
+    #[test]
+    fn report_with_branches_counts_partial_branches() {
+        // if x:        (1)
+        //     a = 1    (2)
+        // b = 2        (3)
+        // Only the `x` true path ran: line 1 never jumped to line 3.
+        let mut data = BTreeMap::new();
+        data.insert(
+            "/proj/a.py".to_string(),
+            cf_branch(&[1, 2, 3], &[1, 2, 3], &[(1, 2), (1, 3)], &[(1, 2)]),
+        );
+
+        let mut buf: Vec<u8> = Vec::new();
+        let total = print_report(Utf8Path::new("/proj"), &data, true, true, &mut buf).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+
+        assert!(out.contains("Branch"));
+        assert!(out.contains("BrPart"));
+        assert!(out.contains("1->3"));
+        // 3 statements and 1 of 2 branches hit: 4 / 5.
+        assert!((total - 80.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn diff_report_counts_changed_executable_lines() {
+        let mut data = BTreeMap::new();
+        data.insert("/proj/a.py".to_string(), cf(&[1, 2, 3, 5], &[1, 2]));
+        data.insert("/proj/b.py".to_string(), cf(&[1, 2], &[]));
+        let diff = DiffLines::parse(
+            "+++ b/a.py\n@@ -0,0 +2,3 @@\n+x\n+y\n+z\n",
+            Path::new("/proj"),
+        );
+
+        let mut buf: Vec<u8> = Vec::new();
+        let pct = print_diff_report(Utf8Path::new("/proj"), &data, &diff, &mut buf).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+
+        // Lines 2 and 3 are executable; line 4 is not.
+        assert!(out.contains("Diff coverage"));
+        assert!(out.contains("a.py"));
+        assert!(!out.contains("b.py"));
+        assert!(out.contains("50%   3"));
+        assert!(pct.is_some_and(|pct| (pct - 50.0).abs() < f64::EPSILON));
+    }
+
+    #[test]
+    fn diff_report_without_changed_lines() {
+        let mut data = BTreeMap::new();
+        data.insert("/proj/a.py".to_string(), cf(&[1], &[1]));
+
+        let mut buf: Vec<u8> = Vec::new();
+        let pct = print_diff_report(
+            Utf8Path::new("/proj"),
+            &data,
+            &DiffLines::default(),
+            &mut buf,
+        )
+        .unwrap();
+
+        assert!(String::from_utf8(buf).unwrap().contains("no changed lines"));
+        assert!(pct.is_none());
+    }
+
+    #[test]
+    fn unpredicted_destination_counts_as_leaving_the_block() {
+        let possible: BTreeSet<i32> = [3, -1].into_iter().collect();
+        let measured: BTreeSet<i32> = [3, 4].into_iter().collect();
+        let taken: Vec<i32> = taken_destinations(2, &possible, &measured)
+            .into_iter()
+            .collect();
+        assert_eq!(taken, vec![-1, 3]);
+    }
+
     #[test]
     fn collapse_empty() {
         let set: BTreeSet<u32> = BTreeSet::new();
-        assert_eq!(collapse_ranges(&set), "");
+        assert_eq!(collapse_ranges(&set, &[]), "");
     }
 
     #[test]
     fn collapse_singletons() {
         let set: BTreeSet<u32> = [3, 7, 12].into_iter().collect();
-        assert_eq!(collapse_ranges(&set), "3, 7, 12");
+        assert_eq!(collapse_ranges(&set, &[]), "3, 7, 12");
     }
 
     #[test]
     fn collapse_mixed_ranges() {
         let set: BTreeSet<u32> = [26, 87, 94, 95, 119, 120, 121, 157].into_iter().collect();
-        assert_eq!(collapse_ranges(&set), "26, 87, 94-95, 119-121, 157");
+        assert_eq!(collapse_ranges(&set, &[]), "26, 87, 94-95, 119-121, 157");
     }
 
     #[test]
     fn collapse_single_contiguous_range() {
         let set: BTreeSet<u32> = [10, 11, 12, 13].into_iter().collect();
-        assert_eq!(collapse_ranges(&set), "10-13");
+        assert_eq!(collapse_ranges(&set, &[]), "10-13");
+    }
+
+    #[test]
+    fn collapse_interleaves_partial_arcs() {
+        let set: BTreeSet<u32> = [4, 5, 9].into_iter().collect();
+        assert_eq!(
+            collapse_ranges(&set, &[(2, 4), (7, -1)]),
+            "2->4, 4-5, 7->exit, 9"
+        );
     }
 }
@@ -4,19 +4,39 @@
 //! configured source roots, then on stop computes executable lines for each
 //! touched file and writes a per-worker JSON file at
 //! [`CoverageConfig::data_file`].
+//!
+//! With [`CoverageConfig::branch`] the tracer also records arcs, the
+//! line-to-line transitions, which the report compares against the arcs
+//! [`branch_arcs`] derives from the source.
+//!
+//! The `include` and `omit` globs narrow the files measured, and the
+//! `exclude-lines` and `partial-branches` regexes leave lines and branches
+//! out of the measurement.
+//!
+//! With `sys.settrace` the tracer is also handed to `threading`, so threads
+//! started during the run are traced too; `sys.monitoring` covers every
+//! thread on its own.
+//!
+//! With [`CoverageConfig::context`] each executed line is also tagged with
+//! the test that ran it, which the runner announces through
+//! [`CoverageSession::set_context`].
 
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Mutex, OnceLock, PoisonError};
 
 use camino::{Utf8Path, Utf8PathBuf};
+use karva_static::EnvVars;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::data::{FileEntry, WorkerFile};
-use crate::executable::executable_lines;
+use crate::executable::{Exclusions, branch_arcs, excluded_lines, executable_lines};
+use crate::filter::SourceFilter;
 
 /// Configuration for a single worker's coverage measurement.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoverageConfig {
     /// Source paths to measure. An empty entry means "measure the current
     /// working directory" (matches pytest-cov's bare `--cov`).
@@ -24,11 +44,40 @@ pub struct CoverageConfig {
 
     /// Per-worker data file path. The runner combines these after the run.
     pub data_file: Utf8PathBuf,
+
+    /// Whether to also measure branch coverage.
+    pub branch: bool,
+
+    /// Whether to record which tests run each line.
+    pub context: bool,
+
+    /// Globs of the files to measure. Empty measures every file under the
+    /// source roots.
+    pub include: Vec<String>,
+
+    /// Globs of the files not to measure.
+    pub omit: Vec<String>,
+
+    /// Regexes of the lines to exclude, along with the block they start.
+    pub exclude_lines: Vec<String>,
+
+    /// Regexes of the branch lines whose arcs are not measured.
+    pub partial_branches: Vec<String>,
 }
 
+/// The name the tracer registers its `sys.monitoring` tool id under.
+pub(crate) const MONITORING_TOOL: &str = "karva";
+
 /// Path components inside a source root that suppress tracking. These match
-/// the conventional locations of installed third-party code.
-const PATH_EXCLUDES: &[&str] = &["site-packages", "dist-packages", ".venv", ".tox"];
+/// the conventional locations of installed third-party code, and karva's own
+/// cache, which holds the subprocess hook.
+const PATH_EXCLUDES: &[&str] = &[
+    "site-packages",
+    "dist-packages",
+    ".venv",
+    ".tox",
+    ".karva_cache",
+];
 
 /// A live coverage measurement. Drop without calling [`Self::stop_and_save`]
 /// to abandon a partial run; the data file is only persisted via
@@ -52,18 +101,26 @@ impl CoverageSession {
                 std::fs::canonicalize(raw).unwrap_or_else(|_| PathBuf::from(raw))
             })
             .collect();
+        let filter = SourceFilter::new(cwd, &config.include, &config.omit)
+            .map_err(|err| PyValueError::new_err(format!("{err:#}")))?;
+        let exclusions = Exclusions::new(&config.exclude_lines, &config.partial_branches)
+            .map_err(|err| PyValueError::new_err(format!("{err:#}")))?;
 
         let tracer = Py::new(
             py,
             CoverageTracer {
                 roots,
+                filter,
+                exclusions,
+                branch: config.branch,
+                context: config.context,
                 state: Mutex::new(TracerState::default()),
                 monitoring_tool_id: OnceLock::new(),
                 monitoring_disable: OnceLock::new(),
             },
         )?;
 
-        if py_version_at_least(py, 3, 12)? {
+        if use_monitoring(py)? {
             install_monitoring(py, &tracer)?;
         } else {
             install_settrace(py, &tracer)?;
@@ -75,29 +132,79 @@ impl CoverageSession {
         })
     }
 
+    /// Make `name` the context of the lines run from now on, or stop
+    /// recording a context with `None`. Has no effect unless
+    /// [`CoverageConfig::context`] is set.
+    ///
+    /// With `sys.monitoring` the line events disabled during earlier tests
+    /// are restarted, so lines already seen are recorded again for the new
+    /// context.
+    pub fn set_context(&self, py: Python<'_>, name: Option<&str>) -> PyResult<()> {
+        let tracer = self.tracer.bind(py).borrow();
+        if !tracer.context {
+            return Ok(());
+        }
+
+        let mut state = tracer.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let next = u32::try_from(state.context_ids.len()).unwrap_or(u32::MAX);
+        let context = name.map(|name| *state.context_ids.entry(name.to_string()).or_insert(next));
+        state.current_context = context;
+        drop(state);
+
+        if name.is_some() && tracer.monitoring_tool_id.get().is_some() {
+            py.import("sys")?
+                .getattr("monitoring")?
+                .call_method0("restart_events")?;
+        }
+        Ok(())
+    }
+
     pub fn stop_and_save(self, py: Python<'_>) -> PyResult<()> {
         let Self { tracer, data_file } = self;
         let bound = tracer.bind(py);
         let tool_id = bound.borrow().monitoring_tool_id.get().copied();
+        let branch = bound.borrow().branch;
 
         if let Some(tool_id) = tool_id {
             let mon = py.import("sys")?.getattr("monitoring")?;
-            let line_event = mon.getattr("events")?.getattr("LINE")?;
+            let events = mon.getattr("events")?;
             mon.call_method1("set_events", (tool_id, 0u32))?;
-            mon.call_method1("register_callback", (tool_id, line_event, py.None()))?;
+            for (event, _) in monitoring_events(&events, branch)? {
+                mon.call_method1("register_callback", (tool_id, event, py.None()))?;
+            }
             mon.call_method1("free_tool_id", (tool_id,))?;
         } else {
             py.import("sys")?.call_method1("settrace", (py.None(),))?;
+            py.import("threading")?
+                .call_method1("settrace", (py.None(),))?;
         }
 
         let borrowed = bound.borrow();
-        let executed = match borrowed.state.lock() {
-            Ok(mut state) => std::mem::take(&mut state.executed),
-            Err(poisoned) => std::mem::take(&mut poisoned.into_inner().executed),
+        let mut state = borrowed
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let executed = std::mem::take(&mut state.executed);
+        let arcs = std::mem::take(&mut state.arcs);
+        let contexts = Contexts {
+            ids: std::mem::take(&mut state.context_ids),
+            lines: std::mem::take(&mut state.line_contexts),
+        };
+        drop(state);
+        let sources = Sources {
+            roots: borrowed.roots.clone(),
+            filter: borrowed.filter.clone(),
+            exclusions: borrowed.exclusions.clone(),
         };
-        let roots = borrowed.roots.clone();
         drop(borrowed);
-        save_data(&data_file, executed, &roots).map_err(|err| {
+        save_data(
+            &data_file,
+            executed,
+            branch.then_some(arcs),
+            contexts,
+            &sources,
+        )
+        .map_err(|err| {
             pyo3::exceptions::PyOSError::new_err(format!(
                 "failed to write coverage data to {data_file}: {err}"
             ))
@@ -110,10 +217,43 @@ impl CoverageSession {
 struct TracerState {
     /// Files with the set of executed line numbers.
     executed: HashMap<PathBuf, HashSet<u32>>,
+    /// Files with the set of arcs taken. Only recorded for branch coverage.
+    arcs: HashMap<PathBuf, HashSet<(i32, i32)>>,
+    /// Ids of the contexts seen so far, by name.
+    context_ids: HashMap<String, u32>,
+    /// Id of the context lines are recorded for, if any.
+    current_context: Option<u32>,
+    /// Files with the ids of the contexts that ran each line.
+    line_contexts: HashMap<PathBuf, HashMap<u32, HashSet<u32>>>,
+    /// The last line run in each traced frame, keyed by the frame's
+    /// address. Only used by the `sys.settrace` backend.
+    last_lines: HashMap<usize, i32>,
+    /// Line table of each code object a jump was seen in, keyed by the code
+    /// object's address. Only used by the `sys.monitoring` backend.
+    code_lines: HashMap<usize, CodeLines>,
     /// Memoized result of [`compute_tracked_path`] per filename string.
     track_cache: HashMap<String, Option<PathBuf>>,
 }
 
+/// The line table of a code object, from `co_lines()`.
+struct CodeLines {
+    /// Held so the code object outlives the cache entry, and its address
+    /// is never reused for another code object.
+    _code: Py<PyAny>,
+    /// `(start, end, line)` bytecode offset ranges.
+    ranges: Vec<(u32, u32, Option<u32>)>,
+}
+
+impl CodeLines {
+    fn line_at(&self, offset: u32) -> Option<i32> {
+        self.ranges
+            .iter()
+            .find(|(start, end, _)| (*start..*end).contains(&offset))
+            .and_then(|(_, _, line)| *line)
+            .and_then(|line| i32::try_from(line).ok())
+    }
+}
+
 /// Thread-safe because the trace callbacks fire on whichever Python thread
 /// happens to be executing tracked code: `sys.monitoring` LINE events are
 /// global to the registered tool id, and `sys.settrace` propagates to threads
@@ -123,6 +263,10 @@ struct TracerState {
 #[pyclass(module = "karva_coverage")]
 struct CoverageTracer {
     roots: Vec<PathBuf>,
+    filter: SourceFilter,
+    exclusions: Exclusions,
+    branch: bool,
+    context: bool,
     state: Mutex<TracerState>,
     monitoring_tool_id: OnceLock<u8>,
     /// Cached `sys.monitoring.DISABLE` sentinel. Populated when the
@@ -145,14 +289,47 @@ impl CoverageTracer {
         lineno: u32,
     ) -> PyResult<Option<Py<PyAny>>> {
         let filename: String = code.getattr("co_filename")?.extract()?;
-        if let Some(path) = self.tracked_path(&filename)
-            && let Ok(mut state) = self.state.lock()
-        {
-            state.executed.entry(path).or_default().insert(lineno);
+        if let Some(path) = self.tracked_path(&filename) {
+            self.record_line(path, None, lineno);
         }
         Ok(self.monitoring_disable.get().map(|d| d.clone_ref(py)))
     }
 
+    /// `sys.monitoring` BRANCH event callback, for Python 3.12 and 3.13.
+    /// Records the arc from the branch to its destination.
+    ///
+    /// BRANCH fires for both ways out of one instruction, so disabling it
+    /// after the first would lose the other; it stays enabled in tracked
+    /// files.
+    fn branch_cb(
+        &self,
+        py: Python<'_>,
+        code: &Bound<'_, PyAny>,
+        instruction_offset: u32,
+        destination_offset: u32,
+    ) -> PyResult<Option<Py<PyAny>>> {
+        if self.record_jump(code, instruction_offset, destination_offset)? {
+            Ok(None)
+        } else {
+            Ok(self.monitoring_disable.get().map(|d| d.clone_ref(py)))
+        }
+    }
+
+    /// `sys.monitoring` JUMP, BRANCH_LEFT and BRANCH_RIGHT event callback.
+    /// Records the arc from the jump to its destination. Each of these
+    /// events has a single destination per instruction, so it is disabled
+    /// once seen.
+    fn jump_cb(
+        &self,
+        py: Python<'_>,
+        code: &Bound<'_, PyAny>,
+        instruction_offset: u32,
+        destination_offset: u32,
+    ) -> PyResult<Option<Py<PyAny>>> {
+        self.record_jump(code, instruction_offset, destination_offset)?;
+        Ok(self.monitoring_disable.get().map(|d| d.clone_ref(py)))
+    }
+
     /// `sys.settrace` global trace function. Returns the per-frame
     /// [`Self::local_trace`] when the frame's file is under a source root.
     #[expect(
@@ -166,16 +343,28 @@ impl CoverageTracer {
         _arg: &Bound<'py, PyAny>,
     ) -> PyResult<Option<Py<PyAny>>> {
         if event == "call" {
-            let filename: String = frame.getattr("f_code")?.getattr("co_filename")?.extract()?;
-            if slf.borrow().tracked_path(&filename).is_some() {
+            let code = frame.getattr("f_code")?;
+            let filename: String = code.getattr("co_filename")?.extract()?;
+            let tracer = slf.borrow();
+            if tracer.tracked_path(&filename).is_some() {
+                // Entering the frame is an arc from the code object's entry,
+                // written like an exit as the negated first line.
+                if tracer.branch {
+                    let first_line: i32 = code.getattr("co_firstlineno")?.extract()?;
+                    if let Ok(mut state) = tracer.state.lock() {
+                        state.last_lines.insert(frame_key(frame), -first_line);
+                    }
+                }
+                drop(tracer);
                 return Ok(Some(slf.getattr("local_trace")?.unbind()));
             }
         }
         Ok(None)
     }
 
-    /// `sys.settrace` per-frame trace function. Records `line` events and
-    /// returns itself so Python keeps tracing the frame.
+    /// `sys.settrace` per-frame trace function. Records `line` events, and
+    /// `return` events for branch coverage, and returns itself so Python
+    /// keeps tracing the frame.
     #[expect(
         clippy::needless_pass_by_value,
         reason = "PyO3 requires Bound<Self> by value as a self receiver"
@@ -186,13 +375,17 @@ impl CoverageTracer {
         event: &str,
         _arg: &Bound<'py, PyAny>,
     ) -> PyResult<Py<PyAny>> {
-        if event == "line" {
-            let filename: String = frame.getattr("f_code")?.getattr("co_filename")?.extract()?;
-            let path = slf.borrow().tracked_path(&filename);
-            if let Some(path) = path {
-                let lineno: u32 = frame.getattr("f_lineno")?.extract()?;
-                if let Ok(mut state) = slf.borrow().state.lock() {
-                    state.executed.entry(path).or_default().insert(lineno);
+        if event == "line" || event == "return" {
+            let code = frame.getattr("f_code")?;
+            let filename: String = code.getattr("co_filename")?.extract()?;
+            let tracer = slf.borrow();
+            if let Some(path) = tracer.tracked_path(&filename) {
+                if event == "line" {
+                    let lineno: u32 = frame.getattr("f_lineno")?.extract()?;
+                    tracer.record_line(path, Some(frame_key(frame)), lineno);
+                } else if tracer.branch {
+                    let first_line: i32 = code.getattr("co_firstlineno")?.extract()?;
+                    tracer.record_return(path, frame_key(frame), -first_line);
                 }
             }
         }
@@ -201,6 +394,98 @@ impl CoverageTracer {
 }
 
 impl CoverageTracer {
+    /// Record that `lineno` ran, in the current context if there is one.
+    /// For branch coverage, a line run in a frame traced with
+    /// `sys.settrace` also records the arc from the frame's previous line.
+    fn record_line(&self, path: PathBuf, frame: Option<usize>, lineno: u32) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if self.branch
+            && let Some(frame) = frame
+        {
+            let line = i32::try_from(lineno).unwrap_or(i32::MAX);
+            if let Some(previous) = state.last_lines.insert(frame, line) {
+                state
+                    .arcs
+                    .entry(path.clone())
+                    .or_default()
+                    .insert((previous, line));
+            }
+        }
+        if let Some(context) = state.current_context {
+            state
+                .line_contexts
+                .entry(path.clone())
+                .or_default()
+                .entry(lineno)
+                .or_default()
+                .insert(context);
+        }
+        state.executed.entry(path).or_default().insert(lineno);
+    }
+
+    /// Record the arc out of a frame traced with `sys.settrace` that is
+    /// returning, to the negated first line of its code object.
+    fn record_return(&self, path: PathBuf, frame: usize, exit: i32) {
+        if let Ok(mut state) = self.state.lock()
+            && let Some(previous) = state.last_lines.remove(&frame)
+        {
+            state.arcs.entry(path).or_default().insert((previous, exit));
+        }
+    }
+
+    /// Record the arc of a jump seen by `sys.monitoring`, between the lines
+    /// of the two bytecode offsets. Returns whether the code object is in a
+    /// tracked file.
+    fn record_jump(
+        &self,
+        code: &Bound<'_, PyAny>,
+        instruction_offset: u32,
+        destination_offset: u32,
+    ) -> PyResult<bool> {
+        let filename: String = code.getattr("co_filename")?.extract()?;
+        let Some(path) = self.tracked_path(&filename) else {
+            return Ok(false);
+        };
+
+        let key = code.as_ptr() as usize;
+        let cached = self
+            .state
+            .lock()
+            .is_ok_and(|state| state.code_lines.contains_key(&key));
+        // Read the line table before taking the lock for the insert, so no
+        // Python code runs while the state is locked.
+        let new_lines = if cached {
+            None
+        } else {
+            let mut ranges = Vec::new();
+            for entry in code.call_method0("co_lines")?.try_iter()? {
+                ranges.push(entry?.extract::<(u32, u32, Option<u32>)>()?);
+            }
+            Some(CodeLines {
+                _code: code.clone().unbind(),
+                ranges,
+            })
+        };
+
+        if let Ok(mut state) = self.state.lock() {
+            if let Some(new_lines) = new_lines {
+                state.code_lines.entry(key).or_insert(new_lines);
+            }
+            let arc = state.code_lines.get(&key).and_then(|lines| {
+                Some((
+                    lines.line_at(instruction_offset)?,
+                    lines.line_at(destination_offset)?,
+                ))
+            });
+            if let Some(arc) = arc {
+                state.arcs.entry(path).or_default().insert(arc);
+            }
+        }
+        Ok(true)
+    }
+
     /// Resolve `filename` against the source roots. Returns the canonical
     /// path if the file should be tracked, or `None` otherwise. Memoized
     /// per filename string.
@@ -210,7 +495,7 @@ impl CoverageTracer {
         {
             return cached.clone();
         }
-        let resolved = compute_tracked_path(filename, &self.roots);
+        let resolved = compute_tracked_path(filename, &self.roots, &self.filter);
         if let Ok(mut state) = self.state.lock() {
             state
                 .track_cache
@@ -220,7 +505,16 @@ impl CoverageTracer {
     }
 }
 
-fn compute_tracked_path(filename: &str, roots: &[PathBuf]) -> Option<PathBuf> {
+/// Identifies a frame while it runs.
+fn frame_key(frame: &Bound<'_, PyAny>) -> usize {
+    frame.as_ptr() as usize
+}
+
+fn compute_tracked_path(
+    filename: &str,
+    roots: &[PathBuf],
+    filter: &SourceFilter,
+) -> Option<PathBuf> {
     if filename.is_empty() || filename.starts_with('<') {
         return None;
     }
@@ -231,12 +525,19 @@ fn compute_tracked_path(filename: &str, roots: &[PathBuf]) -> Option<PathBuf> {
     {
         return None;
     }
-    for root in roots {
-        if canonical == *root || canonical.starts_with(root) {
-            return Some(canonical);
-        }
+    let under_root = roots
+        .iter()
+        .any(|root| canonical == *root || canonical.starts_with(root));
+    (under_root && filter.is_measured(&canonical)).then_some(canonical)
+}
+
+/// Whether to trace with `sys.monitoring`: on Python 3.12 and later, unless
+/// `KARVA_COVERAGE_TRACER` asks for `sys.settrace`.
+fn use_monitoring(py: Python<'_>) -> PyResult<bool> {
+    if std::env::var(EnvVars::KARVA_COVERAGE_TRACER).is_ok_and(|tracer| tracer == "settrace") {
+        return Ok(false);
     }
-    None
+    py_version_at_least(py, 3, 12)
 }
 
 fn py_version_at_least(py: Python<'_>, major: u8, minor: u8) -> PyResult<bool> {
@@ -246,22 +547,55 @@ fn py_version_at_least(py: Python<'_>, major: u8, minor: u8) -> PyResult<bool> {
     Ok((actual_major, actual_minor) >= (major, minor))
 }
 
+/// The `sys.monitoring` events the tracer listens to, with the name of the
+/// tracer method that handles each.
+///
+/// Branch coverage adds the jump events. Python 3.14 splits BRANCH into
+/// BRANCH_LEFT and BRANCH_RIGHT, which can be disabled one way at a time.
+fn monitoring_events<'py>(
+    events: &Bound<'py, PyAny>,
+    branch: bool,
+) -> PyResult<Vec<(Bound<'py, PyAny>, &'static str)>> {
+    let mut monitored = vec![(events.getattr("LINE")?, "line_cb")];
+    if branch {
+        monitored.push((events.getattr("JUMP")?, "jump_cb"));
+        if let (Ok(left), Ok(right)) = (
+            events.getattr("BRANCH_LEFT"),
+            events.getattr("BRANCH_RIGHT"),
+        ) {
+            monitored.push((left, "jump_cb"));
+            monitored.push((right, "jump_cb"));
+        } else {
+            monitored.push((events.getattr("BRANCH")?, "branch_cb"));
+        }
+    }
+    Ok(monitored)
+}
+
 fn install_monitoring(py: Python<'_>, tracer: &Py<CoverageTracer>) -> PyResult<()> {
     let mon = py.import("sys")?.getattr("monitoring")?;
-    let line_event = mon.getattr("events")?.getattr("LINE")?;
+    let events = mon.getattr("events")?;
     let disable = mon.getattr("DISABLE")?.unbind();
 
     let tool_id = (0u8..6u8)
-        .find(|id| mon.call_method1("use_tool_id", (*id, "karva")).is_ok())
+        .find(|id| {
+            mon.call_method1("use_tool_id", (*id, MONITORING_TOOL))
+                .is_ok()
+        })
         .ok_or_else(|| {
             pyo3::exceptions::PyRuntimeError::new_err(
                 "no free sys.monitoring tool id available for coverage",
             )
         })?;
 
-    let callback = tracer.bind(py).getattr("line_cb")?;
-    mon.call_method1("register_callback", (tool_id, &line_event, callback))?;
-    mon.call_method1("set_events", (tool_id, line_event))?;
+    let branch = tracer.bind(py).borrow().branch;
+    let mut event_set = 0u32;
+    for (event, method) in monitoring_events(&events, branch)? {
+        let callback = tracer.bind(py).getattr(method)?;
+        mon.call_method1("register_callback", (tool_id, &event, callback))?;
+        event_set |= event.extract::<u32>()?;
+    }
+    mon.call_method1("set_events", (tool_id, event_set))?;
     {
         let bound = tracer.bind(py).borrow();
         let _ = bound.monitoring_tool_id.set(tool_id);
@@ -270,9 +604,12 @@ fn install_monitoring(py: Python<'_>, tracer: &Py<CoverageTracer>) -> PyResult<(
     Ok(())
 }
 
+/// Install the tracer with `sys.settrace`, which only covers the current
+/// thread, and with `threading.settrace` for the threads started later.
 fn install_settrace(py: Python<'_>, tracer: &Py<CoverageTracer>) -> PyResult<()> {
     let trace = tracer.bind(py).getattr("trace")?;
-    py.import("sys")?.call_method1("settrace", (trace,))?;
+    py.import("sys")?.call_method1("settrace", (&trace,))?;
+    py.import("threading")?.call_method1("settrace", (trace,))?;
     Ok(())
 }
 
@@ -329,30 +666,93 @@ fn is_python_source(path: &Path) -> bool {
     path.extension().and_then(|e| e.to_str()) == Some("py")
 }
 
+/// Where the measured files come from, and what to leave out of them.
+struct Sources {
+    roots: Vec<PathBuf>,
+    filter: SourceFilter,
+    exclusions: Exclusions,
+}
+
+/// The contexts recorded by a tracer.
+struct Contexts {
+    /// Context ids by name.
+    ids: HashMap<String, u32>,
+    /// Files with the ids of the contexts that ran each line.
+    lines: HashMap<PathBuf, HashMap<u32, HashSet<u32>>>,
+}
+
+/// Write the worker's data file. `arcs` holds the arcs taken per file
+/// when branch coverage is measured.
 fn save_data(
     data_file: &Utf8Path,
     mut executed: HashMap<PathBuf, HashSet<u32>>,
-    roots: &[PathBuf],
+    mut arcs: Option<HashMap<PathBuf, HashSet<(i32, i32)>>>,
+    mut contexts: Contexts,
+    sources: &Sources,
 ) -> std::io::Result<()> {
-    for path in walk_source_files(roots) {
-        executed.entry(path).or_default();
+    for path in walk_source_files(&sources.roots) {
+        if sources.filter.is_measured(&path) {
+            executed.entry(path).or_default();
+        }
     }
 
+    let mut context_names: Vec<(String, u32)> = contexts.ids.into_iter().collect();
+    context_names.sort_unstable_by_key(|&(_, id)| id);
+
+    let branch = arcs.is_some();
     let mut files = BTreeMap::new();
     for (path, hits) in executed {
-        let executable = executable_lines(&path);
+        let executable = executable_lines(&path, &sources.exclusions);
         if executable.is_empty() {
             continue;
         }
         let mut executed_lines: Vec<u32> = hits.intersection(&executable).copied().collect();
         executed_lines.sort_unstable();
+        let line_contexts: BTreeMap<u32, Vec<u32>> = contexts
+            .lines
+            .remove(&path)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(line, _)| executable.contains(line))
+            .map(|(line, ids)| {
+                let mut ids: Vec<u32> = ids.into_iter().collect();
+                ids.sort_unstable();
+                (line, ids)
+            })
+            .collect();
         let mut executable_lines_vec: Vec<u32> = executable.into_iter().collect();
         executable_lines_vec.sort_unstable();
+        let mut excluded: Vec<u32> = excluded_lines(&path, &sources.exclusions)
+            .into_iter()
+            .collect();
+        excluded.sort_unstable();
+
+        // Only arcs out of branch lines are reported, so the rest are dropped.
+        let (possible_arcs, executed_arcs) = match arcs.as_mut() {
+            Some(arcs) => {
+                let possible = branch_arcs(&path, &sources.exclusions);
+                let sources: HashSet<i32> = possible.iter().map(|&(from, _)| from).collect();
+                let mut taken: Vec<(i32, i32)> = arcs
+                    .remove(&path)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(from, _)| sources.contains(from))
+                    .collect();
+                taken.sort_unstable();
+                (possible.into_iter().collect(), taken)
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+
         files.insert(
             path.to_string_lossy().into_owned(),
             FileEntry {
                 executable: executable_lines_vec,
                 executed: executed_lines,
+                excluded,
+                arcs: possible_arcs,
+                executed_arcs,
+                contexts: line_contexts,
             },
         );
     }
@@ -362,6 +762,10 @@ fn save_data(
     {
         std::fs::create_dir_all(parent.as_std_path())?;
     }
-    let bytes = serde_json::to_vec(&WorkerFile { files })?;
+    let bytes = serde_json::to_vec(&WorkerFile {
+        branch,
+        contexts: context_names.into_iter().map(|(name, _)| name).collect(),
+        files,
+    })?;
     std::fs::write(data_file.as_std_path(), bytes)
 }
@@ -0,0 +1,338 @@
+//! Structured per-test events.
+//!
+//! Every event is a single JSON object on its own line, tagged with a
+//! kebab-case `type`. Workers stream the per-test events to the main process
+//! as tests execute, and the main process renders them, either as terminal
+//! result lines or, with `--message-format json`, by re-emitting them on
+//! stdout between its own `run-started` and `run-finished` events.
+
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use karva_python_semantic::QualifiedTestName;
+use serde::{Deserialize, Serialize};
+
+use crate::reporter::Reporter;
+use crate::result::{IndividualTestResultKind, TestCaseOutcome, TestResultStats};
+
+/// A single event in the JSON message stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum RunEvent {
+    /// Tests have been collected and workers are about to start.
+    RunStarted {
+        test_count: usize,
+        worker_count: usize,
+    },
+    /// A test variant is about to run. Not emitted for tests that are
+    /// skipped before they run.
+    TestStarted {
+        #[serde(flatten)]
+        test: EventTestName,
+        /// The hard timeout that applies to the test, in seconds.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timeout: Option<f64>,
+    },
+    /// One attempt of a retried test finished.
+    TestAttempt {
+        #[serde(flatten)]
+        test: EventTestName,
+        attempt: u32,
+        outcome: TestCaseOutcome,
+        /// Duration of this attempt, in seconds.
+        duration: f64,
+    },
+    /// A test variant finished with its final outcome.
+    TestFinished {
+        #[serde(flatten)]
+        test: EventTestName,
+        outcome: TestCaseOutcome,
+        /// Total duration in seconds, including every retry.
+        duration: f64,
+        /// The attempt that produced `outcome`, starting at 1.
+        attempt: u32,
+        /// The number of attempts the test was allowed, for retried tests.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_attempts: Option<u32>,
+        /// `true` when the test passed only after being retried.
+        flaky: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        skip_reason: Option<String>,
+    },
+    /// A test exceeded the configured `slow-timeout`.
+    TestSlow {
+        #[serde(flatten)]
+        test: EventTestName,
+        duration: f64,
+    },
+    /// Every worker has finished and results have been aggregated.
+    RunFinished {
+        /// Wall-clock duration of the whole run, in seconds.
+        duration: f64,
+        success: bool,
+        stats: EventStats,
+    },
+}
+
+impl RunEvent {
+    /// Write the event to stdout as a single line.
+    pub fn emit(&self) {
+        let _ = self.write_line(&mut std::io::stdout().lock());
+    }
+
+    /// Write the event to `writer` as a single newline-terminated line.
+    ///
+    /// The line is written with one call so that a reader never observes a
+    /// partial event.
+    pub fn write_line(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(self)?;
+        line.push('\n');
+        writer.write_all(line.as_bytes())?;
+        writer.flush()
+    }
+
+    /// Parse an event from a single line written by [`Self::write_line`].
+    pub fn from_line(line: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(line)
+    }
+}
+
+/// Identifies the test an event refers to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventTestName {
+    /// The full name as shown in terminal output, e.g. `tests.test_math::test_add(x=1)`.
+    pub name: String,
+    pub module: String,
+    pub function: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<String>,
+}
+
+impl From<&QualifiedTestName> for EventTestName {
+    fn from(test_name: &QualifiedTestName) -> Self {
+        let function_name = test_name.function_name();
+        Self {
+            name: test_name.to_string(),
+            module: function_name.module_path().module_name().to_string(),
+            function: function_name.local_name(),
+            params: test_name.params().map(str::to_string),
+        }
+    }
+}
+
+/// Summary counts reported in `run-finished`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventStats {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub flaky: usize,
+    pub slow: usize,
+}
+
+impl From<&TestResultStats> for EventStats {
+    fn from(stats: &TestResultStats) -> Self {
+        Self {
+            total: stats.total(),
+            passed: stats.passed(),
+            failed: stats.failed(),
+            skipped: stats.skipped(),
+            flaky: stats.flaky(),
+            slow: stats.slow(),
+        }
+    }
+}
+
+/// A reporter that writes per-test [`RunEvent`]s to a stream, one per line.
+///
+/// Workers use this to stream events to the main process. Write errors are
+/// ignored: if the main process has gone away there is nobody left to report
+/// to, and the worker is about to be killed anyway.
+pub struct EventReporter<W> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write> EventReporter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    fn send(&self, event: &RunEvent) {
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+        let _ = event.write_line(&mut *writer);
+    }
+}
+
+impl<W: Write + Send> Reporter for EventReporter<W> {
+    fn report_test_started(&self, test_name: &QualifiedTestName, timeout: Option<Duration>) {
+        self.send(&RunEvent::TestStarted {
+            test: test_name.into(),
+            timeout: timeout.map(|timeout| timeout.as_secs_f64()),
+        });
+    }
+
+    fn report_test_case_result(
+        &self,
+        test_name: &QualifiedTestName,
+        result_kind: IndividualTestResultKind,
+        duration: Duration,
+    ) {
+        let skip_reason = match &result_kind {
+            IndividualTestResultKind::Skipped { reason } => reason.clone(),
+            _ => None,
+        };
+        self.send(&RunEvent::TestFinished {
+            test: test_name.into(),
+            outcome: TestCaseOutcome::from(&result_kind),
+            duration: duration.as_secs_f64(),
+            attempt: 1,
+            max_attempts: None,
+            flaky: false,
+            skip_reason,
+        });
+    }
+
+    fn report_test_attempt(
+        &self,
+        test_name: &QualifiedTestName,
+        attempt: u32,
+        result_kind: IndividualTestResultKind,
+        duration: Duration,
+    ) {
+        self.send(&RunEvent::TestAttempt {
+            test: test_name.into(),
+            attempt,
+            outcome: TestCaseOutcome::from(&result_kind),
+            duration: duration.as_secs_f64(),
+        });
+    }
+
+    fn report_retried_result(
+        &self,
+        test_name: &QualifiedTestName,
+        result_kind: &IndividualTestResultKind,
+        duration: Duration,
+        attempt: u32,
+        max_attempts: u32,
+    ) {
+        let outcome = TestCaseOutcome::from(result_kind);
+        self.send(&RunEvent::TestFinished {
+            test: test_name.into(),
+            outcome,
+            duration: duration.as_secs_f64(),
+            attempt,
+            max_attempts: Some(max_attempts),
+            flaky: outcome == TestCaseOutcome::Passed,
+            skip_reason: None,
+        });
+    }
+
+    fn report_test_slow(&self, test_name: &QualifiedTestName, duration: Duration) {
+        self.send(&RunEvent::TestSlow {
+            test: test_name.into(),
+            duration: duration.as_secs_f64(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use insta::assert_snapshot;
+    use karva_python_semantic::{ModulePath, QualifiedFunctionName};
+
+    use super::*;
+    use crate::TestResultKind;
+
+    fn test_name(params: Option<&str>) -> QualifiedTestName {
+        let function = QualifiedFunctionName::new(
+            "test_add".to_string(),
+            ModulePath::new_with_name(
+                Utf8PathBuf::from("/project/tests/test_math.py"),
+                "tests.test_math".to_string(),
+            ),
+        );
+        let full_name = params.map(|params| format!("{function}{params}"));
+        QualifiedTestName::new(function, full_name)
+    }
+
+    fn to_json(event: &RunEvent) -> String {
+        serde_json::to_string(event).unwrap()
+    }
+
+    #[test]
+    fn serializes_test_events() {
+        let name = test_name(Some("(x=1)"));
+
+        assert_snapshot!(
+            to_json(&RunEvent::TestStarted { test: (&name).into(), timeout: None }),
+            @r#"{"type":"test-started","name":"tests.test_math::test_add(x=1)","module":"tests.test_math","function":"test_add","params":"(x=1)"}"#
+        );
+
+        assert_snapshot!(
+            to_json(&RunEvent::TestStarted {
+                test: (&test_name(None)).into(),
+                timeout: Some(2.5),
+            }),
+            @r#"{"type":"test-started","name":"tests.test_math::test_add","module":"tests.test_math","function":"test_add","timeout":2.5}"#
+        );
+
+        assert_snapshot!(
+            to_json(&RunEvent::TestFinished {
+                test: (&test_name(None)).into(),
+                outcome: TestCaseOutcome::Skipped,
+                duration: 0.0,
+                attempt: 1,
+                max_attempts: None,
+                flaky: false,
+                skip_reason: Some("not ready".to_string()),
+            }),
+            @r#"{"type":"test-finished","name":"tests.test_math::test_add","module":"tests.test_math","function":"test_add","outcome":"skipped","duration":0.0,"attempt":1,"flaky":false,"skip_reason":"not ready"}"#
+        );
+    }
+
+    #[test]
+    fn serializes_run_finished_stats() {
+        let mut stats = TestResultStats::default();
+        stats.add(TestResultKind::Passed);
+        stats.add(TestResultKind::Passed);
+        stats.add(TestResultKind::Flaky);
+        stats.add(TestResultKind::Failed);
+
+        assert_snapshot!(
+            to_json(&RunEvent::RunFinished {
+                duration: 1.5,
+                success: stats.is_success(),
+                stats: (&stats).into(),
+            }),
+            @r#"{"type":"run-finished","duration":1.5,"success":false,"stats":{"total":3,"passed":2,"failed":1,"skipped":0,"flaky":1,"slow":0}}"#
+        );
+    }
+
+    #[test]
+    fn events_round_trip_through_lines() {
+        let event = RunEvent::TestFinished {
+            test: (&test_name(Some("(x=1)"))).into(),
+            outcome: TestCaseOutcome::Failed,
+            duration: 0.25,
+            attempt: 2,
+            max_attempts: Some(3),
+            flaky: false,
+            skip_reason: None,
+        };
+
+        let mut buf = Vec::new();
+        event.write_line(&mut buf).unwrap();
+        let line = String::from_utf8(buf).unwrap();
+        assert!(line.ends_with('\n'));
+
+        let parsed = RunEvent::from_line(line.trim_end()).unwrap();
+        assert_eq!(to_json(&parsed), to_json(&event));
+    }
+}
@@ -0,0 +1,322 @@
+//! JUnit XML rendering of recorded test cases.
+//!
+//! The output follows the de-facto schema understood by CI systems and code
+//! review tools: one `<testsuite>` per Python module and one `<testcase>` per
+//! test variant. Retried tests use the Maven Surefire `<flakyFailure>` /
+//! `<rerunFailure>` elements for their earlier attempts.
+
+use std::fmt;
+use std::time::Duration;
+
+use camino::Utf8Path;
+
+use crate::escape_xml;
+use crate::result::{TestCaseAttempt, TestCaseFailure, TestCaseOutcome, TestCaseRecord};
+
+/// Renders a set of [`TestCaseRecord`]s as a JUnit XML document.
+pub struct JunitReport<'a> {
+    test_cases: &'a [TestCaseRecord],
+    root: &'a Utf8Path,
+}
+
+impl<'a> JunitReport<'a> {
+    /// `root` is used to make the `file` attribute of each test case relative.
+    pub fn new(test_cases: &'a [TestCaseRecord], root: &'a Utf8Path) -> Self {
+        Self { test_cases, root }
+    }
+
+    /// Groups test cases by module. Suites are sorted by module name and test
+    /// cases by function name so that parallel runs produce a stable document
+    /// regardless of how tests were partitioned across workers.
+    fn suites(&self) -> Vec<(&'a str, Vec<&'a TestCaseRecord>)> {
+        let mut suites: Vec<(&str, Vec<&TestCaseRecord>)> = Vec::new();
+
+        for record in self.test_cases {
+            match suites
+                .iter_mut()
+                .find(|(name, _)| *name == record.module_name)
+            {
+                Some((_, records)) => records.push(record),
+                None => suites.push((record.module_name.as_str(), vec![record])),
+            }
+        }
+
+        suites.sort_by_key(|(name, _)| *name);
+        for (_, records) in &mut suites {
+            records.sort_by(|a, b| a.function_name.cmp(&b.function_name));
+        }
+
+        suites
+    }
+
+    fn write_test_case(&self, f: &mut fmt::Formatter<'_>, record: &TestCaseRecord) -> fmt::Result {
+        let file = record
+            .file
+            .strip_prefix(self.root)
+            .unwrap_or(record.file.as_path());
+
+        write!(
+            f,
+            r#"    <testcase name="{}" classname="{}" file="{}" time="{}""#,
+            escape_xml(&record.display_name()),
+            escape_xml(&record.module_name),
+            escape_xml(file.as_str()),
+            format_seconds(record.duration),
+        )?;
+
+        let retried_failures: Vec<&TestCaseAttempt> = record
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.outcome == TestCaseOutcome::Failed)
+            .collect();
+
+        let has_body = record.outcome != TestCaseOutcome::Passed
+            || !retried_failures.is_empty()
+            || !record.stdout.is_empty()
+            || !record.stderr.is_empty();
+
+        if !has_body {
+            return writeln!(f, "/>");
+        }
+        writeln!(f, ">")?;
+
+        match record.outcome {
+            TestCaseOutcome::Passed => {
+                for attempt in &retried_failures {
+                    write_failure_element(f, "flakyFailure", attempt.failure.as_ref())?;
+                }
+            }
+            TestCaseOutcome::Failed | TestCaseOutcome::TimedOut => {
+                write_failure_element(f, "failure", record.failure.as_ref())?;
+                // The final attempt is the one reported as `<failure>`; every
+                // earlier failed attempt is a rerun.
+                let reruns = retried_failures.len().saturating_sub(1);
+                for attempt in retried_failures.iter().take(reruns) {
+                    write_failure_element(f, "rerunFailure", attempt.failure.as_ref())?;
+                }
+            }
+            TestCaseOutcome::Skipped => match &record.skip_reason {
+                Some(reason) => {
+                    writeln!(f, r#"      <skipped message="{}"/>"#, escape_xml(reason))?;
+                }
+                None => writeln!(f, "      <skipped/>")?,
+            },
+        }
+
+        if !record.stdout.is_empty() {
+            writeln!(
+                f,
+                "      <system-out>{}</system-out>",
+                escape_xml(&record.stdout)
+            )?;
+        }
+        if !record.stderr.is_empty() {
+            writeln!(
+                f,
+                "      <system-err>{}</system-err>",
+                escape_xml(&record.stderr)
+            )?;
+        }
+
+        writeln!(f, "    </testcase>")
+    }
+}
+
+impl fmt::Display for JunitReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suites = self.suites();
+        let totals = SuiteCounts::from_records(self.test_cases.iter());
+
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            f,
+            r#"<testsuites name="karva" tests="{}" failures="{}" errors="0" skipped="{}" time="{}">"#,
+            totals.tests,
+            totals.failures,
+            totals.skipped,
+            format_seconds(totals.time),
+        )?;
+
+        for (name, records) in suites {
+            let counts = SuiteCounts::from_records(records.iter().copied());
+            writeln!(
+                f,
+                r#"  <testsuite name="{}" tests="{}" failures="{}" errors="0" skipped="{}" time="{}">"#,
+                escape_xml(name),
+                counts.tests,
+                counts.failures,
+                counts.skipped,
+                format_seconds(counts.time),
+            )?;
+            for record in records {
+                self.write_test_case(f, record)?;
+            }
+            writeln!(f, "  </testsuite>")?;
+        }
+
+        writeln!(f, "</testsuites>")
+    }
+}
+
+fn write_failure_element(
+    f: &mut fmt::Formatter<'_>,
+    element: &str,
+    failure: Option<&TestCaseFailure>,
+) -> fmt::Result {
+    let Some(failure) = failure else {
+        return writeln!(f, r#"      <{element} message="test failed"/>"#);
+    };
+
+    write!(
+        f,
+        r#"      <{element} message="{}""#,
+        escape_xml(&failure.message)
+    )?;
+    if let Some(kind) = &failure.kind {
+        write!(f, r#" type="{}""#, escape_xml(kind))?;
+    }
+
+    match &failure.details {
+        Some(details) => writeln!(f, ">{}</{element}>", escape_xml(details)),
+        None => writeln!(f, "/>"),
+    }
+}
+
+#[derive(Default)]
+struct SuiteCounts {
+    tests: usize,
+    failures: usize,
+    skipped: usize,
+    time: Duration,
+}
+
+impl SuiteCounts {
+    fn from_records<'a>(records: impl Iterator<Item = &'a TestCaseRecord>) -> Self {
+        let mut counts = Self::default();
+        for record in records {
+            counts.tests += 1;
+            counts.time += record.duration;
+            match record.outcome {
+                TestCaseOutcome::Passed => {}
+                TestCaseOutcome::Failed | TestCaseOutcome::TimedOut => counts.failures += 1,
+                TestCaseOutcome::Skipped => counts.skipped += 1,
+            }
+        }
+        counts
+    }
+}
+
+fn format_seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use insta::assert_snapshot;
+
+    use super::*;
+
+    fn record(function_name: &str, outcome: TestCaseOutcome) -> TestCaseRecord {
+        TestCaseRecord {
+            module_name: "tests.test_math".to_string(),
+            function_name: function_name.to_string(),
+            params: None,
+            file: Utf8PathBuf::from("/project/tests/test_math.py"),
+            outcome,
+            duration: Duration::from_millis(12),
+            skip_reason: None,
+            failure: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            attempts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn renders_each_outcome() {
+        let passed = TestCaseRecord {
+            params: Some("(x=1)".to_string()),
+            stdout: "hello <world>\n".to_string(),
+            ..record("test_add", TestCaseOutcome::Passed)
+        };
+        let failed = TestCaseRecord {
+            failure: Some(TestCaseFailure {
+                kind: Some("AssertionError".to_string()),
+                message: "AssertionError: 1 != 2".to_string(),
+                details: Some("File \"test_math.py\", line 3\n    assert 1 == 2".to_string()),
+            }),
+            ..record("test_sub", TestCaseOutcome::Failed)
+        };
+        let skipped = TestCaseRecord {
+            skip_reason: Some("not \"ready\"".to_string()),
+            ..record("test_div", TestCaseOutcome::Skipped)
+        };
+
+        let records = [passed, failed, skipped];
+        assert_snapshot!(JunitReport::new(&records, Utf8Path::new("/project")), @r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <testsuites name="karva" tests="3" failures="1" errors="0" skipped="1" time="0.036">
+          <testsuite name="tests.test_math" tests="3" failures="1" errors="0" skipped="1" time="0.036">
+            <testcase name="test_add(x=1)" classname="tests.test_math" file="tests/test_math.py" time="0.012">
+              <system-out>hello &lt;world&gt;
+        </system-out>
+            </testcase>
+            <testcase name="test_div" classname="tests.test_math" file="tests/test_math.py" time="0.012">
+              <skipped message="not &quot;ready&quot;"/>
+            </testcase>
+            <testcase name="test_sub" classname="tests.test_math" file="tests/test_math.py" time="0.012">
+              <failure message="AssertionError: 1 != 2" type="AssertionError">File &quot;test_math.py&quot;, line 3
+            assert 1 == 2</failure>
+            </testcase>
+          </testsuite>
+        </testsuites>
+        "#);
+    }
+
+    #[test]
+    fn renders_retried_attempts() {
+        let attempt = |attempt, outcome| TestCaseAttempt {
+            attempt,
+            outcome,
+            duration: Duration::from_millis(4),
+            failure: (outcome == TestCaseOutcome::Failed).then(|| TestCaseFailure {
+                kind: Some("ValueError".to_string()),
+                message: format!("ValueError: attempt {attempt}"),
+                details: None,
+            }),
+        };
+
+        let flaky = TestCaseRecord {
+            attempts: vec![
+                attempt(1, TestCaseOutcome::Failed),
+                attempt(2, TestCaseOutcome::Passed),
+            ],
+            ..record("test_flaky", TestCaseOutcome::Passed)
+        };
+        let exhausted = TestCaseRecord {
+            failure: attempt(2, TestCaseOutcome::Failed).failure,
+            attempts: vec![
+                attempt(1, TestCaseOutcome::Failed),
+                attempt(2, TestCaseOutcome::Failed),
+            ],
+            ..record("test_broken", TestCaseOutcome::Failed)
+        };
+
+        let records = [flaky, exhausted];
+        assert_snapshot!(JunitReport::new(&records, Utf8Path::new("/project")), @r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <testsuites name="karva" tests="2" failures="1" errors="0" skipped="0" time="0.024">
+          <testsuite name="tests.test_math" tests="2" failures="1" errors="0" skipped="0" time="0.024">
+            <testcase name="test_broken" classname="tests.test_math" file="tests/test_math.py" time="0.012">
+              <failure message="ValueError: attempt 2" type="ValueError"/>
+              <rerunFailure message="ValueError: attempt 1" type="ValueError"/>
+            </testcase>
+            <testcase name="test_flaky" classname="tests.test_math" file="tests/test_math.py" time="0.012">
+              <flakyFailure message="ValueError: attempt 1" type="ValueError"/>
+            </testcase>
+          </testsuite>
+        </testsuites>
+        "#);
+    }
+}
@@ -1,13 +1,20 @@
+mod event;
+mod junit;
 mod reporter;
 mod result;
 #[cfg(feature = "traceback")]
 mod traceback;
+mod xml;
 
+pub use event::{EventReporter, EventStats, EventTestName, RunEvent};
+pub use junit::JunitReport;
 pub use reporter::{DummyReporter, Reporter, TestCaseReporter};
 pub use result::{
-    DisplayFlakyTest, DisplayFlakyTests, FlakyTest, IndividualTestResultKind, TestResultKind,
+    DisplayFlakyTest, DisplayFlakyTests, FlakyTest, IndividualTestResultKind, TestCaseAttempt,
+    TestCaseDetails, TestCaseFailure, TestCaseOutcome, TestCaseRecord, TestResultKind,
     TestResultStats, TestRunResult,
 };
 
 #[cfg(feature = "traceback")]
 pub use traceback::Traceback;
+pub use xml::escape_xml;
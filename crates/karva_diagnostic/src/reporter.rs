@@ -6,10 +6,17 @@ use karva_logging::time::format_duration_bracketed;
 use karva_logging::{Printer, StatusLevel};
 use karva_python_semantic::QualifiedTestName;
 
-use crate::result::IndividualTestResultKind;
+use crate::event::{EventTestName, RunEvent};
+use crate::result::{IndividualTestResultKind, TestCaseOutcome};
 
 /// A reporter for test execution time logging to the user.
 pub trait Reporter: Send + Sync {
+    /// Report that a test variant is about to run, along with the hard
+    /// timeout that applies to it. Default no-op.
+    fn report_test_started(&self, test_name: &QualifiedTestName, timeout: Option<Duration>) {
+        let _ = (test_name, timeout);
+    }
+
     /// Report the completion of a non-retried test.
     fn report_test_case_result(
         &self,
@@ -34,6 +41,23 @@ pub trait Reporter: Send + Sync {
         let _ = (test_name, attempt, result_kind, duration);
     }
 
+    /// Report the final outcome of a retried test, after every attempt has
+    /// been reported through [`Self::report_test_attempt`].
+    ///
+    /// `attempt` is the attempt that produced the outcome and `max_attempts`
+    /// the number of attempts the test was allowed. Default no-op, since the
+    /// per-attempt lines already show the outcome in terminal output.
+    fn report_retried_result(
+        &self,
+        test_name: &QualifiedTestName,
+        result_kind: &IndividualTestResultKind,
+        duration: Duration,
+        attempt: u32,
+        max_attempts: u32,
+    ) {
+        let _ = (test_name, result_kind, duration, attempt, max_attempts);
+    }
+
     /// Report that a test exceeded the configured slow-test threshold.
     ///
     /// Emitted in addition to (and ahead of) the regular result line. Default
@@ -49,12 +73,15 @@ fn show_for_status_level(level: StatusLevel, kind: &IndividualTestResultKind) ->
     // `report_test_slow`, so `Slow` here acts the same as `Retry`.
     match level {
         StatusLevel::None => false,
-        StatusLevel::Fail | StatusLevel::Retry | StatusLevel::Slow => {
-            matches!(kind, IndividualTestResultKind::Failed)
-        }
+        StatusLevel::Fail | StatusLevel::Retry | StatusLevel::Slow => matches!(
+            kind,
+            IndividualTestResultKind::Failed | IndividualTestResultKind::TimedOut
+        ),
         StatusLevel::Pass => matches!(
             kind,
-            IndividualTestResultKind::Failed | IndividualTestResultKind::Passed
+            IndividualTestResultKind::Failed
+                | IndividualTestResultKind::TimedOut
+                | IndividualTestResultKind::Passed
         ),
         StatusLevel::Skip | StatusLevel::All => true,
     }
@@ -83,26 +110,66 @@ impl TestCaseReporter {
     pub fn new(printer: Printer) -> Self {
         Self { printer }
     }
-}
 
-impl Reporter for TestCaseReporter {
-    fn report_test_case_result(
+    /// Render an event streamed from a worker.
+    ///
+    /// Produces the same lines as the [`Reporter`] methods would have if the
+    /// test had run in this process. The final outcome of a retried test is
+    /// not rendered, since its attempts have already been shown.
+    pub fn report_event(&self, event: &RunEvent) {
+        match event {
+            RunEvent::TestFinished {
+                test,
+                outcome,
+                duration,
+                max_attempts: None,
+                skip_reason,
+                ..
+            } => {
+                let result_kind = result_kind(*outcome, skip_reason.clone());
+                self.write_result(test, &result_kind, Duration::from_secs_f64(*duration));
+            }
+            RunEvent::TestAttempt {
+                test,
+                attempt,
+                outcome,
+                duration,
+            } => {
+                let result_kind = result_kind(*outcome, None);
+                self.write_attempt(
+                    test,
+                    *attempt,
+                    &result_kind,
+                    Duration::from_secs_f64(*duration),
+                );
+            }
+            RunEvent::TestSlow { test, duration } => {
+                self.write_slow(test, Duration::from_secs_f64(*duration));
+            }
+            RunEvent::RunStarted { .. }
+            | RunEvent::TestStarted { .. }
+            | RunEvent::TestFinished { .. }
+            | RunEvent::RunFinished { .. } => {}
+        }
+    }
+
+    fn write_result(
         &self,
-        test_name: &QualifiedTestName,
-        result_kind: IndividualTestResultKind,
+        test: &EventTestName,
+        result_kind: &IndividualTestResultKind,
         duration: Duration,
     ) {
-        if !show_for_status_level(self.printer.status_level(), &result_kind) {
+        if !show_for_status_level(self.printer.status_level(), result_kind) {
             return;
         }
 
-        let label = ResultLabel::from(&result_kind);
+        let label = ResultLabel::from(result_kind);
         let padding = label_padding(label.text().len());
         let colored_label = label.colored();
         let duration_str = format_duration_bracketed(duration);
-        let test_path = format_test_path(test_name);
+        let test_path = format_test_path(test);
 
-        let suffix = match &result_kind {
+        let suffix = match result_kind {
             IndividualTestResultKind::Skipped {
                 reason: Some(reason),
             } => format!(": {reason}"),
@@ -117,7 +184,7 @@ impl Reporter for TestCaseReporter {
         .ok();
     }
 
-    fn report_test_slow(&self, test_name: &QualifiedTestName, duration: Duration) {
+    fn write_slow(&self, test: &EventTestName, duration: Duration) {
         if self.printer.status_level() < StatusLevel::Slow {
             return;
         }
@@ -126,7 +193,7 @@ impl Reporter for TestCaseReporter {
         let padding = label_padding(label.text().len());
         let colored_label = label.colored();
         let duration_str = format_duration_bracketed(duration);
-        let test_path = format_test_path(test_name);
+        let test_path = format_test_path(test);
 
         let mut stdout = self.printer.stream_for_test_result().lock();
         writeln!(
@@ -136,11 +203,11 @@ impl Reporter for TestCaseReporter {
         .ok();
     }
 
-    fn report_test_attempt(
+    fn write_attempt(
         &self,
-        test_name: &QualifiedTestName,
+        test: &EventTestName,
         attempt: u32,
-        result_kind: IndividualTestResultKind,
+        result_kind: &IndividualTestResultKind,
         duration: Duration,
     ) {
         if self.printer.status_level() < StatusLevel::Retry {
@@ -149,12 +216,12 @@ impl Reporter for TestCaseReporter {
 
         // Skips don't go through the retry loop; we still render them so the
         // From impl and trait remain total.
-        let label = ResultLabel::from(&result_kind);
+        let label = ResultLabel::from(result_kind);
         let label_len = "TRY ".len() + count_digits(attempt) + 1 + label.text().len();
         let padding = label_padding(label_len);
         let colored_status = label.colored();
         let duration_str = format_duration_bracketed(duration);
-        let test_path = format_test_path(test_name);
+        let test_path = format_test_path(test);
 
         let mut stdout = self.printer.stream_for_test_result().lock();
         writeln!(
@@ -165,8 +232,45 @@ impl Reporter for TestCaseReporter {
     }
 }
 
-/// The width that result labels (`PASS`, `FAIL`, `SKIP`, `SLOW`, `TRY N PASS`,
-/// etc.) are right-padded to so columns align.
+impl Reporter for TestCaseReporter {
+    fn report_test_case_result(
+        &self,
+        test_name: &QualifiedTestName,
+        result_kind: IndividualTestResultKind,
+        duration: Duration,
+    ) {
+        self.write_result(&test_name.into(), &result_kind, duration);
+    }
+
+    fn report_test_slow(&self, test_name: &QualifiedTestName, duration: Duration) {
+        self.write_slow(&test_name.into(), duration);
+    }
+
+    fn report_test_attempt(
+        &self,
+        test_name: &QualifiedTestName,
+        attempt: u32,
+        result_kind: IndividualTestResultKind,
+        duration: Duration,
+    ) {
+        self.write_attempt(&test_name.into(), attempt, &result_kind, duration);
+    }
+}
+
+/// Rebuild the result kind carried by an event.
+fn result_kind(outcome: TestCaseOutcome, skip_reason: Option<String>) -> IndividualTestResultKind {
+    match outcome {
+        TestCaseOutcome::Passed => IndividualTestResultKind::Passed,
+        TestCaseOutcome::Failed => IndividualTestResultKind::Failed,
+        TestCaseOutcome::Skipped => IndividualTestResultKind::Skipped {
+            reason: skip_reason,
+        },
+        TestCaseOutcome::TimedOut => IndividualTestResultKind::TimedOut,
+    }
+}
+
+/// The width that result labels (`PASS`, `FAIL`, `SKIP`, `SLOW`, `TIMEOUT`,
+/// `TRY N PASS`, etc.) are right-padded to so columns align.
 const LABEL_COLUMN_WIDTH: usize = 12;
 
 fn label_padding(label_len: usize) -> String {
@@ -174,11 +278,12 @@ fn label_padding(label_len: usize) -> String {
 }
 
 /// Render the colored `module::function[params]` portion of a result line.
-fn format_test_path(test_name: &QualifiedTestName) -> String {
-    let module = test_name.function_name().module_path().module_name().cyan();
-    let fn_name = test_name.function_name().function_name().blue().bold();
-    let params = test_name
-        .params()
+fn format_test_path(test: &EventTestName) -> String {
+    let module = test.module.cyan();
+    let fn_name = test.function.blue().bold();
+    let params = test
+        .params
+        .as_deref()
         .map(|p| p.blue().bold().to_string())
         .unwrap_or_default();
     format!("{module}::{fn_name}{params}")
@@ -194,6 +299,7 @@ enum ResultLabel {
     Fail,
     Skip,
     Slow,
+    Timeout,
 }
 
 impl ResultLabel {
@@ -203,6 +309,7 @@ impl ResultLabel {
             Self::Fail => "FAIL",
             Self::Skip => "SKIP",
             Self::Slow => "SLOW",
+            Self::Timeout => "TIMEOUT",
         }
     }
 
@@ -210,7 +317,7 @@ impl ResultLabel {
         let text = self.text();
         match self {
             Self::Pass => text.green().bold().to_string(),
-            Self::Fail => text.red().bold().to_string(),
+            Self::Fail | Self::Timeout => text.red().bold().to_string(),
             Self::Skip | Self::Slow => text.yellow().bold().to_string(),
         }
     }
@@ -222,6 +329,7 @@ impl From<&IndividualTestResultKind> for ResultLabel {
             IndividualTestResultKind::Passed => Self::Pass,
             IndividualTestResultKind::Failed => Self::Fail,
             IndividualTestResultKind::Skipped { .. } => Self::Skip,
+            IndividualTestResultKind::TimedOut => Self::Timeout,
         }
     }
 }
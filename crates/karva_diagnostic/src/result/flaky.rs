@@ -30,7 +30,7 @@ impl FlakyTest {
                 .module_path()
                 .module_name()
                 .to_string(),
-            function_name: test_name.function_name().function_name().to_string(),
+            function_name: test_name.function_name().local_name(),
             params: test_name.params().map(str::to_string),
             passed_on,
             total_attempts,
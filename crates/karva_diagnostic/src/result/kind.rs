@@ -6,7 +6,12 @@
 pub enum IndividualTestResultKind {
     Passed,
     Failed,
-    Skipped { reason: Option<String> },
+    Skipped {
+        reason: Option<String>,
+    },
+    /// The test exceeded its hard timeout and its worker process was
+    /// killed. Counted as a failure in the summary.
+    TimedOut,
 }
 
 /// A test result kind suitable for aggregation in [`super::TestResultStats`].
@@ -56,7 +61,7 @@ impl From<IndividualTestResultKind> for TestResultKind {
     fn from(val: IndividualTestResultKind) -> Self {
         match val {
             IndividualTestResultKind::Passed => Self::Passed,
-            IndividualTestResultKind::Failed => Self::Failed,
+            IndividualTestResultKind::Failed | IndividualTestResultKind::TimedOut => Self::Failed,
             IndividualTestResultKind::Skipped { .. } => Self::Skipped,
         }
     }
@@ -1,9 +1,11 @@
 mod flaky;
 mod kind;
 mod stats;
+mod test_case;
 
 use std::collections::HashMap;
 
+use camino::Utf8PathBuf;
 use karva_python_semantic::{QualifiedFunctionName, QualifiedTestName};
 use ruff_db::diagnostic::Diagnostic;
 
@@ -12,6 +14,9 @@ use crate::reporter::Reporter;
 pub use flaky::{DisplayFlakyTest, DisplayFlakyTests, FlakyTest};
 pub use kind::{IndividualTestResultKind, TestResultKind};
 pub use stats::TestResultStats;
+pub use test_case::{
+    TestCaseAttempt, TestCaseDetails, TestCaseFailure, TestCaseOutcome, TestCaseRecord,
+};
 
 /// Represents the result of a test run.
 ///
@@ -33,6 +38,17 @@ pub struct TestRunResult {
 
     /// Tests that passed only after at least one retry.
     flaky_tests: Vec<FlakyTest>,
+
+    /// One record per executed or skipped test variant, in execution order.
+    test_cases: Vec<TestCaseRecord>,
+
+    /// The `.snap` files that snapshot assertions compared against, whether
+    /// or not they existed.
+    snapshots: Vec<Utf8PathBuf>,
+
+    /// Attempts reported for the retried test currently in progress. Drained
+    /// into its [`TestCaseRecord`] by [`Self::register_retried_result`].
+    pending_attempts: Vec<TestCaseAttempt>,
 }
 
 impl TestRunResult {
@@ -53,16 +69,27 @@ impl TestRunResult {
         test_case_name: &QualifiedTestName,
         result: IndividualTestResultKind,
         duration: std::time::Duration,
+        details: TestCaseDetails,
         reporter: Option<&dyn Reporter>,
     ) {
         self.stats.add(result.clone().into());
 
         let function_name = test_case_name.function_name().clone();
 
-        if matches!(result, IndividualTestResultKind::Failed) {
+        if matches!(
+            result,
+            IndividualTestResultKind::Failed | IndividualTestResultKind::TimedOut
+        ) {
             self.failed_tests.push(function_name.clone());
         }
 
+        self.test_cases.push(TestCaseRecord::new(
+            test_case_name,
+            &result,
+            duration,
+            details,
+        ));
+
         if let Some(reporter) = reporter {
             reporter.report_test_case_result(test_case_name, result, duration);
         }
@@ -83,6 +110,8 @@ impl TestRunResult {
     /// nextest's `FLAKY M/T` denominator: the maximum number of attempts the
     /// test was allowed (`retries + 1`), not just the count that ran.
     /// When the final outcome is `Passed`, the test is counted as flaky.
+    /// The attempts reported since the previous registration are attached
+    /// to the test's [`TestCaseRecord`].
     pub fn register_retried_result(
         &mut self,
         test_case_name: &QualifiedTestName,
@@ -90,13 +119,17 @@ impl TestRunResult {
         duration: std::time::Duration,
         passed_on: u32,
         total_attempts: u32,
-        _reporter: Option<&dyn Reporter>,
+        details: TestCaseDetails,
+        reporter: Option<&dyn Reporter>,
     ) {
         self.stats.add(result.clone().into());
 
         let function_name = test_case_name.function_name().clone();
 
-        if matches!(result, IndividualTestResultKind::Failed) {
+        if matches!(
+            result,
+            IndividualTestResultKind::Failed | IndividualTestResultKind::TimedOut
+        ) {
             self.failed_tests.push(function_name.clone());
         } else if matches!(result, IndividualTestResultKind::Passed) {
             self.stats.add(TestResultKind::Flaky);
@@ -108,6 +141,20 @@ impl TestRunResult {
             ));
         }
 
+        if let Some(reporter) = reporter {
+            reporter.report_retried_result(
+                test_case_name,
+                result,
+                duration,
+                passed_on,
+                total_attempts,
+            );
+        }
+
+        let mut record = TestCaseRecord::new(test_case_name, result, duration, details);
+        record.attempts = std::mem::take(&mut self.pending_attempts);
+        self.test_cases.push(record);
+
         self.durations
             .entry(function_name)
             .and_modify(|existing_duration| *existing_duration += duration)
@@ -116,15 +163,24 @@ impl TestRunResult {
 
     /// Forward a per-attempt notification to the reporter without touching
     /// summary stats. Called once per attempt of a retried test, including
-    /// the final attempt.
+    /// the final attempt. The attempt is buffered until the test's final
+    /// outcome is registered.
     pub fn report_test_attempt(
-        &self,
+        &mut self,
         test_case_name: &QualifiedTestName,
         attempt: u32,
         result: IndividualTestResultKind,
         duration: std::time::Duration,
+        failure: Option<TestCaseFailure>,
         reporter: Option<&dyn Reporter>,
     ) {
+        self.pending_attempts.push(TestCaseAttempt {
+            attempt,
+            outcome: TestCaseOutcome::from(&result),
+            duration,
+            failure,
+        });
+
         if let Some(reporter) = reporter {
             reporter.report_test_attempt(test_case_name, attempt, result, duration);
         }
@@ -162,4 +218,16 @@ impl TestRunResult {
     pub fn flaky_tests(&self) -> &[FlakyTest] {
         &self.flaky_tests
     }
+
+    pub fn test_cases(&self) -> &[TestCaseRecord] {
+        &self.test_cases
+    }
+
+    pub fn snapshots(&self) -> &[Utf8PathBuf] {
+        &self.snapshots
+    }
+
+    pub fn set_snapshots(&mut self, snapshots: Vec<Utf8PathBuf>) {
+        self.snapshots = snapshots;
+    }
 }
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use camino::Utf8PathBuf;
+use karva_python_semantic::QualifiedTestName;
+use serde::{Deserialize, Serialize};
+
+use super::kind::IndividualTestResultKind;
+
+/// The final outcome of a test case as recorded for machine-readable reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TestCaseOutcome {
+    Passed,
+    Failed,
+    Skipped,
+    TimedOut,
+}
+
+impl From<&IndividualTestResultKind> for TestCaseOutcome {
+    fn from(kind: &IndividualTestResultKind) -> Self {
+        match kind {
+            IndividualTestResultKind::Passed => Self::Passed,
+            IndividualTestResultKind::Failed => Self::Failed,
+            IndividualTestResultKind::Skipped { .. } => Self::Skipped,
+            IndividualTestResultKind::TimedOut => Self::TimedOut,
+        }
+    }
+}
+
+/// The error that caused a test (or one attempt of it) to fail.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TestCaseFailure {
+    /// The exception type name, e.g. `AssertionError`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// A single-line summary of the failure.
+    pub message: String,
+    /// The full traceback, when one is available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+/// One attempt of a retried test.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCaseAttempt {
+    /// 1-indexed attempt number.
+    pub attempt: u32,
+    pub outcome: TestCaseOutcome,
+    pub duration: Duration,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<TestCaseFailure>,
+}
+
+/// Runner-provided context for a test case that isn't part of its result kind.
+#[derive(Debug, Clone, Default)]
+pub struct TestCaseDetails {
+    pub failure: Option<TestCaseFailure>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Everything recorded about a single test variant, used to build reports
+/// that need one entry per test (such as JUnit XML).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCaseRecord {
+    pub module_name: String,
+    pub function_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<String>,
+    /// Path of the module the test was defined in.
+    pub file: Utf8PathBuf,
+    pub outcome: TestCaseOutcome,
+    /// Total wall-clock duration, including every retry.
+    pub duration: Duration,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<TestCaseFailure>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stdout: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stderr: String,
+    /// Every attempt of a retried test, in order. Empty for tests that ran once.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<TestCaseAttempt>,
+}
+
+impl TestCaseRecord {
+    pub fn new(
+        test_name: &QualifiedTestName,
+        result: &IndividualTestResultKind,
+        duration: Duration,
+        details: TestCaseDetails,
+    ) -> Self {
+        let function_name = test_name.function_name();
+        let skip_reason = match result {
+            IndividualTestResultKind::Skipped { reason } => reason.clone(),
+            _ => None,
+        };
+        let failure = if matches!(
+            result,
+            IndividualTestResultKind::Failed | IndividualTestResultKind::TimedOut
+        ) {
+            details.failure
+        } else {
+            None
+        };
+
+        Self {
+            module_name: function_name.module_path().module_name().to_string(),
+            function_name: function_name.local_name(),
+            params: test_name.params().map(str::to_string),
+            file: function_name.module_path().path().clone(),
+            outcome: TestCaseOutcome::from(result),
+            duration,
+            skip_reason,
+            failure,
+            stdout: details.stdout,
+            stderr: details.stderr,
+            attempts: Vec::new(),
+        }
+    }
+
+    /// The test name as shown in terminal output, without the module prefix.
+    pub fn display_name(&self) -> String {
+        match &self.params {
+            Some(params) => format!("{}{params}", self.function_name),
+            None => self.function_name.clone(),
+        }
+    }
+}
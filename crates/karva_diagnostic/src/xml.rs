@@ -0,0 +1,39 @@
+/// Escapes text for use in XML or HTML attribute values and character data.
+///
+/// Characters that are not allowed anywhere in an XML 1.0 document (most C0
+/// control characters, `U+FFFE` and `U+FFFF`) are dropped, since captured
+/// output and source files may contain them.
+pub fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(escape_xml(r#"a<b>&"c'"#), "a&lt;b&gt;&amp;&quot;c&apos;");
+    }
+
+    #[test]
+    fn drops_forbidden_characters() {
+        assert_eq!(
+            escape_xml("\u{1b}[31mred\u{1b}[0m\tok\r\n\u{0}\u{ffff}"),
+            "[31mred[0m\tok\r\n"
+        );
+    }
+}
@@ -12,13 +12,13 @@ mod settings;
 pub use max_fail::MaxFail;
 pub use options::{
     Config, CovReport, CoverageOptions, DEFAULT_PROFILE, IncompatibleVersionError, Options,
-    OutputFormat, ProjectOptionsOverrides, SrcOptions, TerminalOptions, TestOptions,
-    UnknownProfile,
+    OutputFormat, ProjectOptionsOverrides, SnapshotOptions, SrcOptions, TerminalOptions,
+    TestOptions, UnknownProfile,
 };
 pub use pyproject::{PyProject, PyProjectError};
 pub use settings::{
-    CovFailUnder, CoverageSettings, NoTestsMode, ProjectSettings, RunIgnoredMode, SlowTimeoutSecs,
-    TestTimeoutSecs,
+    CovFailUnder, CoverageSettings, NoTestsMode, ProjectSettings, ResumePoint, RunIgnoredMode,
+    SlowTimeoutSecs, SnapshotSettings, SnapshotUpdateMode, TestTimeoutSecs, TimeoutMethod,
 };
 
 use crate::options::KarvaTomlError;
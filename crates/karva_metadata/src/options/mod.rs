@@ -1,6 +1,7 @@
 mod config;
 mod overrides;
 
+use camino::Utf8PathBuf;
 use karva_combine::Combine;
 use karva_logging::{FinalStatusLevel, StatusLevel};
 use karva_macros::{Combine, OptionsMetadata};
@@ -16,7 +17,8 @@ use crate::filter::FiltersetSet;
 use crate::max_fail::MaxFail;
 use crate::settings::{
     CovFailUnder, CoverageSettings, NoTestsMode, ProjectSettings, RunIgnoredMode, SlowTimeoutSecs,
-    SrcSettings, TerminalSettings, TestSettings, TestTimeoutSecs,
+    SnapshotSettings, SnapshotUpdateMode, SrcSettings, TerminalSettings, TestSettings,
+    TestTimeoutSecs, TimeoutMethod,
 };
 
 #[derive(
@@ -36,6 +38,9 @@ pub struct Options {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[option_group]
     pub coverage: Option<CoverageOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option_group]
+    pub snapshot: Option<SnapshotOptions>,
 }
 
 impl Options {
@@ -45,6 +50,7 @@ impl Options {
             src: self.src.clone().unwrap_or_default().to_settings(),
             test: self.test.clone().unwrap_or_default().to_settings(),
             coverage: self.coverage.clone().unwrap_or_default().to_settings(),
+            snapshot: self.snapshot.clone().unwrap_or_default().to_settings(),
         }
     }
 }
@@ -157,6 +163,25 @@ pub struct TerminalOptions {
         "#
     )]
     pub final_status_level: Option<FinalStatusLevel>,
+
+    /// Write a JUnit XML report of the run to this path.
+    ///
+    /// The report contains one `<testcase>` per test variant with its
+    /// duration, skip reason, failure message and traceback, captured
+    /// stdout/stderr, and any failed attempts of retried tests. Results from
+    /// every worker are merged into a single file. Relative paths are
+    /// resolved against the project root.
+    ///
+    /// Defaults to unset, which disables the report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = "string",
+        example = r#"
+            junit-path = "target/junit.xml"
+        "#
+    )]
+    pub junit_path: Option<String>,
 }
 
 impl TerminalOptions {
@@ -166,6 +191,7 @@ impl TerminalOptions {
             show_python_output: self.show_python_output.unwrap_or_default(),
             status_level: self.status_level.unwrap_or_default(),
             final_status_level: self.final_status_level.unwrap_or_default(),
+            junit_path: self.junit_path.as_deref().map(Utf8PathBuf::from),
         }
     }
 }
@@ -188,6 +214,23 @@ pub struct TestOptions {
     )]
     pub test_function_prefix: Option<String>,
 
+    /// The prefix to use for test classes.
+    ///
+    /// Methods matching [`test-function-prefix`](#test-function-prefix)
+    /// inside a class with this prefix are collected as tests, and the
+    /// class is instantiated once per test.
+    ///
+    /// Defaults to `Test`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"Test"#,
+        value_type = "string",
+        example = r#"
+            test-class-prefix = "Test"
+        "#
+    )]
+    pub test_class_prefix: Option<String>,
+
     /// Whether to stop at the first test failure.
     ///
     /// This is a legacy alias for [`max_fail`](#max-fail): `true`
@@ -298,6 +341,45 @@ pub struct TestOptions {
         "#
     )]
     pub timeout: Option<TestTimeoutSecs>,
+
+    /// How hard per-test timeouts are enforced.
+    ///
+    /// With `thread` (the default), each test with a timeout runs on a
+    /// separate thread and is abandoned once the timeout expires. This cannot
+    /// stop a test that is stuck in native code or an infinite loop, which
+    /// keeps its worker busy until it finishes.
+    ///
+    /// With `process`, the main process kills the worker running a test that
+    /// exceeds its timeout, reports the test as timed out, and starts a new
+    /// worker for the tests that worker had not reached yet.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"thread"#,
+        value_type = "thread | process",
+        example = r#"
+            timeout-method = "process"
+        "#
+    )]
+    pub timeout_method: Option<TimeoutMethod>,
+
+    /// Whether to start a fresh worker when one crashes.
+    ///
+    /// A test that crashes its worker process, for example through a
+    /// segfault in a native extension or a call to `os._exit`, is always
+    /// reported as failed. When this is set, a new worker is started to run
+    /// the tests that the crashed worker had not reached yet; otherwise those
+    /// tests are not run.
+    ///
+    /// Defaults to `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"false"#,
+        value_type = "true | false",
+        example = r#"
+            respawn-on-crash = true
+        "#
+    )]
+    pub respawn_on_crash: Option<bool>,
 }
 
 impl TestOptions {
@@ -312,6 +394,10 @@ impl TestOptions {
                 .test_function_prefix
                 .clone()
                 .unwrap_or_else(|| "test".to_string()),
+            test_class_prefix: self
+                .test_class_prefix
+                .clone()
+                .unwrap_or_else(|| "Test".to_string()),
             max_fail,
             try_import_fixtures: self.try_import_fixtures.unwrap_or_default(),
             retry: self.retry.unwrap_or_default(),
@@ -320,6 +406,9 @@ impl TestOptions {
             no_tests: self.no_tests.unwrap_or_default(),
             slow_timeout: self.slow_timeout.and_then(SlowTimeoutSecs::as_duration),
             timeout: self.timeout.and_then(TestTimeoutSecs::as_duration),
+            timeout_method: self.timeout_method.unwrap_or_default(),
+            respawn_on_crash: self.respawn_on_crash.unwrap_or_default(),
+            resume: None,
         }
     }
 }
@@ -375,6 +464,219 @@ pub struct CoverageOptions {
     )]
     pub fail_under: Option<CovFailUnder>,
 
+    /// Minimum coverage percentage of the changed lines required for the
+    /// run to succeed.
+    ///
+    /// Only checked when a diff is given with `diff-base` or `diff-file`.
+    /// The diff coverage counts the executable lines the diff adds or
+    /// modifies in the measured files, ignoring branches. A diff that
+    /// changes no executable line always passes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = r#"float (0..=100)"#,
+        example = r#"
+            fail-under-diff = 100
+        "#
+    )]
+    pub fail_under_diff: Option<CovFailUnder>,
+
+    /// Git ref to measure diff coverage against.
+    ///
+    /// The changed lines are read with `git diff` in the project root,
+    /// starting from where `HEAD` branched off `<ref>`, so commits that
+    /// landed on `<ref>` since then don't count. Uncommitted changes to
+    /// tracked files are included but untracked files are not. A range such
+    /// as `base...HEAD` is passed to `git diff` as given. Equivalent to
+    /// passing `--cov-diff-base=<ref>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = "string",
+        example = r#"
+            diff-base = "origin/main...HEAD"
+        "#
+    )]
+    pub diff_base: Option<String>,
+
+    /// Path to a unified diff to measure diff coverage against.
+    ///
+    /// File paths in the diff are resolved against the project root, with
+    /// git's `a/` and `b/` prefixes removed. Relative paths are resolved
+    /// against the project root. Cannot be combined with `diff-base`.
+    /// Equivalent to passing `--cov-diff-file=<path>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = "string",
+        example = r#"
+            diff-file = "changes.diff"
+        "#
+    )]
+    pub diff_file: Option<String>,
+
+    /// Whether to measure branch coverage in addition to line coverage.
+    ///
+    /// When enabled, the report gains `Branch` and `BrPart` columns, and
+    /// the `TOTAL` percentage checked by `fail-under` counts branches
+    /// alongside statements.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"false"#,
+        value_type = r#"bool"#,
+        example = r#"
+            branch = true
+        "#
+    )]
+    pub branch: Option<bool>,
+
+    /// Whether to record which tests run each line.
+    ///
+    /// Each executed line is tagged with the qualified name of every test
+    /// that ran it, as exported in `KARVA_TEST_NAME`. The JSON and HTML
+    /// reports list the tests per line, and `karva coverage who-covers`
+    /// looks them up after the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"false"#,
+        value_type = r#"bool"#,
+        example = r#"
+            context = true
+        "#
+    )]
+    pub context: Option<bool>,
+
+    /// Glob patterns of the files to measure.
+    ///
+    /// When set, only files under the sources that match one of the
+    /// patterns are measured and reported. Patterns match the absolute path
+    /// of a file or its path relative to the project root. Equivalent to
+    /// passing `--cov-include=<glob>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = r#"list[str]"#,
+        example = r#"
+            include = ["src/*"]
+        "#
+    )]
+    pub include: Option<Vec<String>>,
+
+    /// Glob patterns of the files not to measure.
+    ///
+    /// Files matching one of the patterns are left out of the measurement
+    /// and the reports, even when they match `include`. Equivalent to
+    /// passing `--cov-omit=<glob>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = r#"list[str]"#,
+        example = r#"
+            omit = ["*/migrations/*", "src/generated/*"]
+        "#
+    )]
+    pub omit: Option<Vec<String>>,
+
+    /// Regular expressions of the source lines to exclude from coverage.
+    ///
+    /// A line matching one of the patterns is excluded along with the
+    /// block it starts, like a line marked `# pragma: no cover`, which is
+    /// always excluded. A pattern matching a decorator excludes the whole
+    /// decorated function or class. Equivalent to passing
+    /// `--cov-exclude-line=<regex>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = r#"list[str]"#,
+        example = r#"
+            exclude-lines = ["if TYPE_CHECKING:", "raise NotImplementedError", "@overload"]
+        "#
+    )]
+    pub exclude_lines: Option<Vec<String>>,
+
+    /// Regular expressions of the branch lines not to measure as branches.
+    ///
+    /// A line matching one of the patterns, or marked `# pragma: no
+    /// branch`, still counts as a statement, but its arcs are not reported
+    /// as partial or missing branches. Only used with `branch`. Equivalent
+    /// to passing `--cov-partial-branch=<regex>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = r#"list[str]"#,
+        example = r#"
+            partial-branches = ["while True:"]
+        "#
+    )]
+    pub partial_branches: Option<Vec<String>>,
+
+    /// Path to write a Cobertura XML coverage report to.
+    ///
+    /// Relative paths are resolved against the project root. Parent
+    /// directories are created if needed. Equivalent to passing
+    /// `--cov-report=xml:<path>`. Defaults to unset, which disables the
+    /// report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = "string",
+        example = r#"
+            xml-path = "coverage.xml"
+        "#
+    )]
+    pub xml_path: Option<String>,
+
+    /// Path to write a JSON coverage report to.
+    ///
+    /// The report lists the executed, missing and excluded lines of every
+    /// measured file, with summary counts, and the tests that ran each line
+    /// when `context` is enabled. Relative paths are resolved against the
+    /// project root. Equivalent to passing `--cov-report=json:<path>`.
+    /// Defaults to unset, which disables the report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = "string",
+        example = r#"
+            json-path = "coverage.json"
+        "#
+    )]
+    pub json_path: Option<String>,
+
+    /// Path to write an LCOV coverage report to.
+    ///
+    /// Relative paths are resolved against the project root. Parent
+    /// directories are created if needed. Equivalent to passing
+    /// `--cov-report=lcov:<path>`. Defaults to unset, which disables the
+    /// report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = "string",
+        example = r#"
+            lcov-path = "coverage.lcov"
+        "#
+    )]
+    pub lcov_path: Option<String>,
+
+    /// Directory to write an HTML coverage report into.
+    ///
+    /// The report has an index of every measured file, sorted by coverage,
+    /// and a page per file with its source highlighted and each line
+    /// marked as run, missing, excluded or a partial branch. Relative paths
+    /// are resolved against the project root. Equivalent to passing
+    /// `--cov-report=html:<dir>`. Defaults to unset, which disables the
+    /// report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"null"#,
+        value_type = "string",
+        example = r#"
+            html-dir = "htmlcov"
+        "#
+    )]
+    pub html_dir: Option<String>,
+
     /// Set by `--no-cov` to disable coverage for a single run, overriding
     /// any sources configured in `karva.toml`.
     ///
@@ -395,6 +697,54 @@ impl CoverageOptions {
             sources,
             report: self.report.unwrap_or_default(),
             fail_under: self.fail_under.map(|t| t.0),
+            fail_under_diff: self.fail_under_diff.map(|t| t.0),
+            diff_base: self.diff_base.clone(),
+            diff_file: self.diff_file.as_deref().map(Utf8PathBuf::from),
+            branch: self.branch.unwrap_or_default(),
+            context: self.context.unwrap_or_default(),
+            include: self.include.clone().unwrap_or_default(),
+            omit: self.omit.clone().unwrap_or_default(),
+            exclude_lines: self.exclude_lines.clone().unwrap_or_default(),
+            partial_branches: self.partial_branches.clone().unwrap_or_default(),
+            xml_path: self.xml_path.as_deref().map(Utf8PathBuf::from),
+            json_path: self.json_path.as_deref().map(Utf8PathBuf::from),
+            lcov_path: self.lcov_path.as_deref().map(Utf8PathBuf::from),
+            html_dir: self.html_dir.as_deref().map(Utf8PathBuf::from),
+        }
+    }
+}
+
+#[derive(
+    Debug, Default, Clone, Eq, PartialEq, Combine, Serialize, Deserialize, OptionsMetadata,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct SnapshotOptions {
+    /// When snapshot assertions write snapshot files.
+    ///
+    /// `new` writes every new or changed snapshot to a pending `.snap.new`
+    /// file, to review with `karva snapshot review` or accept with
+    /// `karva snapshot accept`. `always` writes them straight to `.snap`
+    /// files. `unseen` writes new snapshots to `.snap` files and keeps
+    /// changed ones pending. `no` writes nothing, so the run fails without
+    /// touching the tree. `auto` (the default) is `no` on CI, detected from
+    /// the `CI` environment variable, and `new` elsewhere.
+    ///
+    /// Equivalent to passing `--snapshot-update=<mode>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[option(
+        default = r#"auto"#,
+        value_type = "auto | new | always | unseen | no",
+        example = r#"
+            update = "unseen"
+        "#
+    )]
+    pub update: Option<SnapshotUpdateMode>,
+}
+
+impl SnapshotOptions {
+    pub fn to_settings(&self) -> SnapshotSettings {
+        SnapshotSettings {
+            update: self.update.unwrap_or_default(),
         }
     }
 }
@@ -535,7 +885,7 @@ nonsense = 42
           |
         4 | nonsense = 42
           | ^^^^^^^^
-        unknown field `nonsense`, expected one of `test-function-prefix`, `fail-fast`, `max-fail`, `try-import-fixtures`, `retry`, `no-tests`, `slow-timeout`, `timeout`
+        unknown field `nonsense`, expected one of `test-function-prefix`, `test-class-prefix`, `fail-fast`, `max-fail`, `try-import-fixtures`, `retry`, `no-tests`, `slow-timeout`, `timeout`, `timeout-method`, `respawn-on-crash`
         "
         );
     }
@@ -624,6 +974,7 @@ max-fail = 0
             test_function_prefix: Some(
                 "cli_prefix",
             ),
+            test_class_prefix: None,
             fail_fast: None,
             max_fail: None,
             try_import_fixtures: Some(
@@ -635,6 +986,8 @@ max-fail = 0
             no_tests: None,
             slow_timeout: None,
             timeout: None,
+            timeout_method: None,
+            respawn_on_crash: None,
         }
         "#);
     }
@@ -653,6 +1006,7 @@ max-fail = 0
             test_function_prefix: Some(
                 "from_file",
             ),
+            test_class_prefix: None,
             fail_fast: Some(
                 true,
             ),
@@ -664,6 +1018,8 @@ max-fail = 0
             no_tests: None,
             slow_timeout: None,
             timeout: None,
+            timeout_method: None,
+            respawn_on_crash: None,
         }
         "#);
     }
@@ -717,6 +1073,7 @@ retry = 2
                 test_function_prefix: Some(
                     "cli",
                 ),
+                test_class_prefix: None,
                 fail_fast: None,
                 max_fail: None,
                 try_import_fixtures: None,
@@ -726,6 +1083,8 @@ retry = 2
                 no_tests: None,
                 slow_timeout: None,
                 timeout: None,
+                timeout_method: None,
+                respawn_on_crash: None,
             },
         )
         "#);
@@ -771,6 +1130,7 @@ retry = 5
                 test_function_prefix: Some(
                     "base",
                 ),
+                test_class_prefix: None,
                 fail_fast: Some(
                     true,
                 ),
@@ -782,6 +1142,8 @@ retry = 5
                 no_tests: None,
                 slow_timeout: None,
                 timeout: None,
+                timeout_method: None,
+                respawn_on_crash: None,
             },
         )
         "#);
@@ -908,6 +1270,19 @@ report = "term-missing"
                     TermMissing,
                 ),
                 fail_under: None,
+                fail_under_diff: None,
+                diff_base: None,
+                diff_file: None,
+                branch: None,
+                context: None,
+                include: None,
+                omit: None,
+                exclude_lines: None,
+                partial_branches: None,
+                xml_path: None,
+                json_path: None,
+                lcov_path: None,
+                html_dir: None,
                 disabled: None,
             },
         )
@@ -939,6 +1314,19 @@ report = "term-missing"
                 TermMissing,
             ),
             fail_under: None,
+            fail_under_diff: None,
+            diff_base: None,
+            diff_file: None,
+            branch: None,
+            context: None,
+            include: None,
+            omit: None,
+            exclude_lines: None,
+            partial_branches: None,
+            xml_path: None,
+            json_path: None,
+            lcov_path: None,
+            html_dir: None,
             disabled: None,
         }
         "#);
@@ -978,6 +1366,38 @@ report = "term-missing"
         assert_debug_snapshot!(combined.to_settings().sources, @"[]");
     }
 
+    #[test]
+    fn to_settings_reads_coverage_patterns() {
+        let toml = r#"
+[profile.default.coverage]
+omit = ["*/migrations/*"]
+exclude-lines = ["if TYPE_CHECKING:", "@overload"]
+"#;
+        let coverage = Config::from_toml_str(toml)
+            .expect("parse")
+            .resolve_profile(None)
+            .expect("resolves")
+            .coverage
+            .expect("coverage options")
+            .to_settings();
+        assert_debug_snapshot!(
+            (coverage.include, coverage.omit, coverage.exclude_lines, coverage.partial_branches),
+            @r#"
+        (
+            [],
+            [
+                "*/migrations/*",
+            ],
+            [
+                "if TYPE_CHECKING:",
+                "@overload",
+            ],
+            [],
+        )
+        "#
+        );
+    }
+
     /// `disabled` is CLI-only; `deny_unknown_fields` should reject it from TOML.
     #[test]
     fn from_toml_str_rejects_disabled_key() {
@@ -992,11 +1412,34 @@ disabled = true
           |
         3 | disabled = true
           | ^^^^^^^^
-        unknown field `disabled`, expected one of `sources`, `report`, `fail-under`
+        unknown field `disabled`, expected one of `sources`, `report`, `fail-under`, `fail-under-diff`, `diff-base`, `diff-file`, `branch`, `context`, `include`, `omit`, `exclude-lines`, `partial-branches`, `xml-path`, `json-path`, `lcov-path`, `html-dir`
         "
         );
     }
 
+    #[test]
+    fn parse_snapshot_section() {
+        let toml = r#"
+[profile.default.snapshot]
+update = "unseen"
+
+[profile.ci.snapshot]
+update = "no"
+"#;
+        let config = Config::from_toml_str(toml).expect("parse");
+        let update = |profile| {
+            config
+                .clone()
+                .resolve_profile(profile)
+                .expect("resolves")
+                .to_settings()
+                .snapshot()
+                .update
+        };
+        assert_eq!(update(None), SnapshotUpdateMode::Unseen);
+        assert_eq!(update(Some("ci")), SnapshotUpdateMode::No);
+    }
+
     #[test]
     fn from_toml_str_rejects_unknown_coverage_key() {
         let toml = r#"
@@ -1011,7 +1454,7 @@ nonsense = 1
           |
         4 | nonsense = 1
           | ^^^^^^^^
-        unknown field `nonsense`, expected one of `sources`, `report`, `fail-under`
+        unknown field `nonsense`, expected one of `sources`, `report`, `fail-under`, `fail-under-diff`, `diff-base`, `diff-file`, `branch`, `context`, `include`, `omit`, `exclude-lines`, `partial-branches`, `xml-path`, `json-path`, `lcov-path`, `html-dir`
         "
         );
     }
@@ -1,7 +1,9 @@
 use std::time::Duration;
 
+use camino::Utf8PathBuf;
 use karva_combine::Combine;
 use karva_logging::{FinalStatusLevel, StatusLevel};
+use karva_static::EnvVars;
 use serde::{Deserialize, Serialize};
 
 use crate::filter::FiltersetSet;
@@ -36,6 +38,114 @@ impl Combine for NoTestsMode {
     }
 }
 
+/// How hard per-test timeouts are enforced.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub enum TimeoutMethod {
+    /// Run the test on a separate thread inside the worker and stop waiting
+    /// for it once the timeout expires.
+    #[default]
+    Thread,
+    /// Run the test on the worker's main thread and have the main process
+    /// kill the worker once the timeout expires.
+    Process,
+}
+
+impl TimeoutMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Thread => "thread",
+            Self::Process => "process",
+        }
+    }
+}
+
+impl Combine for TimeoutMethod {
+    #[inline(always)]
+    fn combine_with(&mut self, _other: Self) {}
+
+    #[inline]
+    fn combine(self, _other: Self) -> Self {
+        self
+    }
+}
+
+/// When snapshot assertions write snapshot files.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub enum SnapshotUpdateMode {
+    /// `no` when running on CI, `new` otherwise.
+    #[default]
+    Auto,
+    /// Write new and changed snapshots to pending `.snap.new` files.
+    New,
+    /// Write new and changed snapshots straight to `.snap` files.
+    Always,
+    /// Write new snapshots to `.snap` files, and changed ones to pending
+    /// `.snap.new` files.
+    Unseen,
+    /// Write no files: new and changed snapshots only fail the test.
+    No,
+}
+
+impl SnapshotUpdateMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::New => "new",
+            Self::Always => "always",
+            Self::Unseen => "unseen",
+            Self::No => "no",
+        }
+    }
+
+    /// Resolve `auto` for this environment: `no` on CI, detected from the
+    /// `CI` and `TF_BUILD` variables, and `new` elsewhere.
+    #[must_use]
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto if is_ci() => Self::No,
+            Self::Auto => Self::New,
+            mode => mode,
+        }
+    }
+
+    /// Whether new snapshots are written to `.snap` files.
+    pub fn writes_new(self) -> bool {
+        matches!(self, Self::Always | Self::Unseen)
+    }
+
+    /// Whether changed snapshots are written to `.snap` files.
+    pub fn writes_changed(self) -> bool {
+        matches!(self, Self::Always)
+    }
+
+    /// Whether snapshots that are not written to `.snap` files are written
+    /// to pending `.snap.new` files.
+    pub fn writes_pending(self) -> bool {
+        !matches!(self, Self::No)
+    }
+}
+
+impl Combine for SnapshotUpdateMode {
+    #[inline(always)]
+    fn combine_with(&mut self, _other: Self) {}
+
+    #[inline]
+    fn combine(self, _other: Self) -> Self {
+        self
+    }
+}
+
+/// Whether karva runs on a CI service. Most set `CI`; Azure Pipelines sets
+/// `TF_BUILD`.
+fn is_ci() -> bool {
+    let set = |name: &str| {
+        std::env::var(name).is_ok_and(|value| !matches!(value.as_str(), "" | "0" | "false"))
+    };
+    set(EnvVars::CI) || set(EnvVars::TF_BUILD)
+}
+
 /// A slow-test threshold expressed in seconds.
 ///
 /// Wraps `f64` so the surrounding [`crate::options::TestOptions`] can keep
@@ -50,8 +160,8 @@ impl Eq for SlowTimeoutSecs {}
 
 impl SlowTimeoutSecs {
     pub fn as_duration(self) -> Option<Duration> {
-        if self.0.is_finite() && self.0 > 0.0 {
-            Some(Duration::from_secs_f64(self.0))
+        if self.0 > 0.0 {
+            Duration::try_from_secs_f64(self.0).ok()
         } else {
             None
         }
@@ -81,8 +191,8 @@ impl Eq for TestTimeoutSecs {}
 
 impl TestTimeoutSecs {
     pub fn as_duration(self) -> Option<Duration> {
-        if self.0.is_finite() && self.0 > 0.0 {
-            Some(Duration::from_secs_f64(self.0))
+        if self.0 > 0.0 {
+            Duration::try_from_secs_f64(self.0).ok()
         } else {
             None
         }
@@ -127,6 +237,7 @@ pub struct ProjectSettings {
     pub(crate) src: SrcSettings,
     pub(crate) test: TestSettings,
     pub(crate) coverage: CoverageSettings,
+    pub(crate) snapshot: SnapshotSettings,
 }
 
 impl ProjectSettings {
@@ -146,6 +257,10 @@ impl ProjectSettings {
         &self.coverage
     }
 
+    pub fn snapshot(&self) -> &SnapshotSettings {
+        &self.snapshot
+    }
+
     pub fn max_fail(&self) -> MaxFail {
         self.test.max_fail
     }
@@ -157,6 +272,10 @@ impl ProjectSettings {
     pub fn set_run_ignored(&mut self, mode: RunIgnoredMode) {
         self.test.run_ignored = mode;
     }
+
+    pub fn set_resume(&mut self, resume: Option<ResumePoint>) {
+        self.test.resume = resume;
+    }
 }
 
 #[derive(Default, Debug, Clone)]
@@ -165,6 +284,9 @@ pub struct TerminalSettings {
     pub show_python_output: bool,
     pub status_level: StatusLevel,
     pub final_status_level: FinalStatusLevel,
+    /// Where to write the JUnit XML report, if one was requested. Relative
+    /// paths are resolved against the project root by the caller.
+    pub junit_path: Option<Utf8PathBuf>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -181,11 +303,44 @@ pub struct CoverageSettings {
     /// reported `TOTAL` coverage is below this value, the test command
     /// exits with a non-zero status even if every test passed.
     pub fail_under: Option<f64>,
+    /// Minimum coverage percentage (`0..=100`) of the lines changed by the
+    /// diff, checked like `fail_under`.
+    pub fail_under_diff: Option<f64>,
+    /// Git ref whose diff gives the changed lines, if any.
+    pub diff_base: Option<String>,
+    /// Unified diff file giving the changed lines, if any. `-` reads the
+    /// diff from stdin.
+    pub diff_file: Option<Utf8PathBuf>,
+    /// Whether branch coverage is measured alongside line coverage.
+    pub branch: bool,
+    /// Whether executed lines are tagged with the tests that ran them.
+    pub context: bool,
+    /// Globs of the files to measure. Empty measures every file under the
+    /// sources.
+    pub include: Vec<String>,
+    /// Globs of the files left out of the measurement.
+    pub omit: Vec<String>,
+    /// Regexes of the source lines excluded from coverage.
+    pub exclude_lines: Vec<String>,
+    /// Regexes of the branch lines whose arcs are not measured.
+    pub partial_branches: Vec<String>,
+    pub xml_path: Option<Utf8PathBuf>,
+    pub json_path: Option<Utf8PathBuf>,
+    pub lcov_path: Option<Utf8PathBuf>,
+    pub html_dir: Option<Utf8PathBuf>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct SnapshotSettings {
+    /// When snapshot assertions write snapshot files. Still `auto` until
+    /// [`SnapshotUpdateMode::resolve`] is called.
+    pub update: SnapshotUpdateMode,
 }
 
 #[derive(Default, Debug, Clone)]
 pub struct TestSettings {
     pub test_function_prefix: String,
+    pub test_class_prefix: String,
     pub max_fail: MaxFail,
     pub try_import_fixtures: bool,
     pub retry: u32,
@@ -199,4 +354,23 @@ pub struct TestSettings {
     /// killed and reported as failures. `None` disables the hard timeout
     /// (tests may still set their own limit via `@karva.tags.timeout`).
     pub timeout: Option<Duration>,
+    /// How `timeout` and `@karva.tags.timeout` limits are enforced.
+    pub timeout_method: TimeoutMethod,
+    /// Start a fresh worker for the remaining tests when a worker crashes.
+    pub respawn_on_crash: bool,
+    /// A test function whose first variants an earlier worker already ran.
+    /// Only set on a worker that takes over from one that crashed or timed
+    /// out.
+    pub resume: Option<ResumePoint>,
+}
+
+/// Where a worker picks up a parametrized test that an earlier worker
+/// stopped partway through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResumePoint {
+    /// Qualified name of the test function, e.g. `test_a::test_1` or
+    /// `test_a::TestClass::test_1`.
+    pub test: String,
+    /// How many of its variants already finished, in the order they run.
+    pub finished_variants: usize,
 }
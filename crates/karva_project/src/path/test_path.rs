@@ -26,20 +26,20 @@ pub struct TestPathFunction {
     pub function_name: String,
 }
 
-/// Parse a `path::function` specification.
+/// Parse a `path::function` or `path::Class::method` specification.
+///
+/// Everything after the first `::` is the function name, so methods of a
+/// test class are named `Class::method`.
 ///
 /// Returns `Ok(None)` when `value` does not contain a `::` separator (callers
 /// can then fall back to file/directory parsing). Any other failure—including
 /// an empty function name or a non-Python target file—is returned as `Err`.
 fn parse_function_spec(value: &str) -> Result<Option<TestPathFunction>, TestPathError> {
-    let Some(separator_pos) = value.rfind("::") else {
+    let Some((file_part, function_name)) = value.split_once("::") else {
         return Ok(None);
     };
 
-    let file_part = &value[..separator_pos];
-    let function_name = &value[separator_pos + 2..];
-
-    if function_name.is_empty() {
+    if function_name.split("::").any(str::is_empty) {
         return Err(TestPathError::MissingFunctionName(Utf8PathBuf::from(
             file_part,
         )));
@@ -81,6 +81,8 @@ pub enum TestPath {
     /// Some examples are:
     /// - `test_file.py::test_function`
     /// - `test_file::test_function`
+    /// - `test_file.py::TestClass::test_method`
+    /// - `test_file.py::TestClass`
     Function(TestPathFunction),
 }
 
@@ -315,6 +317,40 @@ mod tests {
         assert!(matches!(result, Err(TestPathError::MissingFunctionName(_))));
     }
 
+    #[test]
+    fn test_method_specification() {
+        let env = TestEnv::new();
+        let path = env.create_file(
+            "test.py",
+            "class TestClass:\n    def test_method(self): pass",
+        );
+
+        let function_spec = format!("{path}::TestClass::test_method");
+        let result = TestPath::new(&function_spec);
+
+        assert_eq!(
+            result,
+            Ok(TestPath::Function(TestPathFunction {
+                path,
+                function_name: "TestClass::test_method".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_method_specification_empty_method_name() {
+        let env = TestEnv::new();
+        let path = env.create_file(
+            "test.py",
+            "class TestClass:\n    def test_method(self): pass",
+        );
+
+        let function_spec = format!("{path}::TestClass::");
+        let result = TestPath::new(&function_spec);
+
+        assert!(matches!(result, Err(TestPathError::MissingFunctionName(_))));
+    }
+
     #[test]
     fn test_function_specification_nonexistent_file() {
         let env = TestEnv::new();
@@ -20,6 +20,17 @@ pub fn is_fixture_function(val: &StmtFunctionDef) -> bool {
         .any(|decorator| is_fixture(&decorator.expression))
 }
 
+/// Check if a function definition has a @staticmethod decorator
+pub fn is_staticmethod(val: &StmtFunctionDef) -> bool {
+    val.decorator_list
+        .iter()
+        .any(|decorator| match &decorator.expression {
+            Expr::Name(name) => name.id == "staticmethod",
+            Expr::Attribute(attr) => attr.attr.id == "staticmethod",
+            _ => false,
+        })
+}
+
 /// Check whether an expression resolves to a fixture reference.
 pub fn is_fixture(expr: &Expr) -> bool {
     match expr {
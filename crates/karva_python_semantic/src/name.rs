@@ -4,9 +4,13 @@ use serde::{Serialize, Serializer};
 use crate::module_name;
 
 /// Represents a fully qualified function name including its module path.
+///
+/// Methods of a test class also carry the name of their class, which is
+/// displayed between the module and the function (`module::Class::function`).
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct QualifiedFunctionName {
     function_name: String,
+    class_name: Option<String>,
     module_path: ModulePath,
 }
 
@@ -15,15 +19,37 @@ impl QualifiedFunctionName {
     pub fn new(function_name: String, module_path: ModulePath) -> Self {
         Self {
             function_name,
+            class_name: None,
             module_path,
         }
     }
 
+    /// Qualify the function as a method of the given class.
+    #[must_use]
+    pub fn with_class_name(mut self, class_name: String) -> Self {
+        self.class_name = Some(class_name);
+        self
+    }
+
     /// Return the unqualified function name.
     pub fn function_name(&self) -> &str {
         &self.function_name
     }
 
+    /// Return the name of the class this function is a method of, if any.
+    pub fn class_name(&self) -> Option<&str> {
+        self.class_name.as_deref()
+    }
+
+    /// Return the name of the function within its module, e.g.
+    /// `TestMath::test_add` for a method of a test class.
+    pub fn local_name(&self) -> String {
+        match &self.class_name {
+            Some(class_name) => format!("{class_name}::{}", self.function_name),
+            None => self.function_name.clone(),
+        }
+    }
+
     /// Return the module path this function belongs to.
     pub fn module_path(&self) -> &ModulePath {
         &self.module_path
@@ -36,7 +62,7 @@ impl std::fmt::Display for QualifiedFunctionName {
             f,
             "{}::{}",
             self.module_path.module_name(),
-            self.function_name
+            self.local_name()
         )
     }
 }
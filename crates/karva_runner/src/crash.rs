@@ -0,0 +1,518 @@
+//! Accounting for workers that exit without saving their results.
+//!
+//! A worker normally writes its results to the cache just before it exits.
+//! When it crashes instead, for example through a segfault in a native
+//! extension or a call to `os._exit`, or is killed because a test ran past
+//! its timeout, those results are never written. The main process
+//! reconstructs what it can from the events the worker sent before it
+//! stopped, and reports the test that was running at the time as a failure.
+//! The same applies to workers the main process kills once the run reaches
+//! its max-fail limit.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+
+use camino::Utf8PathBuf;
+use colored::Colorize;
+
+use karva_cache::AggregatedResults;
+use karva_diagnostic::{
+    EventTestName, RunEvent, TestCaseFailure, TestCaseOutcome, TestCaseRecord, TestResultKind,
+    TestResultStats,
+};
+use karva_metadata::{OutputFormat, ResumePoint};
+
+use crate::partition::Partition;
+
+/// What the main process has learned about a worker process from its events.
+#[derive(Debug, Default)]
+pub struct WorkerProgress {
+    /// The test that has started but not yet finished.
+    in_flight: Option<InFlightTest>,
+    /// Qualified `module::function` names of every test the worker reached,
+    /// with methods of test classes named `module::Class::method`.
+    reached: HashSet<String>,
+    stats: TestResultStats,
+    /// Qualified names of the failed tests, in the form read by `--last-failed`.
+    failed_tests: Vec<String>,
+    /// Every test that finished, in order.
+    finished: Vec<FinishedTest>,
+    /// The test function the worker most recently ran a variant of, with
+    /// how many of its variants finished. The worker may have stopped
+    /// before running the rest.
+    last_function: Option<ResumePoint>,
+}
+
+#[derive(Debug)]
+struct FinishedTest {
+    test: EventTestName,
+    outcome: TestCaseOutcome,
+    duration: Duration,
+    skip_reason: Option<String>,
+}
+
+impl FinishedTest {
+    /// The record of this test. The worker keeps the details of a failure to
+    /// itself until it saves its results, so a failed test only says that
+    /// they were lost.
+    fn record(&self, partition: &Partition) -> TestCaseRecord {
+        let file = partition
+            .file_of(&qualified_function_name(&self.test))
+            .map(Utf8PathBuf::from)
+            .unwrap_or_default();
+        let failure = matches!(
+            self.outcome,
+            TestCaseOutcome::Failed | TestCaseOutcome::TimedOut
+        )
+        .then(|| TestCaseFailure {
+            kind: None,
+            message: "The worker process stopped before saving the failure details".to_string(),
+            details: None,
+        });
+        TestCaseRecord {
+            module_name: self.test.module.clone(),
+            function_name: self.test.function.clone(),
+            params: self.test.params.clone(),
+            file,
+            outcome: self.outcome,
+            duration: self.duration,
+            skip_reason: self.skip_reason.clone(),
+            failure,
+            stdout: String::new(),
+            stderr: String::new(),
+            attempts: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct InFlightTest {
+    test: EventTestName,
+    started: Instant,
+    /// When the current attempt started. The timeout applies to each attempt
+    /// of a retried test separately.
+    attempt_started: Instant,
+    timeout: Option<Duration>,
+}
+
+impl WorkerProgress {
+    pub fn record(&mut self, event: &RunEvent) {
+        match event {
+            RunEvent::TestStarted { test, timeout } => {
+                self.count_variant(test, false);
+                self.reached.insert(qualified_function_name(test));
+                let now = Instant::now();
+                self.in_flight = Some(InFlightTest {
+                    test: test.clone(),
+                    started: now,
+                    attempt_started: now,
+                    timeout: timeout.and_then(|seconds| Duration::try_from_secs_f64(seconds).ok()),
+                });
+            }
+            RunEvent::TestAttempt { .. } => {
+                if let Some(in_flight) = &mut self.in_flight {
+                    in_flight.attempt_started = Instant::now();
+                }
+            }
+            RunEvent::TestFinished {
+                test,
+                outcome,
+                duration,
+                flaky,
+                skip_reason,
+                ..
+            } => {
+                self.count_variant(test, true);
+                self.reached.insert(qualified_function_name(test));
+                self.in_flight = None;
+                match outcome {
+                    TestCaseOutcome::Passed => self.stats.add(TestResultKind::Passed),
+                    TestCaseOutcome::Failed | TestCaseOutcome::TimedOut => {
+                        self.stats.add(TestResultKind::Failed);
+                        self.failed_tests.push(qualified_function_name(test));
+                    }
+                    TestCaseOutcome::Skipped => self.stats.add(TestResultKind::Skipped),
+                }
+                if *flaky {
+                    self.stats.add(TestResultKind::Flaky);
+                }
+                self.finished.push(FinishedTest {
+                    test: test.clone(),
+                    outcome: *outcome,
+                    duration: Duration::try_from_secs_f64(*duration).unwrap_or_default(),
+                    skip_reason: skip_reason.clone(),
+                });
+            }
+            RunEvent::TestSlow { .. } => self.stats.add(TestResultKind::Slow),
+            RunEvent::RunStarted { .. } | RunEvent::RunFinished { .. } => {}
+        }
+    }
+
+    /// Track the variants of the test function that is running. The variants
+    /// of one function run one after another, so a new function starts the
+    /// count again.
+    fn count_variant(&mut self, test: &EventTestName, finished: bool) {
+        let name = qualified_function_name(test);
+        if self
+            .last_function
+            .as_ref()
+            .is_none_or(|last_function| last_function.test != name)
+        {
+            self.last_function = Some(ResumePoint {
+                test: name,
+                finished_variants: 0,
+            });
+        }
+        if finished && let Some(last_function) = &mut self.last_function {
+            last_function.finished_variants += 1;
+        }
+    }
+
+    /// The test that has started but not finished, and how long it has
+    /// been running.
+    pub fn in_flight(&self) -> Option<(&EventTestName, Duration)> {
+        self.in_flight
+            .as_ref()
+            .map(|in_flight| (&in_flight.test, in_flight.started.elapsed()))
+    }
+
+    /// The timeout of the in-flight test, if its current attempt has run
+    /// longer than that.
+    pub fn exceeded_timeout(&self) -> Option<Duration> {
+        let in_flight = self.in_flight.as_ref()?;
+        let timeout = in_flight.timeout?;
+        (in_flight.attempt_started.elapsed() > timeout).then_some(timeout)
+    }
+
+    /// Qualified names of the test functions the worker reached.
+    pub fn reached(&self) -> &HashSet<String> {
+        &self.reached
+    }
+
+    /// The test function the worker may have stopped partway through, with
+    /// how many of its variants finished.
+    pub fn last_function(&self) -> Option<&ResumePoint> {
+        self.last_function.as_ref()
+    }
+}
+
+/// Why a worker process stopped before saving its results.
+#[derive(Debug, Clone, Copy)]
+pub enum Interruption {
+    /// The process exited on its own.
+    Crash(ExitStatus),
+    /// The main process killed it because its test ran past this timeout.
+    Timeout(Duration),
+}
+
+impl Interruption {
+    /// The outcome recorded for the test that was running.
+    pub fn outcome(self) -> TestCaseOutcome {
+        match self {
+            Self::Crash(_) => TestCaseOutcome::Failed,
+            Self::Timeout(_) => TestCaseOutcome::TimedOut,
+        }
+    }
+}
+
+/// The results of a worker process that crashed, timed out or was stopped at
+/// the max-fail limit, to be merged with the results read from the cache.
+pub struct CrashReport {
+    stats: TestResultStats,
+    failed_tests: Vec<String>,
+    test_cases: Vec<TestCaseRecord>,
+    diagnostic: String,
+}
+
+impl CrashReport {
+    /// Build the report for an interrupted worker.
+    ///
+    /// `progress` must already include the failure of the in-flight test, as
+    /// its last finished test, so that the test is counted like any other
+    /// failure.
+    pub fn new(
+        worker_id: usize,
+        interruption: Interruption,
+        interrupted: bool,
+        progress: WorkerProgress,
+        partition: &Partition,
+        output_format: OutputFormat,
+    ) -> Self {
+        let failure = match interruption {
+            Interruption::Crash(status) => TestCaseFailure {
+                kind: Some("WorkerCrash".to_string()),
+                message: format!("The worker process {}", describe_exit_status(status)),
+                details: None,
+            },
+            Interruption::Timeout(timeout) => TestCaseFailure {
+                kind: Some("Timeout".to_string()),
+                message: format!("Test exceeded timeout of {} seconds", timeout.as_secs_f64()),
+                details: None,
+            },
+        };
+
+        let mut test_cases: Vec<TestCaseRecord> = progress
+            .finished
+            .iter()
+            .map(|finished| finished.record(partition))
+            .collect();
+        let test_case = if interrupted {
+            test_cases.last_mut().map(|record| {
+                record.failure = Some(failure.clone());
+                &*record
+            })
+        } else {
+            None
+        };
+
+        let (id, message, detail) = match interruption {
+            Interruption::Crash(_) => (
+                "worker-crash",
+                match test_case {
+                    Some(record) => format!(
+                        "Test `{}` crashed worker {worker_id}",
+                        record.display_name()
+                    ),
+                    None => format!("Worker {worker_id} crashed outside of a test"),
+                },
+                failure.message,
+            ),
+            Interruption::Timeout(timeout) => (
+                "test-timeout",
+                match test_case {
+                    Some(record) => format!(
+                        "Test `{}` exceeded timeout of {} seconds",
+                        record.display_name(),
+                        timeout.as_secs_f64()
+                    ),
+                    None => format!("Worker {worker_id} was stopped after a test timed out"),
+                },
+                format!("Worker {worker_id} was killed to stop the test"),
+            ),
+        };
+
+        let mut diagnostic = String::new();
+        let label = format!("error[{id}]").red().bold();
+        match output_format {
+            OutputFormat::Full => {
+                let _ = writeln!(diagnostic, "{label}: {message}");
+                let _ = writeln!(diagnostic, "{}: {detail}", "info".cyan().bold());
+                let _ = writeln!(diagnostic);
+            }
+            OutputFormat::Concise => {
+                let _ = writeln!(diagnostic, "{label} {message}: {detail}");
+            }
+        }
+
+        Self {
+            stats: progress.stats,
+            failed_tests: progress.failed_tests,
+            test_cases,
+            diagnostic,
+        }
+    }
+
+    /// Build the report for a worker killed because the run reached its
+    /// max-fail limit. The test it was running is left out, since it was
+    /// stopped rather than failed.
+    pub fn stopped(
+        worker_id: usize,
+        progress: WorkerProgress,
+        partition: &Partition,
+        output_format: OutputFormat,
+    ) -> Self {
+        let mut diagnostic = String::new();
+        let failed = progress.failed_tests.len();
+        if failed > 0 {
+            let label = "warning[max-fail]".yellow().bold();
+            let message = format!("Worker {worker_id} was stopped at the max-fail limit");
+            let tests = if failed == 1 { "test" } else { "tests" };
+            let detail = format!("The details of its {failed} failed {tests} were not saved");
+            match output_format {
+                OutputFormat::Full => {
+                    let _ = writeln!(diagnostic, "{label}: {message}");
+                    let _ = writeln!(diagnostic, "{}: {detail}", "info".cyan().bold());
+                    let _ = writeln!(diagnostic);
+                }
+                OutputFormat::Concise => {
+                    let _ = writeln!(diagnostic, "{label} {message}: {detail}");
+                }
+            }
+        }
+
+        Self {
+            stats: progress.stats,
+            failed_tests: progress.failed_tests,
+            test_cases: progress
+                .finished
+                .iter()
+                .map(|finished| finished.record(partition))
+                .collect(),
+            diagnostic,
+        }
+    }
+
+    pub fn merge_into(self, results: &mut AggregatedResults) {
+        results.stats.merge(&self.stats);
+        results.failed_tests.extend(self.failed_tests);
+        results.test_cases.extend(self.test_cases);
+        results.diagnostics.push_str(&self.diagnostic);
+    }
+}
+
+/// The event reporting the final outcome of the test that was running when
+/// its worker was interrupted.
+pub fn interrupted_test_event(
+    test: &EventTestName,
+    duration: Duration,
+    interruption: Interruption,
+) -> RunEvent {
+    RunEvent::TestFinished {
+        test: test.clone(),
+        outcome: interruption.outcome(),
+        duration: duration.as_secs_f64(),
+        attempt: 1,
+        max_attempts: None,
+        flaky: false,
+        skip_reason: None,
+    }
+}
+
+fn qualified_function_name(test: &EventTestName) -> String {
+    format!("{}::{}", test.module, test.function)
+}
+
+/// Describe how a worker process ended, e.g. `exited with code 3` or
+/// `was terminated by signal 11 (SIGSEGV)`.
+pub fn describe_exit_status(status: ExitStatus) -> String {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            return match signal_name(signal) {
+                Some(name) => format!("was terminated by signal {signal} ({name})"),
+                None => format!("was terminated by signal {signal}"),
+            };
+        }
+    }
+
+    match status.code() {
+        Some(code) => format!("exited with code {code}"),
+        None => "exited with an unknown status".to_string(),
+    }
+}
+
+/// Names of the signals a crashing test is likely to raise. These numbers
+/// are the same on Linux and macOS.
+#[cfg(unix)]
+fn signal_name(signal: i32) -> Option<&'static str> {
+    match signal {
+        4 => Some("SIGILL"),
+        6 => Some("SIGABRT"),
+        8 => Some("SIGFPE"),
+        9 => Some("SIGKILL"),
+        11 => Some("SIGSEGV"),
+        15 => Some("SIGTERM"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_name(function: &str) -> EventTestName {
+        EventTestName {
+            name: format!("test_a::{function}"),
+            module: "test_a".to_string(),
+            function: function.to_string(),
+            params: None,
+        }
+    }
+
+    #[test]
+    fn progress_tracks_the_in_flight_test() {
+        let mut progress = WorkerProgress::default();
+
+        progress.record(&RunEvent::TestStarted {
+            test: test_name("test_one"),
+            timeout: None,
+        });
+        progress.record(&RunEvent::TestFinished {
+            test: test_name("test_one"),
+            outcome: TestCaseOutcome::Passed,
+            duration: 0.1,
+            attempt: 1,
+            max_attempts: None,
+            flaky: false,
+            skip_reason: None,
+        });
+        assert!(progress.in_flight().is_none());
+
+        progress.record(&RunEvent::TestStarted {
+            test: test_name("test_two"),
+            timeout: None,
+        });
+        let (test, _) = progress.in_flight().expect("test_two is in flight");
+        assert_eq!(test.name, "test_a::test_two");
+
+        let mut reached: Vec<_> = progress.reached().iter().cloned().collect();
+        reached.sort();
+        assert_eq!(reached, ["test_a::test_one", "test_a::test_two"]);
+    }
+
+    #[test]
+    fn progress_counts_the_finished_variants_of_the_last_function() {
+        let mut progress = WorkerProgress::default();
+        let finished = |function: &str| RunEvent::TestFinished {
+            test: test_name(function),
+            outcome: TestCaseOutcome::Passed,
+            duration: 0.1,
+            attempt: 1,
+            max_attempts: None,
+            flaky: false,
+            skip_reason: None,
+        };
+
+        progress.record(&finished("test_one"));
+        progress.record(&finished("test_param"));
+        progress.record(&finished("test_param"));
+        progress.record(&RunEvent::TestStarted {
+            test: test_name("test_param"),
+            timeout: None,
+        });
+
+        assert_eq!(
+            progress.last_function(),
+            Some(&ResumePoint {
+                test: "test_a::test_param".to_string(),
+                finished_variants: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn progress_reports_an_exceeded_timeout() {
+        let mut progress = WorkerProgress::default();
+
+        progress.record(&RunEvent::TestStarted {
+            test: test_name("test_untimed"),
+            timeout: None,
+        });
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(progress.exceeded_timeout(), None);
+
+        progress.record(&RunEvent::TestStarted {
+            test: test_name("test_hang"),
+            timeout: Some(0.001),
+        });
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(
+            progress.exceeded_timeout(),
+            Some(Duration::from_secs_f64(0.001))
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! Event channel between workers and the main process.
+//!
+//! The main process listens on a loopback TCP socket and passes its address
+//! to every worker, along with a random token for the run. A worker connects
+//! once at startup, writes the token and its worker id on the first line, and
+//! then streams one [`RunEvent`] per line as tests execute. Any local process
+//! can connect to the socket, so connections without the token are dropped
+//! before their events are read. Each connection is read on its own thread
+//! and forwarded into a single channel, so the main process sees every
+//! worker's events in the order they arrive.
+
+use std::io::{BufRead, BufReader};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use anyhow::{Context, Result};
+use crossbeam_channel::{Receiver, Sender};
+
+use karva_diagnostic::RunEvent;
+
+/// A message received from a worker connection.
+#[derive(Debug)]
+pub enum WorkerMessage {
+    /// The worker connected and identified itself.
+    Connected { worker_id: usize },
+    /// The worker reported an event.
+    Event { worker_id: usize, event: RunEvent },
+    /// The worker's connection closed, normally because the process exited.
+    Disconnected { worker_id: usize },
+}
+
+/// Listens for worker connections for the duration of a run.
+pub struct EventServer {
+    address: SocketAddr,
+    token: String,
+    receiver: Receiver<WorkerMessage>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl EventServer {
+    /// Bind a listener on an ephemeral loopback port and start accepting
+    /// worker connections in the background.
+    pub fn bind() -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .context("Failed to bind the worker event socket")?;
+        let address = listener.local_addr()?;
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let accept_stopped = Arc::clone(&stopped);
+        let accept_token = token.clone();
+        thread::spawn(move || {
+            accept_connections(&listener, &sender, &accept_token, &accept_stopped);
+        });
+
+        Ok(Self {
+            address,
+            token,
+            receiver,
+            stopped,
+        })
+    }
+
+    /// The address workers should connect to.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The token workers must send to be accepted.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn receiver(&self) -> &Receiver<WorkerMessage> {
+        &self.receiver
+    }
+}
+
+impl Drop for EventServer {
+    fn drop(&mut self) {
+        // Wake the accept loop with a throwaway connection so it notices the
+        // flag and releases the listener.
+        self.stopped.store(true, Ordering::SeqCst);
+        let _ = TcpStream::connect(self.address);
+    }
+}
+
+fn accept_connections(
+    listener: &TcpListener,
+    sender: &Sender<WorkerMessage>,
+    token: &str,
+    stopped: &AtomicBool,
+) {
+    for stream in listener.incoming() {
+        if stopped.load(Ordering::SeqCst) {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                let sender = sender.clone();
+                let token = token.to_string();
+                thread::spawn(move || read_worker_events(stream, &sender, &token));
+            }
+            Err(err) => tracing::warn!("Failed to accept worker connection: {err}"),
+        }
+    }
+}
+
+fn read_worker_events(stream: TcpStream, sender: &Sender<WorkerMessage>, token: &str) {
+    let mut lines = BufReader::new(stream).lines();
+
+    let Some(Ok(handshake)) = lines.next() else {
+        return;
+    };
+    let worker_id = match handshake.trim().split_once(' ') {
+        Some((sent_token, worker_id)) if sent_token == token => worker_id.parse::<usize>().ok(),
+        _ => None,
+    };
+    let Some(worker_id) = worker_id else {
+        tracing::warn!(
+            "Ignoring a connection to the worker event socket with an invalid handshake"
+        );
+        return;
+    };
+
+    let _ = sender.send(WorkerMessage::Connected { worker_id });
+
+    for line in lines {
+        let Ok(line) = line else {
+            break;
+        };
+        match RunEvent::from_line(&line) {
+            Ok(event) => {
+                let _ = sender.send(WorkerMessage::Event { worker_id, event });
+            }
+            Err(err) => {
+                tracing::warn!("Ignoring malformed event from worker {worker_id}: {err}");
+            }
+        }
+    }
+
+    let _ = sender.send(WorkerMessage::Disconnected { worker_id });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::time::Duration;
+
+    use karva_diagnostic::EventTestName;
+
+    use super::*;
+
+    fn recv(server: &EventServer) -> WorkerMessage {
+        server
+            .receiver()
+            .recv_timeout(Duration::from_secs(5))
+            .expect("expected a worker message")
+    }
+
+    #[test]
+    fn forwards_events_from_a_worker_connection() {
+        let server = EventServer::bind().unwrap();
+
+        let mut stream = TcpStream::connect(server.address()).unwrap();
+        writeln!(stream, "{} 3", server.token()).unwrap();
+        RunEvent::TestStarted {
+            test: EventTestName {
+                name: "test_a::test_one".to_string(),
+                module: "test_a".to_string(),
+                function: "test_one".to_string(),
+                params: None,
+            },
+            timeout: None,
+        }
+        .write_line(&mut stream)
+        .unwrap();
+        drop(stream);
+
+        assert!(matches!(
+            recv(&server),
+            WorkerMessage::Connected { worker_id: 3 }
+        ));
+        match recv(&server) {
+            WorkerMessage::Event {
+                worker_id: 3,
+                event: RunEvent::TestStarted { test, .. },
+            } => assert_eq!(test.name, "test_a::test_one"),
+            other => panic!("unexpected message: {other:?}"),
+        }
+        assert!(matches!(
+            recv(&server),
+            WorkerMessage::Disconnected { worker_id: 3 }
+        ));
+    }
+
+    #[test]
+    fn drops_connections_without_the_token() {
+        let server = EventServer::bind().unwrap();
+
+        for handshake in ["3", "wrong-token 3"] {
+            let mut stream = TcpStream::connect(server.address()).unwrap();
+            writeln!(stream, "{handshake}").unwrap();
+            RunEvent::RunStarted {
+                test_count: 1,
+                worker_count: 1,
+            }
+            .write_line(&mut stream)
+            .unwrap();
+        }
+
+        let mut stream = TcpStream::connect(server.address()).unwrap();
+        writeln!(stream, "{} 4", server.token()).unwrap();
+        drop(stream);
+
+        assert!(matches!(
+            recv(&server),
+            WorkerMessage::Connected { worker_id: 4 }
+        ));
+        assert!(matches!(
+            recv(&server),
+            WorkerMessage::Disconnected { worker_id: 4 }
+        ));
+    }
+}
@@ -1,5 +1,7 @@
 mod binary;
 mod collection;
+mod crash;
+mod ipc;
 mod orchestration;
 mod partition;
 mod shutdown;
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 use std::fmt::Write;
-use std::process::{Child, Stdio};
+use std::process::{Child, ExitStatus, Stdio};
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
@@ -13,14 +13,20 @@ use karva_cache::{
     AggregatedResults, CACHE_DIR, RunCache, RunHash, read_last_failed, read_recent_durations,
     write_last_failed,
 };
-use karva_cli::{PartitionSelection, SubTestCommand};
+use karva_cli::{MessageFormat, PartitionSelection, SubTestCommand};
 use karva_collector::{CollectedPackage, CollectionSettings};
+use karva_diagnostic::{RunEvent, TestCaseOutcome, TestCaseReporter};
 use karva_logging::Printer;
 use karva_logging::time::format_duration;
+use karva_metadata::{MaxFail, TimeoutMethod};
 use karva_project::Project;
 
 use crate::binary::find_karva_worker_binary;
 use crate::collection::ParallelCollector;
+use crate::crash::{
+    CrashReport, Interruption, WorkerProgress, describe_exit_status, interrupted_test_event,
+};
+use crate::ipc::{EventServer, WorkerMessage};
 use crate::partition::{Partition, partition_collected_tests};
 use crate::worker_args::{WorkerSpawn, worker_command};
 
@@ -28,40 +34,209 @@ use crate::worker_args::{WorkerSpawn, worker_command};
 struct Worker {
     id: usize,
     child: Child,
+    /// The tests this worker process was started with.
+    partition: Partition,
     start_time: Instant,
+    /// When the process exited, if it has.
+    exited_at: Option<Instant>,
+    /// How the process exited, once it has.
+    status: Option<ExitStatus>,
+    /// Whether the worker's event connection has closed.
+    disconnected: bool,
+    /// Number of failed tests this worker has reported.
+    failed_count: u32,
+    progress: WorkerProgress,
+    /// The test timeout this worker was killed for exceeding, if any.
+    timed_out: Option<Duration>,
 }
 
 impl Worker {
-    fn new(id: usize, child: Child) -> Self {
+    fn new(id: usize, child: Child, partition: Partition) -> Self {
         Self {
             id,
             child,
+            partition,
             start_time: Instant::now(),
+            exited_at: None,
+            status: None,
+            disconnected: false,
+            failed_count: 0,
+            progress: WorkerProgress::default(),
+            timed_out: None,
         }
     }
 
     fn duration(&self) -> Duration {
         self.start_time.elapsed()
     }
+
+    /// A worker is done once its process has exited and every event it sent
+    /// has been received.
+    ///
+    /// The connection normally closes together with the process, but the
+    /// reader may lag slightly behind the exit, a worker that failed early
+    /// may never have connected, and a process forked by a test can keep the
+    /// socket open. Waiting at most [`WORKER_DRAIN_TIMEOUT`] after the exit
+    /// covers all three.
+    fn is_done(&self) -> bool {
+        self.exited_at.is_some_and(|exited_at| {
+            self.disconnected || exited_at.elapsed() >= WORKER_DRAIN_TIMEOUT
+        })
+    }
+
+    /// Kill the process if the test it is running has exceeded its timeout.
+    fn enforce_timeout(&mut self) {
+        if self.exited_at.is_some() || self.timed_out.is_some() {
+            return;
+        }
+        let Some(timeout) = self.progress.exceeded_timeout() else {
+            return;
+        };
+        tracing::info!(
+            "Worker {} exceeded a test timeout of {} — killing it",
+            self.id,
+            format_duration(timeout),
+        );
+        let _ = self.child.kill();
+        self.timed_out = Some(timeout);
+    }
+
+    /// Check whether the process has exited, logging its status if so.
+    fn poll_exit(&mut self) {
+        if self.exited_at.is_some() {
+            return;
+        }
+        match self.child.try_wait() {
+            Ok(Some(status)) => {
+                if self.timed_out.is_some() {
+                    tracing::info!(
+                        "Worker {} stopped after {}",
+                        self.id,
+                        format_duration(self.duration()),
+                    );
+                } else if status.success() {
+                    tracing::info!(
+                        "Worker {} completed successfully in {}",
+                        self.id,
+                        format_duration(self.duration()),
+                    );
+                } else {
+                    tracing::error!(
+                        "Worker {} {} after {}",
+                        self.id,
+                        describe_exit_status(status),
+                        format_duration(self.duration()),
+                    );
+                }
+                self.exited_at = Some(Instant::now());
+                self.status = Some(status);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::error!("Error waiting on worker {}: {}", self.id, e);
+                self.exited_at = Some(Instant::now());
+                self.disconnected = true;
+            }
+        }
+    }
+}
+
+/// Where events streamed from workers are rendered.
+enum EventOutput {
+    /// Render events as terminal result lines.
+    Terminal(TestCaseReporter),
+    /// Re-emit events on stdout for `--message-format json`.
+    Json,
+}
+
+impl EventOutput {
+    fn render(&self, event: &RunEvent) {
+        match self {
+            Self::Terminal(reporter) => reporter.report_event(event),
+            Self::Json => event.emit(),
+        }
+    }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default)]
 struct WorkerManager {
     workers: Vec<Worker>,
+    /// Results recovered from workers that crashed, timed out or were stopped
+    /// at the max-fail limit before saving their own.
+    crashes: Vec<CrashReport>,
+    /// Number of failed tests reported across all workers.
+    failed_count: u32,
 }
 
 impl WorkerManager {
-    fn spawn(&mut self, worker_id: usize, child: Child) {
-        self.workers.push(Worker::new(worker_id, child));
+    fn spawn(&mut self, spawn: &WorkerSpawn, worker_id: usize, partition: Partition) -> Result<()> {
+        // The JSON event stream owns stdout, so anything a worker prints,
+        // such as test output shown with `-s`, goes to stderr instead.
+        let stdout = if spawn
+            .args
+            .message_format
+            .is_some_and(MessageFormat::is_json)
+        {
+            Stdio::from(std::io::stderr())
+        } else {
+            Stdio::inherit()
+        };
+        let child = worker_command(spawn, worker_id, &partition)
+            .stdout(stdout)
+            .stderr(Stdio::inherit())
+            .spawn()
+            .context("Failed to spawn karva-worker process")?;
+
+        tracing::info!(
+            "Worker {} spawned with {} tests",
+            worker_id,
+            partition.tests().len()
+        );
+
+        self.workers.push(Worker::new(worker_id, child, partition));
+        Ok(())
     }
 
-    /// Wait for all workers to complete.
-    /// Returns early if a message is received on `shutdown_rx` or if the cache
-    /// contains a fail-fast signal indicating a worker encountered a test failure.
+    fn worker_mut(&mut self, worker_id: usize) -> Option<&mut Worker> {
+        self.workers
+            .iter_mut()
+            .find(|worker| worker.id == worker_id)
+    }
+
+    /// Render an event and update the bookkeeping for the worker that sent it.
+    fn handle_event(&mut self, worker_id: usize, event: &RunEvent, output: &EventOutput) {
+        output.render(event);
+
+        let failed = matches!(
+            event,
+            RunEvent::TestFinished {
+                outcome: TestCaseOutcome::Failed | TestCaseOutcome::TimedOut,
+                ..
+            }
+        );
+        if failed {
+            self.failed_count = self.failed_count.saturating_add(1);
+        }
+
+        if let Some(worker) = self.worker_mut(worker_id) {
+            worker.progress.record(event);
+            if failed {
+                worker.failed_count = worker.failed_count.saturating_add(1);
+            }
+        }
+    }
+
+    /// Wait for all workers to complete, rendering their events as they
+    /// arrive.
+    ///
+    /// Returns early if a message is received on `shutdown_rx` or once the
+    /// failures reported across all workers reach the max-fail limit.
     fn wait_for_completion(
         &mut self,
+        spawn: &WorkerSpawn,
+        events: &Receiver<WorkerMessage>,
+        output: &EventOutput,
         shutdown_rx: Option<&Receiver<()>>,
-        cache: Option<&RunCache>,
     ) {
         if self.workers.is_empty() {
             return;
@@ -72,6 +247,10 @@ impl WorkerManager {
             self.workers.len()
         );
 
+        let max_fail = spawn.project.settings().max_fail();
+        let kill_on_timeout =
+            spawn.project.settings().test().timeout_method == TimeoutMethod::Process;
+
         loop {
             if let Some(rx) = shutdown_rx {
                 match rx.try_recv() {
@@ -83,45 +262,141 @@ impl WorkerManager {
                 }
             }
 
-            if let Some(cache) = cache
-                && cache.has_fail_fast_signal()
-            {
-                tracing::info!("Fail-fast signal received — stopping remaining workers");
-                break;
-            }
-
-            self.workers
-                .retain_mut(|worker| match worker.child.try_wait() {
-                    Ok(Some(status)) => {
-                        if status.success() {
-                            tracing::info!(
-                                "Worker {} completed successfully in {}",
-                                worker.id,
-                                format_duration(worker.duration()),
-                            );
-                        } else {
-                            tracing::error!(
-                                "Worker {} failed with exit code {} in {}",
-                                worker.id,
-                                status.code().unwrap_or(-1),
-                                format_duration(worker.duration()),
-                            );
-                        }
-                        false
+            let first = events.recv_timeout(WORKER_POLL_INTERVAL).ok();
+            for message in first.into_iter().chain(events.try_iter()) {
+                match message {
+                    WorkerMessage::Connected { worker_id } => {
+                        tracing::debug!("Worker {worker_id} connected");
                     }
-                    Ok(None) => true,
-                    Err(e) => {
-                        tracing::error!("Error waiting on worker {}: {}", worker.id, e);
-                        false
+                    WorkerMessage::Event { worker_id, event } => {
+                        self.handle_event(worker_id, &event, output);
                     }
-                });
+                    WorkerMessage::Disconnected { worker_id } => {
+                        if let Some(worker) = self.worker_mut(worker_id) {
+                            worker.disconnected = true;
+                        }
+                    }
+                }
+            }
+
+            for worker in &mut self.workers {
+                if kill_on_timeout {
+                    worker.enforce_timeout();
+                }
+                worker.poll_exit();
+            }
+            let (done, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.workers)
+                .into_iter()
+                .partition(Worker::is_done);
+            self.workers = running;
+            for worker in done {
+                self.finish(spawn, worker, output);
+            }
+
+            if max_fail.is_exceeded_by(self.failed_count) {
+                tracing::info!("Max-fail limit reached — stopping remaining workers");
+                self.stop_at_max_fail(spawn, max_fail);
+                break;
+            }
 
             if self.workers.is_empty() {
                 tracing::info!("All workers completed");
                 break;
             }
+        }
+    }
+
+    /// Account for a worker whose process has exited.
+    ///
+    /// A worker that exited without saving its results either crashed or was
+    /// killed for exceeding a test timeout. The test it was running is
+    /// reported as failed or timed out, and the results it reported before
+    /// stopping are kept. A fresh worker takes over the tests it had not
+    /// reached after a timeout, or after a crash when `respawn-on-crash` is
+    /// set.
+    fn finish(&mut self, spawn: &WorkerSpawn, mut worker: Worker, output: &EventOutput) {
+        let Some(status) = worker.status else {
+            return;
+        };
+        if spawn.cache.has_worker_results(worker.id) {
+            return;
+        }
+
+        let interruption = match worker.timed_out {
+            Some(timeout) => Interruption::Timeout(timeout),
+            None => Interruption::Crash(status),
+        };
 
-            std::thread::sleep(WORKER_POLL_INTERVAL);
+        let interrupted_test = worker
+            .progress
+            .in_flight()
+            .map(|(test, duration)| (test.clone(), duration));
+        if let Some((test, duration)) = &interrupted_test {
+            let event = interrupted_test_event(test, *duration, interruption);
+            output.render(&event);
+            worker.progress.record(&event);
+            self.failed_count = self.failed_count.saturating_add(1);
+        }
+
+        let settings = spawn.project.settings();
+        let remaining = worker
+            .partition
+            .remaining(worker.progress.reached(), worker.progress.last_function());
+
+        self.crashes.push(CrashReport::new(
+            worker.id,
+            interruption,
+            interrupted_test.is_some(),
+            std::mem::take(&mut worker.progress),
+            &worker.partition,
+            settings.terminal().output_format,
+        ));
+
+        let respawn = match interruption {
+            Interruption::Crash(_) => settings.test().respawn_on_crash,
+            Interruption::Timeout(_) => true,
+        };
+        if !respawn
+            || remaining.tests().is_empty()
+            || settings.max_fail().is_exceeded_by(self.failed_count)
+        {
+            return;
+        }
+
+        tracing::info!(
+            "Respawning worker {} for {} remaining tests",
+            worker.id,
+            remaining.tests().len()
+        );
+        if let Err(err) = self.spawn(spawn, worker.id, remaining) {
+            tracing::error!("Failed to respawn worker {}: {err:#}", worker.id);
+        }
+    }
+
+    /// Stop the run once the max-fail limit has been reached.
+    ///
+    /// A worker that has used up the budget on its own has already stopped
+    /// scheduling tests and is saving its results, so it is left to exit;
+    /// every other worker is killed, and the results it reported before
+    /// being killed are kept.
+    fn stop_at_max_fail(&mut self, spawn: &WorkerSpawn, max_fail: MaxFail) {
+        for worker in &mut self.workers {
+            if !max_fail.is_exceeded_by(worker.failed_count) {
+                let _ = worker.child.kill();
+            }
+        }
+        let output_format = spawn.project.settings().terminal().output_format;
+        for mut worker in self.workers.drain(..) {
+            let _ = worker.child.wait();
+            if spawn.cache.has_worker_results(worker.id) {
+                continue;
+            }
+            self.crashes.push(CrashReport::stopped(
+                worker.id,
+                std::mem::take(&mut worker.progress),
+                &worker.partition,
+                output_format,
+            ));
         }
     }
 
@@ -138,6 +413,13 @@ impl WorkerManager {
             let _ = worker.child.wait();
         }
     }
+
+    /// Merge the results recovered from interrupted workers into `results`.
+    fn merge_crashes(&mut self, results: &mut AggregatedResults) {
+        for crash in self.crashes.drain(..) {
+            crash.merge_into(results);
+        }
+    }
 }
 
 pub struct ParallelTestConfig {
@@ -162,28 +444,16 @@ pub struct ParallelTestConfig {
 ///
 /// Creates a worker process for each non-empty partition, passing the appropriate
 /// subset of tests and command-line arguments to each worker.
-fn spawn_workers(spawn: &WorkerSpawn, partitions: &[Partition]) -> Result<WorkerManager> {
+fn spawn_workers(spawn: &WorkerSpawn, partitions: Vec<Partition>) -> Result<WorkerManager> {
     let mut worker_manager = WorkerManager::default();
 
-    for (worker_id, partition) in partitions.iter().enumerate() {
+    for (worker_id, partition) in partitions.into_iter().enumerate() {
         if partition.tests().is_empty() {
             tracing::debug!("Skipping worker {} with no tests", worker_id);
             continue;
         }
 
-        let child = worker_command(spawn, worker_id, partition)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .spawn()
-            .context("Failed to spawn karva-worker process")?;
-
-        tracing::info!(
-            "Worker {} spawned with {} tests",
-            worker_id,
-            partition.tests().len()
-        );
-
-        worker_manager.spawn(worker_id, child);
+        worker_manager.spawn(spawn, worker_id, partition)?;
     }
 
     Ok(worker_manager)
@@ -205,6 +475,7 @@ pub fn collect_tests(project: &Project) -> Result<CollectedPackage> {
     let collection_settings = CollectionSettings {
         python_version: project.metadata().python_version(),
         test_function_prefix: &project.settings().test().test_function_prefix,
+        test_class_prefix: &project.settings().test().test_class_prefix,
         respect_ignore_files: project.settings().src().respect_ignore_files,
         collect_fixtures: false,
     };
@@ -255,7 +526,15 @@ pub fn run_parallel_tests(
         );
     }
 
-    if total_tests > 0 {
+    let json_output = args.message_format.is_some_and(MessageFormat::is_json);
+
+    if json_output {
+        RunEvent::RunStarted {
+            test_count: total_tests,
+            worker_count: num_workers,
+        }
+        .emit();
+    } else if total_tests > 0 {
         let mut stdout = printer.stream_for_test_result().lock();
         let label = format!("{:>12}", "Starting").green().bold();
         let test_label = if total_tests == 1 { "test" } else { "tests" };
@@ -314,6 +593,7 @@ pub fn run_parallel_tests(
     tracing::info!("Spawning {} workers", partitions.len());
 
     let worker_binary = find_karva_worker_binary(project.cwd())?;
+    let event_server = EventServer::bind()?;
     let spawn = WorkerSpawn {
         project,
         cache_dir: &cache_dir,
@@ -324,8 +604,10 @@ pub fn run_parallel_tests(
         profile: config.profile.as_deref().unwrap_or("default"),
         worker_binary: &worker_binary,
         coverage_enabled: !project.settings().coverage().sources.is_empty(),
+        ipc_address: event_server.address(),
+        ipc_token: event_server.token(),
     };
-    let mut worker_manager = spawn_workers(&spawn, &partitions)?;
+    let mut worker_manager = spawn_workers(&spawn, partitions)?;
 
     let shutdown_rx = if config.create_ctrlc_handler {
         Some(shutdown_receiver())
@@ -333,12 +615,17 @@ pub fn run_parallel_tests(
         None
     };
 
-    let max_fail_cache = project.settings().max_fail().has_limit().then_some(&cache);
+    let output = if json_output {
+        EventOutput::Json
+    } else {
+        EventOutput::Terminal(TestCaseReporter::new(printer))
+    };
 
-    worker_manager.wait_for_completion(shutdown_rx, max_fail_cache);
+    worker_manager.wait_for_completion(&spawn, event_server.receiver(), &output, shutdown_rx);
     worker_manager.kill_remaining();
 
-    let results = cache.aggregate_results()?;
+    let mut results = cache.aggregate_results()?;
+    worker_manager.merge_crashes(&mut results);
 
     if !config.no_cache {
         let _ = write_last_failed(&cache_dir, &results.failed_tests);
@@ -358,3 +645,4 @@ pub fn run_parallel_tests(
 
 const MIN_TESTS_PER_WORKER: usize = 5;
 const WORKER_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const WORKER_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
 use karva_cli::PartitionSelection;
+use karva_metadata::ResumePoint;
 
 /// Test metadata used for partitioning decisions
 #[derive(Debug, Clone)]
@@ -46,20 +47,28 @@ impl ModuleGroup {
 #[derive(Debug)]
 pub struct Partition {
     tests: Vec<String>,
+    /// Qualified `module::function` name of each entry in `tests`.
+    names: Vec<String>,
     /// Cumulative weight (duration in microseconds or 1 for unknown tests)
     weight: u128,
+    /// The test whose first variants an earlier worker already ran, for a
+    /// partition that takes over from a worker that stopped partway.
+    resume: Option<ResumePoint>,
 }
 
 impl Partition {
     fn new() -> Self {
         Self {
             tests: Vec::new(),
+            names: Vec::new(),
             weight: 0,
+            resume: None,
         }
     }
 
     fn add_test(&mut self, test: TestInfo, test_weight: u128) {
         self.tests.push(test.path);
+        self.names.push(test.qualified_name);
         self.weight += test_weight;
     }
 
@@ -70,6 +79,51 @@ impl Partition {
     pub(crate) fn tests(&self) -> &[String] {
         &self.tests
     }
+
+    /// The file that defines the test function with the given qualified name.
+    pub(crate) fn file_of(&self, qualified_name: &str) -> Option<&str> {
+        let index = self.names.iter().position(|name| name == qualified_name)?;
+        self.tests[index].rsplit_once("::").map(|(file, _)| file)
+    }
+
+    pub(crate) fn resume(&self) -> Option<&ResumePoint> {
+        self.resume.as_ref()
+    }
+
+    /// A partition with the tests whose qualified names are not in `reached`.
+    ///
+    /// `last_function` is the test the worker may have stopped partway
+    /// through. It is kept, and resumes after the variants that finished.
+    pub(crate) fn remaining(
+        &self,
+        reached: &HashSet<String>,
+        last_function: Option<&ResumePoint>,
+    ) -> Self {
+        let mut remaining = Self::new();
+        for (path, name) in self.tests.iter().zip(&self.names) {
+            let resumed = last_function.is_some_and(|last_function| &last_function.test == name);
+            if !reached.contains(name) || resumed {
+                remaining.tests.push(path.clone());
+                remaining.names.push(name.clone());
+            }
+        }
+        remaining.resume = last_function
+            .filter(|last_function| remaining.names.contains(&last_function.test))
+            .map(|last_function| {
+                // A worker that itself resumed this test started counting
+                // after the variants its predecessor finished.
+                let earlier = self
+                    .resume
+                    .as_ref()
+                    .filter(|resume| resume.test == last_function.test)
+                    .map_or(0, |resume| resume.finished_variants);
+                ResumePoint {
+                    test: last_function.test.clone(),
+                    finished_variants: earlier + last_function.finished_variants,
+                }
+            });
+        remaining
+    }
 }
 
 /// Partition collected tests into N groups using module-aware greedy bin-packing
@@ -243,14 +297,26 @@ fn collect_test_paths_recursive(
     previous_durations: &HashMap<String, Duration>,
 ) {
     for module in package.modules.values() {
-        for test_fn_def in &module.test_function_defs {
-            let qualified_name = format!("{}::{}", module.path.module_name(), test_fn_def.name);
+        let class_methods = module.test_classes.iter().flat_map(|class| {
+            class
+                .test_function_defs
+                .iter()
+                .map(move |test_fn_def| format!("{}::{}", class.name, test_fn_def.name))
+        });
+        let local_names = module
+            .test_function_defs
+            .iter()
+            .map(|test_fn_def| test_fn_def.name.to_string())
+            .chain(class_methods);
+
+        for local_name in local_names {
+            let qualified_name = format!("{}::{local_name}", module.path.module_name());
             let duration = previous_durations.get(&qualified_name).copied();
 
             test_infos.push(TestInfo {
                 module_name: module.path.module_name().to_string(),
                 qualified_name,
-                path: format!("{}::{}", module.path.path(), test_fn_def.name),
+                path: format!("{}::{local_name}", module.path.path()),
                 duration,
             });
         }
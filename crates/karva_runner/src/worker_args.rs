@@ -1,3 +1,4 @@
+use std::net::SocketAddr;
 use std::process::Command;
 
 use camino::Utf8PathBuf;
@@ -6,7 +7,7 @@ use karva_cache::{RunCache, RunHash};
 use karva_cli::SubTestCommand;
 use karva_metadata::ProjectSettings;
 use karva_project::Project;
-use karva_static::WorkerEnvVars;
+use karva_static::{EnvVars, WorkerEnvVars};
 
 use crate::partition::Partition;
 
@@ -21,6 +22,10 @@ pub struct WorkerSpawn<'a> {
     pub profile: &'a str,
     pub worker_binary: &'a Utf8PathBuf,
     pub coverage_enabled: bool,
+    /// Address of the main process's event socket.
+    pub ipc_address: SocketAddr,
+    /// Secret that workers present when connecting to the event socket.
+    pub ipc_token: &'a str,
 }
 
 /// Build the `Command` for a single worker.
@@ -32,9 +37,14 @@ pub fn worker_command(spawn: &WorkerSpawn, worker_id: usize, partition: &Partiti
         .arg(spawn.run_hash.inner())
         .arg("--worker-id")
         .arg(worker_id.to_string())
+        .arg("--ipc-address")
+        .arg(spawn.ipc_address.to_string())
         .current_dir(spawn.project.cwd())
         // Ensure python does not buffer output
         .env("PYTHONUNBUFFERED", "1")
+        // Passed through the environment rather than the command line, which
+        // other users on the machine can read.
+        .env(EnvVars::KARVA_IPC_TOKEN, spawn.ipc_token)
         .env(WorkerEnvVars::KARVA, "1")
         .env(WorkerEnvVars::KARVA_WORKER_ID, worker_id.to_string())
         .env(WorkerEnvVars::KARVA_RUN_ID, spawn.run_hash.inner())
@@ -53,6 +63,13 @@ pub fn worker_command(spawn: &WorkerSpawn, worker_id: usize, partition: &Partiti
         cmd.arg(path);
     }
 
+    if let Some(resume) = partition.resume() {
+        cmd.arg("--resume-test")
+            .arg(&resume.test)
+            .arg("--resume-after")
+            .arg(resume.finished_variants.to_string());
+    }
+
     cmd.args(inner_cli_args(spawn.project.settings(), spawn.args));
 
     if spawn.coverage_enabled {
@@ -90,6 +107,18 @@ fn inner_cli_args(settings: &ProjectSettings, args: &SubTestCommand) -> Vec<Stri
     cli_args.push("--final-status-level".to_string());
     cli_args.push(settings.terminal().final_status_level.as_str().to_string());
 
+    // Workers don't write the report themselves, but need to know it was
+    // requested so they capture per-test output for it.
+    if let Some(junit_path) = &settings.terminal().junit_path {
+        cli_args.push("--junit-path".to_string());
+        cli_args.push(junit_path.to_string());
+    }
+
+    if let Some(message_format) = args.message_format {
+        cli_args.push("--message-format".to_string());
+        cli_args.push(message_format.as_str().to_string());
+    }
+
     if let Some(color) = args.color {
         cli_args.push("--color".to_string());
         cli_args.push(color.as_str().to_string());
@@ -99,9 +128,11 @@ fn inner_cli_args(settings: &ProjectSettings, args: &SubTestCommand) -> Vec<Stri
         cli_args.push("--try-import-fixtures".to_string());
     }
 
-    if args.snapshot_update.unwrap_or(false) {
-        cli_args.push("--snapshot-update".to_string());
-    }
+    // Resolve `auto` here so every worker agrees on whether this is CI.
+    cli_args.push(format!(
+        "--snapshot-update={}",
+        settings.snapshot().update.resolve().as_str()
+    ));
 
     if let Some(retry) = args.retry {
         cli_args.push("--retry".to_string());
@@ -118,6 +149,9 @@ fn inner_cli_args(settings: &ProjectSettings, args: &SubTestCommand) -> Vec<Stri
         cli_args.push(format!("{}", timeout.as_secs_f64()));
     }
 
+    cli_args.push("--timeout-method".to_string());
+    cli_args.push(settings.test().timeout_method.as_str().to_string());
+
     for expr in &args.filter_expressions {
         cli_args.push("--filter".to_string());
         cli_args.push(expr.clone());
@@ -132,5 +166,29 @@ fn inner_cli_args(settings: &ProjectSettings, args: &SubTestCommand) -> Vec<Stri
         cli_args.push(format!("--cov={source}"));
     }
 
+    if settings.coverage().branch {
+        cli_args.push("--cov-branch".to_string());
+    }
+
+    if settings.coverage().context {
+        cli_args.push("--cov-context".to_string());
+    }
+
+    for glob in &settings.coverage().include {
+        cli_args.push(format!("--cov-include={glob}"));
+    }
+
+    for glob in &settings.coverage().omit {
+        cli_args.push(format!("--cov-omit={glob}"));
+    }
+
+    for regex in &settings.coverage().exclude_lines {
+        cli_args.push(format!("--cov-exclude-line={regex}"));
+    }
+
+    for regex in &settings.coverage().partial_branches {
+        cli_args.push(format!("--cov-partial-branch={regex}"));
+    }
+
     cli_args
 }
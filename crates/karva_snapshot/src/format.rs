@@ -1,9 +1,76 @@
 use std::fmt::Write;
 
+/// The serializer that turned an asserted value into snapshot content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Serializer {
+    /// `str(value)`, from `assert_snapshot`.
+    Str,
+    Json,
+    Yaml,
+    Toml,
+    /// `pprint.pformat(value)`, from `assert_debug_snapshot`.
+    Pprint,
+    /// Command output, from `assert_cmd_snapshot`.
+    Cmd,
+    /// Raw bytes stored next to the snapshot file, from `assert_binary_snapshot`.
+    Binary,
+    /// A raised exception and its chain, from `assert_raises_snapshot`.
+    Raises,
+    /// The warnings a call emitted, from `assert_warns_snapshot`.
+    Warns,
+}
+
+impl Serializer {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Str => "str",
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+            Self::Pprint => "pprint",
+            Self::Cmd => "cmd",
+            Self::Binary => "binary",
+            Self::Raises => "raises",
+            Self::Warns => "warns",
+        }
+    }
+
+    /// Parse a serializer name, returning `None` for unknown names.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "str" => Self::Str,
+            "json" => Self::Json,
+            "yaml" => Self::Yaml,
+            "toml" => Self::Toml,
+            "pprint" => Self::Pprint,
+            "cmd" => Self::Cmd,
+            "binary" => Self::Binary,
+            "raises" => Self::Raises,
+            "warns" => Self::Warns,
+            _ => return None,
+        })
+    }
+}
+
 /// Metadata stored in the YAML frontmatter of a snapshot file.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct SnapshotMetadata {
+    /// The test file and test name, as `test.py::test_name`. Files from older
+    /// versions also hold the assertion line, as `test.py:5::test_name`.
     pub source: Option<String>,
+    /// Line of the assertion in the file it was called from.
+    pub assertion_line: Option<u32>,
+    /// Source text of the asserted value.
+    pub expression: Option<String>,
+    /// The serializer that produced the content. Files written by
+    /// `assert_snapshot`, and those from older versions, leave it out.
+    pub serializer: Option<Serializer>,
+    /// File extension of the data of a binary snapshot, without the dot.
+    pub extension: Option<String>,
+    /// Parameters of a parametrized test, as `x=1, y=2`.
+    pub variant: Option<String>,
+    /// User data from `snapshot_settings(info=...)`, as YAML.
+    pub info: Option<String>,
     pub inline_source: Option<String>,
     pub inline_line: Option<u32>,
 }
@@ -15,6 +82,19 @@ pub struct SnapshotFile {
     pub content: String,
 }
 
+impl SnapshotMetadata {
+    /// The test file, assertion line and test name, as `test.py:5::test_name`.
+    pub fn location(&self) -> Option<String> {
+        let source = self.source.as_deref()?;
+        match (self.assertion_line, source.split_once("::")) {
+            (Some(line), Some((file, name))) if !file.contains(':') => {
+                Some(format!("{file}:{line}::{name}"))
+            }
+            _ => Some(source.to_string()),
+        }
+    }
+}
+
 impl SnapshotFile {
     /// Parse a snapshot file from its string representation.
     ///
@@ -22,23 +102,53 @@ impl SnapshotFile {
     /// ```text
     /// ---
     /// source: path/to/test.py::test_name
-    /// expression: "str(value)"
+    /// assertion_line: 5
+    /// expression: "value"
+    /// serializer: json
+    /// info:
+    ///   key: value
     /// ---
     /// snapshot content here
     /// ```
+    ///
+    /// A field with an empty value takes the indented lines below it as its
+    /// value.
     pub fn parse(input: &str) -> Option<Self> {
         let input = input.strip_prefix("---\n")?;
         let (frontmatter, content) = input.split_once("\n---\n")?;
 
         let mut metadata = SnapshotMetadata::default();
 
-        for line in frontmatter.lines() {
-            if let Some(value) = line.strip_prefix("source: ") {
-                metadata.source = Some(value.to_string());
-            } else if let Some(value) = line.strip_prefix("inline_source: ") {
-                metadata.inline_source = Some(value.to_string());
-            } else if let Some(value) = line.strip_prefix("inline_line: ") {
-                metadata.inline_line = value.parse().ok();
+        let mut lines = frontmatter.lines().peekable();
+        while let Some(line) = lines.next() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = if value.is_empty() {
+                let mut block = Vec::new();
+                while let Some(line) =
+                    lines.next_if(|line| line.is_empty() || line.starts_with("  "))
+                {
+                    block.push(line.get(2..).unwrap_or_default());
+                }
+                block.join("\n").trim_end().to_string()
+            } else if let Some(value) = value.strip_prefix(' ') {
+                value.to_string()
+            } else {
+                continue;
+            };
+
+            match key {
+                "source" => metadata.source = Some(value),
+                "assertion_line" => metadata.assertion_line = value.parse().ok(),
+                "expression" => metadata.expression = Some(value),
+                "serializer" => metadata.serializer = Serializer::from_name(&value),
+                "extension" => metadata.extension = Some(value),
+                "variant" => metadata.variant = Some(value),
+                "info" => metadata.info = Some(value),
+                "inline_source" => metadata.inline_source = Some(value),
+                "inline_line" => metadata.inline_line = value.parse().ok(),
+                _ => {}
             }
         }
 
@@ -56,6 +166,28 @@ impl SnapshotFile {
         if let Some(source) = &self.metadata.source {
             let _ = writeln!(output, "source: {source}");
         }
+        if let Some(assertion_line) = self.metadata.assertion_line {
+            let _ = writeln!(output, "assertion_line: {assertion_line}");
+        }
+        if let Some(expression) = &self.metadata.expression {
+            if expression.contains('\n') {
+                write_block(&mut output, "expression", expression);
+            } else {
+                let _ = writeln!(output, "expression: {expression}");
+            }
+        }
+        if let Some(serializer) = self.metadata.serializer {
+            let _ = writeln!(output, "serializer: {}", serializer.as_str());
+        }
+        if let Some(extension) = &self.metadata.extension {
+            let _ = writeln!(output, "extension: {extension}");
+        }
+        if let Some(variant) = &self.metadata.variant {
+            let _ = writeln!(output, "variant: {variant}");
+        }
+        if let Some(info) = &self.metadata.info {
+            write_block(&mut output, "info", info);
+        }
         if let Some(inline_source) = &self.metadata.inline_source {
             let _ = writeln!(output, "inline_source: {inline_source}");
         }
@@ -74,6 +206,18 @@ impl SnapshotFile {
     }
 }
 
+/// Write a field whose value is on the indented lines below its key.
+fn write_block(output: &mut String, key: &str, value: &str) {
+    let _ = writeln!(output, "{key}:");
+    for line in value.lines() {
+        if line.is_empty() {
+            output.push('\n');
+        } else {
+            let _ = writeln!(output, "  {line}");
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,6 +232,12 @@ mod tests {
                 source: Some(
                     "tests/test_example.py:5::test_example",
                 ),
+                assertion_line: None,
+                expression: None,
+                serializer: None,
+                extension: None,
+                variant: None,
+                info: None,
                 inline_source: None,
                 inline_line: None,
             },
@@ -151,6 +301,7 @@ mod tests {
                 source: Some("test.py:5::test_hello".to_string()),
                 inline_source: Some("/abs/path/to/test.py".to_string()),
                 inline_line: Some(5),
+                ..Default::default()
             },
             content: "hello world\n".to_string(),
         };
@@ -171,6 +322,7 @@ mod tests {
                 source: Some("test.py:5::test_hello".to_string()),
                 inline_source: Some("/abs/path/to/test.py".to_string()),
                 inline_line: Some(5),
+                ..Default::default()
             },
             content: "hello world\n".to_string(),
         };
@@ -212,6 +364,88 @@ mod tests {
         assert_eq!(snapshot, reparsed);
     }
 
+    #[test]
+    fn serialize_binary_metadata() {
+        let snapshot = SnapshotFile {
+            metadata: SnapshotMetadata {
+                source: Some("test.py:5::test_logo".to_string()),
+                serializer: Some(Serializer::Binary),
+                extension: Some("png".to_string()),
+                ..Default::default()
+            },
+            content: ".png file, 4 bytes, sha256 9f86d081\n".to_string(),
+        };
+        insta::assert_snapshot!(snapshot.serialize(), @r"
+        ---
+        source: test.py:5::test_logo
+        serializer: binary
+        extension: png
+        ---
+        .png file, 4 bytes, sha256 9f86d081
+        ");
+        let reparsed = SnapshotFile::parse(&snapshot.serialize()).expect("should reparse");
+        assert_eq!(snapshot, reparsed);
+    }
+
+    #[test]
+    fn serialize_assertion_metadata() {
+        let snapshot = SnapshotFile {
+            metadata: SnapshotMetadata {
+                source: Some("test.py::test_user(role='admin')".to_string()),
+                assertion_line: Some(7),
+                expression: Some("{\n    \"name\": name,\n\n    \"role\": role,\n}".to_string()),
+                serializer: Some(Serializer::Json),
+                variant: Some("role='admin'".to_string()),
+                info: Some("request: GET /users\nstatus: 200".to_string()),
+                ..Default::default()
+            },
+            content: "{}\n".to_string(),
+        };
+        insta::assert_snapshot!(snapshot.serialize(), @r#"
+        ---
+        source: test.py::test_user(role='admin')
+        assertion_line: 7
+        expression:
+          {
+              "name": name,
+
+              "role": role,
+          }
+        serializer: json
+        variant: role='admin'
+        info:
+          request: GET /users
+          status: 200
+        ---
+        {}
+        "#);
+        let reparsed = SnapshotFile::parse(&snapshot.serialize()).expect("should reparse");
+        assert_eq!(snapshot, reparsed);
+    }
+
+    #[test]
+    fn location_includes_assertion_line() {
+        let metadata = SnapshotMetadata {
+            source: Some("test.py::test_foo".to_string()),
+            assertion_line: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(metadata.location().as_deref(), Some("test.py:5::test_foo"));
+
+        let old = SnapshotMetadata {
+            source: Some("test.py:5::test_foo".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(old.location().as_deref(), Some("test.py:5::test_foo"));
+    }
+
+    #[test]
+    fn parse_unknown_serializer() {
+        let input = "---\nsource: test.py::test_foo\nserializer: xml\n---\n<a/>\n";
+        let snapshot = SnapshotFile::parse(input).expect("should parse");
+        assert!(snapshot.metadata.serializer.is_none());
+    }
+
     #[test]
     fn parse_malformed_no_closing_separator() {
         assert!(SnapshotFile::parse("---\nsource: test.py::test\nno closing").is_none());
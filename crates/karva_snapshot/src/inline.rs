@@ -197,7 +197,12 @@ fn containing_function_name(source: &str, byte_pos: usize) -> Option<&str> {
 const SNAPSHOT_CALL_PATTERNS: &[&str] = &[
     "assert_snapshot(",
     "assert_json_snapshot(",
+    "assert_yaml_snapshot(",
+    "assert_toml_snapshot(",
+    "assert_debug_snapshot(",
     "assert_cmd_snapshot(",
+    "assert_raises_snapshot(",
+    "assert_warns_snapshot(",
 ];
 
 /// Find the first snapshot assertion call in the given source slice.
@@ -535,6 +540,13 @@ mod tests {
         insta::assert_snapshot!(&source[loc.start..loc.end], @r#""""#);
     }
 
+    #[test]
+    fn find_inline_yaml_snapshot() {
+        let source = "    karva.assert_yaml_snapshot({'a': 1}, inline='a: 1')\n";
+        let loc = find_inline_argument(source, 1, None).expect("should find");
+        insta::assert_snapshot!(&source[loc.start..loc.end], @"'a: 1'");
+    }
+
     #[test]
     fn find_inline_skips_string_containing_inline() {
         let source = "    karva.assert_snapshot('inline=bad', inline=\"good\")\n";
@@ -3,5 +3,7 @@ pub mod diff;
 pub mod filters;
 pub mod format;
 pub mod inline;
+pub mod redactions;
 pub mod review;
+pub mod serializers;
 pub mod storage;
@@ -0,0 +1,246 @@
+use std::fmt::Write;
+
+/// One step of a redaction selector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// `.name` or `["name"]`: a map entry with this key.
+    Key(String),
+    /// `.*`: any map entry.
+    AnyKey,
+    /// `[N]`: the sequence item at this index, counted from the end when negative.
+    Index(i64),
+    /// `[*]`: any sequence item.
+    AnyIndex,
+    /// `.**`: any number of steps, including none.
+    Deep,
+}
+
+impl Segment {
+    fn matches(&self, item: &PathItem) -> bool {
+        match (self, item) {
+            (Self::Key(key), PathItem::Key(name)) => key == name,
+            (Self::AnyKey, PathItem::Key(_)) | (Self::AnyIndex, PathItem::Index { .. }) => true,
+            (Self::Index(wanted), PathItem::Index { index, len }) => {
+                let wanted = if *wanted < 0 {
+                    i64::try_from(*len).unwrap_or(i64::MAX) + wanted
+                } else {
+                    *wanted
+                };
+                i64::try_from(*index).is_ok_and(|index| index == wanted)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// One step from the root of a value to one of its nested values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathItem {
+    /// The entry of a map with this key.
+    Key(String),
+    /// The item at `index` of a sequence of `len` items.
+    Index { index: usize, len: usize },
+}
+
+/// A parsed redaction selector such as `.items[*].id`, in the syntax of
+/// insta's redactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    segments: Vec<Segment>,
+}
+
+impl Selector {
+    /// Parse a selector.
+    ///
+    /// Returns `None` if the selector is empty or malformed.
+    pub fn new(selector: &str) -> Option<Self> {
+        let mut segments = Vec::new();
+        let mut rest = selector;
+        while let Some(c) = rest.chars().next() {
+            rest = &rest[c.len_utf8()..];
+            match c {
+                '.' => {
+                    if let Some(after) = rest.strip_prefix("**") {
+                        segments.push(Segment::Deep);
+                        rest = after;
+                    } else if let Some(after) = rest.strip_prefix('*') {
+                        segments.push(Segment::AnyKey);
+                        rest = after;
+                    } else {
+                        let end = rest.find(['.', '[', ']']).unwrap_or(rest.len());
+                        if end == 0 {
+                            return None;
+                        }
+                        segments.push(Segment::Key(rest[..end].to_string()));
+                        rest = &rest[end..];
+                    }
+                }
+                '[' => {
+                    let (segment, after) = parse_bracket(rest)?;
+                    segments.push(segment);
+                    rest = after;
+                }
+                _ => return None,
+            }
+        }
+        if segments.is_empty() {
+            return None;
+        }
+        Some(Self { segments })
+    }
+
+    /// Whether the value at `path` is selected.
+    pub fn matches(&self, path: &[PathItem]) -> bool {
+        matches_from(&self.segments, path)
+    }
+}
+
+/// Parse the inside of a `[...]` segment, returning it and the input after
+/// the closing bracket.
+fn parse_bracket(input: &str) -> Option<(Segment, &str)> {
+    if let Some(quoted) = input.strip_prefix('"') {
+        let mut key = String::new();
+        let mut chars = quoted.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => key.push(chars.next()?.1),
+                '"' => {
+                    let after = quoted[i + 1..].strip_prefix(']')?;
+                    return Some((Segment::Key(key), after));
+                }
+                _ => key.push(c),
+            }
+        }
+        return None;
+    }
+
+    let end = input.find(']')?;
+    let inner = input[..end].trim();
+    let segment = if inner == "*" {
+        Segment::AnyIndex
+    } else {
+        Segment::Index(inner.parse().ok()?)
+    };
+    Some((segment, &input[end + 1..]))
+}
+
+fn matches_from(segments: &[Segment], path: &[PathItem]) -> bool {
+    match segments.split_first() {
+        None => path.is_empty(),
+        Some((Segment::Deep, rest)) => {
+            (0..=path.len()).any(|skip| matches_from(rest, &path[skip..]))
+        }
+        Some((segment, rest)) => path
+            .split_first()
+            .is_some_and(|(item, path)| segment.matches(item) && matches_from(rest, path)),
+    }
+}
+
+/// Format a path in selector syntax, such as `.items[0].id`.
+pub fn format_path(path: &[PathItem]) -> String {
+    let mut out = String::new();
+    for item in path {
+        match item {
+            PathItem::Key(key)
+                if !key.is_empty()
+                    && key
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_' || c == '-') =>
+            {
+                let _ = write!(out, ".{key}");
+            }
+            PathItem::Key(key) => {
+                let escaped = key.replace('\\', "\\\\").replace('"', "\\\"");
+                let _ = write!(out, "[\"{escaped}\"]");
+            }
+            PathItem::Index { index, .. } => {
+                let _ = write!(out, "[{index}]");
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str) -> PathItem {
+        PathItem::Key(name.to_string())
+    }
+
+    fn index(index: usize, len: usize) -> PathItem {
+        PathItem::Index { index, len }
+    }
+
+    fn selects(selector: &str, path: &[PathItem]) -> bool {
+        Selector::new(selector)
+            .expect("valid selector")
+            .matches(path)
+    }
+
+    #[test]
+    fn key_selector_matches_only_that_key() {
+        assert!(selects(".created_at", &[key("created_at")]));
+        assert!(!selects(".created_at", &[key("updated_at")]));
+        assert!(!selects(".created_at", &[key("created_at"), key("nested")]));
+    }
+
+    #[test]
+    fn wildcard_index_matches_every_item() {
+        let path = [key("items"), index(3, 5), key("id")];
+        assert!(selects(".items[*].id", &path));
+        assert!(selects(".items[3].id", &path));
+        assert!(!selects(".items[0].id", &path));
+        assert!(!selects(".items.*.id", &path));
+    }
+
+    #[test]
+    fn negative_index_counts_from_the_end() {
+        assert!(selects(".items[-1]", &[key("items"), index(4, 5)]));
+        assert!(!selects(".items[-1]", &[key("items"), index(3, 5)]));
+    }
+
+    #[test]
+    fn any_key_matches_one_level() {
+        assert!(selects(
+            ".users.*.token",
+            &[key("users"), key("alice"), key("token")]
+        ));
+        assert!(!selects(".users.*.token", &[key("users"), key("token")]));
+    }
+
+    #[test]
+    fn deep_wildcard_matches_any_depth() {
+        assert!(selects(".**.uuid", &[key("uuid")]));
+        assert!(selects(
+            ".**.uuid",
+            &[key("a"), index(0, 1), key("b"), key("uuid")]
+        ));
+        assert!(!selects(".**.uuid", &[key("uuid"), key("value")]));
+    }
+
+    #[test]
+    fn quoted_keys_allow_any_characters() {
+        assert!(selects(r#"["a.b"]"#, &[key("a.b")]));
+        assert!(selects(
+            r#".headers["Content \"Type\""]"#,
+            &[key("headers"), key("Content \"Type\"")]
+        ));
+    }
+
+    #[test]
+    fn malformed_selectors_are_rejected() {
+        for selector in ["", "items", ".", "..a", ".a[", ".a[x]", r#".a["b"#, ".a]"] {
+            assert!(Selector::new(selector).is_none(), "{selector}");
+        }
+    }
+
+    #[test]
+    fn format_path_uses_selector_syntax() {
+        insta::assert_snapshot!(
+            format_path(&[key("items"), index(0, 2), key("id"), key("a b")]),
+            @r#".items[0].id["a b"]"#
+        );
+    }
+}
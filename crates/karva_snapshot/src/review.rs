@@ -1,3 +1,4 @@
+use std::fmt::Write as _;
 use std::io::{self, BufRead, Write};
 
 use camino::{Utf8Path, Utf8PathBuf};
@@ -5,8 +6,9 @@ use colored::Colorize;
 use console::{Key, Term};
 
 use crate::diff::print_changeset;
+use crate::format::SnapshotMetadata;
 use crate::storage::{
-    PendingSnapshotInfo, accept_pending, accept_pending_batch, find_pending_snapshots,
+    PendingSnapshotInfo, accept_pending, accept_pending_batch, binary_path, find_pending_snapshots,
     read_snapshot, reject_pending,
 };
 
@@ -144,10 +146,11 @@ pub fn run_review(root: &Utf8Path, resolved_filters: &[Utf8PathBuf]) -> io::Resu
 
             if show_info {
                 writeln!(out, "File: {}", info.pending_path)?;
-                if let Some(source) =
-                    read_snapshot(&info.pending_path).and_then(|s| s.metadata.source)
-                {
-                    writeln!(out, "Source: {source}")?;
+                if let Some(snapshot) = read_snapshot(&info.pending_path) {
+                    write!(out, "{}", format_metadata(&snapshot.metadata, ""))?;
+                    if let Some(extension) = &snapshot.metadata.extension {
+                        writeln!(out, "Data: {}", binary_path(&info.pending_path, extension))?;
+                    }
                 }
             }
 
@@ -221,6 +224,40 @@ pub fn run_review(root: &Utf8Path, resolved_filters: &[Utf8PathBuf]) -> io::Resu
 }
 
 /// Print a labeled list of paths if non-empty.
+/// Format the metadata of a snapshot as `Label: value` lines, each starting
+/// with `indent`. Lines after the first of a multiline value are indented
+/// further.
+pub fn format_metadata(metadata: &SnapshotMetadata, indent: &str) -> String {
+    let fields = [
+        ("Source", metadata.location()),
+        ("Expression", metadata.expression.clone()),
+        (
+            "Serializer",
+            metadata
+                .serializer
+                .map(|serializer| serializer.as_str().to_string()),
+        ),
+        ("Variant", metadata.variant.clone()),
+        ("Info", metadata.info.clone()),
+    ];
+
+    let mut output = String::new();
+    for (label, value) in fields {
+        let Some(value) = value else {
+            continue;
+        };
+        if value.contains('\n') {
+            let _ = writeln!(output, "{indent}{label}:");
+            for line in value.lines() {
+                let _ = writeln!(output, "{indent}  {line}");
+            }
+        } else {
+            let _ = writeln!(output, "{indent}{label}: {value}");
+        }
+    }
+    output
+}
+
 fn print_summary_section(out: &mut impl Write, label: &str, paths: &[String]) -> io::Result<()> {
     if !paths.is_empty() {
         writeln!(out, "{label}")?;
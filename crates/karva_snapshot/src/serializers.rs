@@ -0,0 +1,277 @@
+use serde_json::Value;
+
+/// Serialize a value as block-style YAML.
+///
+/// Keys keep the order of the value, strings are only quoted when they would
+/// otherwise read as another type, and multiline strings become literal
+/// blocks.
+pub fn to_yaml(value: &Value) -> String {
+    let mut out = String::new();
+    match value {
+        Value::Object(map) if !map.is_empty() => write_map(&mut out, map, 0, false),
+        Value::Array(items) if !items.is_empty() => write_seq(&mut out, items, 0, false),
+        _ => {
+            let mut scalar = String::new();
+            write_node(&mut scalar, value, 2);
+            out.push_str(scalar.trim_start());
+        }
+    }
+    out.push('\n');
+    out
+}
+
+/// Serialize a value as TOML. Fails when the value is not a table, or
+/// contains `null`, which TOML cannot represent.
+pub fn to_toml(value: &Value) -> Result<String, toml::ser::Error> {
+    toml::to_string_pretty(value)
+}
+
+/// The content stored in the snapshot file of binary data, so a changed
+/// file shows up in diffs and reviews.
+pub fn binary_summary(extension: &str, len: usize, sha256: &str) -> String {
+    format!(".{extension} file, {len} bytes, sha256 {sha256}")
+}
+
+/// Write the part of a node that follows `key:` or `-`.
+fn write_node(out: &mut String, value: &Value, indent: usize) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            out.push('\n');
+            write_map(out, map, indent, false);
+        }
+        Value::Array(items) if !items.is_empty() => {
+            out.push('\n');
+            write_seq(out, items, indent, false);
+        }
+        Value::String(s) if is_block_string(s) => {
+            let (header, body) = match s.strip_suffix('\n') {
+                Some(body) => ("|", body),
+                None => ("|-", s.as_str()),
+            };
+            out.push(' ');
+            out.push_str(header);
+            for line in body.split('\n') {
+                out.push('\n');
+                if !line.is_empty() {
+                    push_indent(out, indent);
+                    out.push_str(line);
+                }
+            }
+        }
+        _ => {
+            out.push(' ');
+            out.push_str(&scalar(value));
+        }
+    }
+}
+
+fn write_map(
+    out: &mut String,
+    map: &serde_json::Map<String, Value>,
+    indent: usize,
+    inline_first: bool,
+) {
+    for (i, (key, value)) in map.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        if i > 0 || !inline_first {
+            push_indent(out, indent);
+        }
+        out.push_str(&string_scalar(key));
+        out.push(':');
+        write_node(out, value, indent + 2);
+    }
+}
+
+fn write_seq(out: &mut String, items: &[Value], indent: usize, inline_first: bool) {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        if i > 0 || !inline_first {
+            push_indent(out, indent);
+        }
+        out.push('-');
+        match item {
+            Value::Object(map) if !map.is_empty() => {
+                out.push(' ');
+                write_map(out, map, indent + 2, true);
+            }
+            Value::Array(nested) if !nested.is_empty() => {
+                out.push(' ');
+                write_seq(out, nested, indent + 2, true);
+            }
+            _ => write_node(out, item, indent + 2),
+        }
+    }
+}
+
+fn push_indent(out: &mut String, indent: usize) {
+    out.extend(std::iter::repeat_n(' ', indent));
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => string_scalar(s),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+    }
+}
+
+/// Whether a multiline string can be written as a literal block and read
+/// back unchanged.
+fn is_block_string(s: &str) -> bool {
+    s.contains('\n')
+        && !s.starts_with([' ', '\n'])
+        && !s.ends_with("\n\n")
+        && !s.chars().any(|c| c.is_control() && c != '\n' && c != '\t')
+}
+
+fn string_scalar(s: &str) -> String {
+    if is_plain(s) {
+        s.to_string()
+    } else {
+        serde_json::to_string(s).unwrap_or_default()
+    }
+}
+
+/// Whether a string can be written without quotes and still read back as
+/// the same string.
+fn is_plain(s: &str) -> bool {
+    const RESERVED: &[&str] = &[
+        "null", "~", "true", "false", "yes", "no", "on", "off", "y", "n",
+    ];
+
+    let Some(first) = s.chars().next() else {
+        return false;
+    };
+    !RESERVED.iter().any(|word| s.eq_ignore_ascii_case(word))
+        && !first.is_ascii_digit()
+        && !"-?:,[]{}#&*!|>'\"%@`.+ ".contains(first)
+        && !s.ends_with([' ', ':'])
+        && !s.contains(": ")
+        && !s.contains(" #")
+        && !s.chars().any(char::is_control)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn yaml_nested_structures() {
+        let value = json!({
+            "name": "karva",
+            "tags": ["fast", "rust"],
+            "owner": {"name": "Alice", "admin": true},
+            "releases": [{"version": "0.1", "yanked": false}, {"version": "0.2", "yanked": null}],
+            "matrix": [[1, 2], [3]],
+            "empty": {},
+            "none": [],
+        });
+        insta::assert_snapshot!(to_yaml(&value), @r#"
+        empty: {}
+        matrix:
+          - - 1
+            - 2
+          - - 3
+        name: karva
+        none: []
+        owner:
+          admin: true
+          name: Alice
+        releases:
+          - version: "0.1"
+            yanked: false
+          - version: "0.2"
+            yanked: null
+        tags:
+          - fast
+          - rust
+        "#);
+    }
+
+    #[test]
+    fn yaml_quotes_ambiguous_strings() {
+        let value = json!([
+            "true",
+            "No",
+            "42",
+            "",
+            " padded",
+            "a: b",
+            "#hash",
+            "-dash",
+            "plain text"
+        ]);
+        insta::assert_snapshot!(to_yaml(&value), @r##"
+        - "true"
+        - "No"
+        - "42"
+        - ""
+        - " padded"
+        - "a: b"
+        - "#hash"
+        - "-dash"
+        - plain text
+        "##);
+    }
+
+    #[test]
+    fn yaml_multiline_strings_are_literal_blocks() {
+        let value = json!({"kept": "line 1\nline 2\n", "stripped": "line 1\n\nline 3"});
+        insta::assert_snapshot!(to_yaml(&value), @r"
+        kept: |
+          line 1
+          line 2
+        stripped: |-
+          line 1
+
+          line 3
+        ");
+    }
+
+    #[test]
+    fn yaml_scalar_root() {
+        insta::assert_snapshot!(to_yaml(&json!("hello")), @"hello");
+        insta::assert_snapshot!(to_yaml(&json!(1.5)), @"1.5");
+        insta::assert_snapshot!(to_yaml(&json!("a\nb")), @r"
+        |-
+          a
+          b
+        ");
+    }
+
+    #[test]
+    fn toml_tables() {
+        let value = json!({
+            "name": "karva",
+            "owner": {"name": "Alice"},
+            "releases": [{"version": "0.1"}, {"version": "0.2"}],
+        });
+        insta::assert_snapshot!(to_toml(&value).expect("valid toml"), @r#"
+        name = "karva"
+
+        [owner]
+        name = "Alice"
+
+        [[releases]]
+        version = "0.1"
+
+        [[releases]]
+        version = "0.2"
+        "#);
+    }
+
+    #[test]
+    fn toml_rejects_non_tables_and_null() {
+        assert!(to_toml(&json!([1, 2])).is_err());
+        assert!(to_toml(&json!({"a": null})).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io;
 
 use camino::{Utf8Path, Utf8PathBuf};
@@ -29,6 +29,20 @@ pub fn pending_path(snap_path: &Utf8Path) -> Utf8PathBuf {
     Utf8PathBuf::from(format!("{snap_path}.new"))
 }
 
+/// Return the path of the file holding the data of a binary snapshot: the
+/// path of its `.snap` or `.snap.new` file with the extension appended.
+pub fn binary_path(snap_path: &Utf8Path, extension: &str) -> Utf8PathBuf {
+    Utf8PathBuf::from(format!("{snap_path}.{extension}"))
+}
+
+/// Delete the data file of the snapshot file at `path`, if it is a binary
+/// snapshot.
+pub fn remove_binary_data(path: &Utf8Path) {
+    if let Some(extension) = read_snapshot(path).and_then(|s| s.metadata.extension) {
+        let _ = std::fs::remove_file(binary_path(path, &extension));
+    }
+}
+
 /// Read and parse a snapshot file, returning `None` if it doesn't exist or can't be parsed.
 pub fn read_snapshot(path: &Utf8Path) -> Option<SnapshotFile> {
     let content = std::fs::read_to_string(path).ok()?;
@@ -52,6 +66,15 @@ pub fn write_pending_snapshot(snap_path: &Utf8Path, snapshot: &SnapshotFile) ->
     std::fs::write(pending, snapshot.serialize())
 }
 
+/// Write the data of a binary snapshot next to its `.snap` or `.snap.new`
+/// file, creating parent directories as needed.
+pub fn write_binary_data(path: &Utf8Path, extension: &str, data: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(binary_path(path, extension), data)
+}
+
 /// Information about a pending snapshot found on disk.
 #[derive(Debug, Clone)]
 pub struct PendingSnapshotInfo {
@@ -128,9 +151,11 @@ fn extract_function_name(source: Option<&str>) -> Option<&str> {
 ///
 /// For inline snapshots (with `inline_source`/`inline_line` metadata),
 /// rewrites the source file in-place and deletes the `.snap.new` file.
-/// For file-based snapshots, renames `.snap.new` to `.snap`.
+/// For file-based snapshots, renames `.snap.new` to `.snap`, along with the
+/// data file of a binary snapshot.
 pub fn accept_pending(pending_path: &Utf8Path) -> io::Result<()> {
-    if let Some(snapshot) = read_snapshot(pending_path)
+    let snapshot = read_snapshot(pending_path);
+    if let Some(snapshot) = &snapshot
         && let Some(source_file) = &snapshot.metadata.inline_source
         && let Some(line) = snapshot.metadata.inline_line
     {
@@ -145,6 +170,15 @@ pub fn accept_pending(pending_path: &Utf8Path) -> io::Result<()> {
         .strip_suffix(".new")
         .map(Utf8PathBuf::from)
         .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Not a .snap.new file"))?;
+
+    // The accepted data may use another extension than the data it replaces.
+    remove_binary_data(&snap_path);
+    if let Some(extension) = snapshot.and_then(|snapshot| snapshot.metadata.extension) {
+        std::fs::rename(
+            binary_path(pending_path, &extension),
+            binary_path(&snap_path, &extension),
+        )?;
+    }
     std::fs::rename(pending_path, snap_path)
 }
 
@@ -230,8 +264,10 @@ pub fn accept_pending_batch(pending: &[&PendingSnapshotInfo]) -> io::Result<()>
     process_file_based_snapshots(&file_based)
 }
 
-/// Reject a pending snapshot by deleting the `.snap.new` file.
+/// Reject a pending snapshot by deleting the `.snap.new` file, and the data
+/// file of a binary snapshot.
 pub fn reject_pending(pending_path: &Utf8Path) -> io::Result<()> {
+    remove_binary_data(pending_path);
     std::fs::remove_file(pending_path)
 }
 
@@ -405,8 +441,51 @@ fn check_snapshot_reference(info: &SnapshotInfo) -> Option<UnreferencedReason> {
     None
 }
 
-/// Remove a snapshot file. Also removes the parent directory if it becomes empty.
+/// Find the `.snap` files of `test_file` that are not in `referenced`, the
+/// snapshots its assertions used during a run, sorted.
+///
+/// Snapshots of the functions in `incomplete`, given as `func` or
+/// `Class.method`, are never returned, since not all of their assertions
+/// may have run.
+pub fn find_unused_snapshots(
+    test_file: &Utf8Path,
+    referenced: &HashSet<Utf8PathBuf>,
+    incomplete: &[String],
+) -> Vec<Utf8PathBuf> {
+    let Some(module_name) = test_file.file_stem() else {
+        return Vec::new();
+    };
+    let Ok(entries) = snapshot_dir(test_file).read_dir_utf8() else {
+        return Vec::new();
+    };
+    let prefix = format!("{module_name}__");
+
+    let mut unused: Vec<Utf8PathBuf> = entries
+        .flatten()
+        .filter(|entry| {
+            let Some(name) = entry
+                .file_name()
+                .strip_prefix(&prefix)
+                .and_then(|name| name.strip_suffix(".snap"))
+            else {
+                return false;
+            };
+            !incomplete.iter().any(|function| {
+                name.strip_prefix(function.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(['(', '-']))
+            })
+        })
+        .map(|entry| entry.path().to_path_buf())
+        .filter(|path| !referenced.contains(path))
+        .collect();
+    unused.sort();
+    unused
+}
+
+/// Remove a snapshot file and the data file of a binary snapshot. Also
+/// removes the parent directory if it becomes empty.
 pub fn remove_snapshot(path: &Utf8Path) -> io::Result<()> {
+    remove_binary_data(path);
     std::fs::remove_file(path)?;
     if let Some(parent) = path.parent() {
         if parent.file_name().is_some_and(|name| name == "snapshots") {
@@ -497,6 +576,57 @@ mod tests {
         assert!(!pending.exists());
     }
 
+    fn binary_snapshot(extension: &str) -> SnapshotFile {
+        SnapshotFile {
+            metadata: crate::format::SnapshotMetadata {
+                source: Some("test.py:3::test_logo".to_string()),
+                serializer: Some(crate::format::Serializer::Binary),
+                extension: Some(extension.to_string()),
+                ..Default::default()
+            },
+            content: format!(".{extension} file, 3 bytes, sha256 0000\n"),
+        }
+    }
+
+    #[test]
+    fn accept_pending_moves_binary_data() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let dir_path = Utf8Path::from_path(dir.path()).expect("utf8");
+        let snap_path = dir_path.join("snapshots").join("test__test_logo.snap");
+
+        write_snapshot(&snap_path, &binary_snapshot("bin")).expect("write");
+        write_binary_data(&snap_path, "bin", b"old").expect("write data");
+        write_pending_snapshot(&snap_path, &binary_snapshot("png")).expect("write pending");
+        write_binary_data(&pending_path(&snap_path), "png", b"new").expect("write data");
+
+        accept_pending(&pending_path(&snap_path)).expect("accept");
+
+        assert!(!binary_path(&snap_path, "bin").exists());
+        assert!(!binary_path(&pending_path(&snap_path), "png").exists());
+        let data = std::fs::read(binary_path(&snap_path, "png")).expect("read data");
+        assert_eq!(data, b"new");
+    }
+
+    #[test]
+    fn reject_and_remove_delete_binary_data() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let dir_path = Utf8Path::from_path(dir.path()).expect("utf8");
+        let snap_path = dir_path.join("snapshots").join("test__test_logo.snap");
+        let pending = pending_path(&snap_path);
+
+        write_snapshot(&snap_path, &binary_snapshot("png")).expect("write");
+        write_binary_data(&snap_path, "png", b"old").expect("write data");
+        write_pending_snapshot(&snap_path, &binary_snapshot("png")).expect("write pending");
+        write_binary_data(&pending, "png", b"new").expect("write data");
+
+        reject_pending(&pending).expect("reject");
+        assert!(!binary_path(&pending, "png").exists());
+
+        remove_snapshot(&snap_path).expect("remove");
+        assert!(!binary_path(&snap_path, "png").exists());
+        assert!(!dir_path.join("snapshots").exists());
+    }
+
     #[test]
     fn find_pending_excludes_committed() {
         let dir = tempfile::tempdir().expect("temp dir");
@@ -673,4 +803,37 @@ mod tests {
         assert!(!snap_path.exists());
         assert!(!snap_dir.exists());
     }
+
+    #[test]
+    fn unused_snapshots_of_a_run() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let dir_path = Utf8Path::from_path(dir.path()).expect("utf8");
+        let snap_dir = dir_path.join("snapshots");
+        std::fs::create_dir_all(&snap_dir).expect("mkdir");
+
+        for name in [
+            "test__test_a(x=1).snap",
+            "test__test_a(x=2).snap",
+            "test__test_a--old.snap",
+            "test__test_ab.snap",
+            "test__test_skipped-1.snap",
+            "test__test_b.snap.new",
+            "other__test_a.snap",
+        ] {
+            std::fs::write(snap_dir.join(name), "content").expect("write");
+        }
+
+        let test_file = dir_path.join("test.py");
+        let referenced = HashSet::from([snap_dir.join("test__test_a(x=1).snap")]);
+        let unused = find_unused_snapshots(&test_file, &referenced, &["test_skipped".to_string()]);
+        let names: Vec<&str> = unused.iter().filter_map(|path| path.file_name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "test__test_a(x=2).snap",
+                "test__test_a--old.snap",
+                "test__test_ab.snap"
+            ]
+        );
+    }
 }
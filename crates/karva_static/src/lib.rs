@@ -51,15 +51,32 @@ env_vars! {
         /// This is a standard Rayon environment variable.
         pub const RAYON_NUM_THREADS: &'static str = "RAYON_NUM_THREADS";
 
+        /// Set by most CI services. When set to anything but "", "0" or
+        /// "false", `--snapshot-update=auto` writes no snapshot files.
+        pub const CI: &'static str = "CI";
+
+        /// Set by Azure Pipelines, and read like `CI`.
+        pub const TF_BUILD: &'static str = "TF_BUILD";
+
         /// This is a standard Karva environment variable.
         pub const KARVA_MAX_PARALLELISM: &'static str = "KARVA_MAX_PARALLELISM";
 
         /// This is a standard Karva environment variable.
         pub const KARVA_CONFIG_FILE: &'static str = "KARVA_CONFIG_FILE";
 
-        /// When set to "1" or "true", snapshot assertions write directly to `.snap`
-        /// instead of creating `.snap.new` pending files.
+        /// Sets `--snapshot-update`: one of "auto", "new", "always", "unseen"
+        /// or "no". "1" and "true" mean "always", "0" and "false" mean "no".
         pub const KARVA_SNAPSHOT_UPDATE: &'static str = "KARVA_SNAPSHOT_UPDATE";
+
+        /// When set to "settrace", coverage is measured with `sys.settrace`
+        /// even on Python 3.12 and later, where `sys.monitoring` is used by
+        /// default.
+        pub const KARVA_COVERAGE_TRACER: &'static str = "KARVA_COVERAGE_TRACER";
+
+        /// Set by the main process on every worker it spawns. A worker sends
+        /// it when connecting to the main process's event socket, which drops
+        /// connections without it. Not meant to be set by hand.
+        pub const KARVA_IPC_TOKEN: &'static str = "KARVA_IPC_TOKEN";
     }
 }
 
@@ -113,6 +130,11 @@ env_vars! {
 
         /// Version of the running karva CLI.
         pub const KARVA_VERSION: &'static str = "KARVA_VERSION";
+
+        /// Set when coverage is measured. Holds the worker's coverage
+        /// configuration, which Python subprocesses started by tests read to
+        /// measure their own coverage. Not meant to be set by hand.
+        pub const KARVA_COVERAGE_PROCESS: &'static str = "KARVA_COVERAGE_PROCESS";
     }
 }
 
@@ -3,7 +3,9 @@ use std::rc::Rc;
 
 use camino::Utf8Path;
 use karva_collector::CollectionSettings;
-use karva_diagnostic::{IndividualTestResultKind, Reporter, TestRunResult};
+use karva_diagnostic::{
+    IndividualTestResultKind, Reporter, TestCaseDetails, TestCaseFailure, TestRunResult,
+};
 use karva_metadata::ProjectSettings;
 use karva_python_semantic::QualifiedTestName;
 use ruff_python_ast::PythonVersion;
@@ -59,6 +61,7 @@ impl<'a> Context<'a> {
         CollectionSettings {
             python_version: self.python_version,
             test_function_prefix: &self.settings.test().test_function_prefix,
+            test_class_prefix: &self.settings.test().test_class_prefix,
             respect_ignore_files: self.settings.src().respect_ignore_files,
             collect_fixtures: true,
         }
@@ -77,6 +80,7 @@ impl<'a> Context<'a> {
         test_case_name: &QualifiedTestName,
         test_result: IndividualTestResultKind,
         duration: std::time::Duration,
+        details: TestCaseDetails,
     ) -> bool {
         let result = matches!(
             &test_result,
@@ -87,12 +91,22 @@ impl<'a> Context<'a> {
             test_case_name,
             test_result,
             duration,
+            details,
             Some(self.reporter),
         );
 
         result
     }
 
+    /// Notify the reporter that a test variant is about to run.
+    pub fn report_test_started(
+        &self,
+        test_case_name: &QualifiedTestName,
+        timeout: Option<std::time::Duration>,
+    ) {
+        self.reporter.report_test_started(test_case_name, timeout);
+    }
+
     /// Forward a per-attempt outcome to the reporter. Does not touch
     /// summary stats; the test's final outcome is registered separately
     /// via [`Self::register_retried_result`].
@@ -102,12 +116,14 @@ impl<'a> Context<'a> {
         attempt: u32,
         result: IndividualTestResultKind,
         duration: std::time::Duration,
+        failure: Option<TestCaseFailure>,
     ) {
         self.result().report_test_attempt(
             test_case_name,
             attempt,
             result,
             duration,
+            failure,
             Some(self.reporter),
         );
     }
@@ -135,6 +151,7 @@ impl<'a> Context<'a> {
         duration: std::time::Duration,
         passed_on: u32,
         total_attempts: u32,
+        details: TestCaseDetails,
     ) -> bool {
         let passed = matches!(
             result,
@@ -146,6 +163,7 @@ impl<'a> Context<'a> {
             duration,
             passed_on,
             total_attempts,
+            details,
             Some(self.reporter),
         );
         passed
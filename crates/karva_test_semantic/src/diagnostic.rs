@@ -14,6 +14,7 @@ use ruff_db::diagnostic::{
 };
 use ruff_python_ast::StmtFunctionDef;
 use ruff_source_file::SourceFile;
+use ruff_text_size::TextRange;
 
 mod metadata;
 
@@ -48,6 +49,19 @@ declare_diagnostic_type! {
     }
 }
 
+declare_diagnostic_type! {
+    /// ## Dynamic fixture request
+    ///
+    /// Fixtures looked up with `request.getfixturevalue` are set up before
+    /// the fixture is called, so their names must be string literals. If a
+    /// fixture looks one up by a name computed at runtime, we will raise
+    /// this error.
+    pub static DYNAMIC_FIXTURE_REQUEST = {
+        summary: "Fixture looked up by a dynamic name",
+        severity: Severity::Error,
+    }
+}
+
 declare_diagnostic_type! {
     /// ## Invalid fixture finalizer
     ///
@@ -100,6 +114,41 @@ declare_diagnostic_type! {
     }
 }
 
+declare_diagnostic_type! {
+    /// ## Failed class teardown
+    ///
+    /// If `tearDownClass` or a class cleanup of a `unittest.TestCase` raises
+    /// an exception, we will raise this error.
+    pub static CLASS_TEARDOWN_FAILURE = {
+        summary: "Test class teardown raises exception",
+        severity: Severity::Error,
+    }
+}
+
+declare_diagnostic_type! {
+    /// ## Duplicate parametrize ids
+    ///
+    /// If two parameter sets of one `parametrize` decorator are given the same
+    /// id, we will raise this error. The test still runs once per parameter
+    /// set, but the variants can't be told apart by name.
+    pub static DUPLICATE_PARAMETRIZE_IDS = {
+        summary: "Parameter sets share an id",
+        severity: Severity::Error,
+    }
+}
+
+declare_diagnostic_type! {
+    /// ## Invalid parametrize ids
+    ///
+    /// If the `ids` of a pytest `parametrize` mark can't be applied, because
+    /// there isn't one id for each parameter set or the `ids` callable raises,
+    /// we will raise this error. The test still runs under its default names.
+    pub static INVALID_PARAMETRIZE_IDS = {
+        summary: "Parametrize ids can't be applied",
+        severity: Severity::Error,
+    }
+}
+
 /// Annotate a diagnostic with a primary span pointing at a function's name.
 fn annotate_function_name(
     diagnostic: &mut Diagnostic,
@@ -169,6 +218,29 @@ pub fn report_invalid_fixture(
     }
 }
 
+pub fn report_dynamic_fixture_requests(
+    context: &Context,
+    source_file: SourceFile,
+    stmt_function_def: &StmtFunctionDef,
+    ranges: &[TextRange],
+) {
+    let builder = context.report_diagnostic(&DYNAMIC_FIXTURE_REQUEST);
+
+    let mut diagnostic = builder.into_diagnostic(format!(
+        "Fixture `{}` looks up a fixture by a dynamic name",
+        stmt_function_def.name
+    ));
+
+    for range in ranges {
+        let span = Span::from(source_file.clone()).with_range(*range);
+        diagnostic.annotate(Annotation::primary(span));
+    }
+
+    diagnostic.info(
+        "Fixtures looked up with `request.getfixturevalue` must be named with a string literal",
+    );
+}
+
 pub fn report_invalid_fixture_finalizer(
     context: &Context,
     source_file: SourceFile,
@@ -326,6 +398,68 @@ pub fn report_test_failure(
     );
 }
 
+pub fn report_class_teardown_failure(
+    context: &Context,
+    py: Python,
+    class_name: &str,
+    error: &PyErr,
+) {
+    let builder = context.report_diagnostic(&CLASS_TEARDOWN_FAILURE);
+
+    let mut diagnostic =
+        builder.into_diagnostic(format!("Teardown of test class `{class_name}` failed"));
+
+    let error_string = error.value(py).to_string();
+
+    if !error_string.is_empty() {
+        diagnostic.info(indent_continuation_lines(&error_string));
+    }
+}
+
+pub fn report_duplicate_parametrize_ids(
+    context: &Context,
+    source_file: SourceFile,
+    stmt_function_def: &StmtFunctionDef,
+    duplicate_ids: &[String],
+) {
+    let builder = context.report_diagnostic(&DUPLICATE_PARAMETRIZE_IDS);
+
+    let mut diagnostic = builder.into_diagnostic(format!(
+        "Test `{}` has duplicate parametrize ids",
+        stmt_function_def.name
+    ));
+
+    annotate_function_name(&mut diagnostic, source_file, stmt_function_def);
+
+    let duplicate_ids_string = duplicate_ids
+        .iter()
+        .map(|id| format!("`{}`", truncate_string(id)))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    diagnostic.info(format!("Duplicate ids: {duplicate_ids_string}"));
+}
+
+pub fn report_invalid_parametrize_ids(
+    context: &Context,
+    source_file: SourceFile,
+    stmt_function_def: &StmtFunctionDef,
+    errors: &[String],
+) {
+    let builder = context.report_diagnostic(&INVALID_PARAMETRIZE_IDS);
+
+    let mut diagnostic = builder.into_diagnostic(format!(
+        "Test `{}` has invalid parametrize ids",
+        stmt_function_def.name
+    ));
+
+    annotate_function_name(&mut diagnostic, source_file, stmt_function_def);
+
+    for error in errors {
+        diagnostic.info(error);
+    }
+}
+
 fn handle_failed_function_call(
     diagnostic: &mut Diagnostic,
     py: Python,
@@ -64,6 +64,7 @@ impl<'ctx, 'a> StandardDiscoverer<'ctx, 'a> {
         session_package.set_framework_module(discover_framework_fixtures(
             py,
             self.context.python_version(),
+            self.context.cwd(),
         ));
 
         session_package
@@ -109,6 +110,7 @@ impl<'ctx, 'a> StandardDiscoverer<'ctx, 'a> {
             source_text,
             test_function_defs,
             fixture_function_defs,
+            test_classes,
         } = collected_module;
 
         let mut module = DiscoveredModule::new_with_source(path, source_text);
@@ -119,6 +121,7 @@ impl<'ctx, 'a> StandardDiscoverer<'ctx, 'a> {
             &mut module,
             test_function_defs,
             fixture_function_defs,
+            test_classes,
         );
 
         module
@@ -139,6 +142,7 @@ impl<'ctx, 'a> StandardDiscoverer<'ctx, 'a> {
 fn discover_framework_fixtures(
     py: Python<'_>,
     python_version: PythonVersion,
+    cwd: &Utf8Path,
 ) -> Option<DiscoveredModule> {
     let builtins_module = match py.import("karva._builtins") {
         Ok(module) => module,
@@ -202,6 +206,7 @@ fn discover_framework_fixtures(
             &builtins_module,
             &module_path,
             is_gen,
+            cwd,
         ) {
             Ok(fixture) => framework_module.add_fixture(fixture),
             Err(err) => {
@@ -3,6 +3,7 @@ pub mod models;
 pub mod visitor;
 
 pub use discoverer::StandardDiscoverer;
+pub use models::class::DiscoveredTestClass;
 pub use models::function::DiscoveredTestFunction;
 pub use models::module::DiscoveredModule;
 pub use models::package::DiscoveredPackage;
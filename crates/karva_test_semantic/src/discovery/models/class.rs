@@ -0,0 +1,60 @@
+use pyo3::prelude::*;
+
+use crate::extensions::fixtures::DiscoveredFixture;
+use crate::extensions::tags::Tags;
+
+/// Represents a test class discovered from Python source code.
+///
+/// The class's test methods are stored as ordinary test functions in the
+/// module, each referring back to its class. The class holds what they
+/// share: the Python class object, the decorator tags applied to the class,
+/// and any fixtures defined as methods.
+#[derive(Debug)]
+pub struct DiscoveredTestClass {
+    /// The name of the class.
+    pub(crate) name: String,
+
+    /// Reference to the Python class object, instantiated once per test.
+    pub(crate) py_class: Py<PyAny>,
+
+    /// Decorator tags applied to the class, inherited by each of its tests.
+    pub(crate) tags: Tags,
+
+    /// Whether the class is a `unittest.TestCase` subclass, whose tests run
+    /// through `unittest` itself.
+    pub(crate) is_test_case: bool,
+
+    /// Fixtures defined as methods of the class.
+    fixtures: Vec<DiscoveredFixture>,
+}
+
+impl DiscoveredTestClass {
+    pub(crate) fn new(name: String, py_class: Py<PyAny>, tags: Tags, is_test_case: bool) -> Self {
+        Self {
+            name,
+            py_class,
+            tags,
+            is_test_case,
+            fixtures: Vec::new(),
+        }
+    }
+
+    pub(crate) fn fixtures(&self) -> &Vec<DiscoveredFixture> {
+        &self.fixtures
+    }
+
+    pub(crate) fn add_fixture(&mut self, fixture: DiscoveredFixture) {
+        self.fixtures.push(fixture);
+    }
+
+    /// Create a fresh instance of the class for a single test.
+    ///
+    /// A `unittest.TestCase` is created for the name of the method it runs.
+    pub(crate) fn instantiate(&self, py: Python<'_>, method_name: &str) -> PyResult<Py<PyAny>> {
+        if self.is_test_case {
+            self.py_class.call1(py, (method_name,))
+        } else {
+            self.py_class.call0(py)
+        }
+    }
+}
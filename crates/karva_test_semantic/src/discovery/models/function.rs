@@ -4,7 +4,8 @@ use karva_python_semantic::QualifiedFunctionName;
 use pyo3::prelude::*;
 use ruff_python_ast::StmtFunctionDef;
 
-use crate::discovery::DiscoveredModule;
+use crate::discovery::{DiscoveredModule, DiscoveredTestClass};
+use crate::extensions::fixtures::RequiresFixtures;
 use crate::extensions::tags::Tags;
 
 /// Represents a single test function discovered from Python source code.
@@ -25,6 +26,9 @@ pub struct DiscoveredTestFunction {
 
     /// Decorator tags like parametrize, skip, xfail, etc.
     pub(crate) tags: Tags,
+
+    /// The test class this function is a method of, if any.
+    pub(crate) class: Option<Rc<DiscoveredTestClass>>,
 }
 
 impl DiscoveredTestFunction {
@@ -46,6 +50,45 @@ impl DiscoveredTestFunction {
             stmt_function_def,
             py_function,
             tags,
+            class: None,
         }
     }
+
+    /// Create a test from a method of a test class.
+    ///
+    /// The method inherits the tags of its class. Methods of a
+    /// `unittest.TestCase` also take tags from the `unittest` decorators.
+    pub(crate) fn new_method(
+        py: Python<'_>,
+        module: &DiscoveredModule,
+        class: &Rc<DiscoveredTestClass>,
+        stmt_function_def: Rc<StmtFunctionDef>,
+        py_function: Py<PyAny>,
+    ) -> Self {
+        let mut test_function = Self::new(py, module, stmt_function_def, py_function);
+
+        test_function.name = test_function.name.with_class_name(class.name.clone());
+        if class.is_test_case {
+            let unittest_tags = Tags::from_unittest_attributes(py, &test_function.py_function);
+            test_function.tags.extend(&unittest_tags);
+        }
+        test_function.tags.extend(&class.tags);
+        test_function.class = Some(Rc::clone(class));
+
+        test_function
+    }
+}
+
+impl RequiresFixtures for DiscoveredTestFunction {
+    /// The fixtures requested by the test's parameters. For methods, the
+    /// first parameter receives the class instance instead.
+    fn required_fixtures(&self, py: Python<'_>) -> Vec<String> {
+        let mut required_fixtures = self.stmt_function_def.required_fixtures(py);
+
+        if self.class.is_some() && !required_fixtures.is_empty() {
+            required_fixtures.remove(0);
+        }
+
+        required_fixtures
+    }
 }
@@ -2,19 +2,25 @@ use std::path::Path;
 use std::rc::Rc;
 
 use camino::Utf8Path;
-use karva_python_semantic::ModulePath;
+use karva_collector::CollectedClass;
+use karva_python_semantic::{ModulePath, is_staticmethod};
 use pyo3::prelude::*;
-use pyo3::types::PyModule;
+use pyo3::types::{PyModule, PyType};
 use ruff_python_ast::visitor::source_order::{self, SourceOrderVisitor};
 use ruff_python_ast::{Expr, PythonVersion, Stmt, StmtFunctionDef};
 use ruff_python_parser::{Mode, ParseOptions, parse_unchecked};
-use ruff_source_file::SourceFileBuilder;
+use ruff_source_file::{SourceFile, SourceFileBuilder};
 
 use crate::Context;
-use crate::diagnostic::{report_failed_to_import_module, report_invalid_fixture};
-use crate::discovery::{DiscoveredModule, DiscoveredTestFunction};
-use crate::extensions::fixtures::DiscoveredFixture;
+use crate::diagnostic::{
+    report_duplicate_parametrize_ids, report_dynamic_fixture_requests,
+    report_failed_to_import_module, report_invalid_fixture, report_invalid_parametrize_ids,
+};
+use crate::discovery::{DiscoveredModule, DiscoveredTestClass, DiscoveredTestFunction};
 use crate::extensions::fixtures::python::FixtureFunctionDefinition;
+use crate::extensions::fixtures::{DiscoveredFixture, dynamic_fixture_requests};
+use crate::extensions::tags::Tags;
+use crate::extensions::tags::python::PyTestFunction;
 
 /// Visitor for discovering test functions and fixture definitions in a given module.
 ///
@@ -92,8 +98,14 @@ impl FunctionDefinitionVisitor<'_, '_, '_, '_> {
             py_module,
             self.module.module_path(),
             is_generator_function,
+            self.context.cwd(),
         ) {
-            Ok(fixture_def) => self.module.add_fixture(fixture_def),
+            Ok(fixture_def) => {
+                check_fixture_requests(self.context, &stmt_function_def, || {
+                    self.module.source_file()
+                });
+                self.module.add_fixture(fixture_def);
+            }
             Err(e) => {
                 report_invalid_fixture(
                     self.context,
@@ -114,12 +126,122 @@ impl FunctionDefinitionVisitor<'_, '_, '_, '_> {
         };
 
         if let Ok(py_function) = py_module.getattr(stmt_function_def.name.to_string()) {
-            self.module.add_test_function(DiscoveredTestFunction::new(
+            let test_function = DiscoveredTestFunction::new(
                 self.py,
                 self.module,
                 Rc::new(stmt_function_def),
                 py_function.unbind(),
-            ));
+            );
+            self.add_test_function(test_function);
+        }
+    }
+
+    /// Add a test to the module, reporting parametrize ids that are invalid
+    /// or shared by several parameter sets.
+    fn add_test_function(&mut self, test_function: DiscoveredTestFunction) {
+        let invalid_ids = test_function.tags.invalid_parametrize_ids();
+        if !invalid_ids.is_empty() {
+            report_invalid_parametrize_ids(
+                self.context,
+                self.module.source_file(),
+                &test_function.stmt_function_def,
+                &invalid_ids,
+            );
+        }
+
+        let duplicate_ids = test_function.tags.duplicate_parametrize_ids();
+        if !duplicate_ids.is_empty() {
+            report_duplicate_parametrize_ids(
+                self.context,
+                self.module.source_file(),
+                &test_function.stmt_function_def,
+                &duplicate_ids,
+            );
+        }
+
+        self.module.add_test_function(test_function);
+    }
+
+    fn process_test_class(&mut self, collected_class: CollectedClass) {
+        self.try_import_module();
+
+        let Some(py_module) = self.py_module.as_ref() else {
+            return;
+        };
+
+        let Ok(py_attribute) = py_module.getattr(collected_class.name.as_str()) else {
+            return;
+        };
+        let py_attribute = py_attribute.unbind();
+
+        let mut tags = Tags::from_py_any(self.py, &py_attribute, None);
+
+        // Karva tags applied to a class wrap it like they would a function.
+        let py_class = match py_attribute.extract::<PyRef<PyTestFunction>>(self.py) {
+            Ok(test_function) => test_function.function.clone_ref(self.py),
+            Err(_) => py_attribute,
+        };
+        let py_class_bound = py_class.bind(self.py).clone();
+
+        let is_test_case = is_unittest_test_case(&py_class_bound);
+
+        if is_test_case {
+            tags.extend(&Tags::from_unittest_attributes(self.py, &py_class));
+        }
+
+        let mut class =
+            DiscoveredTestClass::new(collected_class.name, py_class, tags, is_test_case);
+
+        for fixture_function_def in collected_class.fixture_function_defs {
+            let is_generator_function = is_generator(&fixture_function_def);
+
+            let stmt_function_def = Rc::new(fixture_function_def);
+
+            match DiscoveredFixture::try_from_function(
+                self.py,
+                stmt_function_def.clone(),
+                &py_class_bound,
+                self.module.module_path(),
+                is_generator_function,
+                self.context.cwd(),
+            ) {
+                Ok(fixture_def) => {
+                    check_fixture_requests(self.context, &stmt_function_def, || {
+                        self.module.source_file()
+                    });
+                    let fixture_def = fixture_def.with_class_name(class.name.clone());
+                    // A static method isn't called with the test's instance.
+                    if is_staticmethod(&stmt_function_def) {
+                        class.add_fixture(fixture_def);
+                    } else {
+                        class.add_fixture(fixture_def.into_method());
+                    }
+                }
+                Err(e) => {
+                    report_invalid_fixture(
+                        self.context,
+                        self.py,
+                        self.module.source_file(),
+                        &stmt_function_def,
+                        &e,
+                    );
+                }
+            }
+        }
+
+        let class = Rc::new(class);
+
+        for test_function_def in collected_class.test_function_defs {
+            if let Ok(py_function) = py_class_bound.getattr(test_function_def.name.as_str()) {
+                let test_function = DiscoveredTestFunction::new_method(
+                    self.py,
+                    self.module,
+                    &class,
+                    Rc::new(test_function_def),
+                    py_function.unbind(),
+                );
+                self.add_test_function(test_function);
+            }
         }
     }
 
@@ -172,7 +294,7 @@ impl FunctionDefinitionVisitor<'_, '_, '_, '_> {
             .module
             .test_functions()
             .iter()
-            .any(|f| f.name.function_name() == name)
+            .any(|f| f.class.is_none() && f.name.function_name() == name)
         {
             return None;
         }
@@ -218,6 +340,7 @@ impl FunctionDefinitionVisitor<'_, '_, '_, '_> {
             &imported_module,
             &module_path,
             is_generator_function,
+            self.context.cwd(),
         ) {
             Ok(fixture_def) => self.module.add_fixture(fixture_def),
             Err(e) => {
@@ -241,6 +364,7 @@ pub fn discover(
     module: &mut DiscoveredModule,
     test_function_defs: Vec<StmtFunctionDef>,
     fixture_function_defs: Vec<StmtFunctionDef>,
+    test_classes: Vec<CollectedClass>,
 ) {
     let is_conftest = module
         .path()
@@ -253,6 +377,10 @@ pub fn discover(
         visitor.process_test_function(test_function_def);
     }
 
+    for test_class in test_classes {
+        visitor.process_test_class(test_class);
+    }
+
     for fixture_function_def in fixture_function_defs {
         visitor.process_fixture_function(fixture_function_def);
     }
@@ -262,6 +390,19 @@ pub fn discover(
     }
 }
 
+/// Reports the `request.getfixturevalue` calls of a fixture that look up a
+/// fixture by a name that isn't a string literal.
+fn check_fixture_requests(
+    context: &Context,
+    stmt_function_def: &StmtFunctionDef,
+    source_file: impl FnOnce() -> SourceFile,
+) {
+    let ranges = dynamic_fixture_requests(stmt_function_def);
+    if !ranges.is_empty() {
+        report_dynamic_fixture_requests(context, source_file(), stmt_function_def, &ranges);
+    }
+}
+
 /// Returns `true` if the function body contains a yield or yield-from expression.
 pub fn is_generator(stmt_function_def: &StmtFunctionDef) -> bool {
     let mut visitor = GeneratorFunctionVisitor::default();
@@ -296,6 +437,20 @@ fn is_fixture_value(value: &Bound<'_, PyAny>) -> bool {
         || value.cast::<FixtureFunctionDefinition>().is_ok()
 }
 
+/// Returns `true` if `class` is a subclass of `unittest.TestCase`.
+fn is_unittest_test_case(class: &Bound<'_, PyAny>) -> bool {
+    let Ok(class) = class.cast::<PyType>() else {
+        return false;
+    };
+
+    class
+        .py()
+        .import("unittest")
+        .and_then(|unittest| unittest.getattr("TestCase"))
+        .and_then(|test_case| class.is_subclass(&test_case))
+        .unwrap_or(false)
+}
+
 fn find_function_statement(
     name: &str,
     source_text: &str,
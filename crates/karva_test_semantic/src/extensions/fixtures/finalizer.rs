@@ -22,13 +22,17 @@ use crate::utils::{run_coroutine, source_file};
 ///     yield value
 ///     # teardown (finalizer runs this part)
 /// ```
+///
+/// Callables a fixture registers with `request.addfinalizer` are also run
+/// as finalizers.
 #[derive(Debug)]
 pub struct Finalizer {
-    /// The generator or async generator, positioned after yield, ready for teardown.
+    /// The generator or async generator, positioned after yield, ready for
+    /// teardown, or the callable passed to `request.addfinalizer`.
     pub(crate) fixture_return: Py<PyAny>,
 
-    /// Whether this finalizer wraps an async generator (requires `asyncio.run()`).
-    pub(crate) is_async: bool,
+    /// How `fixture_return` is run for teardown.
+    pub(crate) kind: FinalizerKind,
 
     /// The scope determines when this finalizer runs.
     pub(crate) scope: FixtureScope,
@@ -42,10 +46,10 @@ pub struct Finalizer {
 
 impl Finalizer {
     pub(crate) fn run(self, context: &Context, py: Python<'_>) {
-        let invalid_finalizer_reason = if self.is_async {
-            self.run_async_teardown(py)
-        } else {
-            self.run_sync_teardown(py)
+        let invalid_finalizer_reason = match self.kind {
+            FinalizerKind::Generator => self.run_sync_teardown(py),
+            FinalizerKind::AsyncGenerator => self.run_async_teardown(py),
+            FinalizerKind::Callback => self.run_callback(py),
         };
 
         if let Some(reason) = invalid_finalizer_reason
@@ -79,6 +83,14 @@ impl Finalizer {
         Some(reason)
     }
 
+    /// Runs a callable registered with `request.addfinalizer`.
+    fn run_callback(&self, py: Python<'_>) -> Option<String> {
+        self.fixture_return
+            .call0(py)
+            .err()
+            .map(|err| format!("Finalizer raised an exception: {}", err.value(py)))
+    }
+
     /// Runs teardown for an async generator fixture.
     fn run_async_teardown(&self, py: Python<'_>) -> Option<String> {
         let bound = self.fixture_return.bind(py);
@@ -98,3 +110,16 @@ impl Finalizer {
         Some(reason)
     }
 }
+
+/// The kinds of teardown a [`Finalizer`] can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinalizerKind {
+    /// A generator fixture, resumed with `next()`.
+    Generator,
+
+    /// An async generator fixture, resumed with `__anext__()`.
+    AsyncGenerator,
+
+    /// A callable registered with `request.addfinalizer`.
+    Callback,
+}
@@ -1,5 +1,6 @@
 use std::rc::Rc;
 
+use camino::Utf8Path;
 use karva_python_semantic::{ModulePath, QualifiedFunctionName};
 use pyo3::exceptions::PyAttributeError;
 use pyo3::prelude::*;
@@ -7,18 +8,25 @@ use ruff_python_ast::StmtFunctionDef;
 
 mod finalizer;
 mod normalized_fixture;
+mod param;
 pub mod python;
+mod request;
 mod scope;
 mod traits;
 mod utils;
 
-pub use finalizer::Finalizer;
+pub use finalizer::{Finalizer, FinalizerKind};
 pub use normalized_fixture::NormalizedFixture;
-pub use scope::FixtureScope;
-pub use traits::{HasFixtures, RequiresFixtures};
+pub use param::FixtureParam;
+pub use request::{
+    FixtureRequest, RequestContext, dynamic_fixture_requests, requested_fixture_names,
+};
+pub use scope::{FixtureConfig, FixtureScope};
+pub use traits::{ClassFixtures, HasFixtures, RequiresFixtures};
 pub use utils::missing_arguments_from_error;
 
 use crate::discovery::DiscoveredPackage;
+use crate::extensions::fixtures::param::parse_fixture_params;
 use crate::extensions::fixtures::python::InvalidFixtureError;
 use crate::extensions::fixtures::scope::fixture_scope;
 
@@ -48,6 +56,13 @@ pub struct DiscoveredFixture {
 
     /// Whether this fixture is a generator (uses yield for teardown).
     is_generator: bool,
+
+    /// Whether this fixture is a method of a test class, called with the
+    /// instance the test runs on.
+    is_method: bool,
+
+    /// The values of a parametrized fixture. Empty if it has no `params`.
+    params: Vec<FixtureParam>,
 }
 
 impl DiscoveredFixture {
@@ -66,6 +81,32 @@ impl DiscoveredFixture {
             auto_use,
             function: Rc::new(function),
             is_generator,
+            is_method: false,
+            params: Vec::new(),
+        }
+    }
+
+    /// Parametrize this fixture with the given values.
+    #[must_use]
+    pub(crate) fn with_params(self, params: Vec<FixtureParam>) -> Self {
+        Self { params, ..self }
+    }
+
+    /// Qualify this fixture as defined in the test class `class_name`.
+    #[must_use]
+    pub(crate) fn with_class_name(self, class_name: String) -> Self {
+        Self {
+            name: self.name.with_class_name(class_name),
+            ..self
+        }
+    }
+
+    /// Mark this fixture as a method of a test class.
+    #[must_use]
+    pub(crate) fn into_method(self) -> Self {
+        Self {
+            is_method: true,
+            ..self
         }
     }
 
@@ -85,6 +126,14 @@ impl DiscoveredFixture {
         self.auto_use
     }
 
+    pub(crate) fn is_method(&self) -> bool {
+        self.is_method
+    }
+
+    pub(crate) fn params(&self) -> &[FixtureParam] {
+        &self.params
+    }
+
     pub(crate) fn function(&self) -> &Py<PyAny> {
         &self.function
     }
@@ -93,16 +142,20 @@ impl DiscoveredFixture {
         &self.stmt_function_def
     }
 
+    /// Create a fixture from the attribute of `py_parent` named after the
+    /// function definition. The parent is the module the fixture is defined
+    /// in, or the class for fixtures defined as methods.
     pub(crate) fn try_from_function(
         py: Python<'_>,
         stmt_function_def: Rc<StmtFunctionDef>,
-        py_module: &Bound<'_, PyModule>,
+        py_parent: &Bound<'_, PyAny>,
         module_path: &ModulePath,
         is_generator_function: bool,
+        rootpath: &Utf8Path,
     ) -> PyResult<Self> {
         tracing::debug!("Trying to parse `{}` as a fixture", stmt_function_def.name);
 
-        let function = py_module.getattr(stmt_function_def.name.to_string())?;
+        let function = py_parent.getattr(stmt_function_def.name.to_string())?;
 
         let try_karva = Self::try_from_karva_function(
             py,
@@ -110,6 +163,7 @@ impl DiscoveredFixture {
             &function,
             module_path.clone(),
             is_generator_function,
+            rootpath,
         );
 
         let try_karva_err = match try_karva {
@@ -126,6 +180,7 @@ impl DiscoveredFixture {
             &function,
             module_path.clone(),
             is_generator_function,
+            rootpath,
         );
 
         match try_pytest {
@@ -143,6 +198,7 @@ impl DiscoveredFixture {
         function: &Bound<'_, PyAny>,
         module_name: ModulePath,
         is_generator_function: bool,
+        rootpath: &Utf8Path,
     ) -> PyResult<Self> {
         let fixture_function_marker = get_fixture_function_marker(function)?;
 
@@ -152,6 +208,17 @@ impl DiscoveredFixture {
 
         let auto_use = fixture_function_marker.getattr("autouse")?;
 
+        let params = fixture_function_marker
+            .getattr("params")
+            .ok()
+            .and_then(|params| params.extract::<Option<Vec<Py<PyAny>>>>().ok())
+            .flatten();
+
+        let ids = fixture_function_marker
+            .getattr("ids")
+            .ok()
+            .filter(|ids| !ids.is_none());
+
         let fixture_function = get_fixture_function(function)?;
 
         let name = if found_name.is_none() {
@@ -161,7 +228,12 @@ impl DiscoveredFixture {
         };
 
         let fixture_scope =
-            fixture_scope(py, &scope, &name).map_err(InvalidFixtureError::new_err)?;
+            fixture_scope(py, &scope, &name, rootpath).map_err(InvalidFixtureError::new_err)?;
+
+        let params = params
+            .map(|params| parse_fixture_params(py, params, ids.as_ref()))
+            .transpose()?
+            .unwrap_or_default();
 
         Ok(Self::new(
             QualifiedFunctionName::new(name, module_name),
@@ -170,7 +242,8 @@ impl DiscoveredFixture {
             auto_use.extract::<bool>().unwrap_or(false),
             fixture_function.into(),
             is_generator_function,
-        ))
+        )
+        .with_params(params))
     }
 
     pub(crate) fn try_from_karva_function(
@@ -179,6 +252,7 @@ impl DiscoveredFixture {
         function: &Bound<'_, PyAny>,
         module_path: ModulePath,
         is_generator_function: bool,
+        rootpath: &Utf8Path,
     ) -> PyResult<Self> {
         let py_function = function
             .clone()
@@ -190,8 +264,21 @@ impl DiscoveredFixture {
         let name = py_function_borrow.name.clone();
         let auto_use = py_function_borrow.auto_use;
 
-        let fixture_scope =
-            fixture_scope(py, scope_obj.bind(py), &name).map_err(InvalidFixtureError::new_err)?;
+        let fixture_scope = fixture_scope(py, scope_obj.bind(py), &name, rootpath)
+            .map_err(InvalidFixtureError::new_err)?;
+
+        let params = py_function_borrow
+            .params
+            .as_ref()
+            .map(|params| {
+                let params = params.iter().map(|param| param.clone_ref(py)).collect();
+                let ids = py_function_borrow.ids.as_ref().map(|ids| ids.bind(py));
+                parse_fixture_params(py, params, ids)
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        drop(py_function_borrow);
 
         Ok(Self::new(
             QualifiedFunctionName::new(name, module_path),
@@ -200,7 +287,8 @@ impl DiscoveredFixture {
             auto_use,
             py_function.into(),
             is_generator_function,
-        ))
+        )
+        .with_params(params))
     }
 }
 
@@ -1,12 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 
 use karva_python_semantic::QualifiedFunctionName;
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDict, PyTuple};
 use ruff_python_ast::StmtFunctionDef;
 
-use crate::extensions::fixtures::FixtureScope;
+use crate::extensions::fixtures::{FixtureParam, FixtureRequest, FixtureScope};
 use crate::extensions::tags::Tags;
 use crate::utils::run_coroutine;
 
@@ -29,6 +29,10 @@ pub struct NormalizedFixture {
     /// Whether this fixture uses yield for teardown logic.
     pub(crate) is_generator: bool,
 
+    /// Whether this fixture is a method of a test class, called with the
+    /// instance the test runs on.
+    pub(crate) is_method: bool,
+
     /// Reference to the Python callable that produces the fixture value.
     /// Wrapped in ``Rc`` so ``NormalizedFixture`` stays cheaply ``Clone``
     /// without needing a Python token (``Py<T>`` only supports
@@ -37,6 +41,21 @@ pub struct NormalizedFixture {
 
     /// AST representation of the fixture function definition.
     pub(crate) stmt_function_def: Rc<StmtFunctionDef>,
+
+    /// The value this variant of a parametrized fixture runs for.
+    pub(crate) param: Option<FixtureParam>,
+
+    /// The param index of every parametrized fixture in this fixture's
+    /// dependency tree, itself included, keyed by qualified fixture name.
+    pub(crate) param_indices: BTreeMap<String, usize>,
+
+    /// Whether the fixture takes a `request` parameter.
+    pub(crate) takes_request: bool,
+
+    /// Fixtures looked up with `request.getfixturevalue` that are not also
+    /// parameters. They are among `dependencies`, so they are set up first,
+    /// but are not passed as arguments.
+    pub(crate) requested_fixtures: Vec<String>,
 }
 
 impl NormalizedFixture {
@@ -55,9 +74,62 @@ impl NormalizedFixture {
         self.scope
     }
 
+    /// The key the fixture's value is cached under.
+    ///
+    /// Each combination of params gets its own key, so a parametrized
+    /// fixture is set up once per param within its scope. Fixtures of test
+    /// classes are keyed by their class too, so that same-named fixtures of
+    /// different classes don't share a value.
+    pub(crate) fn cache_key(&self) -> String {
+        let name = self.name.local_name();
+        if self.param_indices.is_empty() {
+            return name;
+        }
+
+        let params = self
+            .param_indices
+            .iter()
+            .map(|(name, index)| format!("{name}={index}"))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!("{name}[{params}]")
+    }
+
+    /// Returns `true` if both fixtures use the same param for every
+    /// parametrized fixture they share, so they can run for the same test.
+    pub(crate) fn is_compatible_with(&self, other: &Self) -> bool {
+        self.param_indices.iter().all(|(name, index)| {
+            other
+                .param_indices
+                .get(name)
+                .is_none_or(|other_index| other_index == index)
+        })
+    }
+
+    /// Returns the params of this fixture and its dependencies, with the
+    /// name of the fixture each belongs to.
+    pub(crate) fn params(&self) -> Vec<(&str, &FixtureParam)> {
+        let mut params: Vec<_> = self
+            .dependencies
+            .iter()
+            .flat_map(|dependency| dependency.params())
+            .collect();
+
+        if let Some(param) = &self.param {
+            params.push((self.function_name(), param));
+        }
+
+        params
+    }
+
     pub(crate) fn resolved_tags(&self) -> Tags {
         let mut tags = Tags::default();
 
+        if let Some(param) = &self.param {
+            tags.extend(&param.tags);
+        }
+
         for dependency in self.dependencies() {
             tags.extend(&dependency.resolved_tags());
         }
@@ -66,13 +138,21 @@ impl NormalizedFixture {
     }
 
     /// Call this fixture with the already-resolved arguments and return the result.
+    ///
+    /// `instance` is passed as the first argument, and should be the test's
+    /// class instance for fixtures defined as methods. `request` is passed
+    /// as the `request` argument, if given.
     pub(crate) fn call(
         &self,
         py: Python,
+        instance: Option<&Py<PyAny>>,
         fixture_arguments: &HashMap<String, Py<PyAny>>,
+        request: Option<&Bound<'_, FixtureRequest>>,
     ) -> PyResult<Py<PyAny>> {
-        let result = if fixture_arguments.is_empty() {
-            self.py_function.call0(py)
+        let args = PyTuple::new(py, instance)?;
+
+        let result = if fixture_arguments.is_empty() && request.is_none() {
+            self.py_function.call1(py, args)
         } else {
             let kwargs_dict = PyDict::new(py);
 
@@ -80,7 +160,11 @@ impl NormalizedFixture {
                 kwargs_dict.set_item(key, value)?;
             }
 
-            self.py_function.call(py, (), Some(&kwargs_dict))
+            if let Some(request) = request {
+                kwargs_dict.set_item("request", request)?;
+            }
+
+            self.py_function.call(py, args, Some(&kwargs_dict))
         };
 
         if self.stmt_function_def.is_async && !self.is_generator {
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::extensions::tags::Tags;
+use crate::extensions::tags::parametrize::handle_custom_parametrize_param;
+
+/// One value of a parametrized fixture.
+///
+/// A fixture declared with `params=[...]` runs once per value, and every
+/// test depending on it runs once per value too.
+#[derive(Debug, Clone)]
+pub struct FixtureParam {
+    /// Position of the value in the fixture's `params`.
+    pub(crate) index: usize,
+
+    /// The value, available to the fixture as `request.param`.
+    pub(crate) value: Arc<Py<PyAny>>,
+
+    /// The id shown in test names in place of the value, from `ids=`.
+    pub(crate) id: Option<String>,
+
+    /// Tags from a `karva.param` or `pytest.param` wrapping the value.
+    pub(crate) tags: Tags,
+}
+
+/// Parse the `params` and `ids` arguments of a fixture decorator.
+///
+/// `ids` is either a list of ids, where `None` keeps the default for that
+/// value, or a callable returning the id for a value. An id given with
+/// `karva.param` or `pytest.param` takes precedence.
+pub(crate) fn parse_fixture_params(
+    py: Python<'_>,
+    params: Vec<Py<PyAny>>,
+    ids: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Vec<FixtureParam>> {
+    let id_list = fixture_id_list(ids, params.len())?;
+
+    params
+        .into_iter()
+        .enumerate()
+        .map(|(index, param)| {
+            let parametrization = handle_custom_parametrize_param(py, param, false);
+            let value = parametrization
+                .values
+                .first()
+                .map_or_else(|| Arc::new(py.None()), Arc::clone);
+
+            let id = match (parametrization.id, ids) {
+                (Some(id), _) => Some(id),
+                (None, Some(ids)) if ids.is_callable() => {
+                    ids.call1((value.bind(py),))?.extract::<Option<String>>()?
+                }
+                (None, _) => id_list.get(index).cloned().flatten(),
+            };
+
+            Ok(FixtureParam {
+                index,
+                value,
+                id,
+                tags: parametrization.tags,
+            })
+        })
+        .collect()
+}
+
+/// Extract a list of fixture `ids`, which must have one id for each of the
+/// `param_count` params. Returns an empty list when `ids` is a callable or
+/// not given.
+pub(crate) fn fixture_id_list(
+    ids: Option<&Bound<'_, PyAny>>,
+    param_count: usize,
+) -> PyResult<Vec<Option<String>>> {
+    let Some(ids) = ids.filter(|ids| !ids.is_none() && !ids.is_callable()) else {
+        return Ok(Vec::new());
+    };
+
+    let id_list = ids.extract::<Vec<Option<String>>>()?;
+    if id_list.len() != param_count {
+        return Err(PyValueError::new_err(format!(
+            "`ids` must have one id for each of the {param_count} params, got {}",
+            id_list.len()
+        )));
+    }
+    Ok(id_list)
+}
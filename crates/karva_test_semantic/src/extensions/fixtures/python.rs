@@ -1,6 +1,8 @@
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyTuple};
 
+use crate::extensions::fixtures::param::fixture_id_list;
+
 /// Marker object created when `@fixture(...)` is called with arguments.
 ///
 /// This is an intermediate object that captures the decorator arguments
@@ -18,6 +20,12 @@ pub struct FixtureFunctionMarker {
     /// Whether this fixture should be automatically used.
     #[pyo3(get)]
     pub auto_use: bool,
+
+    /// Values to parametrize the fixture with, one test variant per value.
+    pub params: Option<Vec<Py<PyAny>>>,
+
+    /// Display ids for the params: a list, or a callable taking a param value.
+    pub ids: Option<Py<PyAny>>,
 }
 
 impl FixtureFunctionMarker {
@@ -26,6 +34,8 @@ impl FixtureFunctionMarker {
         scope: Option<Py<PyAny>>,
         name: Option<String>,
         auto_use: bool,
+        params: Option<Vec<Py<PyAny>>>,
+        ids: Option<Py<PyAny>>,
     ) -> Self {
         let scope =
             scope.unwrap_or_else(|| "function".to_string().into_pyobject(py).unwrap().into());
@@ -34,6 +44,8 @@ impl FixtureFunctionMarker {
             scope,
             name,
             auto_use,
+            params,
+            ids,
         }
     }
 }
@@ -56,6 +68,11 @@ impl FixtureFunctionMarker {
             name: func_name,
             scope: self.scope.clone_ref(py),
             auto_use: self.auto_use,
+            params: self
+                .params
+                .as_ref()
+                .map(|params| params.iter().map(|param| param.clone_ref(py)).collect()),
+            ids: self.ids.as_ref().map(|ids| ids.clone_ref(py)),
         };
 
         Ok(fixture_def)
@@ -65,7 +82,7 @@ impl FixtureFunctionMarker {
 /// The final decorated fixture function with all metadata attached.
 ///
 /// This object wraps the original Python function and carries the fixture
-/// configuration (scope, name, `auto_use`) for later discovery.
+/// configuration (scope, name, `auto_use`, params) for later discovery.
 #[derive(Debug)]
 #[pyclass]
 pub struct FixtureFunctionDefinition {
@@ -84,6 +101,12 @@ pub struct FixtureFunctionDefinition {
     /// The underlying Python function that produces the fixture value.
     #[pyo3(get)]
     pub function: Py<PyAny>,
+
+    /// Values to parametrize the fixture with, one test variant per value.
+    pub params: Option<Vec<Py<PyAny>>>,
+
+    /// Display ids for the params: a list, or a callable taking a param value.
+    pub ids: Option<Py<PyAny>>,
 }
 
 #[pymethods]
@@ -100,15 +123,22 @@ impl FixtureFunctionDefinition {
 }
 
 #[pyfunction(name = "fixture")]
-#[pyo3(signature = (func=None, *, scope=None, name=None, auto_use=false))]
+#[pyo3(signature = (func=None, *, scope=None, name=None, auto_use=false, params=None, ids=None))]
 pub fn fixture_decorator(
     py: Python<'_>,
     func: Option<Py<PyAny>>,
     scope: Option<Py<PyAny>>,
     name: Option<&str>,
     auto_use: bool,
+    params: Option<Vec<Py<PyAny>>>,
+    ids: Option<Py<PyAny>>,
 ) -> PyResult<Py<PyAny>> {
-    let marker = FixtureFunctionMarker::new(py, scope, name.map(String::from), auto_use);
+    if let Some(params) = &params {
+        fixture_id_list(ids.as_ref().map(|ids| ids.bind(py)), params.len())?;
+    }
+
+    let marker =
+        FixtureFunctionMarker::new(py, scope, name.map(String::from), auto_use, params, ids);
     if let Some(f) = func {
         let fixture_def = marker.__call__(py, f)?;
         Ok(Py::new(py, fixture_def)?.into_any())
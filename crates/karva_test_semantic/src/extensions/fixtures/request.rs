@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use pyo3::exceptions::{PyAttributeError, PyLookupError};
+use pyo3::prelude::*;
+use ruff_python_ast::visitor::source_order::{self, SourceOrderVisitor};
+use ruff_python_ast::{Expr, StmtFunctionDef};
+use ruff_text_size::{Ranged, TextRange};
+
+use crate::extensions::fixtures::{FixtureScope, NormalizedFixture};
+
+/// What the runner is running when a fixture is set up.
+///
+/// The runner updates this as it enters packages, modules and tests, and
+/// each [`FixtureRequest`] takes the parts visible from its fixture's scope.
+#[derive(Debug, Default)]
+pub struct RequestContext {
+    /// Dotted name of the package being run, if it is not the session root.
+    pub(crate) package: Option<String>,
+
+    /// Dotted name of the module being run.
+    pub(crate) module: Option<String>,
+
+    /// Qualified name of the test being run.
+    pub(crate) test_name: Option<String>,
+
+    /// Custom tag names of the test being run.
+    pub(crate) tags: Vec<String>,
+}
+
+/// The object passed to a fixture that takes a `request` parameter.
+#[pyclass]
+pub struct FixtureRequest {
+    /// The name of the fixture being set up.
+    #[pyo3(get)]
+    fixture_name: String,
+
+    /// The scope of the fixture being set up.
+    scope: FixtureScope,
+
+    /// The qualified name of the test the fixture is set up for. Only set
+    /// for function-scoped fixtures.
+    #[pyo3(get)]
+    test_name: Option<String>,
+
+    /// The module the fixture is set up in. Unset for package and session
+    /// scoped fixtures.
+    #[pyo3(get)]
+    module: Option<String>,
+
+    /// The package the fixture is set up in. Unset for session scoped
+    /// fixtures and the session root.
+    #[pyo3(get)]
+    package: Option<String>,
+
+    /// Custom tag names of the test. Only set for function-scoped fixtures.
+    #[pyo3(get)]
+    tags: Vec<String>,
+
+    /// The current value of a parametrized fixture.
+    param: Option<Py<PyAny>>,
+
+    /// Values of the fixtures the fixture depends on, by name.
+    fixture_values: HashMap<String, Py<PyAny>>,
+
+    /// Callables registered with `addfinalizer`, in registration order.
+    finalizers: Vec<Py<PyAny>>,
+}
+
+impl FixtureRequest {
+    pub(crate) fn new(
+        py: Python<'_>,
+        context: &RequestContext,
+        fixture: &NormalizedFixture,
+        fixture_values: HashMap<String, Py<PyAny>>,
+    ) -> Self {
+        let scope = fixture.scope();
+        let is_function = scope == FixtureScope::Function;
+
+        Self {
+            fixture_name: fixture.function_name().to_string(),
+            scope,
+            test_name: context.test_name.clone().filter(|_| is_function),
+            module: context
+                .module
+                .clone()
+                .filter(|_| matches!(scope, FixtureScope::Function | FixtureScope::Module)),
+            package: context
+                .package
+                .clone()
+                .filter(|_| scope != FixtureScope::Session),
+            tags: if is_function {
+                context.tags.clone()
+            } else {
+                Vec::new()
+            },
+            param: fixture
+                .param
+                .as_ref()
+                .map(|param| param.value.clone_ref(py)),
+            fixture_values,
+            finalizers: Vec::new(),
+        }
+    }
+
+    /// Take the callables registered with `addfinalizer`.
+    pub(crate) fn take_finalizers(&mut self) -> Vec<Py<PyAny>> {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+#[pymethods]
+impl FixtureRequest {
+    /// The value the fixture is running for, from its `params`.
+    #[getter]
+    fn param(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.param
+            .as_ref()
+            .map(|param| param.clone_ref(py))
+            .ok_or_else(|| {
+                PyAttributeError::new_err("`param` is only set for fixtures with `params`")
+            })
+    }
+
+    /// The scope of the fixture, such as `"function"` or `"module"`.
+    #[getter]
+    fn scope(&self) -> &'static str {
+        self.scope.as_str()
+    }
+
+    /// Register a callable to run when the fixture's scope ends.
+    fn addfinalizer(&mut self, finalizer: Py<PyAny>) {
+        self.finalizers.push(finalizer);
+    }
+
+    /// Return the value of the fixture with the given name.
+    fn getfixturevalue(&self, py: Python<'_>, name: &str) -> PyResult<Py<PyAny>> {
+        self.fixture_values
+            .get(name)
+            .map(|value| value.clone_ref(py))
+            .ok_or_else(|| {
+                PyLookupError::new_err(format!(
+                    "Fixture `{name}` is not available to `{}`. Fixtures looked up with \
+                     `getfixturevalue` must exist and be named with a string literal",
+                    self.fixture_name
+                ))
+            })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<FixtureRequest for fixture `{}`>", self.fixture_name)
+    }
+}
+
+/// Returns the fixture names a function looks up with
+/// `request.getfixturevalue("name")`.
+///
+/// Only names written as string literals are found. These fixtures are
+/// resolved and set up before the function is called.
+pub fn requested_fixture_names(stmt_function_def: &StmtFunctionDef) -> Vec<String> {
+    let mut visitor = GetFixtureValueVisitor::default();
+    source_order::walk_body(&mut visitor, &stmt_function_def.body);
+    visitor.names
+}
+
+/// Returns the ranges of the names of `request.getfixturevalue` calls that
+/// aren't string literals, such as `request.getfixturevalue(name)`.
+///
+/// These fixtures can't be set up before the function is called.
+pub fn dynamic_fixture_requests(stmt_function_def: &StmtFunctionDef) -> Vec<TextRange> {
+    let mut visitor = GetFixtureValueVisitor::default();
+    source_order::walk_body(&mut visitor, &stmt_function_def.body);
+    visitor.dynamic_names
+}
+
+/// Visitor that collects the arguments of `request.getfixturevalue` calls.
+#[derive(Default)]
+struct GetFixtureValueVisitor {
+    /// Names written as string literals.
+    names: Vec<String>,
+    /// Ranges of the names that aren't string literals.
+    dynamic_names: Vec<TextRange>,
+}
+
+impl SourceOrderVisitor<'_> for GetFixtureValueVisitor {
+    fn visit_expr(&mut self, expr: &'_ Expr) {
+        if let Expr::Call(call) = expr
+            && let Expr::Attribute(attribute) = &*call.func
+            && attribute.attr.id == "getfixturevalue"
+            && let Expr::Name(name) = &*attribute.value
+            && name.id == "request"
+            && let Some(argument) = call.arguments.args.first()
+        {
+            if let Expr::StringLiteral(literal) = argument {
+                let fixture_name = literal.value.to_str().to_string();
+                if !self.names.contains(&fixture_name) {
+                    self.names.push(fixture_name);
+                }
+            } else {
+                self.dynamic_names.push(argument.range());
+            }
+        }
+
+        source_order::walk_expr(self, expr);
+    }
+}
@@ -1,3 +1,4 @@
+use camino::{Utf8Path, Utf8PathBuf};
 use pyo3::prelude::*;
 
 /// A scope for a fixture.
@@ -22,6 +23,16 @@ impl FixtureScope {
             Session => vec![Session],
         }
     }
+
+    /// Returns the name of the scope, as written in `@fixture(scope=...)`.
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Module => "module",
+            Self::Package => "package",
+            Self::Session => "session",
+        }
+    }
 }
 
 impl TryFrom<String> for FixtureScope {
@@ -38,20 +49,58 @@ impl TryFrom<String> for FixtureScope {
     }
 }
 
+/// The `config` passed to a dynamic scope function.
+///
+/// This stands in for pytest's `Config`, with the parts a scope function
+/// can use in karva.
+#[pyclass(frozen)]
+pub struct FixtureConfig {
+    /// The root directory of the run.
+    rootpath: Utf8PathBuf,
+}
+
+#[pymethods]
+impl FixtureConfig {
+    /// The root directory of the run, as a `pathlib.Path`.
+    #[getter]
+    fn rootpath<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        py.import("pathlib")?
+            .getattr("Path")?
+            .call1((self.rootpath.as_str(),))
+    }
+
+    /// Return the value of a command line option.
+    ///
+    /// Karva has no options for plugins to add, so this always returns
+    /// `default`.
+    #[pyo3(signature = (name, default = None))]
+    #[expect(clippy::unused_self, unused_variables)]
+    fn getoption(&self, name: &str, default: Option<Py<PyAny>>) -> Option<Py<PyAny>> {
+        default
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<FixtureConfig rootpath={}>", self.rootpath)
+    }
+}
+
 /// Resolve a dynamic scope function to a concrete `FixtureScope`
 pub fn resolve_dynamic_scope(
     py: Python<'_>,
     scope_fn: &Bound<'_, PyAny>,
     fixture_name: &str,
+    rootpath: &Utf8Path,
 ) -> Result<FixtureScope, String> {
     let kwargs = pyo3::types::PyDict::new(py);
     kwargs
         .set_item("fixture_name", fixture_name)
         .map_err(|e| format!("Failed to set fixture_name: {e}"))?;
 
-    // TODO: Support config
+    let config = FixtureConfig {
+        rootpath: rootpath.to_path_buf(),
+    };
     kwargs
-        .set_item("config", py.None())
+        .set_item("config", config)
         .map_err(|e| format!("Failed to set config: {e}"))?;
 
     let result = scope_fn
@@ -69,9 +118,10 @@ pub fn fixture_scope(
     py: Python<'_>,
     scope_obj: &Bound<'_, PyAny>,
     name: &str,
+    rootpath: &Utf8Path,
 ) -> Result<FixtureScope, String> {
     if scope_obj.is_callable() {
-        resolve_dynamic_scope(py, scope_obj, name)
+        resolve_dynamic_scope(py, scope_obj, name, rootpath)
     } else if let Ok(scope_str) = scope_obj.extract::<String>() {
         FixtureScope::try_from(scope_str)
     } else {
@@ -3,7 +3,7 @@ use std::fmt::Debug;
 use pyo3::Python;
 use ruff_python_ast::StmtFunctionDef;
 
-use crate::discovery::{DiscoveredModule, DiscoveredPackage};
+use crate::discovery::{DiscoveredModule, DiscoveredPackage, DiscoveredTestClass};
 use crate::extensions::fixtures::{DiscoveredFixture, FixtureScope};
 
 /// This trait is used to get all fixtures (from a module or package) that have a given scope.
@@ -35,6 +35,45 @@ impl<'a> HasFixtures<'a> for DiscoveredModule {
     }
 }
 
+/// The fixtures visible to the methods of a test class: the fixtures
+/// defined on the class, followed by those of its module.
+#[derive(Debug)]
+pub struct ClassFixtures<'a> {
+    pub class: &'a DiscoveredTestClass,
+    pub module: &'a DiscoveredModule,
+}
+
+impl<'a, 'b: 'a> HasFixtures<'a> for ClassFixtures<'b> {
+    fn get_fixture(&'a self, fixture_name: &str) -> Option<&'a DiscoveredFixture> {
+        self.class
+            .fixtures()
+            .iter()
+            .find(|f| f.name().function_name() == fixture_name)
+            .or_else(|| self.module.get_fixture(fixture_name))
+    }
+
+    fn auto_use_fixtures(&'a self, scopes: &[FixtureScope]) -> Vec<&'a DiscoveredFixture> {
+        let mut fixtures: Vec<&'a DiscoveredFixture> = self
+            .class
+            .fixtures()
+            .iter()
+            .filter(|f| f.auto_use() && scopes.contains(&f.scope()))
+            .collect();
+
+        // A class fixture shadows a module fixture with the same name.
+        for fixture in self.module.auto_use_fixtures(scopes) {
+            if !fixtures
+                .iter()
+                .any(|f| f.name().function_name() == fixture.name().function_name())
+            {
+                fixtures.push(fixture);
+            }
+        }
+
+        fixtures
+    }
+}
+
 impl<'a> HasFixtures<'a> for DiscoveredPackage {
     fn get_fixture(&'a self, fixture_name: &str) -> Option<&'a DiscoveredFixture> {
         self.configuration_module_impl()
@@ -101,6 +140,13 @@ impl RequiresFixtures for StmtFunctionDef {
 
 impl RequiresFixtures for DiscoveredFixture {
     fn required_fixtures(&self, py: Python<'_>) -> Vec<String> {
-        self.stmt_function_def.required_fixtures(py)
+        let mut required_fixtures = self.stmt_function_def.required_fixtures(py);
+
+        // The first parameter of a fixture method receives the test's instance.
+        if self.is_method() && !required_fixtures.is_empty() {
+            required_fixtures.remove(0);
+        }
+
+        required_fixtures
     }
 }
@@ -1,6 +1,6 @@
 pub use self::python::{FailError, Param, SkipError, fail, param, skip};
 pub use self::raises::{ExceptionInfo, RaisesContext};
-pub use self::snapshot::{Command, SnapshotMismatchError, SnapshotSettings};
+pub use self::snapshot::{Command, SnapshotMismatchError, SnapshotSettings, SortedRedaction};
 
 pub mod python;
 pub mod raises;
@@ -20,10 +20,18 @@ pub struct Param {
 
     /// Tags associated with this parametrization
     pub(crate) tags: Tags,
+
+    /// The id shown in test names in place of the values
+    pub(crate) id: Option<String>,
 }
 
 impl Param {
-    pub(crate) fn new(py: Python, values: Vec<Py<PyAny>>, tags: Vec<Py<PyAny>>) -> PyResult<Self> {
+    pub(crate) fn new(
+        py: Python,
+        values: Vec<Py<PyAny>>,
+        tags: Vec<Py<PyAny>>,
+        id: Option<String>,
+    ) -> PyResult<Self> {
         let mut new_tags = Vec::new();
 
         for tag in tags {
@@ -36,11 +44,14 @@ impl Param {
         Ok(Self {
             values: values.into_iter().map(Arc::new).collect(),
             tags: Tags::new(new_tags),
+            id,
         })
     }
 
-    pub(crate) fn from_parametrization(Parametrization { values, tags }: Parametrization) -> Self {
-        Self { values, tags }
+    pub(crate) fn from_parametrization(
+        Parametrization { values, tags, id }: Parametrization,
+    ) -> Self {
+        Self { values, tags, id }
     }
 }
 
@@ -65,11 +76,12 @@ pub fn fail(_py: Python<'_>, reason: Option<String>) -> PyResult<()> {
 }
 
 #[pyfunction]
-#[pyo3(signature = (*values, tags = None))]
+#[pyo3(signature = (*values, tags = None, id = None))]
 pub fn param(
     py: Python<'_>,
     values: Vec<Py<PyAny>>,
     tags: Option<Vec<Py<PyAny>>>,
+    id: Option<String>,
 ) -> PyResult<Param> {
-    Param::new(py, values, tags.unwrap_or_default())
+    Param::new(py, values, tags.unwrap_or_default(), id)
 }
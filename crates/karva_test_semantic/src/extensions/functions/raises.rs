@@ -88,6 +88,17 @@ impl RaisesContext {
     }
 }
 
+impl RaisesContext {
+    /// The exception the `with` block raised, once it has exited.
+    pub(crate) fn caught(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+        self.exc_info
+            .borrow(py)
+            .value
+            .as_ref()
+            .map(|value| value.clone_ref(py))
+    }
+}
+
 /// Assert that a block of code raises a specific exception.
 #[pyfunction]
 #[pyo3(signature = (expected_exception, *, r#match = None))]
@@ -1,18 +1,28 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::process;
 
 use camino::{Utf8Path, Utf8PathBuf};
+use karva_metadata::SnapshotUpdateMode;
 use karva_snapshot::cmd::{CommandOutput, format_cmd_output};
 use karva_snapshot::diff::format_diff;
 use karva_snapshot::filters::{SnapshotFilter, apply_filters};
-use karva_snapshot::format::{SnapshotFile, SnapshotMetadata};
+use karva_snapshot::format::{Serializer, SnapshotFile, SnapshotMetadata};
+use karva_snapshot::redactions::{PathItem, Selector, format_path};
+use karva_snapshot::serializers::{binary_summary, to_toml, to_yaml};
 use karva_snapshot::storage::{
-    read_snapshot, snapshot_path, write_pending_snapshot, write_snapshot,
+    binary_path, pending_path, read_snapshot, remove_binary_data, snapshot_path, write_binary_data,
+    write_pending_snapshot, write_snapshot,
 };
-use karva_static::EnvVars;
 use pyo3::exceptions::PyOSError;
 use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyFrozenSet, PyList, PySet, PyString, PyTuple};
+
+use self::exceptions::{caught_exception, format_exception, recorded_warnings};
+use self::expression::asserted_expression;
+
+mod exceptions;
+mod expression;
 
 pyo3::create_exception!(
     karva,
@@ -24,39 +34,72 @@ struct SnapshotContext {
     test_file: String,
     test_name: String,
     counter: u32,
+    update: SnapshotUpdateMode,
 }
 
 struct ActiveSettings {
     filters: Vec<(String, String)>,
+    redactions: Vec<(String, Py<PyAny>)>,
+    info: Option<String>,
     allow_duplicates: bool,
 }
 
 thread_local! {
     static SNAPSHOT_CONTEXT: RefCell<Option<SnapshotContext>> = const { RefCell::new(None) };
     static SNAPSHOT_SETTINGS: RefCell<Vec<ActiveSettings>> = const { RefCell::new(Vec::new()) };
+    /// Every `.snap` file an assertion has compared against, so the main
+    /// process can find the ones no test uses any more.
+    static REFERENCED_SNAPSHOTS: RefCell<BTreeSet<Utf8PathBuf>> = const { RefCell::new(BTreeSet::new()) };
 }
 
 #[pyclass]
 pub struct SnapshotSettings {
     filters: Vec<(String, String)>,
+    redactions: Vec<(String, Py<PyAny>)>,
+    /// The `info` value, as YAML.
+    info: Option<String>,
     allow_duplicates: bool,
 }
 
 #[pymethods]
 impl SnapshotSettings {
     #[new]
-    #[pyo3(signature = (*, filters=None, allow_duplicates=false))]
-    fn new(filters: Option<Vec<(String, String)>>, allow_duplicates: bool) -> Self {
-        Self {
+    #[pyo3(signature = (*, filters=None, redactions=None, info=None, allow_duplicates=false))]
+    fn new(
+        filters: Option<Vec<(String, String)>>,
+        redactions: Option<&Bound<'_, PyDict>>,
+        info: Option<&Bound<'_, PyAny>>,
+        allow_duplicates: bool,
+    ) -> PyResult<Self> {
+        let info = info
+            .map(|info| {
+                to_data(
+                    info,
+                    &[],
+                    "snapshot_settings() info is not JSON serializable",
+                )
+                .map(|data| to_yaml(&data).trim_end().to_string())
+            })
+            .transpose()?;
+        Ok(Self {
             filters: filters.unwrap_or_default(),
+            redactions: extract_redactions(redactions)?,
+            info,
             allow_duplicates,
-        }
+        })
     }
 
     fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        let py = slf.py();
         SNAPSHOT_SETTINGS.with(|stack| {
             stack.borrow_mut().push(ActiveSettings {
                 filters: slf.filters.clone(),
+                redactions: slf
+                    .redactions
+                    .iter()
+                    .map(|(selector, redaction)| (selector.clone(), redaction.clone_ref(py)))
+                    .collect(),
+                info: slf.info.clone(),
                 allow_duplicates: slf.allow_duplicates,
             });
         });
@@ -74,12 +117,25 @@ impl SnapshotSettings {
 
 /// Create a `SnapshotSettings` context manager for scoped snapshot configuration.
 #[pyfunction]
-#[pyo3(signature = (*, filters=None, allow_duplicates=false))]
+#[pyo3(signature = (*, filters=None, redactions=None, info=None, allow_duplicates=false))]
 pub fn snapshot_settings(
     filters: Option<Vec<(String, String)>>,
+    redactions: Option<&Bound<'_, PyDict>>,
+    info: Option<&Bound<'_, PyAny>>,
     allow_duplicates: bool,
-) -> SnapshotSettings {
-    SnapshotSettings::new(filters, allow_duplicates)
+) -> PyResult<SnapshotSettings> {
+    SnapshotSettings::new(filters, redactions, info, allow_duplicates)
+}
+
+/// Marker redaction that sorts the selected list, set or dict.
+#[pyclass(frozen)]
+pub struct SortedRedaction;
+
+/// Create a redaction that sorts the selected value instead of replacing it,
+/// for lists and sets whose order is not stable.
+#[pyfunction]
+pub fn sorted_redaction() -> SortedRedaction {
+    SortedRedaction
 }
 
 #[pyclass]
@@ -199,7 +255,14 @@ pub fn assert_cmd_snapshot(
     let output = run_command(cmd)?;
     let serialized = format_cmd_output(&output);
     let serialized = apply_active_filters(&serialized)?;
-    assert_snapshot_impl(py, &serialized, inline.as_deref(), name.as_deref())
+    assert_snapshot_impl(
+        py,
+        &serialized,
+        Serializer::Cmd,
+        None,
+        inline.as_deref(),
+        name.as_deref(),
+    )
 }
 
 /// Check if any active settings scope has `allow_duplicates` enabled.
@@ -207,6 +270,11 @@ fn is_allow_duplicates_active() -> bool {
     SNAPSHOT_SETTINGS.with(|stack| stack.borrow().iter().any(|s| s.allow_duplicates))
 }
 
+/// The `info` of the innermost settings scope that sets one.
+fn active_info() -> Option<String> {
+    SNAPSHOT_SETTINGS.with(|stack| stack.borrow().iter().rev().find_map(|s| s.info.clone()))
+}
+
 /// Collect all filters from the settings stack and apply them to the input.
 fn apply_active_filters(input: &str) -> PyResult<String> {
     SNAPSHOT_SETTINGS.with(|stack| {
@@ -230,6 +298,177 @@ fn apply_active_filters(input: &str) -> PyResult<String> {
     })
 }
 
+/// Convert a `{selector: redaction}` dict into pairs, keeping its order.
+fn extract_redactions(
+    redactions: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Vec<(String, Py<PyAny>)>> {
+    let Some(redactions) = redactions else {
+        return Ok(Vec::new());
+    };
+    redactions
+        .iter()
+        .map(|(selector, redaction)| Ok((selector.extract::<String>()?, redaction.unbind())))
+        .collect()
+}
+
+/// What a redaction does to the values its selector matches.
+enum RedactionKind {
+    /// Replace the value with this one.
+    Replace(Py<PyAny>),
+    /// Replace the value with the result of calling this with the value and
+    /// its path.
+    Call(Py<PyAny>),
+    /// Sort the value.
+    Sort,
+}
+
+struct Redaction {
+    selector: Selector,
+    kind: RedactionKind,
+}
+
+/// Compile the redactions of the settings stack, from the outermost scope
+/// to the innermost, followed by those passed to the assertion.
+fn active_redactions(
+    py: Python<'_>,
+    call_redactions: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Vec<Redaction>> {
+    let mut pairs: Vec<(String, Py<PyAny>)> = SNAPSHOT_SETTINGS.with(|stack| {
+        stack
+            .borrow()
+            .iter()
+            .flat_map(|settings| &settings.redactions)
+            .map(|(selector, redaction)| (selector.clone(), redaction.clone_ref(py)))
+            .collect()
+    });
+    pairs.extend(extract_redactions(call_redactions)?);
+
+    pairs
+        .into_iter()
+        .map(|(selector, redaction)| {
+            let compiled = Selector::new(&selector).ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Invalid redaction selector: {selector}"
+                ))
+            })?;
+            let bound = redaction.bind(py);
+            let kind = if bound.is_instance_of::<SortedRedaction>() {
+                RedactionKind::Sort
+            } else if bound.is_callable() {
+                RedactionKind::Call(redaction)
+            } else {
+                RedactionKind::Replace(redaction)
+            };
+            Ok(Redaction {
+                selector: compiled,
+                kind,
+            })
+        })
+        .collect()
+}
+
+/// Return a copy of `value` with the redactions applied.
+///
+/// Nested values are redacted before the values containing them, so a
+/// sorted list is ordered by its redacted items.
+fn redact<'py>(
+    value: &Bound<'py, PyAny>,
+    path: &mut Vec<PathItem>,
+    redactions: &[Redaction],
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = value.py();
+    let mut value = if let Ok(dict) = value.cast::<PyDict>() {
+        let redacted = PyDict::new(py);
+        for (key, item) in dict.iter() {
+            path.push(PathItem::Key(key.str()?.to_string_lossy().into_owned()));
+            let item = redact(&item, path, redactions)?;
+            path.pop();
+            redacted.set_item(key, item)?;
+        }
+        redacted.into_any()
+    } else if value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>() {
+        let items = value.try_iter()?.collect::<PyResult<Vec<_>>>()?;
+        let len = items.len();
+        let mut redacted = Vec::with_capacity(len);
+        for (index, item) in items.iter().enumerate() {
+            path.push(PathItem::Index { index, len });
+            redacted.push(redact(item, path, redactions)?);
+            path.pop();
+        }
+        if value.is_instance_of::<PyTuple>() {
+            PyTuple::new(py, redacted)?.into_any()
+        } else {
+            PyList::new(py, redacted)?.into_any()
+        }
+    } else {
+        value.clone()
+    };
+
+    for redaction in redactions {
+        if !redaction.selector.matches(path) {
+            continue;
+        }
+        value = match &redaction.kind {
+            RedactionKind::Replace(replacement) => replacement.bind(py).clone(),
+            RedactionKind::Call(function) => function.bind(py).call1((value, format_path(path)))?,
+            RedactionKind::Sort => sort_value(&value)?,
+        };
+    }
+    Ok(value)
+}
+
+/// Sort a list, tuple or set into a list, or a dict by its keys. Other
+/// values are returned unchanged.
+///
+/// Items that cannot be compared with each other, like dicts, are ordered
+/// by their JSON serialization.
+fn sort_value<'py>(value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let py = value.py();
+    if let Ok(dict) = value.cast::<PyDict>() {
+        let keys = sort_items(py, dict.keys().iter().collect())?;
+        let sorted = PyDict::new(py);
+        for key in keys {
+            sorted.set_item(&key, dict.get_item(&key)?)?;
+        }
+        return Ok(sorted.into_any());
+    }
+    if value.is_instance_of::<PyList>()
+        || value.is_instance_of::<PyTuple>()
+        || value.is_instance_of::<PySet>()
+        || value.is_instance_of::<PyFrozenSet>()
+    {
+        let items = value.try_iter()?.collect::<PyResult<Vec<_>>>()?;
+        return Ok(PyList::new(py, sort_items(py, items)?)?.into_any());
+    }
+    Ok(value.clone())
+}
+
+fn sort_items<'py>(
+    py: Python<'py>,
+    items: Vec<Bound<'py, PyAny>>,
+) -> PyResult<Vec<Bound<'py, PyAny>>> {
+    let list = PyList::new(py, &items)?;
+    if list.sort().is_ok() {
+        return Ok(list.iter().collect());
+    }
+
+    let json = py.import("json")?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("sort_keys", true)?;
+    kwargs.set_item("default", py.get_type::<PyString>())?;
+    let mut keyed = items
+        .into_iter()
+        .map(|item| {
+            let key = json
+                .call_method("dumps", (&item,), Some(&kwargs))?
+                .extract::<String>()?;
+            Ok((key, item))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(keyed.into_iter().map(|(_, item)| item).collect())
+}
+
 /// Format a snapshot path for display, relativized against the current
 /// working directory when possible so users see e.g. `snapshots/foo.snap`
 /// instead of an absolute path.
@@ -244,21 +483,34 @@ fn display_relative(path: &Utf8Path) -> String {
 }
 
 /// Called by the test runner before each test to set snapshot context.
-pub fn set_snapshot_context(test_file: String, test_name: String) {
+///
+/// `update` decides which snapshot files the test's assertions may write.
+pub fn set_snapshot_context(test_file: String, test_name: String, update: SnapshotUpdateMode) {
     SNAPSHOT_CONTEXT.with(|ctx| {
         *ctx.borrow_mut() = Some(SnapshotContext {
             test_file,
             test_name,
             counter: 0,
+            update: update.resolve(),
         });
     });
 }
 
+/// Returns the `.snap` files that snapshot assertions have compared against
+/// since the last call, sorted.
+pub fn take_referenced_snapshots() -> Vec<Utf8PathBuf> {
+    REFERENCED_SNAPSHOTS
+        .with(|snapshots| std::mem::take(&mut *snapshots.borrow_mut()))
+        .into_iter()
+        .collect()
+}
+
 /// Assert that a value matches a stored snapshot.
 ///
 /// On first run (no existing snapshot), writes a pending `.snap.new` file.
 /// On subsequent runs, compares against the existing `.snap` file.
-/// If `KARVA_SNAPSHOT_UPDATE` is set, writes directly to `.snap` instead of `.snap.new`.
+/// `--snapshot-update` decides whether new and changed snapshots are
+/// written to `.snap`, to `.snap.new`, or not at all.
 ///
 /// When `inline` is provided, the expected value lives in the test source file
 /// instead of a separate `.snap` file.
@@ -273,7 +525,14 @@ pub fn assert_snapshot(
 ) -> PyResult<()> {
     let serialized = serialize_value(py, &value)?;
     let serialized = apply_active_filters(&serialized)?;
-    assert_snapshot_impl(py, &serialized, inline.as_deref(), name.as_deref())
+    assert_snapshot_impl(
+        py,
+        &serialized,
+        Serializer::Str,
+        None,
+        inline.as_deref(),
+        name.as_deref(),
+    )
 }
 
 /// Assert that a value matches a stored snapshot, serialized as JSON.
@@ -281,24 +540,282 @@ pub fn assert_snapshot(
 /// Uses `json.dumps(value, sort_keys=True, indent=2)` for deterministic,
 /// readable output. Supports all the same features as `assert_snapshot`:
 /// inline snapshots, `--snapshot-update`, filters, and the pending/accept workflow.
+///
+/// `redactions` maps selectors such as `.items[*].id` to the value that
+/// replaces the selected values before serialization, and adds to those of
+/// the active `snapshot_settings`.
 #[pyfunction]
-#[pyo3(signature = (value, *, inline=None, name=None))]
+#[pyo3(signature = (value, *, inline=None, name=None, redactions=None))]
 #[expect(clippy::needless_pass_by_value)]
 pub fn assert_json_snapshot(
     py: Python<'_>,
     value: Py<PyAny>,
     inline: Option<String>,
     name: Option<String>,
+    redactions: Option<&Bound<'_, PyDict>>,
+) -> PyResult<()> {
+    let redactions = active_redactions(py, redactions)?;
+    let serialized = serialize_json(py, &value, &redactions)?;
+    let serialized = apply_active_filters(&serialized)?;
+    assert_snapshot_impl(
+        py,
+        &serialized,
+        Serializer::Json,
+        None,
+        inline.as_deref(),
+        name.as_deref(),
+    )
+}
+
+/// Assert that a value matches a stored snapshot, serialized as YAML.
+///
+/// The value must be JSON serializable. Keys are sorted, and redactions work
+/// as in `assert_json_snapshot`.
+#[pyfunction]
+#[pyo3(signature = (value, *, inline=None, name=None, redactions=None))]
+#[expect(clippy::needless_pass_by_value)]
+pub fn assert_yaml_snapshot(
+    py: Python<'_>,
+    value: Py<PyAny>,
+    inline: Option<String>,
+    name: Option<String>,
+    redactions: Option<&Bound<'_, PyDict>>,
+) -> PyResult<()> {
+    let redactions = active_redactions(py, redactions)?;
+    let data = to_data(
+        value.bind(py),
+        &redactions,
+        "assert_yaml_snapshot() value is not YAML serializable",
+    )?;
+    let serialized = apply_active_filters(&to_yaml(&data))?;
+    assert_snapshot_impl(
+        py,
+        &serialized,
+        Serializer::Yaml,
+        None,
+        inline.as_deref(),
+        name.as_deref(),
+    )
+}
+
+/// Assert that a value matches a stored snapshot, serialized as TOML.
+///
+/// The value must be a JSON serializable dict without `None` values. Keys
+/// are sorted, and redactions work as in `assert_json_snapshot`.
+#[pyfunction]
+#[pyo3(signature = (value, *, inline=None, name=None, redactions=None))]
+#[expect(clippy::needless_pass_by_value)]
+pub fn assert_toml_snapshot(
+    py: Python<'_>,
+    value: Py<PyAny>,
+    inline: Option<String>,
+    name: Option<String>,
+    redactions: Option<&Bound<'_, PyDict>>,
+) -> PyResult<()> {
+    let redactions = active_redactions(py, redactions)?;
+    let data = to_data(
+        value.bind(py),
+        &redactions,
+        "assert_toml_snapshot() value is not TOML serializable",
+    )?;
+    let serialized = to_toml(&data).map_err(|e| {
+        pyo3::exceptions::PyTypeError::new_err(format!(
+            "assert_toml_snapshot() value is not TOML serializable: {e}"
+        ))
+    })?;
+    let serialized = apply_active_filters(&serialized)?;
+    assert_snapshot_impl(
+        py,
+        &serialized,
+        Serializer::Toml,
+        None,
+        inline.as_deref(),
+        name.as_deref(),
+    )
+}
+
+/// Assert that a value matches a stored snapshot, formatted with
+/// `pprint.pformat(value, sort_dicts=True)`.
+///
+/// Unlike `str(value)`, dict keys and set items are sorted, and long values
+/// are wrapped across lines. Redactions work as in `assert_json_snapshot`.
+#[pyfunction]
+#[pyo3(signature = (value, *, inline=None, name=None, redactions=None))]
+#[expect(clippy::needless_pass_by_value)]
+pub fn assert_debug_snapshot(
+    py: Python<'_>,
+    value: Py<PyAny>,
+    inline: Option<String>,
+    name: Option<String>,
+    redactions: Option<&Bound<'_, PyDict>>,
+) -> PyResult<()> {
+    let redactions = active_redactions(py, redactions)?;
+    let value = redacted(value.bind(py), &redactions)?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("sort_dicts", true)?;
+    let serialized = py
+        .import("pprint")?
+        .call_method("pformat", (value,), Some(&kwargs))?
+        .extract::<String>()?;
+    let serialized = apply_active_filters(&serialized)?;
+    assert_snapshot_impl(
+        py,
+        &serialized,
+        Serializer::Pprint,
+        None,
+        inline.as_deref(),
+        name.as_deref(),
+    )
+}
+
+/// Assert that an exception matches a stored snapshot.
+///
+/// `value` is a callable that must raise, a `karva.raises()` context that
+/// caught an exception, or the `ExceptionInfo` it returned. The snapshot
+/// holds the exception's type and message, followed by its notes unless
+/// `notes` is false, and the exceptions it was raised from or while handling
+/// unless `chain` is false.
+#[pyfunction]
+#[pyo3(signature = (value, *, inline=None, name=None, notes=true, chain=true))]
+#[expect(clippy::needless_pass_by_value)]
+pub fn assert_raises_snapshot(
+    py: Python<'_>,
+    value: &Bound<'_, PyAny>,
+    inline: Option<String>,
+    name: Option<String>,
+    notes: bool,
+    chain: bool,
 ) -> PyResult<()> {
-    let serialized = serialize_json(py, &value)?;
+    let exception = caught_exception(value)?;
+    let serialized = format_exception(&exception, notes, chain)?;
     let serialized = apply_active_filters(&serialized)?;
-    assert_snapshot_impl(py, &serialized, inline.as_deref(), name.as_deref())
+    assert_snapshot_impl(
+        py,
+        &serialized,
+        Serializer::Raises,
+        None,
+        inline.as_deref(),
+        name.as_deref(),
+    )
+}
+
+/// Assert that the warnings a callable emits match a stored snapshot.
+///
+/// Every warning is recorded while `value` runs, regardless of the active
+/// warning filters, and stored as one `Category: message` line.
+#[pyfunction]
+#[pyo3(signature = (value, *, inline=None, name=None))]
+#[expect(clippy::needless_pass_by_value)]
+pub fn assert_warns_snapshot(
+    py: Python<'_>,
+    value: &Bound<'_, PyAny>,
+    inline: Option<String>,
+    name: Option<String>,
+) -> PyResult<()> {
+    let serialized = recorded_warnings(value)?;
+    let serialized = apply_active_filters(&serialized)?;
+    assert_snapshot_impl(
+        py,
+        &serialized,
+        Serializer::Warns,
+        None,
+        inline.as_deref(),
+        name.as_deref(),
+    )
+}
+
+/// Assert that bytes match a stored binary snapshot.
+///
+/// The bytes are stored next to the `.snap` file, as `.snap.{extension}`,
+/// and the `.snap` file holds their size and SHA-256 hash.
+#[pyfunction]
+#[pyo3(signature = (data, *, extension, name=None))]
+#[expect(clippy::needless_pass_by_value)]
+pub fn assert_binary_snapshot(
+    py: Python<'_>,
+    data: &Bound<'_, PyBytes>,
+    extension: String,
+    name: Option<String>,
+) -> PyResult<()> {
+    let extension = extension.strip_prefix('.').unwrap_or(&extension);
+    if extension.is_empty()
+        || !extension
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        || extension.eq_ignore_ascii_case("snap")
+        || extension.eq_ignore_ascii_case("new")
+    {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid binary snapshot extension '{extension}': use letters, digits, '-' and '_', other than 'snap' and 'new'"
+        )));
+    }
+
+    let bytes = data.as_bytes();
+    let sha256 = py
+        .import("hashlib")?
+        .call_method1("sha256", (data,))?
+        .call_method0("hexdigest")?
+        .extract::<String>()?;
+    let summary = binary_summary(extension, bytes.len(), &sha256);
+    assert_snapshot_impl(
+        py,
+        &summary,
+        Serializer::Binary,
+        Some(&BinaryData {
+            extension,
+            data: bytes,
+        }),
+        None,
+        name.as_deref(),
+    )
+}
+
+/// The data of a binary snapshot, stored next to its snapshot file.
+struct BinaryData<'a> {
+    extension: &'a str,
+    data: &'a [u8],
+}
+
+/// Write a snapshot to its `.snap` file, replacing the data file of the
+/// snapshot it overwrites.
+fn write_accepted(
+    snap_path: &Utf8Path,
+    snapshot: &SnapshotFile,
+    binary: Option<&BinaryData<'_>>,
+) -> std::io::Result<()> {
+    remove_binary_data(snap_path);
+    write_snapshot(snap_path, snapshot)?;
+    if let Some(binary) = binary {
+        write_binary_data(snap_path, binary.extension, binary.data)?;
+    }
+    Ok(())
+}
+
+/// Write a snapshot to its pending `.snap.new` file, replacing the data file
+/// of the pending snapshot it overwrites.
+fn write_pending(
+    snap_path: &Utf8Path,
+    snapshot: &SnapshotFile,
+    binary: Option<&BinaryData<'_>>,
+) -> std::io::Result<()> {
+    let pending = pending_path(snap_path);
+    remove_binary_data(&pending);
+    write_pending_snapshot(snap_path, snapshot)?;
+    if let Some(binary) = binary {
+        write_binary_data(&pending, binary.extension, binary.data)?;
+    }
+    Ok(())
 }
 
 /// Shared implementation for snapshot assertions.
+///
+/// `binary` holds the data of a binary snapshot, whose `serialized` content
+/// is a summary of it.
 fn assert_snapshot_impl(
     py: Python<'_>,
     serialized: &str,
+    serializer: Serializer,
+    binary: Option<&BinaryData<'_>>,
     inline: Option<&str>,
     name: Option<&str>,
 ) -> PyResult<()> {
@@ -308,13 +825,14 @@ fn assert_snapshot_impl(
         ));
     }
 
-    let (test_file, test_name) = SNAPSHOT_CONTEXT
+    let (test_file, test_name, update) = SNAPSHOT_CONTEXT
         .with(|ctx| {
             let ctx = ctx.borrow();
             let snapshot_ctx = ctx.as_ref()?;
             Some((
                 snapshot_ctx.test_file.clone(),
                 snapshot_ctx.test_name.clone(),
+                snapshot_ctx.update,
             ))
         })
         .ok_or_else(|| {
@@ -323,17 +841,43 @@ fn assert_snapshot_impl(
             )
         })?;
 
-    let update_mode =
-        std::env::var(EnvVars::KARVA_SNAPSHOT_UPDATE).is_ok_and(|v| v == "1" || v == "true");
+    let test_file_path = Utf8Path::new(&test_file);
+    let relative_test_file = test_file_path
+        .file_name()
+        .unwrap_or(test_file_path.as_str());
+    let caller = caller_source_info(py);
+    // The expression is only looked up when a snapshot file is written.
+    let mut metadata = SnapshotMetadata {
+        source: Some(format!("{relative_test_file}::{test_name}")),
+        assertion_line: caller.as_ref().map(|(_, line)| *line),
+        // `str` is the default, so plain snapshots keep their old format.
+        serializer: (serializer != Serializer::Str).then_some(serializer),
+        extension: binary.map(|binary| binary.extension.to_string()),
+        variant: test_name
+            .split_once('(')
+            .and_then(|(_, params)| params.strip_suffix(')'))
+            .map(str::to_string),
+        info: active_info(),
+        ..Default::default()
+    };
 
     if let Some(inline_value) = inline {
+        let (source_file, lineno) = caller.ok_or_else(|| {
+            pyo3::exceptions::PyRuntimeError::new_err(
+                "Could not determine caller source info for inline snapshot",
+            )
+        })?;
         return handle_inline_snapshot(
-            py,
             serialized,
             inline_value,
-            &test_file,
-            &test_name,
-            update_mode,
+            InlineCall {
+                test_file: &test_file,
+                test_name: &test_name,
+                source_file,
+                lineno,
+                metadata,
+            },
+            update,
         );
     }
 
@@ -364,47 +908,41 @@ fn assert_snapshot_impl(
         compute_snapshot_name(&test_name, counter, allow_duplicates)
     };
 
-    let test_file_path = Utf8Path::new(&test_file);
     let module_name = test_file_path.file_stem().unwrap_or("unknown");
 
     // Sanitize `::` to `__` for filesystem compatibility (`:` is reserved on Windows)
     let fs_snapshot_name = snapshot_name.replace("::", "__");
     let snap_path = snapshot_path(test_file_path, module_name, &fs_snapshot_name);
+    REFERENCED_SNAPSHOTS.with(|snapshots| snapshots.borrow_mut().insert(snap_path.clone()));
 
-    let relative_test_file = test_file_path
-        .file_name()
-        .unwrap_or(test_file_path.as_str());
-
-    let source = if let Some(lineno) = caller_line_number(py) {
-        format!("{relative_test_file}:{lineno}::{test_name}")
-    } else {
-        format!("{relative_test_file}::{test_name}")
-    };
+    let existing = read_snapshot(&snap_path);
+    if let Some(existing) = &existing
+        && existing.content.trim_end() == serialized.trim_end()
+        && binary.is_none_or(|binary| binary_path(&snap_path, binary.extension).exists())
+    {
+        return Ok(());
+    }
 
+    metadata.expression = caller.and_then(|(file, line)| expression_at(&file, line));
     let new_snapshot = SnapshotFile {
-        metadata: SnapshotMetadata {
-            source: Some(source),
-            ..Default::default()
-        },
+        metadata,
         content: serialized.to_string(),
     };
 
-    if let Some(existing) = read_snapshot(&snap_path) {
-        if existing.content.trim_end() == serialized.trim_end() {
-            return Ok(());
-        }
-
+    if let Some(existing) = existing {
         // Mismatch
-        if update_mode {
-            write_snapshot(&snap_path, &new_snapshot).map_err(|e| {
+        if update.writes_changed() {
+            write_accepted(&snap_path, &new_snapshot, binary).map_err(|e| {
                 SnapshotMismatchError::new_err(format!("Failed to update snapshot: {e}"))
             })?;
             return Ok(());
         }
 
-        write_pending_snapshot(&snap_path, &new_snapshot).map_err(|e| {
-            SnapshotMismatchError::new_err(format!("Failed to write pending snapshot: {e}"))
-        })?;
+        if update.writes_pending() {
+            write_pending(&snap_path, &new_snapshot, binary).map_err(|e| {
+                SnapshotMismatchError::new_err(format!("Failed to write pending snapshot: {e}"))
+            })?;
+        }
 
         let diff = format_diff(&existing.content, serialized);
         let display_path = display_relative(&snap_path);
@@ -414,39 +952,57 @@ fn assert_snapshot_impl(
     }
 
     // No existing snapshot
-    if update_mode {
-        write_snapshot(&snap_path, &new_snapshot).map_err(|e| {
+    if update.writes_new() {
+        write_accepted(&snap_path, &new_snapshot, binary).map_err(|e| {
             SnapshotMismatchError::new_err(format!("Failed to write snapshot: {e}"))
         })?;
-    } else {
-        write_pending_snapshot(&snap_path, &new_snapshot).map_err(|e| {
-            SnapshotMismatchError::new_err(format!("Failed to write pending snapshot: {e}"))
-        })?;
+        return Ok(());
+    }
 
-        let pending = Utf8PathBuf::from(format!("{snap_path}.new"));
-        let display_path = display_relative(&pending);
+    if !update.writes_pending() {
         return Err(SnapshotMismatchError::new_err(format!(
-            "New snapshot for '{snapshot_name}'.\nRun `karva snapshot accept` to accept, or re-run with `--snapshot-update`.\nPending file: {display_path}"
+            "New snapshot for '{snapshot_name}'.\n{UPDATES_DISABLED}"
         )));
     }
 
-    Ok(())
+    write_pending(&snap_path, &new_snapshot, binary).map_err(|e| {
+        SnapshotMismatchError::new_err(format!("Failed to write pending snapshot: {e}"))
+    })?;
+
+    let pending = pending_path(&snap_path);
+    let display_path = display_relative(&pending);
+    Err(SnapshotMismatchError::new_err(format!(
+        "New snapshot for '{snapshot_name}'.\nRun `karva snapshot accept` to accept, or re-run with `--snapshot-update`.\nPending file: {display_path}"
+    )))
+}
+
+/// Explains why a new snapshot failed without writing a file.
+const UPDATES_DISABLED: &str = "Snapshot updates are disabled, so no file was written.\nRe-run with `--snapshot-update` to write it, or with `--snapshot-update=new` to review it first.";
+
+/// Where an inline snapshot assertion was called, and the metadata of its
+/// pending snapshot.
+struct InlineCall<'a> {
+    test_file: &'a str,
+    test_name: &'a str,
+    source_file: String,
+    lineno: u32,
+    metadata: SnapshotMetadata,
 }
 
 /// Handle an inline snapshot assertion.
 fn handle_inline_snapshot(
-    py: Python<'_>,
     actual: &str,
     inline_value: &str,
-    test_file: &str,
-    test_name: &str,
-    update_mode: bool,
+    call: InlineCall<'_>,
+    update: SnapshotUpdateMode,
 ) -> PyResult<()> {
-    let (source_file, lineno) = caller_source_info(py).ok_or_else(|| {
-        pyo3::exceptions::PyRuntimeError::new_err(
-            "Could not determine caller source info for inline snapshot",
-        )
-    })?;
+    let InlineCall {
+        test_file,
+        test_name,
+        source_file,
+        lineno,
+        metadata,
+    } = call;
 
     let expected = karva_snapshot::inline::dedent(inline_value);
 
@@ -458,7 +1014,12 @@ fn handle_inline_snapshot(
         return Ok(());
     }
 
-    if update_mode {
+    let writes = if is_empty {
+        update.writes_new()
+    } else {
+        update.writes_changed()
+    };
+    if writes {
         let fn_name = test_name
             .rsplit("::")
             .next()
@@ -470,6 +1031,18 @@ fn handle_inline_snapshot(
         return Ok(());
     }
 
+    if !update.writes_pending() {
+        if is_empty {
+            return Err(SnapshotMismatchError::new_err(format!(
+                "New inline snapshot for '{test_name}'.\n{UPDATES_DISABLED}"
+            )));
+        }
+        let diff = format_diff(&expected, actual);
+        return Err(SnapshotMismatchError::new_err(format!(
+            "Inline snapshot mismatch for '{test_name}'.\n{diff}"
+        )));
+    }
+
     // Write a .snap.new with inline metadata so `karva snapshot accept` can rewrite the source
     let test_file_path = Utf8Path::new(test_file);
     let module_name = test_file_path.file_stem().unwrap_or("unknown");
@@ -477,15 +1050,12 @@ fn handle_inline_snapshot(
     let snap_path =
         karva_snapshot::storage::snapshot_path(test_file_path, module_name, &snapshot_name);
 
-    let relative_test_file = test_file_path
-        .file_name()
-        .unwrap_or(test_file_path.as_str());
-
     let pending_snapshot = SnapshotFile {
         metadata: SnapshotMetadata {
-            source: Some(format!("{relative_test_file}:{lineno}::{test_name}")),
+            expression: expression_at(&source_file, lineno),
             inline_source: Some(source_file),
             inline_line: Some(lineno),
+            ..metadata
         },
         content: actual.to_string(),
     };
@@ -508,6 +1078,12 @@ fn handle_inline_snapshot(
     )))
 }
 
+/// The source text of the value asserted on `line` of `file`.
+fn expression_at(file: &str, line: u32) -> Option<String> {
+    let source = std::fs::read_to_string(file).ok()?;
+    asserted_expression(&source, line)
+}
+
 /// Get both the filename and line number of the Python caller using `sys._getframe(0)`.
 ///
 /// Since `assert_snapshot` is a `#[pyfunction]`, it doesn't create a Python frame,
@@ -526,10 +1102,6 @@ fn caller_source_info(py: Python<'_>) -> Option<(String, u32)> {
     Some((filename, lineno))
 }
 
-fn caller_line_number(py: Python<'_>) -> Option<u32> {
-    caller_source_info(py).map(|(_, lineno)| lineno)
-}
-
 /// Compute the snapshot name based on test name and counter.
 ///
 /// When `allow_duplicates` is true, every unnamed snapshot gets an explicit
@@ -567,10 +1139,47 @@ fn serialize_value(py: Python<'_>, value: &Py<PyAny>) -> PyResult<String> {
     Ok(bound.str()?.to_string_lossy().into_owned())
 }
 
-/// Serialize a Python value to JSON using `json.dumps(value, sort_keys=True, indent=2)`.
-fn serialize_json(py: Python<'_>, value: &Py<PyAny>) -> PyResult<String> {
+/// Return `value`, or a copy of it with `redactions` applied.
+fn redacted<'py>(
+    value: &Bound<'py, PyAny>,
+    redactions: &[Redaction],
+) -> PyResult<Bound<'py, PyAny>> {
+    if redactions.is_empty() {
+        Ok(value.clone())
+    } else {
+        redact(value, &mut Vec::new(), redactions)
+    }
+}
+
+/// Convert a JSON serializable Python value to data for the YAML and TOML
+/// serializers, after applying `redactions` to a copy of it.
+///
+/// Raises a `TypeError` with `not_serializable` for other values.
+fn to_data(
+    value: &Bound<'_, PyAny>,
+    redactions: &[Redaction],
+    not_serializable: &str,
+) -> PyResult<serde_json::Value> {
+    let py = value.py();
+    let value = redacted(value, redactions)?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("sort_keys", true)?;
+    kwargs.set_item("allow_nan", false)?;
+    let not_serializable = || pyo3::exceptions::PyTypeError::new_err(not_serializable.to_string());
+    let json = py
+        .import("json")?
+        .call_method("dumps", (value,), Some(&kwargs))
+        .map_err(|_| not_serializable())?
+        .extract::<String>()?;
+    serde_json::from_str(&json).map_err(|_| not_serializable())
+}
+
+/// Serialize a Python value to JSON using `json.dumps(value, sort_keys=True, indent=2)`,
+/// after applying `redactions` to a copy of it.
+fn serialize_json(py: Python<'_>, value: &Py<PyAny>, redactions: &[Redaction]) -> PyResult<String> {
+    let value = redacted(value.bind(py), redactions)?;
     let json = py.import("json")?;
-    let kwargs = pyo3::types::PyDict::new(py);
+    let kwargs = PyDict::new(py);
     kwargs.set_item("sort_keys", true)?;
     kwargs.set_item("indent", 2)?;
     json.call_method("dumps", (value,), Some(&kwargs))
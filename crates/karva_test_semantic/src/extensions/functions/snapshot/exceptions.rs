@@ -0,0 +1,147 @@
+//! Turn raised exceptions and emitted warnings into snapshot content.
+
+use std::fmt::Write;
+
+use pyo3::exceptions::{PyException, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::extensions::functions::{ExceptionInfo, FailError, RaisesContext};
+
+/// The exception to snapshot, from a callable that must raise it, a
+/// `karva.raises()` context, or the `ExceptionInfo` that context returned.
+pub(super) fn caught_exception<'py>(value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let py = value.py();
+    let caught = if let Ok(context) = value.cast::<RaisesContext>() {
+        context.borrow().caught(py)
+    } else if let Ok(info) = value.cast::<ExceptionInfo>() {
+        info.borrow()
+            .value
+            .as_ref()
+            .map(|value| value.clone_ref(py))
+    } else if value.is_callable() {
+        match value.call0() {
+            Ok(_) => return Err(FailError::new_err("DID NOT RAISE")),
+            Err(err) if err.is_instance_of::<PyException>(py) => {
+                Some(err.into_value(py).into_any())
+            }
+            Err(err) => return Err(err),
+        }
+    } else {
+        return Err(PyTypeError::new_err(
+            "assert_raises_snapshot() expects a callable, a `karva.raises()` context or the `ExceptionInfo` it returned",
+        ));
+    };
+
+    caught
+        .map(|exception| exception.into_bound(py))
+        .ok_or_else(|| {
+            PyValueError::new_err(
+                "assert_raises_snapshot() got a `karva.raises()` context that caught no exception",
+            )
+        })
+}
+
+/// Format an exception as `Type: message`, one `note:` line per note, and
+/// when `chain` is set, the exceptions it was raised from or while handling.
+pub(super) fn format_exception(
+    exception: &Bound<'_, PyAny>,
+    notes: bool,
+    chain: bool,
+) -> PyResult<String> {
+    let mut out = String::new();
+    let mut seen: Vec<Bound<'_, PyAny>> = Vec::new();
+    let mut current = Some((None, exception.clone()));
+
+    while let Some((link, exception)) = current.take() {
+        // A chain that loops back on itself would never end.
+        if seen.iter().any(|other| other.is(&exception)) {
+            break;
+        }
+        if let Some(link) = link {
+            out.push_str(link);
+            out.push(' ');
+        }
+        write_exception(&mut out, &exception, notes)?;
+
+        if chain {
+            let cause = exception.getattr("__cause__")?;
+            let context = exception.getattr("__context__")?;
+            current = if !cause.is_none() {
+                Some((Some("caused by:"), cause))
+            } else if !context.is_none()
+                && !exception.getattr("__suppress_context__")?.is_truthy()?
+            {
+                Some((Some("while handling:"), context))
+            } else {
+                None
+            };
+        }
+        seen.push(exception);
+    }
+
+    Ok(out.trim_end().to_string())
+}
+
+fn write_exception(out: &mut String, exception: &Bound<'_, PyAny>, notes: bool) -> PyResult<()> {
+    let type_name = qualified_name(&exception.get_type().into_any())?;
+    let message = exception.str()?.to_string();
+    if message.is_empty() {
+        let _ = writeln!(out, "{type_name}");
+    } else {
+        let _ = writeln!(out, "{type_name}: {message}");
+    }
+
+    if notes && let Ok(notes) = exception.getattr("__notes__") {
+        for note in notes.try_iter()? {
+            let _ = writeln!(out, "  note: {}", note?.str()?);
+        }
+    }
+    Ok(())
+}
+
+/// Call `callable` with every warning recorded, and return the warnings it
+/// emitted as `Category: message` lines.
+pub(super) fn recorded_warnings(callable: &Bound<'_, PyAny>) -> PyResult<String> {
+    if !callable.is_callable() {
+        return Err(PyTypeError::new_err(
+            "assert_warns_snapshot() expects a callable",
+        ));
+    }
+
+    let py = callable.py();
+    let warnings = py.import("warnings")?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("record", true)?;
+    let catcher = warnings.call_method("catch_warnings", (), Some(&kwargs))?;
+    let recorded = catcher.call_method0("__enter__")?;
+    let result = warnings
+        .call_method1("simplefilter", ("always",))
+        .and_then(|_| callable.call0());
+    catcher.call_method1("__exit__", (py.None(), py.None(), py.None()))?;
+    result?;
+
+    let recorded = recorded.cast::<PyList>()?;
+    if recorded.is_empty() {
+        return Err(FailError::new_err("DID NOT WARN"));
+    }
+
+    let mut out = String::new();
+    for warning in recorded.iter() {
+        let category = qualified_name(&warning.getattr("category")?)?;
+        let message = warning.getattr("message")?.str()?.to_string();
+        let _ = writeln!(out, "{category}: {message}");
+    }
+    Ok(out.trim_end().to_string())
+}
+
+/// The name of a class, prefixed with its module unless it is a builtin.
+fn qualified_name(class: &Bound<'_, PyAny>) -> PyResult<String> {
+    let name = class.getattr("__qualname__")?.extract::<String>()?;
+    let module = class.getattr("__module__")?.extract::<String>()?;
+    Ok(if module == "builtins" {
+        name
+    } else {
+        format!("{module}.{name}")
+    })
+}
@@ -0,0 +1,133 @@
+//! Find the source text of the value passed to a snapshot assertion.
+
+use ruff_python_ast::visitor::source_order::{self, SourceOrderVisitor};
+use ruff_python_ast::{Expr, ExprCall};
+use ruff_python_parser::{Mode, ParseOptions, parse_unchecked};
+use ruff_source_file::LineIndex;
+use ruff_text_size::Ranged;
+
+/// Names of the keyword arguments that take the asserted value.
+const VALUE_KEYWORDS: &[&str] = &["value", "cmd", "data"];
+
+/// Return the source text of the value asserted by the snapshot assertion
+/// that runs on `line` of `source`.
+///
+/// When several assertions span the line, the innermost one wins. Lines
+/// after the first of a multiline expression lose the indentation of the
+/// line the expression starts on.
+pub(super) fn asserted_expression(source: &str, line: u32) -> Option<String> {
+    let parsed = parse_unchecked(source, ParseOptions::from(Mode::Module)).try_into_module()?;
+    let line_index = LineIndex::from_source_text(source);
+    let mut visitor = AssertionVisitor {
+        line_index: &line_index,
+        line: usize::try_from(line).ok()?,
+        call: None,
+    };
+    visitor.visit_body(&parsed.syntax().body);
+    let call = visitor.call?;
+
+    let value = call.arguments.args.first().or_else(|| {
+        call.arguments
+            .keywords
+            .iter()
+            .find(|keyword| {
+                keyword
+                    .arg
+                    .as_ref()
+                    .is_some_and(|arg| VALUE_KEYWORDS.contains(&arg.as_str()))
+            })
+            .map(|keyword| &keyword.value)
+    })?;
+
+    let start = value.range().start().to_usize();
+    let line_start = source[..start].rfind('\n').map_or(0, |pos| pos + 1);
+    let indent = source[line_start..start].len() - source[line_start..start].trim_start().len();
+
+    let mut lines = source[value.range()].lines();
+    let mut expression = lines.next()?.to_string();
+    for line in lines {
+        let strip = line
+            .char_indices()
+            .take(indent)
+            .take_while(|(_, c)| c.is_whitespace())
+            .map(|(i, c)| i + c.len_utf8())
+            .last()
+            .unwrap_or(0);
+        expression.push('\n');
+        expression.push_str(&line[strip..]);
+    }
+    Some(expression)
+}
+
+/// Whether a call is a snapshot assertion, such as `karva.assert_snapshot(...)`.
+fn is_snapshot_assertion(call: &ExprCall) -> bool {
+    let name = match call.func.as_ref() {
+        Expr::Name(name) => name.id.as_str(),
+        Expr::Attribute(attribute) => attribute.attr.as_str(),
+        _ => return false,
+    };
+    name.starts_with("assert_") && name.ends_with("snapshot")
+}
+
+/// Visitor that finds the innermost snapshot assertion spanning a line.
+struct AssertionVisitor<'a, 'ast> {
+    line_index: &'a LineIndex,
+    line: usize,
+    call: Option<&'ast ExprCall>,
+}
+
+impl<'ast> SourceOrderVisitor<'ast> for AssertionVisitor<'_, 'ast> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Call(call) = expr
+            && is_snapshot_assertion(call)
+        {
+            let start = self.line_index.line_index(call.range().start()).get();
+            let end = self.line_index.line_index(call.range().end()).get();
+            if (start..=end).contains(&self.line) {
+                self.call = Some(call);
+            }
+        }
+        source_order::walk_expr(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line_assertion() {
+        let source = "def test_x():\n    karva.assert_snapshot(compute(1), name='a')\n";
+        assert_eq!(
+            asserted_expression(source, 2).as_deref(),
+            Some("compute(1)")
+        );
+    }
+
+    #[test]
+    fn value_passed_by_keyword() {
+        let source = "assert_json_snapshot(value={'a': 1})\n";
+        assert_eq!(asserted_expression(source, 1).as_deref(), Some("{'a': 1}"));
+    }
+
+    #[test]
+    fn multiline_expression_is_dedented() {
+        let source = "def test_x():\n    karva.assert_json_snapshot({\n        'a': 1,\n    })\n";
+        assert_eq!(
+            asserted_expression(source, 2).as_deref(),
+            Some("{\n    'a': 1,\n}")
+        );
+    }
+
+    #[test]
+    fn line_inside_multiline_call() {
+        let source = "karva.assert_snapshot(\n    value,\n    name='a',\n)\n";
+        assert_eq!(asserted_expression(source, 3).as_deref(), Some("value"));
+    }
+
+    #[test]
+    fn no_assertion_on_line() {
+        let source = "x = 1\nkarva.assert_snapshot(x)\n";
+        assert!(asserted_expression(source, 1).is_none());
+    }
+}
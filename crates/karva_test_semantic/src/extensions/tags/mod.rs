@@ -229,6 +229,36 @@ impl Tags {
         Some(Self { inner: tags })
     }
 
+    /// Tags for the `unittest` decorators applied to a test case class or
+    /// method, which mark it with `__unittest_skip__` and
+    /// `__unittest_expecting_failure__` attributes.
+    pub(crate) fn from_unittest_attributes(py: Python<'_>, py_any: &Py<PyAny>) -> Self {
+        let is_set = |name: &str| {
+            py_any
+                .getattr(py, name)
+                .ok()
+                .and_then(|value| value.extract::<bool>(py).ok())
+                .unwrap_or(false)
+        };
+
+        let mut tags = Vec::new();
+
+        if is_set("__unittest_skip__") {
+            let reason = py_any
+                .getattr(py, "__unittest_skip_why__")
+                .ok()
+                .and_then(|value| value.extract::<String>(py).ok())
+                .filter(|reason| !reason.is_empty());
+            tags.push(Tag::Skip(SkipTag::new(Vec::new(), reason)));
+        }
+
+        if is_set("__unittest_expecting_failure__") {
+            tags.push(Tag::ExpectFail(ExpectFailTag::new(Vec::new(), None)));
+        }
+
+        Self::new(tags)
+    }
+
     /// Return all parametrizations
     ///
     /// This function ensures that if we have multiple parametrize tags, we combine them together.
@@ -255,6 +285,38 @@ impl Tags {
         param_args
     }
 
+    /// Returns the ids shared by more than one parameter set of a single
+    /// parametrize tag.
+    pub(crate) fn duplicate_parametrize_ids(&self) -> Vec<String> {
+        let mut duplicates = Vec::new();
+
+        for tag in &self.inner {
+            if let Tag::Parametrize(parametrize_tag) = tag {
+                for id in parametrize_tag.duplicate_ids() {
+                    if !duplicates.iter().any(|duplicate| duplicate == id) {
+                        duplicates.push(id.to_string());
+                    }
+                }
+            }
+        }
+
+        duplicates
+    }
+
+    /// Returns why the `ids` of each parametrize tag that has invalid ones
+    /// could not be applied.
+    pub(crate) fn invalid_parametrize_ids(&self) -> Vec<String> {
+        self.inner
+            .iter()
+            .filter_map(|tag| match tag {
+                Tag::Parametrize(parametrize_tag) => {
+                    parametrize_tag.ids_error().map(ToString::to_string)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Get all required fixture names for the given test.
     pub(crate) fn required_fixtures_names(&self) -> Vec<String> {
         self.inner
@@ -1,7 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
 use crate::extensions::functions::Param;
@@ -18,6 +19,10 @@ pub struct Parametrization {
 
     /// Tags specific to this parameter set (e.g., marks on pytest.param).
     pub(crate) tags: Tags,
+
+    /// The id shown in test names in place of the values, from `ids=` or
+    /// the `id` of a `karva.param` or `pytest.param`.
+    pub(crate) id: Option<String>,
 }
 
 impl Parametrization {
@@ -31,10 +36,21 @@ impl From<PyRef<'_, Param>> for Parametrization {
         Self {
             values: param.values.clone(),
             tags: param.tags.clone(),
+            id: param.id.clone(),
         }
     }
 }
 
+/// The id a parametrization is shown by in test names, in place of the
+/// values of its arguments.
+#[derive(Debug, Clone)]
+pub struct ParametrizationId {
+    /// The names of the arguments the id stands for.
+    pub(crate) names: Vec<String>,
+
+    pub(crate) id: String,
+}
+
 /// Named parameter values for a single test invocation.
 ///
 /// Maps parameter names to their values, combining multiple
@@ -46,6 +62,9 @@ pub struct ParametrizationArgs {
 
     /// Combined tags from all parameter sets.
     pub(crate) tags: Tags,
+
+    /// Ids of the parameter sets that have one.
+    pub(crate) ids: Vec<ParametrizationId>,
 }
 
 impl ParametrizationArgs {
@@ -56,6 +75,7 @@ impl ParametrizationArgs {
     pub(crate) fn extend(&mut self, other: Self) {
         self.values.extend(other.values);
         self.tags.extend(&other.tags);
+        self.ids.extend(other.ids);
     }
 }
 
@@ -74,6 +94,76 @@ fn normalize_arg_names(arg_names: &Bound<'_, PyAny>) -> Option<Vec<String>> {
     None
 }
 
+/// Apply the `ids` argument of a parametrize decorator to its parametrizations.
+///
+/// `ids` is either a list with an id, or `None` to keep the default, for
+/// each parametrization, or a callable called with each argument value that
+/// returns its id or `None`. The ids of the values of one parametrization
+/// are joined with `-`. An id set through `karva.param` or `pytest.param`
+/// takes precedence.
+///
+/// Raises `ValueError` when a list of ids doesn't have one id for each
+/// parametrization.
+pub(crate) fn apply_ids(
+    py: Python<'_>,
+    parametrizations: &mut [Parametrization],
+    ids: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    if ids.is_none() {
+        return Ok(());
+    }
+
+    if !ids.is_callable() {
+        let ids = ids.extract::<Vec<Option<String>>>()?;
+        if ids.len() != parametrizations.len() {
+            return Err(PyValueError::new_err(format!(
+                "`ids` must have one id for each of the {} parameter sets, got {}",
+                parametrizations.len(),
+                ids.len()
+            )));
+        }
+        for (parametrization, id) in parametrizations.iter_mut().zip(ids) {
+            if parametrization.id.is_none() {
+                parametrization.id = id;
+            }
+        }
+        return Ok(());
+    }
+
+    // Every id is computed before any is set, so an `ids` callable that
+    // raises leaves the parametrizations as they were.
+    let mut callable_ids = Vec::with_capacity(parametrizations.len());
+    for parametrization in &*parametrizations {
+        if parametrization.id.is_some() {
+            callable_ids.push(None);
+            continue;
+        }
+
+        let mut parts = Vec::with_capacity(parametrization.values.len());
+        let mut has_id = false;
+        for value in &parametrization.values {
+            let value = value.bind(py);
+            match ids.call1((value,))?.extract::<Option<String>>()? {
+                Some(id) => {
+                    has_id = true;
+                    parts.push(id);
+                }
+                None => parts.push(value.to_string()),
+            }
+        }
+
+        callable_ids.push(has_id.then(|| parts.join("-")));
+    }
+
+    for (parametrization, id) in parametrizations.iter_mut().zip(callable_ids) {
+        if id.is_some() {
+            parametrization.id = id;
+        }
+    }
+
+    Ok(())
+}
+
 /// Parse parametrize arguments from Python objects.
 ///
 /// This helper function handles multiple input formats:
@@ -107,6 +197,9 @@ pub struct ParametrizeTag {
     /// These are used as keyword argument names for the test function.
     names: Vec<String>,
     parametrizations: Vec<Parametrization>,
+
+    /// Why the `ids` of a pytest parametrize mark could not be applied.
+    ids_error: Option<String>,
 }
 
 /// Extract argnames and argvalues from a pytest parametrize mark.
@@ -142,11 +235,26 @@ fn extract_parametrize_args<'py>(
     Ok((arg_names, arg_values))
 }
 
+/// Extract the `ids` of a pytest parametrize mark, the fourth positional
+/// argument or the `ids` keyword.
+fn extract_parametrize_ids<'py>(py_mark: &Bound<'py, PyAny>) -> Option<Bound<'py, PyAny>> {
+    py_mark
+        .getattr("args")
+        .and_then(|args| args.get_item(3))
+        .or_else(|_| {
+            py_mark
+                .getattr("kwargs")
+                .and_then(|kwargs| kwargs.get_item("ids"))
+        })
+        .ok()
+}
+
 impl ParametrizeTag {
     pub(crate) fn new(names: Vec<String>, parametrizations: Vec<Parametrization>) -> Self {
         Self {
             names,
             parametrizations,
+            ids_error: None,
         }
     }
 
@@ -159,9 +267,11 @@ impl ParametrizeTag {
                     |Param {
                          values: param_values,
                          tags,
+                         id,
                      }| Parametrization {
                         values: param_values,
                         tags,
+                        id,
                     },
                 )
                 .collect(),
@@ -171,9 +281,45 @@ impl ParametrizeTag {
     pub(crate) fn try_from_pytest_mark(py_mark: &Bound<'_, PyAny>) -> Option<Self> {
         let (arg_names, arg_values) = extract_parametrize_args(py_mark).ok()?;
 
-        let (arg_names, parametrizations) = parse_parametrize_args(&arg_names, &arg_values)?;
+        let (arg_names, mut parametrizations) = parse_parametrize_args(&arg_names, &arg_values)?;
 
-        Some(Self::new(arg_names, parametrizations))
+        // Invalid `ids` are reported when the test is discovered, and the
+        // test still runs under its default names.
+        let mut ids_error = None;
+        if let Some(ids) = extract_parametrize_ids(py_mark)
+            && let Err(err) = apply_ids(py_mark.py(), &mut parametrizations, &ids)
+        {
+            ids_error = Some(err.to_string());
+        }
+
+        Some(Self {
+            ids_error,
+            ..Self::new(arg_names, parametrizations)
+        })
+    }
+
+    /// Returns why the `ids` of the tag could not be applied, if they
+    /// couldn't.
+    pub(crate) fn ids_error(&self) -> Option<&str> {
+        self.ids_error.as_deref()
+    }
+
+    /// Returns the ids given to more than one parametrization.
+    pub(crate) fn duplicate_ids(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        let mut duplicates = Vec::new();
+
+        for id in self
+            .parametrizations
+            .iter()
+            .filter_map(|parametrization| parametrization.id.as_deref())
+        {
+            if !seen.insert(id) && !duplicates.contains(&id) {
+                duplicates.push(id);
+            }
+        }
+
+        duplicates
     }
 
     /// Returns each parameterize case.
@@ -188,9 +334,18 @@ impl ParametrizeTag {
             for (arg_name, arg_value) in self.names.iter().zip(parametrization.values.iter()) {
                 current_parameratisation.insert(arg_name.clone(), Arc::clone(arg_value));
             }
+            let ids = parametrization
+                .id
+                .iter()
+                .map(|id| ParametrizationId {
+                    names: self.names.clone(),
+                    id: id.clone(),
+                })
+                .collect();
             let current_param_args = ParametrizationArgs {
                 values: current_parameratisation,
                 tags: parametrization.tags().clone(),
+                ids,
             };
             param_args.push(current_param_args);
         }
@@ -200,7 +355,7 @@ impl ParametrizeTag {
 
 /// Check for instances of `pytest.ParameterSet` and extract the parameters
 /// from it. Also handles regular tuples by extracting their values.
-pub(super) fn handle_custom_parametrize_param(
+pub(crate) fn handle_custom_parametrize_param(
     py: Python,
     param: Py<PyAny>,
     expect_multiple: bool,
@@ -209,6 +364,7 @@ pub(super) fn handle_custom_parametrize_param(
     let default_parametrization = || Parametrization {
         values: vec![Arc::clone(&param_arc)],
         tags: Tags::default(),
+        id: None,
     };
 
     if let Ok(param_bound) = param_arc.cast_bound::<Param>(py) {
@@ -241,11 +397,18 @@ pub(super) fn handle_custom_parametrize_param(
             .and_then(|m| Tags::from_pytest_marks(py, &m))
             .unwrap_or_default();
 
-        Parametrization { values, tags }
+        let id = bound_param
+            .getattr("id")
+            .ok()
+            .and_then(|id| id.extract::<Option<String>>().ok())
+            .flatten();
+
+        Parametrization { values, tags, id }
     } else if expect_multiple && let Ok(params) = bound_param.extract::<Vec<Py<PyAny>>>() {
         Parametrization {
             values: params.into_iter().map(Arc::new).collect(),
             tags: Tags::default(),
+            id: None,
         }
     } else {
         default_parametrization()
@@ -123,8 +123,9 @@ pub mod tags {
 
     use super::{CustomTagBuilder, PyTag, PyTags};
     use crate::extensions::functions::python::Param;
-    use crate::extensions::tags::parametrize::parse_parametrize_args;
+    use crate::extensions::tags::parametrize::{apply_ids, parse_parametrize_args};
     use crate::extensions::tags::python::PyTestFunction;
+    use crate::extensions::tags::timeout::TimeoutTag;
 
     /// Handle dynamic attribute access for custom tags.
     ///
@@ -135,16 +136,24 @@ pub mod tags {
     }
 
     #[pyfunction]
+    #[pyo3(signature = (arg_names, arg_values, ids = None))]
     fn parametrize(
+        py: Python<'_>,
         arg_names: &Bound<'_, PyAny>,
         arg_values: &Bound<'_, PyAny>,
+        ids: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<PyTags> {
-        let Some((names, parametrization)) = parse_parametrize_args(arg_names, arg_values) else {
+        let Some((names, mut parametrization)) = parse_parametrize_args(arg_names, arg_values)
+        else {
             return Err(PyErr::new::<PyTypeError, _>(
                 "Expected a string or a list of strings for the arg_names, and a list of lists of objects for the arg_values",
             ));
         };
 
+        if let Some(ids) = ids {
+            apply_ids(py, &mut parametrization, ids)?;
+        }
+
         Ok(PyTags {
             inner: vec![PyTag::Parametrize {
                 arg_names: names,
@@ -254,6 +263,9 @@ pub mod tags {
                 "timeout seconds must be a finite, positive number",
             ));
         }
+        if !TimeoutTag::fits_duration(seconds) {
+            return Err(PyErr::new::<PyTypeError, _>("timeout seconds is too large"));
+        }
         Ok(PyTags {
             inner: vec![PyTag::Timeout { seconds }],
         })
@@ -60,6 +60,14 @@ pub fn is_skip_exception(py: Python<'_>, err: &PyErr) -> bool {
         return true;
     }
 
+    // Check for unittest skip exception
+    if let Ok(unittest_module) = py.import("unittest")
+        && let Ok(skip_test) = unittest_module.getattr("SkipTest")
+        && err.matches(py, skip_test).unwrap_or(false)
+    {
+        return true;
+    }
+
     false
 }
 
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use pyo3::prelude::*;
 use pyo3::types::PyTuple;
 
@@ -20,18 +22,27 @@ impl TimeoutTag {
         self.seconds
     }
 
+    /// Whether `seconds` can be represented as a [`Duration`].
+    ///
+    /// Values past `Duration::MAX` would make the worker panic when it
+    /// reports the test's deadline, so both parsers reject them.
+    pub(crate) fn fits_duration(seconds: f64) -> bool {
+        Duration::try_from_secs_f64(seconds).is_ok()
+    }
+
     /// Parse `@pytest.mark.timeout(seconds)`.
     ///
-    /// Drops the tag silently if the first positional arg is missing or not a
-    /// finite, positive number — keeps behavior consistent with the
-    /// Python-side `karva.tags.timeout` validator and avoids passing
-    /// nonsensical values into `future.result()` / `asyncio.wait_for`.
+    /// Drops the tag silently if the first positional arg is missing, not a
+    /// finite, positive number, or too large for a [`Duration`] — keeps
+    /// behavior consistent with the Python-side `karva.tags.timeout`
+    /// validator and avoids passing nonsensical values into
+    /// `future.result()` / `asyncio.wait_for`.
     pub(crate) fn try_from_pytest_mark(py_mark: &Bound<'_, PyAny>) -> Option<Self> {
         let args = py_mark.getattr("args").ok()?;
         let tuple = args.extract::<Bound<'_, PyTuple>>().ok()?;
         let first = tuple.get_item(0).ok()?;
         let seconds = first.extract::<f64>().ok()?;
-        if !(seconds.is_finite() && seconds > 0.0) {
+        if !(seconds.is_finite() && seconds > 0.0 && Self::fits_duration(seconds)) {
             return None;
         }
         Some(Self { seconds })
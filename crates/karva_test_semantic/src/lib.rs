@@ -39,7 +39,12 @@ pub fn run_tests(
 
     attach_with_output(settings.terminal().show_python_output, |py| {
         let cov_session = coverage.and_then(|cfg| match CoverageSession::start(py, cwd, cfg) {
-            Ok(session) => Some(session),
+            Ok(session) => {
+                if let Err(err) = karva_coverage::measure_subprocesses(py, cwd, cfg) {
+                    tracing::error!("Failed to set up coverage of subprocesses: {err}");
+                }
+                Some(session)
+            }
             Err(err) => {
                 tracing::error!("Failed to start coverage measurement: {err}");
                 None
@@ -48,7 +53,7 @@ pub fn run_tests(
 
         let session = StandardDiscoverer::new(&context).discover_with_py(py, test_paths);
 
-        PackageRunner::new(&context).execute(py, &session);
+        PackageRunner::new(&context, cov_session.as_ref()).execute(py, &session);
 
         if let Some(cov_session) = cov_session
             && let Err(err) = cov_session.stop_and_save(py)
@@ -56,6 +61,8 @@ pub fn run_tests(
             tracing::error!("Failed to save coverage data: {err}");
         }
 
-        context.into_result()
+        let mut result = context.into_result();
+        result.set_snapshots(crate::extensions::functions::snapshot::take_referenced_snapshots());
+        result
     })
 }
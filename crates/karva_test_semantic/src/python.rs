@@ -4,13 +4,16 @@ use pyo3::wrap_pymodule;
 use crate::extensions::fixtures::python::{
     FixtureFunctionDefinition, FixtureFunctionMarker, InvalidFixtureError, fixture_decorator,
 };
+use crate::extensions::fixtures::{FixtureConfig, FixtureRequest};
 use crate::extensions::functions::raises::raises;
 use crate::extensions::functions::snapshot::{
-    assert_cmd_snapshot, assert_json_snapshot, assert_snapshot, snapshot_settings,
+    assert_binary_snapshot, assert_cmd_snapshot, assert_debug_snapshot, assert_json_snapshot,
+    assert_raises_snapshot, assert_snapshot, assert_toml_snapshot, assert_warns_snapshot,
+    assert_yaml_snapshot, snapshot_settings, sorted_redaction,
 };
 use crate::extensions::functions::{
     Command, ExceptionInfo, FailError, RaisesContext, SkipError, SnapshotMismatchError,
-    SnapshotSettings, fail, param, skip,
+    SnapshotSettings, SortedRedaction, fail, param, skip,
 };
 use crate::extensions::tags::python::{PyTags, PyTestFunction, tags};
 
@@ -22,16 +25,27 @@ pub fn init_module(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(raises, m)?)?;
     m.add_function(wrap_pyfunction!(assert_snapshot, m)?)?;
     m.add_function(wrap_pyfunction!(assert_json_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(assert_yaml_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(assert_toml_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(assert_debug_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(assert_binary_snapshot, m)?)?;
     m.add_function(wrap_pyfunction!(assert_cmd_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(assert_raises_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(assert_warns_snapshot, m)?)?;
     m.add_function(wrap_pyfunction!(snapshot_settings, m)?)?;
+    m.add_function(wrap_pyfunction!(sorted_redaction, m)?)?;
+    m.add_function(wrap_pyfunction!(karva_coverage::start_process_coverage, m)?)?;
 
     m.add_class::<FixtureFunctionMarker>()?;
     m.add_class::<FixtureFunctionDefinition>()?;
+    m.add_class::<FixtureConfig>()?;
+    m.add_class::<FixtureRequest>()?;
     m.add_class::<PyTags>()?;
     m.add_class::<PyTestFunction>()?;
     m.add_class::<ExceptionInfo>()?;
     m.add_class::<RaisesContext>()?;
     m.add_class::<SnapshotSettings>()?;
+    m.add_class::<SortedRedaction>()?;
     m.add_class::<Command>()?;
 
     m.add_wrapped(wrap_pymodule!(tags))?;
@@ -1,12 +1,12 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::rc::Rc;
 
 use pyo3::prelude::*;
 
 use crate::discovery::DiscoveredPackage;
 use crate::extensions::fixtures::{
-    DiscoveredFixture, FixtureScope, HasFixtures, NormalizedFixture, RequiresFixtures,
-    get_auto_use_fixtures,
+    DiscoveredFixture, FixtureParam, FixtureScope, HasFixtures, NormalizedFixture,
+    RequiresFixtures, get_auto_use_fixtures, requested_fixture_names,
 };
 
 /// Resolves fixtures at runtime during test execution.
@@ -14,16 +14,22 @@ use crate::extensions::fixtures::{
 /// Unlike pre-normalization, this resolver finds and normalizes fixtures
 /// on-demand when tests need them. `current` is typed as a trait object so
 /// callers may pass either a test module (normal test / module-autouse
-/// resolution), a conftest module (package-autouse resolution), or the
+/// resolution), a test class together with its module (tests defined as
+/// methods), a conftest module (package-autouse resolution), or the
 /// session package itself (session-autouse resolution) — the latter gives
 /// session-level autouse fixtures visibility into `framework_module` via
 /// the `HasFixtures` impl on `DiscoveredPackage`.
 pub(super) struct RuntimeFixtureResolver<'a> {
     parents: &'a [&'a DiscoveredPackage],
     current: &'a (dyn HasFixtures<'a> + 'a),
-    fixture_cache: HashMap<String, Rc<NormalizedFixture>>,
+    fixture_cache: HashMap<String, FixtureVariants>,
 }
 
+/// The normalized variants of a fixture, one per combination of its params
+/// and the params of its dependencies. A fixture without parametrized
+/// dependencies has a single variant.
+pub(super) type FixtureVariants = Rc<[Rc<NormalizedFixture>]>;
+
 impl<'a> RuntimeFixtureResolver<'a> {
     pub(super) fn new(
         parents: &'a [&'a DiscoveredPackage],
@@ -42,11 +48,7 @@ impl<'a> RuntimeFixtureResolver<'a> {
     /// (e.g. `tmp_path`) must be fresh for each test invocation. Broader-scoped
     /// fixtures are cached so they are shared across tests within the appropriate
     /// scope.
-    fn normalize_fixture(
-        &mut self,
-        py: Python,
-        fixture: &DiscoveredFixture,
-    ) -> Rc<NormalizedFixture> {
+    fn normalize_fixture(&mut self, py: Python, fixture: &DiscoveredFixture) -> FixtureVariants {
         let cache_key = fixture.name().to_string();
 
         if fixture.scope() != FixtureScope::Function {
@@ -55,17 +57,60 @@ impl<'a> RuntimeFixtureResolver<'a> {
             }
         }
 
-        let required_fixtures: Vec<String> = fixture.required_fixtures(py);
+        let mut required_fixtures: Vec<String> = fixture.required_fixtures(py);
+        let takes_request = required_fixtures.iter().any(|name| name == "request");
+
+        // Fixtures looked up through `request.getfixturevalue` are resolved
+        // like parameters, so that their values are ready when it is called.
+        let requested_fixtures: Vec<String> = if takes_request {
+            requested_fixture_names(fixture.stmt_function_def())
+                .into_iter()
+                .filter(|name| !required_fixtures.contains(name))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        required_fixtures.extend(requested_fixtures.iter().cloned());
+
         let dependent_fixtures = self.get_dependent_fixtures(py, Some(fixture), &required_fixtures);
 
-        let result = Rc::new(NormalizedFixture {
-            name: fixture.name().clone(),
-            dependencies: dependent_fixtures,
-            scope: fixture.scope(),
-            is_generator: fixture.is_generator(),
-            py_function: Rc::new(fixture.function().clone_ref(py)),
-            stmt_function_def: Rc::clone(fixture.stmt_function_def()),
-        });
+        let params: Vec<Option<&FixtureParam>> = if fixture.params().is_empty() {
+            vec![None]
+        } else {
+            fixture.params().iter().map(Some).collect()
+        };
+
+        let mut variants = Vec::new();
+
+        for dependencies in fixture_combinations(&dependent_fixtures) {
+            let dependency_params: BTreeMap<String, usize> = dependencies
+                .iter()
+                .flat_map(|dependency| dependency.param_indices.clone())
+                .collect();
+
+            for param in &params {
+                let mut param_indices = dependency_params.clone();
+                if let Some(param) = param {
+                    param_indices.insert(fixture.name().to_string(), param.index);
+                }
+
+                variants.push(Rc::new(NormalizedFixture {
+                    name: fixture.name().clone(),
+                    dependencies: dependencies.clone(),
+                    scope: fixture.scope(),
+                    is_generator: fixture.is_generator(),
+                    is_method: fixture.is_method(),
+                    py_function: Rc::new(fixture.function().clone_ref(py)),
+                    stmt_function_def: Rc::clone(fixture.stmt_function_def()),
+                    param: param.cloned(),
+                    param_indices,
+                    takes_request,
+                    requested_fixtures: requested_fixtures.clone(),
+                }));
+            }
+        }
+
+        let result: FixtureVariants = Rc::from(variants);
 
         if fixture.scope() != FixtureScope::Function {
             self.fixture_cache.insert(cache_key, Rc::clone(&result));
@@ -79,7 +124,7 @@ impl<'a> RuntimeFixtureResolver<'a> {
         &mut self,
         py: Python,
         scope: FixtureScope,
-    ) -> Vec<Rc<NormalizedFixture>> {
+    ) -> Vec<FixtureVariants> {
         let auto_use_fixtures = get_auto_use_fixtures(self.parents, self.current, scope);
 
         auto_use_fixtures
@@ -94,7 +139,7 @@ impl<'a> RuntimeFixtureResolver<'a> {
         py: Python,
         fixture_names: &[String],
         parametrize_param_names: &HashSet<&str>,
-    ) -> Vec<Rc<NormalizedFixture>> {
+    ) -> Vec<FixtureVariants> {
         let regular_fixture_names: Vec<String> = fixture_names
             .iter()
             .filter(|name| !parametrize_param_names.contains(name.as_str()))
@@ -109,7 +154,7 @@ impl<'a> RuntimeFixtureResolver<'a> {
         &mut self,
         py: Python,
         fixture_names: &[String],
-    ) -> Vec<Rc<NormalizedFixture>> {
+    ) -> Vec<FixtureVariants> {
         self.get_dependent_fixtures(py, None, fixture_names)
     }
 
@@ -119,7 +164,7 @@ impl<'a> RuntimeFixtureResolver<'a> {
         py: Python,
         current_fixture: Option<&DiscoveredFixture>,
         fixture_names: &[String],
-    ) -> Vec<Rc<NormalizedFixture>> {
+    ) -> Vec<FixtureVariants> {
         let mut normalized_fixtures = Vec::with_capacity(fixture_names.len());
 
         for dep_name in fixture_names {
@@ -135,6 +180,37 @@ impl<'a> RuntimeFixtureResolver<'a> {
     }
 }
 
+/// Returns every combination of one variant of each fixture.
+///
+/// Combinations that would need two different params of the same fixture,
+/// such as two fixtures that both depend on a parametrized one, are left out.
+pub(super) fn fixture_combinations(
+    fixtures: &[FixtureVariants],
+) -> Vec<Vec<Rc<NormalizedFixture>>> {
+    let mut combinations: Vec<Vec<Rc<NormalizedFixture>>> = vec![Vec::new()];
+
+    for variants in fixtures {
+        let mut extended_combinations = Vec::with_capacity(combinations.len() * variants.len());
+
+        for combination in &combinations {
+            for variant in variants.iter() {
+                if combination
+                    .iter()
+                    .all(|fixture| fixture.is_compatible_with(variant))
+                {
+                    let mut extended = combination.clone();
+                    extended.push(Rc::clone(variant));
+                    extended_combinations.push(extended);
+                }
+            }
+        }
+
+        combinations = extended_combinations;
+    }
+
+    combinations
+}
+
 /// Finds a fixture by name, searching in the current node and parent packages.
 /// We pass in the current fixture to avoid returning it (which would cause infinite recursion).
 fn find_fixture<'a>(
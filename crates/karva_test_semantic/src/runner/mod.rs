@@ -1,9 +1,11 @@
 mod finalizer_cache;
 mod fixture_cache;
 mod fixture_resolver;
+mod output_capture;
 mod package_runner;
 mod scoped_storage;
 mod test_iterator;
+mod unittest;
 
 use finalizer_cache::FinalizerCache;
 use fixture_cache::FixtureCache;
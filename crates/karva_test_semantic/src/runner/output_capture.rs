@@ -0,0 +1,69 @@
+use karva_diagnostic::{TestCaseDetails, TestCaseFailure};
+use pyo3::prelude::*;
+
+/// Records what a single test variant writes to `sys.stdout` and
+/// `sys.stderr`, backed by `karva._capture.OutputCapture`.
+///
+/// Output is still forwarded to the streams that were active when capture
+/// started, so enabling capture does not change what the user sees.
+pub(super) struct OutputCapture<'py> {
+    capture: Bound<'py, PyAny>,
+}
+
+impl<'py> OutputCapture<'py> {
+    /// Install the capture streams. Returns `None` (and leaves the streams
+    /// untouched) if the helper module cannot be imported.
+    pub(super) fn start(py: Python<'py>) -> Option<Self> {
+        let capture = py
+            .import("karva._capture")
+            .and_then(|module| module.getattr("OutputCapture")?.call0());
+
+        match capture {
+            Ok(capture) => Some(Self { capture }),
+            Err(err) => {
+                tracing::warn!("Failed to capture test output: {err}");
+                None
+            }
+        }
+    }
+
+    /// Build the details recorded for the test: the captured output so far
+    /// plus the given failure.
+    pub(super) fn details(&self, failure: Option<TestCaseFailure>) -> TestCaseDetails {
+        TestCaseDetails {
+            failure,
+            stdout: self.read("stdout"),
+            stderr: self.read("stderr"),
+        }
+    }
+
+    /// Put back the streams that were active when capture started.
+    pub(super) fn restore(self) {
+        if let Err(err) = self.capture.call_method0("restore") {
+            tracing::warn!("Failed to restore output streams: {err}");
+        }
+    }
+
+    fn read(&self, stream: &str) -> String {
+        self.capture
+            .call_method0(stream)
+            .and_then(|value| value.extract())
+            .unwrap_or_default()
+    }
+}
+
+/// Summarise a Python exception for machine-readable reports.
+pub(super) fn test_case_failure(py: Python<'_>, error: &PyErr) -> TestCaseFailure {
+    let kind = error.get_type(py).name().ok().map(|name| name.to_string());
+    let message = error.to_string();
+    let traceback = error
+        .traceback(py)
+        .and_then(|traceback| traceback.format().ok())
+        .unwrap_or_default();
+
+    TestCaseFailure {
+        kind,
+        details: Some(format!("{traceback}{message}")),
+        message,
+    }
+}
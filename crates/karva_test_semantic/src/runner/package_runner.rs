@@ -1,37 +1,41 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
 
 type FixtureArguments = HashMap<String, Py<PyAny>>;
 
-use karva_diagnostic::IndividualTestResultKind;
-use karva_metadata::RunIgnoredMode;
+use karva_coverage::CoverageSession;
+use karva_diagnostic::{IndividualTestResultKind, TestCaseDetails, TestCaseFailure};
 use karva_metadata::filter::EvalContext;
-use karva_python_semantic::{FunctionKind, QualifiedFunctionName, QualifiedTestName};
+use karva_metadata::{RunIgnoredMode, TimeoutMethod};
+use karva_python_semantic::{FunctionKind, QualifiedFunctionName, QualifiedTestName, module_name};
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyIterator};
+use pyo3::types::{PyDict, PyIterator, PyString};
 use ruff_python_ast::StmtFunctionDef;
 use ruff_source_file::SourceFile;
 
 use crate::Context;
 use crate::diagnostic::{
-    report_fixture_failure, report_missing_fixtures, report_test_failure,
-    report_test_pass_on_expect_failure,
+    report_class_teardown_failure, report_fixture_failure, report_missing_fixtures,
+    report_test_failure, report_test_pass_on_expect_failure,
 };
-use crate::discovery::{DiscoveredModule, DiscoveredPackage};
+use crate::discovery::{DiscoveredModule, DiscoveredPackage, DiscoveredTestClass};
 use crate::extensions::fixtures::{
-    Finalizer, FixtureScope, HasFixtures, NormalizedFixture, missing_arguments_from_error,
+    ClassFixtures, Finalizer, FinalizerKind, FixtureRequest, FixtureScope, HasFixtures,
+    NormalizedFixture, RequestContext, missing_arguments_from_error,
 };
 use crate::extensions::tags::expect_fail::ExpectFailTag;
+use crate::extensions::tags::parametrize::ParametrizationId;
 use crate::extensions::tags::skip::{extract_skip_reason, is_skip_exception};
 use crate::extensions::tags::timeout::TimeoutTag;
 use crate::runner::fixture_resolver::RuntimeFixtureResolver;
+use crate::runner::output_capture::{OutputCapture, test_case_failure};
 use crate::runner::test_iterator::{TestVariant, TestVariantIterator};
-use crate::runner::{FinalizerCache, FixtureCache};
+use crate::runner::{FinalizerCache, FixtureCache, unittest};
 use crate::utils::{
-    full_test_name, run_coroutine, run_test_with_timeout, set_attempt_env, set_test_name_env,
-    source_file,
+    bind_method, full_test_name, run_coroutine, run_test_with_timeout, set_attempt_env,
+    set_test_name_env, source_file,
 };
 
 /// Executes discovered tests within a package hierarchy.
@@ -54,15 +58,47 @@ pub struct PackageRunner<'ctx, 'a> {
     /// Used to enforce `--max-fail=N`: once this counter reaches the
     /// configured budget we stop scheduling new tests.
     failed_count: Cell<u32>,
+
+    /// The instance of the test class the running test is a method of.
+    ///
+    /// Fixtures defined on the class are called with this instance, so they
+    /// share `self` with the test.
+    test_instance: RefCell<Option<Py<PyAny>>>,
+
+    /// The `unittest.TestCase` class whose `setUpClass` last ran, and its
+    /// result. Its `tearDownClass` runs once the runner moves past its tests.
+    test_case_class: RefCell<Option<TestCaseClassSetUp>>,
+
+    /// The package, module and test being run, for fixtures that take a
+    /// `request`.
+    request_context: RefCell<RequestContext>,
+
+    /// The coverage measurement, told which test is running so it can
+    /// record per-test contexts.
+    coverage: Option<&'ctx CoverageSession>,
 }
 
 impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
-    pub(crate) fn new(context: &'ctx Context<'a>) -> Self {
+    pub(crate) fn new(context: &'ctx Context<'a>, coverage: Option<&'ctx CoverageSession>) -> Self {
         Self {
             context,
+            coverage,
             fixture_cache: FixtureCache::default(),
             finalizer_cache: FinalizerCache::default(),
             failed_count: Cell::new(0),
+            test_instance: RefCell::new(None),
+            test_case_class: RefCell::new(None),
+            request_context: RefCell::new(RequestContext::default()),
+        }
+    }
+
+    /// Record the lines run from now on as run by the test `name`, or by no
+    /// test with `None`.
+    fn set_coverage_context(&self, py: Python<'_>, name: Option<&str>) {
+        if let Some(coverage) = self.coverage
+            && let Err(err) = coverage.set_context(py, name)
+        {
+            tracing::error!("Failed to set coverage context: {err}");
         }
     }
 
@@ -91,6 +127,18 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
         }
     }
 
+    /// The number of variants of a test that an earlier worker finished,
+    /// when this worker takes over from one that stopped partway through it.
+    fn finished_variants(&self, name: &QualifiedFunctionName) -> usize {
+        self.context
+            .settings()
+            .test()
+            .resume
+            .as_ref()
+            .filter(|resume| resume.test == name.to_string())
+            .map_or(0, |resume| resume.finished_variants)
+    }
+
     /// Record a test variant's outcome for `max-fail` accounting.
     fn record_outcome(&self, passed: bool) {
         if !passed {
@@ -127,7 +175,12 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
         scope: FixtureScope,
     ) {
         let mut resolver = RuntimeFixtureResolver::new(parents, current);
-        let auto_use_fixtures = resolver.get_normalized_auto_use_fixtures(py, scope);
+        // A parametrized auto-use fixture is set up for each of its params.
+        let auto_use_fixtures: Vec<_> = resolver
+            .get_normalized_auto_use_fixtures(py, scope)
+            .iter()
+            .flat_map(|variants| variants.iter().cloned())
+            .collect();
         let auto_use_errors = self.run_fixtures(py, &auto_use_fixtures);
         for error in auto_use_errors {
             report_fixture_failure(self.context, py, error);
@@ -145,16 +198,32 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
         module: &DiscoveredModule,
         parents: &[&DiscoveredPackage],
     ) -> bool {
+        self.request_context.borrow_mut().module = Some(module.name().to_string());
+
         self.run_auto_use_fixtures(py, parents, module, FixtureScope::Module);
 
         let mut passed = true;
 
         for test_function in module.test_functions() {
-            // Create a new resolver for each test to handle fixture resolution
-            let mut test_resolver = RuntimeFixtureResolver::new(parents, module);
+            // Fixtures defined on a test class are visible to its methods only.
+            let class_fixtures = test_function
+                .class
+                .as_deref()
+                .map(|class| ClassFixtures { class, module });
+            let current: &dyn HasFixtures = match &class_fixtures {
+                Some(class_fixtures) => class_fixtures,
+                None => module,
+            };
 
-            // Iterate over all test variants (parametrize combinations × fixture combinations).
-            for variant in TestVariantIterator::new(py, test_function, &mut test_resolver) {
+            // Create a new resolver for each test to handle fixture resolution
+            let mut test_resolver = RuntimeFixtureResolver::new(parents, current);
+
+            // Iterate over all test variants (parametrize combinations × fixture combinations),
+            // leaving out those an earlier worker finished before it stopped.
+            let finished_variants = self.finished_variants(&test_function.name);
+            for variant in TestVariantIterator::new(py, test_function, &mut test_resolver)
+                .skip(finished_variants)
+            {
                 let variant_passed = self.execute_test_variant(py, variant);
                 self.record_outcome(variant_passed);
                 passed &= variant_passed;
@@ -169,8 +238,12 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
             }
         }
 
+        self.tear_down_test_case_class(py);
+
         self.clean_up_scope(py, FixtureScope::Module);
 
+        self.request_context.borrow_mut().module = None;
+
         passed
     }
 
@@ -188,6 +261,11 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
         let mut new_parents = parents.to_vec();
         new_parents.push(package);
 
+        let package_name = module_name(&self.context.cwd().to_path_buf(), package.path())
+            .filter(|name| !name.is_empty());
+        let parent_package_name =
+            std::mem::replace(&mut self.request_context.borrow_mut().package, package_name);
+
         if let Some(config_module) = package.configuration_module_impl() {
             self.run_auto_use_fixtures(py, parents, config_module, FixtureScope::Package);
         }
@@ -214,6 +292,8 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
 
         self.clean_up_scope(py, FixtureScope::Package);
 
+        self.request_context.borrow_mut().package = parent_package_name;
+
         passed
     }
 
@@ -242,6 +322,7 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
                     &qualified,
                     IndividualTestResultKind::Skipped { reason: None },
                     std::time::Duration::ZERO,
+                    TestCaseDetails::default(),
                 ));
             }
         }
@@ -253,6 +334,7 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
                         &QualifiedTestName::new(name.clone(), None),
                         IndividualTestResultKind::Skipped { reason },
                         std::time::Duration::ZERO,
+                        TestCaseDetails::default(),
                     ));
                 }
             }
@@ -264,6 +346,7 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
                         &QualifiedTestName::new(name.clone(), None),
                         IndividualTestResultKind::Skipped { reason: None },
                         std::time::Duration::ZERO,
+                        TestCaseDetails::default(),
                     ));
                 }
             }
@@ -424,6 +507,10 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
                 attempt,
                 IndividualTestResultKind::Failed,
                 attempt_duration,
+                test_result
+                    .as_ref()
+                    .err()
+                    .map(|err| test_case_failure(py, err)),
             );
             was_retried = true;
 
@@ -443,15 +530,19 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
             //   TRY 2 PASS ...   (or TRY 2 FAIL for an exhausted retry)
             // The diagnostic for the final attempt (if any) is collected by
             // `classify_test_result` and shown in the end-of-run block.
-            let final_kind = match &test_result {
-                Ok(_) => IndividualTestResultKind::Passed,
-                Err(_) => IndividualTestResultKind::Failed,
+            let (final_kind, failure) = match &test_result {
+                Ok(_) => (IndividualTestResultKind::Passed, None),
+                Err(err) => (
+                    IndividualTestResultKind::Failed,
+                    Some(test_case_failure(py, err)),
+                ),
             };
             self.context.report_test_attempt(
                 qualified_test_name,
                 attempt,
                 final_kind,
                 final_attempt_duration,
+                failure,
             );
         }
 
@@ -471,6 +562,7 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
         let TestVariant {
             test,
             params,
+            param_ids,
             fixture_dependencies,
             use_fixture_dependencies,
             auto_use_fixtures,
@@ -478,16 +570,56 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
         } = variant;
 
         let name = test.name.clone();
-        let function = test.py_function.clone_ref(py);
         let stmt_function_def = Rc::clone(&test.stmt_function_def);
 
         if let Some(result) = self.should_skip_variant(&name, &tags) {
             return result;
         }
 
+        // Each test of a class runs against a fresh instance. A class that
+        // fails to instantiate fails the test with that error, as does a
+        // `unittest.TestCase` whose `setUpClass` failed.
+        let function = match &test.class {
+            Some(class) if class.is_test_case => self
+                .set_up_test_case_class(py, class)
+                .and_then(|()| class.instantiate(py, name.function_name()))
+                .and_then(|instance| {
+                    let test_case = unittest::bind_test_case(py, &instance);
+                    self.test_instance.replace(Some(instance));
+                    test_case
+                }),
+            Some(class) => class
+                .instantiate(py, name.function_name())
+                .and_then(|instance| {
+                    let method = bind_method(py, &test.py_function, &instance);
+                    self.test_instance.replace(Some(instance));
+                    method
+                }),
+            None => Ok(test.py_function.clone_ref(py)),
+        };
+
         let start_time = std::time::Instant::now();
         let expect_fail_tag = tags.expect_fail_tag();
 
+        // Only reports that record per-test output need it captured. Capture
+        // starts before fixtures run so that `capsys` and friends nest inside
+        // it and restore our streams on teardown.
+        let capture = if self.context.settings().terminal().junit_path.is_some() {
+            OutputCapture::start(py)
+        } else {
+            None
+        };
+
+        {
+            let mut request_context = self.request_context.borrow_mut();
+            request_context.test_name = Some(name.to_string());
+            request_context.tags = tags
+                .custom_tag_names()
+                .into_iter()
+                .map(String::from)
+                .collect();
+        }
+
         let (function_arguments, fixture_call_errors, test_finalizers) = self.setup_test_fixtures(
             py,
             &fixture_dependencies,
@@ -496,43 +628,70 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
             params,
         );
 
-        let computed_full_test_name = full_test_name(py, name.to_string(), &function_arguments);
+        let display_arguments = display_arguments(
+            py,
+            &function_arguments,
+            &param_ids,
+            [
+                &*fixture_dependencies,
+                &*use_fixture_dependencies,
+                &*auto_use_fixtures,
+            ],
+        );
+
+        let computed_full_test_name = full_test_name(py, name.to_string(), &display_arguments);
 
         let qualified_test_name =
             QualifiedTestName::new(name.clone(), Some(computed_full_test_name));
 
+        let timeout_seconds = tags.timeout_tag().map(TimeoutTag::seconds).or_else(|| {
+            self.context
+                .settings()
+                .test()
+                .timeout
+                .map(|d| d.as_secs_f64())
+        });
+
         tracing::debug!("Running test `{}`", qualified_test_name);
+        self.context.report_test_started(
+            &qualified_test_name,
+            timeout_seconds
+                .and_then(|seconds| std::time::Duration::try_from_secs_f64(seconds).ok()),
+        );
 
-        let _ = set_test_name_env(py, &qualified_test_name.to_string());
+        let test_name = qualified_test_name.to_string();
+        let _ = set_test_name_env(py, &test_name);
+        self.set_coverage_context(py, Some(&test_name));
 
         // Set snapshot context so `karva.assert_snapshot()` can determine the current test.
         // Use `function_name()` (not `qualified_test_name`) to avoid doubling the module prefix,
         // since `snapshot_path()` already prepends the module name from the file stem.
-        let snapshot_test_name =
-            full_test_name(py, name.function_name().to_string(), &function_arguments);
+        let snapshot_function_name = match name.class_name() {
+            Some(class_name) => format!("{class_name}.{}", name.function_name()),
+            None => name.function_name().to_string(),
+        };
+        let snapshot_test_name = full_test_name(py, snapshot_function_name, &display_arguments);
         crate::extensions::functions::snapshot::set_snapshot_context(
             test_module_path.to_string(),
             snapshot_test_name,
+            self.context.settings().snapshot().update,
         );
 
+        // `unittest` runs the coroutines of async test cases itself.
+        let is_test_case = test.class.as_ref().is_some_and(|class| class.is_test_case);
         let is_async = stmt_function_def.is_async
-            && !crate::utils::patch_async_test_function(py, &function).unwrap_or(false);
-        let timeout_seconds = tags.timeout_tag().map(TimeoutTag::seconds).or_else(|| {
-            self.context
-                .settings()
-                .test()
-                .timeout
-                .map(|d| d.as_secs_f64())
-        });
+            && !is_test_case
+            && !function.as_ref().is_ok_and(|function| {
+                crate::utils::patch_async_test_function(py, function).unwrap_or(false)
+            });
+        // With the `process` method the test runs on this thread, and the main
+        // process kills this worker if it overruns.
+        let thread_timeout = timeout_seconds
+            .filter(|_| self.context.settings().test().timeout_method == TimeoutMethod::Thread);
         let run_test = || {
-            if let Some(seconds) = timeout_seconds {
-                return run_test_with_timeout(
-                    py,
-                    &function,
-                    &function_arguments,
-                    is_async,
-                    seconds,
-                );
+            let function = function.as_ref().map_err(|err| err.clone_ref(py))?;
+            if let Some(seconds) = thread_timeout {
+                return run_test_with_timeout(py, function, &function_arguments, is_async, seconds);
             }
             let result = if function_arguments.is_empty() {
                 function.call0(py)
@@ -569,6 +728,26 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
         let total_duration = start_time.elapsed();
         self.maybe_register_slow(&qualified_test_name, total_duration);
 
+        let error_failure = test_result
+            .as_ref()
+            .err()
+            .map(|err| test_case_failure(py, err));
+        let details = |kind: &IndividualTestResultKind| {
+            let failure = matches!(kind, IndividualTestResultKind::Failed).then(|| {
+                error_failure.clone().unwrap_or_else(|| TestCaseFailure {
+                    message: "Test passed when expected to fail".to_string(),
+                    ..TestCaseFailure::default()
+                })
+            });
+            match &capture {
+                Some(capture) => capture.details(failure),
+                None => TestCaseDetails {
+                    failure,
+                    ..TestCaseDetails::default()
+                },
+            }
+        };
+
         let passed = if was_retried {
             let passed_on = attempt;
             // `total_attempts` mirrors nextest: the maximum number of attempts
@@ -583,20 +762,40 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
                     total_duration,
                     passed_on,
                     total_attempts,
+                    details(&kind),
                 )
             })
         } else {
             self.classify_test_result(py, test_result, fixture_call_errors, &report_ctx, |kind| {
-                self.context
-                    .register_test_case_result(&qualified_test_name, kind, total_duration)
+                let details = details(&kind);
+                self.context.register_test_case_result(
+                    &qualified_test_name,
+                    kind,
+                    total_duration,
+                    details,
+                )
             })
         };
 
+        // Fixture teardown isn't attributed to the test, like its set-up.
+        self.set_coverage_context(py, None);
+
         for finalizer in test_finalizers.into_iter().rev() {
             finalizer.run(self.context, py);
         }
 
         self.clean_up_scope(py, FixtureScope::Function);
+        self.test_instance.replace(None);
+
+        {
+            let mut request_context = self.request_context.borrow_mut();
+            request_context.test_name = None;
+            request_context.tags.clear();
+        }
+
+        if let Some(capture) = capture {
+            capture.restore();
+        }
 
         passed
     }
@@ -608,19 +807,25 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
         py: Python<'_>,
         fixture: &NormalizedFixture,
     ) -> Result<(Py<PyAny>, Option<Finalizer>), FixtureCallError> {
-        if let Some(cached) = self
-            .fixture_cache
-            .get(py, fixture.function_name(), fixture.scope())
-        {
+        let cache_key = fixture.cache_key();
+
+        if let Some(cached) = self.fixture_cache.get(py, &cache_key, fixture.scope()) {
             return Ok((cached, None));
         }
 
         let mut function_arguments: FixtureArguments = HashMap::new();
+        let mut requested_values: FixtureArguments = HashMap::new();
 
         for dep in fixture.dependencies() {
             match self.run_fixture(py, dep) {
                 Ok((value, finalizer)) => {
-                    function_arguments.insert(dep.function_name().to_string(), value.clone_ref(py));
+                    let dep_name = dep.function_name().to_string();
+                    if fixture.takes_request {
+                        requested_values.insert(dep_name.clone(), value.clone_ref(py));
+                    }
+                    if !fixture.requested_fixtures.contains(&dep_name) {
+                        function_arguments.insert(dep_name, value.clone_ref(py));
+                    }
 
                     if let Some(finalizer) = finalizer {
                         self.finalizer_cache.add_finalizer(finalizer);
@@ -637,20 +842,58 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
             }
         }
 
-        let fixture_call_result =
-            fixture
-                .call(py, &function_arguments)
-                .map_err(|err| FixtureCallError {
-                    fixture_name: fixture.name.function_name().to_string(),
-                    error: err,
-                    stmt_function_def: fixture.stmt_function_def.clone(),
-                    source_file: source_file(fixture.name.module_path().path()),
-                    arguments: function_arguments,
-                    dependency_chain: Vec::new(),
-                })?;
-
-        let (final_result, finalizer) = get_value_and_finalizer(py, fixture, fixture_call_result)
-            .map_err(|err| FixtureCallError {
+        let instance = self
+            .test_instance
+            .borrow()
+            .as_ref()
+            .filter(|_| fixture.is_method)
+            .map(|instance| instance.clone_ref(py));
+
+        // A fixture named `request` takes precedence over the built-in one.
+        let request = if fixture.takes_request && !function_arguments.contains_key("request") {
+            Bound::new(
+                py,
+                FixtureRequest::new(
+                    py,
+                    &self.request_context.borrow(),
+                    fixture,
+                    requested_values,
+                ),
+            )
+            .map(Some)
+        } else {
+            Ok(None)
+        };
+
+        let (request, fixture_call_result) = match request {
+            Ok(request) => {
+                let result =
+                    fixture.call(py, instance.as_ref(), &function_arguments, request.as_ref());
+                (request, result)
+            }
+            Err(err) => (None, Err(err)),
+        };
+
+        // Finalizers added through `request.addfinalizer` run even if the
+        // fixture goes on to fail. A generator fixture only runs up to its
+        // `yield` once it is resumed for its value, so they are collected
+        // again after that.
+        self.add_request_finalizers(fixture, request.as_ref());
+
+        let fixture_call_result = fixture_call_result.map_err(|err| FixtureCallError {
+            fixture_name: fixture.name.function_name().to_string(),
+            error: err,
+            stmt_function_def: fixture.stmt_function_def.clone(),
+            source_file: source_file(fixture.name.module_path().path()),
+            arguments: function_arguments,
+            dependency_chain: Vec::new(),
+        })?;
+
+        let value_and_finalizer = get_value_and_finalizer(py, fixture, fixture_call_result);
+
+        self.add_request_finalizers(fixture, request.as_ref());
+
+        let (final_result, finalizer) = value_and_finalizer.map_err(|err| FixtureCallError {
             fixture_name: fixture.name.function_name().to_string(),
             error: err,
             stmt_function_def: fixture.stmt_function_def.clone(),
@@ -659,11 +902,8 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
             dependency_chain: Vec::new(),
         })?;
 
-        self.fixture_cache.insert(
-            fixture.function_name().to_string(),
-            final_result.clone_ref(py),
-            fixture.scope(),
-        );
+        self.fixture_cache
+            .insert(cache_key, final_result.clone_ref(py), fixture.scope());
 
         // Handle finalizer based on scope
         // Function-scoped finalizers are returned to be run immediately after the test
@@ -683,6 +923,80 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
         Ok((final_result, return_finalizer))
     }
 
+    /// Register the callables a fixture passed to `request.addfinalizer`, to
+    /// run when the fixture's scope ends.
+    fn add_request_finalizers(
+        &self,
+        fixture: &NormalizedFixture,
+        request: Option<&Bound<'_, FixtureRequest>>,
+    ) {
+        let Some(request) = request else {
+            return;
+        };
+
+        for callback in request.borrow_mut().take_finalizers() {
+            self.finalizer_cache.add_finalizer(Finalizer {
+                fixture_return: callback,
+                kind: FinalizerKind::Callback,
+                scope: fixture.scope(),
+                fixture_name: Some(fixture.name.clone()),
+                stmt_function_def: Some(fixture.stmt_function_def.clone()),
+            });
+        }
+    }
+
+    /// Run `setUpClass` for the `unittest.TestCase` class of the next test,
+    /// if it has not run yet, after tearing down the class that ran before.
+    ///
+    /// Returns the error `setUpClass` raised, for each test of the class.
+    fn set_up_test_case_class(
+        &self,
+        py: Python<'_>,
+        class: &Rc<DiscoveredTestClass>,
+    ) -> PyResult<()> {
+        let is_set_up = self
+            .test_case_class
+            .borrow()
+            .as_ref()
+            .is_some_and(|set_up| Rc::ptr_eq(&set_up.class, class));
+
+        if !is_set_up {
+            self.tear_down_test_case_class(py);
+            let result = unittest::set_up_class(py, &class.py_class);
+            self.test_case_class.replace(Some(TestCaseClassSetUp {
+                class: Rc::clone(class),
+                result,
+            }));
+        }
+
+        self.test_case_class
+            .borrow()
+            .as_ref()
+            .map_or(Ok(()), |set_up| {
+                set_up
+                    .result
+                    .as_ref()
+                    .copied()
+                    .map_err(|err| err.clone_ref(py))
+            })
+    }
+
+    /// Run `tearDownClass` for the `unittest.TestCase` class whose tests ran
+    /// last. Like `unittest`, this is skipped if its `setUpClass` failed.
+    fn tear_down_test_case_class(&self, py: Python<'_>) {
+        let Some(set_up) = self.test_case_class.take() else {
+            return;
+        };
+
+        if set_up.result.is_err() {
+            return;
+        }
+
+        if let Err(err) = unittest::tear_down_class(py, &set_up.class.py_class) {
+            report_class_teardown_failure(self.context, py, &set_up.class.name, &err);
+        }
+    }
+
     /// Cleans up the fixtures and finalizers for a given scope.
     ///
     /// This should be run after the given scope has finished execution.
@@ -718,6 +1032,46 @@ impl<'ctx, 'a> PackageRunner<'ctx, 'a> {
     }
 }
 
+/// The arguments shown in a test's name.
+///
+/// These are the test's arguments, except that each parametrized fixture is
+/// shown by its param, or the param's id, including fixtures the test only
+/// depends on indirectly. Parametrize arguments with an id are shown as one
+/// argument named after all of them, with the id as its value.
+fn display_arguments(
+    py: Python<'_>,
+    function_arguments: &FixtureArguments,
+    param_ids: &[ParametrizationId],
+    fixtures: [&[Rc<NormalizedFixture>]; 3],
+) -> FixtureArguments {
+    let mut arguments: FixtureArguments = function_arguments
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone_ref(py)))
+        .collect();
+
+    for param_id in param_ids {
+        for name in &param_id.names {
+            arguments.remove(name);
+        }
+        arguments.insert(
+            param_id.names.join(","),
+            PyString::new(py, &param_id.id).into_any().unbind(),
+        );
+    }
+
+    for fixture in fixtures.into_iter().flatten() {
+        for (name, param) in fixture.params() {
+            let value = match &param.id {
+                Some(id) => PyString::new(py, id).into_any().unbind(),
+                None => param.value.clone_ref(py),
+            };
+            arguments.insert(name.to_string(), value);
+        }
+    }
+
+    arguments
+}
+
 fn get_value_and_finalizer(
     py: Python<'_>,
     fixture: &NormalizedFixture,
@@ -731,7 +1085,7 @@ fn get_value_and_finalizer(
 
         let finalizer = Finalizer {
             fixture_return: fixture_call_result,
-            is_async: true,
+            kind: FinalizerKind::AsyncGenerator,
             scope: fixture.scope(),
             fixture_name: Some(fixture.name.clone()),
             stmt_function_def: Some(fixture.stmt_function_def.clone()),
@@ -749,7 +1103,7 @@ fn get_value_and_finalizer(
             Some(Ok(value)) => {
                 let finalizer = Finalizer {
                     fixture_return: bound_iterator.clone().unbind().into_any(),
-                    is_async: false,
+                    kind: FinalizerKind::Generator,
                     scope: fixture.scope(),
                     fixture_name: Some(fixture.name.clone()),
                     stmt_function_def: Some(fixture.stmt_function_def.clone()),
@@ -767,6 +1121,12 @@ fn get_value_and_finalizer(
     }
 }
 
+/// A `unittest.TestCase` class whose `setUpClass` has run.
+struct TestCaseClassSetUp {
+    class: Rc<DiscoveredTestClass>,
+    result: PyResult<()>,
+}
+
 /// Outcome of driving a test through the configured retry budget.
 struct RetryOutcome {
     test_result: PyResult<Py<PyAny>>,
@@ -7,8 +7,8 @@ use pyo3::prelude::*;
 use crate::discovery::DiscoveredTestFunction;
 use crate::extensions::fixtures::{NormalizedFixture, RequiresFixtures};
 use crate::extensions::tags::Tags;
-use crate::extensions::tags::parametrize::ParametrizationArgs;
-use crate::runner::fixture_resolver::RuntimeFixtureResolver;
+use crate::extensions::tags::parametrize::{ParametrizationArgs, ParametrizationId};
+use crate::runner::fixture_resolver::{RuntimeFixtureResolver, fixture_combinations};
 
 /// A single variant of a test to be executed.
 ///
@@ -30,6 +30,9 @@ pub(super) struct TestVariant<'a> {
     /// caller can unwrap without a Python refcount bump.
     pub params: HashMap<String, Arc<Py<PyAny>>>,
 
+    /// Ids shown in the test name in place of some of the parameter values.
+    pub param_ids: Vec<ParametrizationId>,
+
     /// Fixtures to be passed as arguments to the test function.
     pub fixture_dependencies: Rc<[Rc<NormalizedFixture>]>,
 
@@ -69,22 +72,34 @@ impl TestVariant<'_> {
     }
 }
 
-/// Iterates over all variants of a test function.
+/// The fixtures a single variant of a test runs with.
 ///
-/// Expands parametrize combinations to produce all concrete test invocations.
-/// The iterator borrows the underlying `DiscoveredTestFunction` from the
-/// module and shares fixture lists between variants via `Rc<[…]>`, so
-/// producing N variants costs N refcount bumps rather than N deep clones.
-pub(super) struct TestVariantIterator<'a> {
-    test: &'a DiscoveredTestFunction,
-    /// Consumed as we iterate, so `values` and `tags` on each
-    /// `ParametrizationArgs` are moved into the emitted variant (not cloned).
-    param_args: std::vec::IntoIter<ParametrizationArgs>,
+/// A test has one fixture set for every combination of the params of the
+/// parametrized fixtures it depends on, and a single one otherwise.
+struct FixtureSet {
     fixture_dependencies: Rc<[Rc<NormalizedFixture>]>,
     use_fixture_dependencies: Rc<[Rc<NormalizedFixture>]>,
     auto_use_fixtures: Rc<[Rc<NormalizedFixture>]>,
 }
 
+/// Iterates over all variants of a test function.
+///
+/// Expands parametrize combinations, for each combination of fixture params,
+/// to produce all concrete test invocations. The iterator borrows the
+/// underlying `DiscoveredTestFunction` from the module and shares fixture
+/// lists between variants via `Rc<[…]>`, so producing N variants costs N
+/// refcount bumps rather than N deep clones.
+pub(super) struct TestVariantIterator<'a> {
+    test: &'a DiscoveredTestFunction,
+    /// Cloned for every fixture set but the last, which moves `values` and
+    /// `tags` on each `ParametrizationArgs` into the emitted variant.
+    param_args: Vec<ParametrizationArgs>,
+    fixture_sets: Vec<FixtureSet>,
+    /// Index of the next variant, counting through the parametrize
+    /// combinations of each fixture set in turn.
+    next_variant: usize,
+}
+
 impl<'a> TestVariantIterator<'a> {
     /// Create a new iterator for the given test function.
     ///
@@ -103,7 +118,7 @@ impl<'a> TestVariantIterator<'a> {
 
         // Only use the function parameter names, NOT the use_fixtures names.
         // use_fixtures are run for side effects but not passed as arguments.
-        let function_param_names = test.stmt_function_def.required_fixtures(py);
+        let function_param_names = test.required_fixtures(py);
 
         let auto_use_fixtures = resolver.get_normalized_auto_use_fixtures(
             py,
@@ -122,21 +137,60 @@ impl<'a> TestVariantIterator<'a> {
             test_params
         };
 
+        let fixture_count = fixture_dependencies.len();
+        let use_fixture_count = use_fixture_dependencies.len();
+
+        let all_fixtures: Vec<_> = fixture_dependencies
+            .into_iter()
+            .chain(use_fixture_dependencies)
+            .chain(auto_use_fixtures)
+            .collect();
+
+        let fixture_sets = fixture_combinations(&all_fixtures)
+            .into_iter()
+            .map(|mut fixtures| {
+                let auto_use_fixtures = fixtures.split_off(fixture_count + use_fixture_count);
+                let use_fixture_dependencies = fixtures.split_off(fixture_count);
+                FixtureSet {
+                    fixture_dependencies: Rc::from(fixtures),
+                    use_fixture_dependencies: Rc::from(use_fixture_dependencies),
+                    auto_use_fixtures: Rc::from(auto_use_fixtures),
+                }
+            })
+            .collect();
+
         Self {
             test,
-            param_args: param_args.into_iter(),
-            fixture_dependencies: Rc::from(fixture_dependencies),
-            use_fixture_dependencies: Rc::from(use_fixture_dependencies),
-            auto_use_fixtures: Rc::from(auto_use_fixtures),
+            param_args,
+            fixture_sets,
+            next_variant: 0,
         }
     }
+
+    fn variant_count(&self) -> usize {
+        self.fixture_sets.len() * self.param_args.len()
+    }
 }
 
 impl<'a> Iterator for TestVariantIterator<'a> {
     type Item = TestVariant<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let param_args = self.param_args.next()?;
+        if self.next_variant >= self.variant_count() {
+            return None;
+        }
+
+        let fixture_set_index = self.next_variant / self.param_args.len();
+        let param_index = self.next_variant % self.param_args.len();
+        self.next_variant += 1;
+
+        let param_args = if fixture_set_index + 1 == self.fixture_sets.len() {
+            std::mem::take(&mut self.param_args[param_index])
+        } else {
+            self.param_args[param_index].clone()
+        };
+
+        let fixture_set = &self.fixture_sets[fixture_set_index];
 
         let mut tags = self.test.tags.clone();
         tags.extend(&param_args.tags);
@@ -144,15 +198,17 @@ impl<'a> Iterator for TestVariantIterator<'a> {
         Some(TestVariant {
             test: self.test,
             params: param_args.values,
-            fixture_dependencies: Rc::clone(&self.fixture_dependencies),
-            use_fixture_dependencies: Rc::clone(&self.use_fixture_dependencies),
-            auto_use_fixtures: Rc::clone(&self.auto_use_fixtures),
+            param_ids: param_args.ids,
+            fixture_dependencies: Rc::clone(&fixture_set.fixture_dependencies),
+            use_fixture_dependencies: Rc::clone(&fixture_set.use_fixture_dependencies),
+            auto_use_fixtures: Rc::clone(&fixture_set.auto_use_fixtures),
             tags,
         })
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.param_args.size_hint()
+        let remaining = self.variant_count() - self.next_variant;
+        (remaining, Some(remaining))
     }
 }
 
@@ -0,0 +1,31 @@
+use pyo3::prelude::*;
+
+/// Returns a callable that runs a single `unittest.TestCase` test through
+/// `karva._unittest`, to be called like a test function.
+///
+/// Calling it runs `setUp`, the test method, `tearDown` and any cleanups, and
+/// raises if the test did not pass.
+pub(super) fn bind_test_case(py: Python<'_>, instance: &Py<PyAny>) -> PyResult<Py<PyAny>> {
+    Ok(py
+        .import("karva._unittest")?
+        .getattr("bind")?
+        .call1((instance,))?
+        .unbind())
+}
+
+/// Run `setUpClass` for a `unittest.TestCase` subclass.
+pub(super) fn set_up_class(py: Python<'_>, class: &Py<PyAny>) -> PyResult<()> {
+    py.import("karva._unittest")?
+        .getattr("set_up_class")?
+        .call1((class,))?;
+    Ok(())
+}
+
+/// Run `tearDownClass` and the class cleanups for a `unittest.TestCase`
+/// subclass.
+pub(super) fn tear_down_class(py: Python<'_>, class: &Py<PyAny>) -> PyResult<()> {
+    py.import("karva._unittest")?
+        .getattr("tear_down_class")?
+        .call1((class,))?;
+    Ok(())
+}
@@ -23,6 +23,16 @@ pub(crate) fn run_coroutine(py: Python<'_>, coroutine: Py<PyAny>) -> PyResult<Py
     Ok(asyncio.call_method1("run", (coroutine,))?.unbind())
 }
 
+/// Binds a function defined on a class to an instance of that class.
+pub(crate) fn bind_method(
+    py: Python<'_>,
+    function: &Py<PyAny>,
+    instance: &Py<PyAny>,
+) -> PyResult<Py<PyAny>> {
+    let method_type = py.import("types")?.getattr("MethodType")?;
+    Ok(method_type.call1((function, instance))?.unbind())
+}
+
 /// Runs a Python test with a timeout, raising `TimeoutError` if it does not
 /// finish in time.
 ///
@@ -1,5 +1,6 @@
 use std::ffi::OsString;
-use std::io;
+use std::io::{self, Write as _};
+use std::net::{SocketAddr, TcpStream};
 use std::process::{ExitCode, Termination};
 
 use anyhow::Context as _;
@@ -8,13 +9,12 @@ use clap::Parser;
 use colored::Colorize;
 use karva_cache::{RunCache, RunHash};
 use karva_cli::{SubTestCommand, Verbosity};
-use karva_diagnostic::{DummyReporter, Reporter, TestCaseReporter};
+use karva_diagnostic::{DummyReporter, EventReporter, Reporter, TestCaseReporter};
 use karva_logging::{Printer, StatusLevel, set_colored_override, setup_tracing};
-use karva_metadata::RunIgnoredMode;
 use karva_metadata::filter::FiltersetSet;
+use karva_metadata::{ResumePoint, RunIgnoredMode};
 use karva_project::path::{TestPath, TestPathError, absolute};
 use karva_python_semantic::current_python_version;
-use karva_static::EnvVars;
 use ruff_db::diagnostic::{DisplayDiagnosticConfig, FileResolver, Input, UnifiedFile};
 use ruff_db::files::File;
 use ruff_notebook::NotebookIndex;
@@ -39,6 +39,25 @@ struct Args {
     #[arg(long)]
     worker_id: usize,
 
+    /// Address of the main process's event socket. When set, per-test
+    /// events are streamed there and the main process renders them.
+    #[arg(long)]
+    ipc_address: Option<SocketAddr>,
+
+    /// Token the main process expects when the worker connects to its
+    /// event socket.
+    #[arg(long, env = "KARVA_IPC_TOKEN", hide_env_values = true)]
+    ipc_token: Option<String>,
+
+    /// Qualified name of a test function an earlier worker stopped partway
+    /// through. Its first `--resume-after` variants are not run again.
+    #[arg(long, requires = "resume_after")]
+    resume_test: Option<String>,
+
+    /// How many variants of `--resume-test` the earlier worker finished.
+    #[arg(long, requires = "resume_test")]
+    resume_after: Option<usize>,
+
     /// Shared test execution options inherited from the main CLI.
     #[clap(flatten)]
     sub_command: SubTestCommand,
@@ -104,14 +123,6 @@ fn run(f: impl FnOnce(Vec<OsString>) -> Vec<OsString>) -> anyhow::Result<ExitSta
 
     let args = Args::parse_from(args);
 
-    // SAFETY: This is called during single-threaded initialization before any
-    // concurrent work begins. The env var is read later by `assert_snapshot`.
-    if args.sub_command.snapshot_update.unwrap_or(false) {
-        unsafe {
-            std::env::set_var(EnvVars::KARVA_SNAPSHOT_UPDATE, "1");
-        }
-    }
-
     let verbosity = args.verbosity().level();
 
     set_colored_override(args.sub_command.color);
@@ -121,6 +132,8 @@ fn run(f: impl FnOnce(Vec<OsString>) -> Vec<OsString>) -> anyhow::Result<ExitSta
         args.sub_command.final_status_level.unwrap_or_default(),
     );
 
+    let message_format = args.sub_command.message_format.unwrap_or_default();
+
     let _guard = setup_tracing(verbosity);
 
     let cwd = cwd()?;
@@ -155,6 +168,12 @@ fn run(f: impl FnOnce(Vec<OsString>) -> Vec<OsString>) -> anyhow::Result<ExitSta
         (false, Some(data_file)) => Some(karva_test_semantic::CoverageConfig {
             sources: args.sub_command.cov.clone(),
             data_file,
+            branch: args.sub_command.cov_branch,
+            context: args.sub_command.cov_context,
+            include: args.sub_command.cov_include.clone(),
+            omit: args.sub_command.cov_omit.clone(),
+            exclude_lines: args.sub_command.cov_exclude_line.clone(),
+            partial_branches: args.sub_command.cov_partial_branch.clone(),
         }),
         _ => None,
     };
@@ -162,12 +181,26 @@ fn run(f: impl FnOnce(Vec<OsString>) -> Vec<OsString>) -> anyhow::Result<ExitSta
     let mut settings = args.sub_command.into_options().to_settings();
     settings.set_filter(filter);
     settings.set_run_ignored(run_ignored);
+    settings.set_resume(args.resume_test.zip(args.resume_after).map(
+        |(test, finished_variants)| ResumePoint {
+            test,
+            finished_variants,
+        },
+    ));
 
     let run_hash = RunHash::from_existing(&args.run_id);
 
     let cache = RunCache::new(&args.cache_dir, &run_hash);
 
-    let reporter: Box<dyn Reporter> = if matches!(printer.status_level(), StatusLevel::None) {
+    let reporter: Box<dyn Reporter> = if let Some(address) = args.ipc_address {
+        Box::new(EventReporter::new(connect_event_stream(
+            address,
+            args.ipc_token.as_deref().unwrap_or_default(),
+            args.worker_id,
+        )?))
+    } else if message_format.is_json() {
+        Box::new(EventReporter::new(io::stdout()))
+    } else if matches!(printer.status_level(), StatusLevel::None) {
         Box::new(DummyReporter)
     } else {
         Box::new(TestCaseReporter::new(printer))
@@ -193,18 +226,22 @@ fn run(f: impl FnOnce(Vec<OsString>) -> Vec<OsString>) -> anyhow::Result<ExitSta
 
     cache.write_result(args.worker_id, &result, &diagnostic_resolver, &config)?;
 
-    // Propagate the stop signal to sibling workers whenever this worker has
-    // reached (or exceeded) its configured max-fail budget. The budget is
-    // enforced locally per worker inside `PackageRunner`, so hitting any
-    // failure here while `max_fail` is set means we ran out of budget.
-    let failed_count = u32::try_from(result.stats().failed()).unwrap_or(u32::MAX);
-    if settings.max_fail().is_exceeded_by(failed_count) {
-        cache.write_fail_fast_signal()?;
-    }
-
     Ok(ExitStatus::Success)
 }
 
+/// Connect to the main process's event socket and identify this worker.
+fn connect_event_stream(
+    address: SocketAddr,
+    token: &str,
+    worker_id: usize,
+) -> anyhow::Result<TcpStream> {
+    let mut stream = TcpStream::connect(address)
+        .with_context(|| format!("Failed to connect to the main process at {address}"))?;
+    stream.set_nodelay(true)?;
+    writeln!(stream, "{token} {worker_id}")?;
+    Ok(stream)
+}
+
 /// Resolves file paths for diagnostic messages.
 ///
 /// Implements the `FileResolver` trait to provide file path information
@@ -5,13 +5,22 @@ from karva._karva import (
     Command,
     ExceptionInfo,
     FailError,
+    FixtureConfig,
+    FixtureRequest,
     RaisesContext,
     SkipError,
     SnapshotMismatchError,
     SnapshotSettings,
+    SortedRedaction,
+    assert_binary_snapshot,
     assert_cmd_snapshot,
+    assert_debug_snapshot,
     assert_json_snapshot,
+    assert_raises_snapshot,
     assert_snapshot,
+    assert_toml_snapshot,
+    assert_warns_snapshot,
+    assert_yaml_snapshot,
     fail,
     fixture,
     karva_run,
@@ -19,6 +28,7 @@ from karva._karva import (
     raises,
     skip,
     snapshot_settings,
+    sorted_redaction,
     tags,
 )
 
@@ -28,14 +38,23 @@ __all__: list[str] = [
     "Command",
     "ExceptionInfo",
     "FailError",
+    "FixtureConfig",
+    "FixtureRequest",
     "MockEnv",
     "RaisesContext",
     "SkipError",
     "SnapshotMismatchError",
     "SnapshotSettings",
+    "SortedRedaction",
+    "assert_binary_snapshot",
     "assert_cmd_snapshot",
+    "assert_debug_snapshot",
     "assert_json_snapshot",
+    "assert_raises_snapshot",
     "assert_snapshot",
+    "assert_toml_snapshot",
+    "assert_warns_snapshot",
+    "assert_yaml_snapshot",
     "fail",
     "fixture",
     "karva_run",
@@ -43,5 +62,6 @@ __all__: list[str] = [
     "raises",
     "skip",
     "snapshot_settings",
+    "sorted_redaction",
     "tags",
 ]
@@ -0,0 +1,67 @@
+"""Per-test output capture used by Karva's machine-readable reports.
+
+The runner installs an :class:`OutputCapture` around each test when a report
+that records test output (such as JUnit XML) was requested. Writes are
+recorded and also forwarded to the stream that was active before capture
+started, so ``-s`` keeps printing output live and fixtures such as
+:fixture:`capsys` can still swap the streams out from under it.
+"""
+
+from __future__ import annotations
+
+import io
+import sys
+from typing import TextIO
+
+
+class _TeeStream(io.TextIOBase):
+    """Text stream that records everything written to it and forwards it on."""
+
+    def __init__(self, target: TextIO | None) -> None:
+        self._target = target
+        self._buffer = io.StringIO()
+
+    @property
+    def encoding(self) -> str:  # type: ignore[override]
+        return getattr(self._target, "encoding", None) or "utf-8"
+
+    def writable(self) -> bool:
+        return True
+
+    def isatty(self) -> bool:
+        return False
+
+    def write(self, s: str) -> int:
+        self._buffer.write(s)
+        if self._target is not None:
+            self._target.write(s)
+        return len(s)
+
+    def flush(self) -> None:
+        if self._target is not None:
+            self._target.flush()
+
+    def getvalue(self) -> str:
+        return self._buffer.getvalue()
+
+
+class OutputCapture:
+    """Records ``sys.stdout`` and ``sys.stderr`` until :meth:`restore` is called."""
+
+    def __init__(self) -> None:
+        self._saved_stdout = sys.stdout
+        self._saved_stderr = sys.stderr
+        self._stdout = _TeeStream(sys.stdout)
+        self._stderr = _TeeStream(sys.stderr)
+        sys.stdout = self._stdout
+        sys.stderr = self._stderr
+
+    def stdout(self) -> str:
+        return self._stdout.getvalue()
+
+    def stderr(self) -> str:
+        return self._stderr.getvalue()
+
+    def restore(self) -> None:
+        sys.stdout = self._saved_stdout
+        sys.stderr = self._saved_stderr
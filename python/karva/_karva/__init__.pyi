@@ -1,7 +1,8 @@
 import builtins
 import types
 from collections.abc import Callable, Sequence
-from typing import Generic, Literal, NoReturn, Self, TypeAlias, TypeVar, overload
+from pathlib import Path
+from typing import Any, Generic, Literal, NoReturn, Self, TypeAlias, TypeVar, overload
 
 from typing_extensions import ParamSpec
 
@@ -27,11 +28,79 @@ def fixture(func: Callable[_P, _T]) -> FixtureFunctionDefinition[_P, _T]: ...
 def fixture(
     func: None = ...,
     *,
-    scope: _ScopeName = "function",
+    scope: _ScopeName | Callable[[str, FixtureConfig], _ScopeName] = "function",
     name: str | None = ...,
     auto_use: bool = ...,
+    params: Sequence[object] | None = ...,
+    ids: Sequence[str | None] | Callable[[object], str | None] | None = ...,
 ) -> FixtureFunctionMarker[_P, _T]: ...
 
+class FixtureConfig:
+    """Passed as `config` to a dynamic scope function."""
+
+    @property
+    def rootpath(self) -> Path:
+        """The root directory of the run."""
+
+    def getoption(self, name: str, default: object = None) -> Any:
+        """Return `default`, since karva has no options for plugins to add."""
+
+class FixtureRequest:
+    """Passed to fixtures that take a `request` parameter."""
+
+    @property
+    def fixture_name(self) -> str:
+        """The name of the fixture being set up."""
+
+    @property
+    def scope(self) -> _ScopeName:
+        """The scope of the fixture being set up."""
+
+    @property
+    def test_name(self) -> str | None:
+        """The qualified name of the test the fixture is set up for.
+
+        Only set for function-scoped fixtures.
+        """
+
+    @property
+    def module(self) -> str | None:
+        """The module the fixture is set up in.
+
+        Not set for package and session scoped fixtures.
+        """
+
+    @property
+    def package(self) -> str | None:
+        """The package the fixture is set up in.
+
+        Not set for session scoped fixtures, or in the root of the project.
+        """
+
+    @property
+    def tags(self) -> list[str]:
+        """The names of the test's custom tags.
+
+        Empty for fixtures that are not function-scoped.
+        """
+
+    @property
+    def param(self) -> object:
+        """The value from `params` the fixture is running for.
+
+        Raises `AttributeError` if the fixture has no `params`.
+        """
+
+    def addfinalizer(self, finalizer: Callable[[], object], /) -> None:
+        """Call `finalizer` when the fixture's scope ends."""
+
+    def getfixturevalue(self, name: str, /) -> Any:
+        """Return the value of the fixture named `name`.
+
+        The name must be written as a string literal in the fixture's body,
+        so that karva can set the fixture up beforehand.
+        """
+
 class TestFunction(Generic[_P, _T]):
     def __call__(self, *args: _P.args, **kwargs: _P.kwargs) -> _T: ...
 
@@ -50,13 +119,16 @@ class Param:
         """The values to parameterize the test case with."""
 
 def param(
-    *values: object, tags: Sequence[Tags | Callable[[], Tags]] | None = None
+    *values: object,
+    tags: Sequence[Tags | Callable[[], Tags]] | None = None,
+    id: str | None = None,
 ) -> None:
     """Define a parameterized test case.
 
     Args:
         *values: The values to parameterize the test case with.
         tags: The tag or tag functions.
+        id: The id shown in the test name in place of the values.
 
     .. code-block:: python
 
@@ -129,12 +201,90 @@ def assert_json_snapshot(
     value: object,
     *,
     inline: str | None = None,
+    redactions: dict[str, object] | None = None,
 ) -> None: ...
 @overload
 def assert_json_snapshot(
     value: object,
     *,
     name: str,
+    redactions: dict[str, object] | None = None,
+) -> None: ...
+@overload
+def assert_yaml_snapshot(
+    value: object,
+    *,
+    inline: str | None = None,
+    redactions: dict[str, object] | None = None,
+) -> None: ...
+@overload
+def assert_yaml_snapshot(
+    value: object,
+    *,
+    name: str,
+    redactions: dict[str, object] | None = None,
+) -> None: ...
+@overload
+def assert_toml_snapshot(
+    value: object,
+    *,
+    inline: str | None = None,
+    redactions: dict[str, object] | None = None,
+) -> None: ...
+@overload
+def assert_toml_snapshot(
+    value: object,
+    *,
+    name: str,
+    redactions: dict[str, object] | None = None,
+) -> None: ...
+@overload
+def assert_debug_snapshot(
+    value: object,
+    *,
+    inline: str | None = None,
+    redactions: dict[str, object] | None = None,
+) -> None: ...
+@overload
+def assert_debug_snapshot(
+    value: object,
+    *,
+    name: str,
+    redactions: dict[str, object] | None = None,
+) -> None: ...
+def assert_binary_snapshot(
+    data: bytes,
+    *,
+    extension: str,
+    name: str | None = None,
+) -> None: ...
+@overload
+def assert_raises_snapshot(
+    value: Callable[[], object] | RaisesContext | ExceptionInfo,
+    *,
+    inline: str | None = None,
+    notes: bool = True,
+    chain: bool = True,
+) -> None: ...
+@overload
+def assert_raises_snapshot(
+    value: Callable[[], object] | RaisesContext | ExceptionInfo,
+    *,
+    name: str,
+    notes: bool = True,
+    chain: bool = True,
+) -> None: ...
+@overload
+def assert_warns_snapshot(
+    value: Callable[[], object],
+    *,
+    inline: str | None = None,
+) -> None: ...
+@overload
+def assert_warns_snapshot(
+    value: Callable[[], object],
+    *,
+    name: str,
 ) -> None: ...
 
 class Command:
@@ -170,13 +320,16 @@ class SnapshotSettings:
     """Context manager for scoped snapshot configuration.
 
     Filters are applied sequentially to the serialized snapshot value before
-    comparison/storage. Nesting accumulates filters from outer to inner scope.
+    comparison/storage. Nesting accumulates filters and redactions from outer
+    to inner scope, while the innermost `info` replaces outer ones.
     """
 
     def __init__(
         self,
         *,
         filters: list[tuple[str, str]] | None = None,
+        redactions: dict[str, object] | None = None,
+        info: object = None,
         allow_duplicates: bool = False,
     ) -> None: ...
     def __enter__(self) -> Self: ...
@@ -190,6 +343,8 @@ class SnapshotSettings:
 def snapshot_settings(
     *,
     filters: list[tuple[str, str]] | None = None,
+    redactions: dict[str, object] | None = None,
+    info: object = None,
     allow_duplicates: bool = False,
 ) -> SnapshotSettings:
     """Create a context manager for scoped snapshot configuration.
@@ -197,9 +352,21 @@ def snapshot_settings(
     Args:
         filters: List of (regex_pattern, replacement) pairs applied sequentially
             to the serialized snapshot value before comparison/storage.
+        redactions: Mapping of selectors such as `.items[*].id` to redactions
+            applied by `assert_json_snapshot` before serialization. A redaction
+            is a replacement value, a `(value, path)` callable returning one, or
+            `sorted_redaction()`.
+        info: JSON-serializable data recorded in the metadata of snapshots
+            written inside the scope. The innermost scope's `info` wins.
         allow_duplicates: If True, allow multiple unnamed snapshots in a single test.
     """
 
+class SortedRedaction:
+    """Redaction that sorts the selected list, set or dict."""
+
+def sorted_redaction() -> SortedRedaction:
+    """Create a redaction that sorts the selected value instead of replacing it."""
+
 class SkipError(Exception):
     """Raised when `karva.skip` is called."""
 
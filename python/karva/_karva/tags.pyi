@@ -9,8 +9,16 @@ _P = ParamSpec("_P")
 def parametrize(
     arg_names: Sequence[str] | str,
     arg_values: Sequence[Sequence[object]] | Sequence[object],
+    ids: Sequence[str | None] | Callable[[object], str | None] | None = None,
 ) -> Tags:
-    """Parametrize the current test with the given arguments."""
+    """Parametrize the current test with the given arguments.
+
+    Args:
+        arg_names: The names of the arguments.
+        arg_values: The values of the arguments, one entry per test case.
+        ids: The ids shown in the test names in place of the values, either
+            one per test case or a callable called with each value.
+    """
 
 def use_fixtures(*fixture_names: str) -> Tags:
     """Use the given fixtures for the current test.
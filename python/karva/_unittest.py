@@ -0,0 +1,131 @@
+"""Support for running ``unittest.TestCase`` tests with Karva.
+
+Each test runs through :meth:`unittest.TestCase.run`, so ``setUp``,
+``tearDown``, cleanups registered with ``addCleanup``, ``subTest`` and the
+``unittest`` skip and expected-failure decorators behave as they do under
+``unittest`` itself. :class:`_Result` records what ``unittest`` reports, and
+:func:`run_test_case` turns it back into the outcome Karva expects from a test
+call: returning for a pass, raising the test's exception for a failure, and
+raising :class:`karva.SkipError` for a skip.
+
+``setUpClass`` and ``tearDownClass`` are run by the Rust-side runner through
+:func:`set_up_class` and :func:`tear_down_class`, around the tests of a class.
+"""
+
+from __future__ import annotations
+
+import functools
+import unittest
+from collections.abc import Callable
+from types import TracebackType
+
+from karva._karva import SkipError
+
+_ExcInfo = tuple[type[BaseException], BaseException, TracebackType]
+
+
+class _Result(unittest.TestResult):
+    """Records the outcome ``unittest`` reports for a single test."""
+
+    def __init__(self) -> None:
+        super().__init__()
+        self.passed = False
+        self.error: BaseException | None = None
+        self.skip_reason: str | None = None
+
+    def _record(self, err: _ExcInfo) -> None:
+        # Keep the first error, which is usually the cause of the others.
+        if self.error is None:
+            self.error = err[1]
+
+    def addSuccess(self, _test: unittest.TestCase) -> None:
+        self.passed = True
+
+    def addError(self, _test: unittest.TestCase, err: _ExcInfo) -> None:
+        self._record(err)
+
+    def addFailure(self, _test: unittest.TestCase, err: _ExcInfo) -> None:
+        self._record(err)
+
+    def addSkip(self, _test: unittest.TestCase, reason: str) -> None:
+        self.skip_reason = reason
+
+    def addExpectedFailure(self, _test: unittest.TestCase, err: _ExcInfo) -> None:
+        # Karva's own expect-fail handling turns the error into a pass.
+        self._record(err)
+
+    def addUnexpectedSuccess(self, _test: unittest.TestCase) -> None:
+        # Karva's own expect-fail handling reports the unexpected pass.
+        self.passed = True
+
+    def addSubTest(
+        self,
+        _test: unittest.TestCase,
+        _subtest: unittest.TestCase,
+        err: _ExcInfo | None,
+    ) -> None:
+        if err is not None:
+            self._record(err)
+
+
+def _is_unittest_frame(tb: TracebackType) -> bool:
+    return "__unittest" in tb.tb_frame.f_globals
+
+
+def _without_unittest_frames(error: BaseException) -> BaseException:
+    """Drop the frames of ``unittest`` itself from the error's traceback.
+
+    ``unittest`` does the same in its own reports, and it leaves the test's
+    own code as the innermost frame, which is where Karva points failures.
+    """
+    tb = error.__traceback__
+    while tb is not None and _is_unittest_frame(tb):
+        tb = tb.tb_next
+
+    first = tb
+    last = None
+    while tb is not None and not _is_unittest_frame(tb):
+        last = tb
+        tb = tb.tb_next
+
+    if last is not None:
+        last.tb_next = None
+
+    return error.with_traceback(first)
+
+
+def run_test_case(test: unittest.TestCase) -> None:
+    """Run a single test of a test case, raising if it did not pass."""
+    result = _Result()
+    test.run(result)
+
+    if result.error is not None:
+        raise _without_unittest_frames(result.error)
+
+    if not result.passed and result.skip_reason is not None:
+        raise SkipError(result.skip_reason)
+
+
+def bind(test: unittest.TestCase) -> Callable[[], None]:
+    """Return a callable that runs ``test``, to be called like a test function."""
+    return functools.partial(run_test_case, test)
+
+
+def set_up_class(cls: type[unittest.TestCase]) -> None:
+    """Run ``setUpClass``, running the class cleanups if it fails."""
+    try:
+        cls.setUpClass()
+    except BaseException:
+        cls.doClassCleanups()
+        raise
+
+
+def tear_down_class(cls: type[unittest.TestCase]) -> None:
+    """Run ``tearDownClass`` and the class cleanups, raising the first error."""
+    try:
+        cls.tearDownClass()
+    finally:
+        cls.doClassCleanups()
+
+    if cls.tearDown_exceptions:
+        raise cls.tearDown_exceptions[0][1]